@@ -29,6 +29,7 @@ use {
     std::{
         cmp::Reverse,
         collections::{BinaryHeap, HashMap, HashSet},
+        ops::Bound,
         sync::{
             atomic::{AtomicUsize, Ordering},
             Arc, Mutex,
@@ -364,6 +365,44 @@ impl Accounts {
             .map(|_| collector)
     }
 
+    /// Like `load_by_program_with_filter`, but visits the accounts in pubkey order, starting
+    /// after `start_after` if any, and stops the scan once `limit` accounts matched
+    pub fn load_by_program_with_filter_in_order<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        program_id: &Pubkey,
+        filter: F,
+        start_after: Option<Pubkey>,
+        limit: usize,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        let config = ScanConfig::new(ScanOrder::Sorted).recreate_with_abort();
+        let range = (
+            start_after.map_or(Bound::Unbounded, Bound::Excluded),
+            Bound::Unbounded,
+        );
+        let mut collector = Vec::new();
+        self.accounts_db
+            .range_scan_accounts(
+                ancestors,
+                bank_id,
+                range,
+                |some_account_tuple| {
+                    if collector.len() >= limit {
+                        return;
+                    }
+                    Self::load_while_filtering(&mut collector, some_account_tuple, |account| {
+                        account.owner() == program_id && filter(account)
+                    });
+                    if collector.len() >= limit {
+                        config.abort();
+                    }
+                },
+                &config,
+            )
+            .map(|_| collector)
+    }
+
     fn calc_scan_result_size(account: &AccountSharedData) -> usize {
         account.data().len()
             + std::mem::size_of::<AccountSharedData>()
@@ -791,6 +830,48 @@ mod tests {
         assert_eq!(loaded, vec![]);
     }
 
+    #[test]
+    fn test_load_by_program_with_filter_in_order() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+
+        let program_id = Pubkey::new_unique();
+        let mut keys: Vec<_> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        keys.sort();
+        for (i, pubkey) in keys.iter().enumerate() {
+            let owner = if i == 2 {
+                Pubkey::new_unique()
+            } else {
+                program_id
+            };
+            let account = AccountSharedData::new(i as u64 + 1, 0, &owner);
+            accounts.store_for_tests(0, pubkey, &account);
+        }
+        let ancestors = vec![(0, 0)].into_iter().collect();
+        let bank_id = 0;
+
+        // keys[2] is owned by another program and keys[4] is filtered out
+        let load_page = |start_after, limit| {
+            accounts
+                .load_by_program_with_filter_in_order(
+                    &ancestors,
+                    bank_id,
+                    &program_id,
+                    |account| account.lamports() != 5,
+                    start_after,
+                    limit,
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(pubkey, _account)| pubkey)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(load_page(None, 2), vec![keys[0], keys[1]]);
+        assert_eq!(load_page(Some(keys[1]), 2), vec![keys[3], keys[5]]);
+        assert_eq!(load_page(Some(keys[3]), 10), vec![keys[5]]);
+        assert_eq!(load_page(Some(keys[5]), 10), vec![]);
+    }
+
     #[test_case(false; "old")]
     #[test_case(true; "simd83")]
     fn test_lock_accounts_with_duplicates(relax_intrabatch_account_locks: bool) {
@@ -59,7 +59,10 @@ use {
             PartitionedEpochRewardsConfig, DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
         },
         read_only_accounts_cache::ReadOnlyAccountsCache,
-        storable_accounts::{StorableAccounts, StorableAccountsBySlot},
+        storable_accounts::{
+            StorableAccounts, StorableAccountsBySlot, StorableAccountsFromStorage,
+        },
+        tiered_storage::{TieredStorage, TieredStorageFormat},
         u64_align, utils,
     },
     dashmap::{DashMap, DashSet},
@@ -1095,6 +1098,56 @@ impl AccountStorageEntry {
     pub fn path(&self) -> &Path {
         self.accounts.path()
     }
+
+    /// Rewrites the accounts in this storage into a new tiered storage file at `path`
+    ///
+    /// Accounts that were marked obsolete as of the passed in slot or earlier are
+    /// not rewritten. If slot is None, then all obsolete accounts are skipped.
+    /// The returned storage keeps the slot and id of this storage.
+    ///
+    /// Account data is copied straight from this storage as the new file is written, so only
+    /// the pubkeys and offsets of the accounts are held in memory.
+    pub fn rewrite_as_tiered_storage(
+        &self,
+        path: impl Into<PathBuf>,
+        format: &TieredStorageFormat,
+        slot: Option<Slot>,
+    ) -> Result<Self, AccountsFileError> {
+        let obsolete_offsets: IntSet<Offset> = self
+            .obsolete_accounts_read_lock()
+            .filter_obsolete_accounts(slot)
+            .map(|(offset, _data_len)| offset)
+            .collect();
+
+        let mut accounts = Vec::with_capacity(self.count());
+        self.accounts
+            .scan_accounts_without_data(|offset, account| {
+                if !obsolete_offsets.contains(&offset) {
+                    accounts.push(AccountFromStorage {
+                        index_info: AccountInfo::new(
+                            StorageLocation::AppendVec(self.id, offset),
+                            account.is_zero_lamport(),
+                        ),
+                        pubkey: *account.pubkey(),
+                        data_len: account.data_len as u64,
+                    });
+                }
+            })?;
+
+        let tiered_storage = TieredStorage::new_writable(path);
+        tiered_storage.write_accounts(
+            &StorableAccountsFromStorage::new(self, &accounts),
+            0,
+            format,
+        )?;
+
+        Ok(Self::new_existing(
+            self.slot,
+            self.id,
+            AccountsFile::TieredStorage(tiered_storage),
+            ObsoleteAccounts::default(),
+        ))
+    }
 }
 
 pub fn get_temp_accounts_paths(count: u32) -> io::Result<(Vec<TempDir>, Vec<PathBuf>)> {
@@ -3763,6 +3816,41 @@ impl AccountsDb {
         Ok(())
     }
 
+    /// Like `scan_accounts`, but only visits the accounts whose pubkey is within `range`
+    pub fn range_scan_accounts<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        range: R,
+        mut scan_func: F,
+        config: &ScanConfig,
+    ) -> ScanResult<()>
+    where
+        F: FnMut(Option<(&Pubkey, AccountSharedData, Slot)>),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        self.accounts_index.range_scan_accounts(
+            ancestors,
+            bank_id,
+            range,
+            |pubkey, (account_info, slot)| {
+                let mut account_accessor =
+                    self.get_account_accessor(slot, pubkey, &account_info.storage_location());
+
+                let account_slot = match account_accessor {
+                    LoadedAccountAccessor::Cached(None) => None,
+                    _ => account_accessor.get_loaded_account(|loaded_account| {
+                        (pubkey, loaded_account.take_account(), slot)
+                    }),
+                };
+                scan_func(account_slot)
+            },
+            config,
+        )?;
+
+        Ok(())
+    }
+
     pub fn index_scan_accounts<F>(
         &self,
         ancestors: &Ancestors,
@@ -5332,6 +5420,54 @@ impl AccountsDb {
         AccountsLtHash(lt_hash)
     }
 
+    /// Calculates the accounts lt hash from the accounts in `storages`
+    ///
+    /// `storages` must hold the same accounts as the storages of this AccountsDb up to
+    /// `max_root`, e.g. copies of them rewritten in another accounts file format.  An account is
+    /// only included from the storage of the slot the accounts index refers to as of `max_root`,
+    /// so the storages can be hashed independently, without tracking the accounts seen so far.
+    ///
+    /// Note, this is *very* expensive!  It reads every account in every storage.
+    pub fn calculate_accounts_lt_hash_from_storages(
+        &self,
+        storages: &[Arc<AccountStorageEntry>],
+        max_root: Slot,
+    ) -> AccountsLtHash {
+        let lt_hash = storages
+            .par_iter()
+            .fold(LtHash::identity, |mut accumulator_lt_hash, storage| {
+                let mut reader = append_vec::new_scan_accounts_reader();
+                storage
+                    .accounts
+                    .scan_accounts(&mut reader, |_offset, account| {
+                        let is_latest_version = self
+                            .accounts_index
+                            .get_with_and_then(
+                                account.pubkey,
+                                None,
+                                Some(max_root),
+                                false,
+                                |(slot, _account_info)| slot == storage.slot(),
+                            )
+                            .unwrap_or_default();
+                        if is_latest_version {
+                            // zero lamport accounts hash to the identity, so mixing them in is a
+                            // no-op
+                            let account_lt_hash = Self::lt_hash_account(&account, account.pubkey);
+                            accumulator_lt_hash.mix_in(&account_lt_hash.0);
+                        }
+                    })
+                    .expect("must scan accounts storage");
+                accumulator_lt_hash
+            })
+            .reduce(LtHash::identity, |mut accum, elem| {
+                accum.mix_in(&elem);
+                accum
+            });
+
+        AccountsLtHash(lt_hash)
+    }
+
     /// Calculates the capitalization
     ///
     /// Panics if capitalization overflows a u64.
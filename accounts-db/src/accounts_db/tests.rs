@@ -6634,3 +6634,67 @@ fn test_batch_insert_zero_lamport_single_ref_account_offsets() {
     assert_eq!(count5, 3, "Should insert only 3 new offsets (60, 70, 80)");
     assert_eq!(storage.num_zero_lamport_single_ref_accounts(), 8);
 }
+
+#[test_case(&crate::tiered_storage::hot::HOT_FORMAT; "hot")]
+#[test_case(&crate::tiered_storage::cold::COLD_FORMAT; "cold")]
+fn test_rewrite_as_tiered_storage(format: &TieredStorageFormat) {
+    let accounts_db = AccountsDb::new_single_for_tests();
+    let owner = Pubkey::new_unique();
+    let pubkeys: Vec<_> = iter::repeat_with(Pubkey::new_unique).take(5).collect();
+
+    // overwrite some of the accounts in later slots, including with zero lamports
+    for slot in 1..=3 {
+        let accounts: Vec<_> = pubkeys
+            .iter()
+            .enumerate()
+            .skip(slot as usize - 1)
+            .map(|(i, pubkey)| {
+                let lamports = if i == 2 && slot == 3 {
+                    0
+                } else {
+                    slot * 100 + i as u64
+                };
+                (*pubkey, AccountSharedData::new(lamports, i * 10, &owner))
+            })
+            .collect();
+        accounts_db.store_for_tests((slot, accounts.as_slice()));
+        accounts_db.add_root_and_flush_write_cache(slot);
+    }
+
+    let ancestors = linear_ancestors(4);
+    let expected_lt_hash =
+        accounts_db.calculate_accounts_lt_hash_at_startup_from_index(&ancestors, 3);
+    let (storages, _slots) = accounts_db.get_storages(..);
+    assert_eq!(
+        accounts_db.calculate_accounts_lt_hash_from_storages(&storages, 3),
+        expected_lt_hash,
+    );
+
+    let temp_dir = TempDir::new().unwrap();
+    let tiered_storages: Vec<_> = storages
+        .iter()
+        .map(|storage| {
+            let path = temp_dir
+                .path()
+                .join(AccountsFile::file_name(storage.slot(), storage.id()));
+            Arc::new(
+                storage
+                    .rewrite_as_tiered_storage(path, format, None)
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+    for (storage, tiered_storage) in storages.iter().zip(&tiered_storages) {
+        assert_eq!(tiered_storage.slot(), storage.slot());
+        assert_eq!(tiered_storage.id(), storage.id());
+        assert!(matches!(
+            tiered_storage.accounts,
+            AccountsFile::TieredStorage(_)
+        ));
+    }
+    assert_eq!(
+        accounts_db.calculate_accounts_lt_hash_from_storages(&tiered_storages, 3),
+        expected_lt_hash,
+    );
+}
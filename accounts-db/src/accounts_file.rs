@@ -9,7 +9,8 @@ use {
         buffered_reader::RequiredLenBufFileRead,
         storable_accounts::StorableAccounts,
         tiered_storage::{
-            error::TieredStorageError, hot::HOT_FORMAT, index::IndexOffset, TieredStorage,
+            error::TieredStorageError, file::TieredReadableFile, hot::HOT_FORMAT,
+            index::IndexOffset, TieredStorage,
         },
     },
     solana_account::AccountSharedData,
@@ -77,6 +78,11 @@ impl AccountsFile {
         current_len: usize,
         storage_access: StorageAccess,
     ) -> Result<(Self, usize)> {
+        let path = path.into();
+        if let Some(ts) = Self::open_tiered_storage(&path)? {
+            let num_accounts = ts.reader().map_or(0, |reader| reader.num_accounts());
+            return Ok((Self::TieredStorage(ts), num_accounts));
+        }
         let (av, num_accounts) = AppendVec::new_from_file(path, current_len, storage_access)?;
         Ok((Self::AppendVec(av), num_accounts))
     }
@@ -91,10 +97,26 @@ impl AccountsFile {
         current_len: usize,
         storage_access: StorageAccess,
     ) -> Result<Self> {
+        let path = path.into();
+        if let Some(ts) = Self::open_tiered_storage(&path)? {
+            return Ok(Self::TieredStorage(ts));
+        }
         let av = AppendVec::new_for_startup(path, current_len, storage_access)?;
         Ok(Self::AppendVec(av))
     }
 
+    /// Opens the file at `path` as tiered storage if it ends with the tiered storage magic number
+    ///
+    /// Append vecs have no header or footer, so the magic number is what tells the two formats
+    /// apart.  Once it matches the file is tiered storage, and failing to open it, e.g. because it
+    /// is corrupt, is an error rather than a reason to open it as an append vec.
+    fn open_tiered_storage(path: &Path) -> Result<Option<TieredStorage>> {
+        if !TieredReadableFile::has_magic_number(path)? {
+            return Ok(None);
+        }
+        Ok(Some(TieredStorage::new_readonly(path)?))
+    }
+
     /// if storage is not readonly, reopen another instance that is read only
     pub(crate) fn reopen_as_readonly(&self) -> Option<Self> {
         match self {
@@ -432,3 +454,21 @@ pub struct StoredAccountsInfo {
     /// total size of all the stored accounts
     pub size: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::tiered_storage::file::FILE_MAGIC_NUMBER, tempfile::TempDir};
+
+    #[test]
+    fn test_new_for_startup_corrupt_tiered_storage() {
+        // A file ending in the magic number is tiered storage, so failing to read its footer must
+        // not fall back to opening it as an append vec
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("corrupt_tiered_storage");
+        std::fs::write(&path, FILE_MAGIC_NUMBER.to_le_bytes()).unwrap();
+        assert!(matches!(
+            AccountsFile::new_for_startup(&path, 8, StorageAccess::File),
+            Err(AccountsFileError::TieredStorageError(_))
+        ));
+    }
+}
@@ -15,6 +15,7 @@ use {
         rolling_bit_field::RollingBitField,
     },
     account_map_entry::{AccountMapEntry, PreAllocatedAccountMapEntry},
+    dashmap::{mapref::one::Ref, DashMap},
     in_mem_accounts_index::{
         ExistedLocation, InMemAccountsIndex, InsertNewEntryResults, StartupStats,
     },
@@ -46,6 +47,7 @@ pub use {
     iter::ITER_BATCH_SIZE,
     secondary::{
         AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude, IndexKey,
+        MemcmpIndex,
     },
 };
 
@@ -295,6 +297,9 @@ pub struct AccountsIndex<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> {
     program_id_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    /// memcmp indexes are configured by the operator, so they are created on
+    /// first insertion rather than up front
+    memcmp_indexes: DashMap<MemcmpIndex, SecondaryIndex<RwLockSecondaryIndexEntry>>,
     pub roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -347,6 +352,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            memcmp_indexes: DashMap::default(),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -649,6 +655,19 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                     config,
                 );
             }
+            ScanTypes::Indexed(IndexKey::Memcmp(memcmp_index, index_key)) => {
+                // No index yet means no account has been inserted into it
+                if let Some(index) = self.memcmp_indexes.get(&memcmp_index) {
+                    self.do_scan_secondary_index(
+                        ancestors,
+                        func,
+                        index.value(),
+                        &index_key,
+                        Some(max_root),
+                        config,
+                    );
+                }
+            }
         }
 
         {
@@ -906,6 +925,30 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         )
     }
 
+    /// call func with every pubkey and index visible from a given set of ancestors, within `range`
+    pub(crate) fn range_scan_accounts<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        scan_bank_id: BankId,
+        range: R,
+        func: F,
+        config: &ScanConfig,
+    ) -> Result<(), ScanError>
+    where
+        F: FnMut(&Pubkey, (&T, Slot)),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // Pass "" not to log metrics, so RPC doesn't get spammy
+        self.do_checked_scan_accounts(
+            "",
+            ancestors,
+            scan_bank_id,
+            func,
+            ScanTypes::Unindexed(Some(range)),
+            config,
+        )
+    }
+
     /// call func with every pubkey and index visible from a given set of ancestors
     pub(crate) fn index_scan_accounts<F>(
         &self,
@@ -1229,9 +1272,25 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 .index
                 .get(index_key)
                 .map(|x| x.len()),
+            AccountIndex::Memcmp(memcmp_index) => self
+                .memcmp_indexes
+                .get(memcmp_index)
+                .and_then(|index| index.index.get(index_key).map(|x| x.len())),
         }
     }
 
+    fn get_or_create_memcmp_index(
+        &self,
+        memcmp_index: &MemcmpIndex,
+    ) -> Ref<'_, MemcmpIndex, SecondaryIndex<RwLockSecondaryIndexEntry>> {
+        self.memcmp_indexes.get(memcmp_index).unwrap_or_else(|| {
+            self.memcmp_indexes
+                .entry(*memcmp_index)
+                .or_insert_with(|| SecondaryIndex::new("memcmp_index_stats"))
+                .downgrade()
+        })
+    }
+
     /// log any secondary index counts, if non-zero
     pub(crate) fn log_secondary_indexes(&self) {
         if !self.program_id_index.index.is_empty() {
@@ -1246,6 +1305,12 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        for entry in self.memcmp_indexes.iter() {
+            if !entry.value().index.is_empty() {
+                info!("secondary index: {:?}", AccountIndex::Memcmp(*entry.key()));
+                entry.value().log_contents();
+            }
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1294,6 +1359,18 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             account_data,
             account_indexes,
         );
+
+        for memcmp_index in account_indexes.memcmp_indexes() {
+            if *account_owner == memcmp_index.program_id {
+                if let Some(index_key) = memcmp_index
+                    .key_from_account_data(account_data)
+                    .filter(|index_key| account_indexes.include_key(index_key))
+                {
+                    self.get_or_create_memcmp_index(memcmp_index)
+                        .insert(&index_key, pubkey);
+                }
+            }
+        }
     }
 
     pub(crate) fn get_bin(&self, pubkey: &Pubkey) -> &InMemAccountsIndex<T, U> {
@@ -1498,6 +1575,12 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        for memcmp_index in account_indexes.memcmp_indexes() {
+            if let Some(index) = self.memcmp_indexes.get(memcmp_index) {
+                index.remove_by_inner_key(inner_key);
+            }
+        }
     }
 
     fn purge_older_root_entries(
@@ -3610,6 +3693,61 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_memcmp_secondary_index() {
+        let program_id = Pubkey::new_unique();
+        let memcmp_index = MemcmpIndex::new(program_id, 8, PUBKEY_BYTES).unwrap();
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::Memcmp(memcmp_index)]),
+        };
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+        let account_key = Pubkey::new_unique();
+        let index_key = Pubkey::new_unique();
+        let mut account_data = vec![0; 8 + PUBKEY_BYTES];
+        account_data[8..].copy_from_slice(index_key.as_ref());
+
+        // Wrong program id
+        index.update_secondary_indexes(
+            &account_key,
+            &AccountSharedData::create(0, account_data.clone(), Pubkey::default(), false, 0),
+            &secondary_indexes,
+        );
+        assert!(index.memcmp_indexes.is_empty());
+
+        // Account data too short to hold the indexed bytes
+        index.update_secondary_indexes(
+            &account_key,
+            &AccountSharedData::create(0, account_data[1..].to_vec(), program_id, false, 0),
+            &secondary_indexes,
+        );
+        assert!(index.memcmp_indexes.is_empty());
+
+        // Just right. Inserting the same index multiple times should be ok
+        for _ in 0..2 {
+            index.update_secondary_indexes(
+                &account_key,
+                &AccountSharedData::create(0, account_data.clone(), program_id, false, 0),
+                &secondary_indexes,
+            );
+            check_secondary_index_mapping_correct(
+                index.memcmp_indexes.get(&memcmp_index).unwrap().value(),
+                &[index_key],
+                &account_key,
+            );
+        }
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::Memcmp(memcmp_index), &index_key),
+            Some(1)
+        );
+
+        // Everything should be deleted
+        let _ = index.handle_dead_keys(&[account_key], &secondary_indexes);
+        let memcmp_secondary_index = index.memcmp_indexes.get(&memcmp_index).unwrap();
+        assert!(memcmp_secondary_index.index.is_empty());
+        assert!(memcmp_secondary_index.reverse_index.is_empty());
+    }
+
     #[test]
     fn test_spl_token_owner_secondary_index() {
         let (key_start, key_end, secondary_indexes) =
@@ -1,11 +1,12 @@
 use {
     dashmap::{mapref::entry::Entry::Occupied, DashMap},
     log::*,
-    solana_pubkey::Pubkey,
+    solana_pubkey::{Pubkey, PUBKEY_BYTES},
     solana_time_utils::AtomicInterval,
     std::{
         collections::HashSet,
-        fmt::Debug,
+        fmt::{self, Debug, Display},
+        str::FromStr,
         sync::{
             atomic::{AtomicU64, Ordering},
             RwLock,
@@ -32,6 +33,13 @@ impl AccountSecondaryIndexes {
             None => true, // include all keys
         }
     }
+    /// Returns the enabled memcmp indexes
+    pub fn memcmp_indexes(&self) -> impl Iterator<Item = &MemcmpIndex> {
+        self.indexes.iter().filter_map(|index| match index {
+            AccountIndex::Memcmp(memcmp_index) => Some(memcmp_index),
+            _ => None,
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -45,6 +53,7 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    Memcmp(MemcmpIndex),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -52,6 +61,95 @@ pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    /// The key is built from the indexed bytes with `MemcmpIndex::key_from_memcmp()`
+    Memcmp(MemcmpIndex, Pubkey),
+}
+
+/// Index of the accounts owned by `program_id`, keyed by the `length` bytes
+/// of account data starting at `offset`.
+///
+/// Index keys are stored as pubkeys, so `length` is limited to
+/// `PUBKEY_BYTES`; shorter keys are zero-padded. As every key of an index has
+/// the same length, padding never makes two different keys collide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemcmpIndex {
+    pub program_id: Pubkey,
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl MemcmpIndex {
+    pub const MAX_LENGTH: usize = PUBKEY_BYTES;
+
+    pub fn new(program_id: Pubkey, offset: usize, length: usize) -> Result<Self, String> {
+        if length == 0 || length > Self::MAX_LENGTH {
+            return Err(format!(
+                "memcmp index length must be between 1 and {}, got {length}",
+                Self::MAX_LENGTH
+            ));
+        }
+        if offset.checked_add(length).is_none() {
+            return Err(format!("memcmp index offset {offset} is too large"));
+        }
+        Ok(Self {
+            program_id,
+            offset,
+            length,
+        })
+    }
+
+    /// Returns the index key of an account owned by `program_id`, or `None`
+    /// if the account data is too short to hold the indexed bytes
+    pub fn key_from_account_data(&self, data: &[u8]) -> Option<Pubkey> {
+        let end = self.offset.checked_add(self.length)?;
+        data.get(self.offset..end).map(Self::pad_key)
+    }
+
+    /// Returns the index key that serves a memcmp filter comparing `bytes` at
+    /// `offset`, or `None` if the filter does not cover the indexed bytes
+    pub fn key_from_memcmp(&self, offset: usize, bytes: &[u8]) -> Option<Pubkey> {
+        (offset == self.offset)
+            .then(|| bytes.get(..self.length))
+            .flatten()
+            .map(Self::pad_key)
+    }
+
+    fn pad_key(bytes: &[u8]) -> Pubkey {
+        let mut key = [0; PUBKEY_BYTES];
+        key[..bytes.len()].copy_from_slice(bytes);
+        Pubkey::new_from_array(key)
+    }
+}
+
+impl Display for MemcmpIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.program_id, self.offset, self.length)
+    }
+}
+
+/// Parses the `PROGRAM_ID:OFFSET:LENGTH` form produced by `Display`
+impl FromStr for MemcmpIndex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(program_id), Some(offset), Some(length), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!(
+                "invalid memcmp index {s}, expected PROGRAM_ID:OFFSET:LENGTH"
+            ));
+        };
+        let program_id = Pubkey::from_str(program_id)
+            .map_err(|err| format!("invalid memcmp index program id {program_id}: {err}"))?;
+        let offset = offset
+            .parse()
+            .map_err(|err| format!("invalid memcmp index offset {offset}: {err}"))?;
+        let length = length
+            .parse()
+            .map_err(|err| format!("invalid memcmp index length {length}: {err}"))?;
+        Self::new(program_id, offset, length)
+    }
 }
 
 // The only cases where an inner key should map to a different outer key is
@@ -283,3 +381,55 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .for_each(|(v, k)| info!("owner: {k}, accounts: {v}"));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memcmp_index_keys() {
+        let program_id = Pubkey::new_unique();
+        let index = MemcmpIndex::new(program_id, 2, 3).unwrap();
+        let padded_key = MemcmpIndex::pad_key(&[3, 4, 5]);
+        assert_eq!(&padded_key.as_ref()[..4], &[3, 4, 5, 0]);
+
+        assert_eq!(
+            index.key_from_account_data(&[1, 2, 3, 4, 5, 6]),
+            Some(padded_key)
+        );
+        assert_eq!(
+            index.key_from_account_data(&[1, 2, 3, 4, 5]),
+            Some(padded_key)
+        );
+        assert_eq!(index.key_from_account_data(&[1, 2, 3, 4]), None);
+
+        // The filter may compare more bytes than are indexed, but not fewer
+        assert_eq!(index.key_from_memcmp(2, &[3, 4, 5]), Some(padded_key));
+        assert_eq!(index.key_from_memcmp(2, &[3, 4, 5, 6]), Some(padded_key));
+        assert_eq!(index.key_from_memcmp(2, &[3, 4]), None);
+        assert_eq!(index.key_from_memcmp(1, &[2, 3, 4, 5]), None);
+    }
+
+    #[test]
+    fn test_memcmp_index_from_str() {
+        let program_id = Pubkey::new_unique();
+        let index = MemcmpIndex::new(program_id, 8, 32).unwrap();
+        assert_eq!(index.to_string().parse::<MemcmpIndex>(), Ok(index));
+        assert_eq!(
+            format!("{program_id}:8:32").parse::<MemcmpIndex>(),
+            Ok(index)
+        );
+
+        for invalid in [
+            format!("{program_id}:8"),
+            format!("{program_id}:8:32:1"),
+            format!("{program_id}:8:0"),
+            format!("{program_id}:8:33"),
+            format!("{program_id}:-1:32"),
+            format!("{program_id}:{}:32", usize::MAX),
+            "not-a-pubkey:8:32".to_string(),
+        ] {
+            assert!(invalid.parse::<MemcmpIndex>().is_err(), "{invalid}");
+        }
+    }
+}
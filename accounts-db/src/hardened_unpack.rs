@@ -353,7 +353,8 @@ pub fn unpack_snapshot<A: Read>(
         ledger_dir,
         account_paths,
         |file, path| {
-            unpacked_append_vec_map.insert(file.to_string(), path.join("accounts").join(file));
+            // Storage files are unpacked directly into the account paths
+            unpacked_append_vec_map.insert(file.to_string(), path.join(file));
         },
         |_| {},
     )
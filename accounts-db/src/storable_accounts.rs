@@ -209,6 +209,54 @@ impl<'a: 'b, 'b> StorableAccounts<'a> for (Slot, &'b [(Pubkey, AccountSharedData
     }
 }
 
+/// holds the accounts being rewritten FROM a single storage, keeping the storage's slot
+/// Account data is read from the storage as each account is stored, so only the metadata of the
+/// accounts is held in memory.
+pub struct StorableAccountsFromStorage<'a> {
+    storage: &'a AccountStorageEntry,
+    accounts: &'a [AccountFromStorage],
+}
+
+impl<'a> StorableAccountsFromStorage<'a> {
+    /// `accounts` must have been read from `storage`
+    pub fn new(storage: &'a AccountStorageEntry, accounts: &'a [AccountFromStorage]) -> Self {
+        Self { storage, accounts }
+    }
+}
+
+impl<'a> StorableAccounts<'a> for StorableAccountsFromStorage<'a> {
+    fn account<Ret>(
+        &self,
+        index: usize,
+        mut callback: impl for<'local> FnMut(AccountForStorage<'local>) -> Ret,
+    ) -> Ret {
+        self.storage
+            .accounts
+            .get_stored_account_callback(self.accounts[index].index_info.offset(), |account| {
+                callback((&account).into())
+            })
+            .expect("account has to exist to be able to store it")
+    }
+    fn is_zero_lamport(&self, index: usize) -> bool {
+        self.accounts[index].is_zero_lamport()
+    }
+    fn data_len(&self, index: usize) -> usize {
+        self.accounts[index].data_len()
+    }
+    fn pubkey(&self, index: usize) -> &Pubkey {
+        self.accounts[index].pubkey()
+    }
+    fn slot(&self, _index: usize) -> Slot {
+        self.target_slot()
+    }
+    fn target_slot(&self) -> Slot {
+        self.storage.slot()
+    }
+    fn len(&self) -> usize {
+        self.accounts.len()
+    }
+}
+
 /// holds slices of accounts being moved FROM a common source slot to 'target_slot'
 pub struct StorableAccountsBySlot<'a> {
     target_slot: Slot,
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 pub mod byte_block;
+pub mod cold;
 pub mod error;
 pub mod file;
 pub mod footer;
@@ -14,6 +15,7 @@ mod test_utils;
 
 use {
     crate::{accounts_file::StoredAccountsInfo, storable_accounts::StorableAccounts},
+    cold::{ColdStorageWriter, COLD_FORMAT},
     error::TieredStorageError,
     footer::{AccountBlockFormat, AccountMetaFormat},
     hot::{HotStorageWriter, HOT_FORMAT},
@@ -117,26 +119,29 @@ impl TieredStorage {
             panic!("cannot write same tiered storage file more than once");
         }
 
-        if format == &HOT_FORMAT {
-            let stored_accounts_info = {
-                let mut writer = HotStorageWriter::new(&self.path)?;
-                let stored_accounts_info = writer.write_accounts(accounts, skip)?;
-                writer.flush()?;
-                stored_accounts_info
-            };
-
-            // panic here if self.reader.get() is not None as self.reader can only be
-            // None since a false-value `was_written` indicates the accounts file has
-            // not been written previously, implying is_read_only() was also false.
-            debug_assert!(!self.is_read_only());
-            self.reader
-                .set(TieredStorageReader::new_from_path(&self.path)?)
-                .unwrap();
-
-            Ok(stored_accounts_info)
+        let stored_accounts_info = if format == &HOT_FORMAT {
+            let mut writer = HotStorageWriter::new(&self.path)?;
+            let stored_accounts_info = writer.write_accounts(accounts, skip)?;
+            writer.flush()?;
+            stored_accounts_info
+        } else if format == &COLD_FORMAT {
+            let mut writer = ColdStorageWriter::new(&self.path)?;
+            let stored_accounts_info = writer.write_accounts(accounts, skip)?;
+            writer.flush()?;
+            stored_accounts_info
         } else {
-            Err(TieredStorageError::UnknownFormat(self.path.to_path_buf()))
-        }
+            return Err(TieredStorageError::UnknownFormat(self.path.to_path_buf()));
+        };
+
+        // panic here if self.reader.get() is not None as self.reader can only be
+        // None since a false-value `was_written` indicates the accounts file has
+        // not been written previously, implying is_read_only() was also false.
+        debug_assert!(!self.is_read_only());
+        self.reader
+            .set(TieredStorageReader::new_from_path(&self.path)?)
+            .unwrap();
+
+        Ok(stored_accounts_info)
     }
 
     /// Returns the underlying reader of the TieredStorage.  None will be
@@ -340,7 +345,6 @@ mod tests {
     }
 
     /// The helper function for all write_accounts tests.
-    /// Currently only supports hot accounts.
     fn do_test_write_accounts(
         path_suffix: &str,
         account_data_sizes: &[u64],
@@ -437,4 +441,24 @@ mod tests {
             HOT_FORMAT.clone(),
         );
     }
+
+    #[test]
+    fn test_write_accounts_cold_mixed_size() {
+        do_test_write_accounts(
+            "test_write_accounts_cold_mixed_size",
+            &[
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 1000, 2000, 3000, 4000, 9, 8, 7, 6, 5, 4, 3, 2, 1,
+            ],
+            COLD_FORMAT.clone(),
+        );
+    }
+
+    #[test]
+    fn test_write_accounts_cold_one_max_len() {
+        do_test_write_accounts(
+            "test_write_accounts_cold_one_max_len",
+            &[MAX_PERMITTED_DATA_LENGTH],
+            COLD_FORMAT.clone(),
+        );
+    }
 }
@@ -0,0 +1,979 @@
+//! The account meta and related structs for cold accounts.
+//!
+//! Unlike hot accounts, cold accounts are packed into compressed account
+//! blocks that are shared by multiple accounts.  A cold accounts file
+//! consists of the following blocks:
+//!
+//! * account blocks: each one holds up to `COLD_ACCOUNT_BLOCK_SIZE` bytes of
+//!   accounts before compression, where each account is stored as its
+//!   ColdAccountMeta followed by its data and its optional fields.  An account
+//!   bigger than `COLD_ACCOUNT_BLOCK_SIZE` gets an account block of its own.
+//! * index block: the account addresses followed by their ColdAccountOffsets.
+//! * account block offsets: the offset of each account block in the file,
+//!   followed by the offset where the last account block ends.
+//! * owners block
+//! * footer
+//!
+//! As accessing any cold account requires decompressing its whole account
+//! block, the cold format trades read performance for storage size.  It is
+//! meant for accounts that are rarely accessed.
+
+use {
+    crate::{
+        account_info::{AccountInfo, Offset},
+        account_storage::stored_account_info::{StoredAccountInfo, StoredAccountInfoWithoutData},
+        accounts_file::StoredAccountsInfo,
+        tiered_storage::{
+            byte_block::{ByteBlockReader, ByteBlockWriter},
+            file::{TieredReadableFile, TieredWritableFile},
+            footer::{AccountBlockFormat, AccountMetaFormat, TieredStorageFooter},
+            hot::RENT_EXEMPT_RENT_EPOCH,
+            index::{AccountIndexWriterEntry, AccountOffset, IndexBlockFormat, IndexOffset},
+            meta::{
+                AccountAddressRange, AccountMetaFlags, AccountMetaOptionalFields, TieredAccountMeta,
+            },
+            mmap_utils::{get_pod, get_slice},
+            owners::{OwnerOffset, OwnersBlockFormat, OwnersTable},
+            StorableAccounts, TieredStorageError, TieredStorageFormat, TieredStorageResult,
+        },
+    },
+    bytemuck_derive::{Pod, Zeroable},
+    memmap2::{Mmap, MmapOptions},
+    solana_account::{AccountSharedData, ReadableAccount, WritableAccount},
+    solana_clock::Epoch,
+    solana_pubkey::Pubkey,
+    std::{io::Write, mem, path::Path},
+};
+
+pub const COLD_FORMAT: TieredStorageFormat = TieredStorageFormat {
+    meta_entry_size: std::mem::size_of::<ColdAccountMeta>(),
+    account_meta_format: AccountMetaFormat::Cold,
+    owners_block_format: OwnersBlockFormat::AddressesOnly,
+    index_block_format: IndexBlockFormat::AddressesThenOffsets,
+    account_block_format: AccountBlockFormat::Lz4,
+};
+
+/// The size of a cold account block before compression.  Accounts are
+/// appended to the current account block until the next account would
+/// exceed this size.
+pub(crate) const COLD_ACCOUNT_BLOCK_SIZE: usize = 64 * 1024;
+
+/// The alignment for the blocks that follow the account blocks inside a cold
+/// accounts file, so that they are readable under mmap.  Note that account
+/// blocks themselves are not aligned as they are compressed.
+pub(crate) const COLD_BLOCK_ALIGNMENT: usize = 8;
+
+/// The buffer that is used for padding.
+const PADDING_BUFFER: [u8; COLD_BLOCK_ALIGNMENT] = [0u8; COLD_BLOCK_ALIGNMENT];
+
+/// A helper function that creates a new default footer for cold
+/// accounts storage.
+fn new_cold_footer() -> TieredStorageFooter {
+    TieredStorageFooter {
+        account_meta_format: COLD_FORMAT.account_meta_format,
+        account_meta_entry_size: COLD_FORMAT.meta_entry_size as u32,
+        account_block_format: COLD_FORMAT.account_block_format,
+        account_block_size: COLD_ACCOUNT_BLOCK_SIZE as u64,
+        index_block_format: COLD_FORMAT.index_block_format,
+        owners_block_format: COLD_FORMAT.owners_block_format,
+        ..TieredStorageFooter::default()
+    }
+}
+
+/// The offset to access a cold account.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Pod, Zeroable)]
+pub struct ColdAccountOffset {
+    /// The index of the account block that contains the account.
+    block: u32,
+    /// The offset of the account meta inside the decompressed account block.
+    offset: u32,
+}
+
+// Ensure there are no implicit padding bytes
+const _: () = assert!(std::mem::size_of::<ColdAccountOffset>() == 8);
+
+impl AccountOffset for ColdAccountOffset {}
+
+/// The storage and in-memory representation of the metadata entry for a
+/// cold account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ColdAccountMeta {
+    /// The balance of this account.
+    lamports: u64,
+    /// The size of the data of this account.
+    account_data_size: u64,
+    /// The index to the owner of this account inside an AccountsFile.
+    owner_offset: u32,
+    /// Stores boolean flags and existence of each optional field.
+    flags: AccountMetaFlags,
+}
+
+// Ensure there are no implicit padding bytes
+const _: () = assert!(std::mem::size_of::<ColdAccountMeta>() == 8 + 8 + 4 + 4);
+
+impl TieredAccountMeta for ColdAccountMeta {
+    /// Construct a ColdAccountMeta instance.
+    fn new() -> Self {
+        ColdAccountMeta {
+            lamports: 0,
+            account_data_size: 0,
+            owner_offset: 0,
+            flags: AccountMetaFlags::new(),
+        }
+    }
+
+    /// A builder function that initializes lamports.
+    fn with_lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// A builder function that initializes the number of padding bytes
+    /// for the account data associated with the current meta.
+    fn with_account_data_padding(self, _padding: u8) -> Self {
+        // Cold accounts are not padded as they are read out of decompressed
+        // account blocks rather than directly from the mmap.
+        self
+    }
+
+    /// A builder function that initializes the owner's index.
+    fn with_owner_offset(mut self, owner_offset: OwnerOffset) -> Self {
+        self.owner_offset = owner_offset.0;
+        self
+    }
+
+    /// A builder function that initializes the account data size.
+    fn with_account_data_size(mut self, account_data_size: u64) -> Self {
+        self.account_data_size = account_data_size;
+        self
+    }
+
+    /// A builder function that initializes the AccountMetaFlags of the current
+    /// meta.
+    fn with_flags(mut self, flags: &AccountMetaFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Returns the balance of the lamports associated with the account.
+    fn lamports(&self) -> u64 {
+        self.lamports
+    }
+
+    /// Always returns 0 as cold accounts are not padded.
+    fn account_data_padding(&self) -> u8 {
+        0
+    }
+
+    /// Returns the index to the accounts' owner in the current AccountsFile.
+    fn owner_offset(&self) -> OwnerOffset {
+        OwnerOffset(self.owner_offset)
+    }
+
+    /// Returns the AccountMetaFlags of the current meta.
+    fn flags(&self) -> &AccountMetaFlags {
+        &self.flags
+    }
+
+    /// Always returns true as multiple cold accounts share one account block.
+    fn supports_shared_account_block() -> bool {
+        true
+    }
+
+    /// Returns the epoch that this account will next owe rent by parsing
+    /// the specified account block.  None will be returned if this account
+    /// does not persist this optional field.
+    fn rent_epoch(&self, account_block: &[u8]) -> Option<Epoch> {
+        self.flags()
+            .has_rent_epoch()
+            .then(|| {
+                let offset = self.optional_fields_offset(account_block)
+                    + AccountMetaOptionalFields::rent_epoch_offset(self.flags());
+                account_block
+                    .get(offset..offset.saturating_add(std::mem::size_of::<Epoch>()))
+                    .map(bytemuck::pod_read_unaligned::<Epoch>)
+            })
+            .flatten()
+    }
+
+    /// Returns the epoch that this account will next owe rent by parsing
+    /// the specified account block.  RENT_EXEMPT_RENT_EPOCH will be returned
+    /// if the account is rent-exempt.
+    ///
+    /// For a zero-lamport account, Epoch::default() will be returned to
+    /// default states of an AccountSharedData.
+    fn final_rent_epoch(&self, account_block: &[u8]) -> Epoch {
+        self.rent_epoch(account_block)
+            .unwrap_or(if self.lamports() != 0 {
+                RENT_EXEMPT_RENT_EPOCH
+            } else {
+                Epoch::default()
+            })
+    }
+
+    /// Returns the offset of the optional fields based on the specified account
+    /// block.
+    ///
+    /// The account block of a cold account starts right after its meta, and
+    /// may be followed by other accounts that share the same account block.
+    fn optional_fields_offset(&self, account_block: &[u8]) -> usize {
+        self.account_data_size(account_block)
+    }
+
+    /// Returns the length of the data associated to this account based on the
+    /// specified account block.
+    fn account_data_size(&self, _account_block: &[u8]) -> usize {
+        self.account_data_size as usize
+    }
+
+    /// Returns the data associated to this account based on the specified
+    /// account block.
+    fn account_data<'a>(&self, account_block: &'a [u8]) -> &'a [u8] {
+        &account_block[..self.account_data_size(account_block)]
+    }
+}
+
+/// An account block decoded by a ColdStorageReader, tagged with its index
+/// so that consecutive accounts sharing it are only decompressed once.
+type DecodedAccountBlock = Option<(u32, Vec<u8>)>;
+
+/// The reader to a cold accounts file.
+#[derive(Debug)]
+pub struct ColdStorageReader {
+    mmap: Mmap,
+    footer: TieredStorageFooter,
+}
+
+impl ColdStorageReader {
+    pub fn new(file: TieredReadableFile) -> TieredStorageResult<Self> {
+        let mmap = unsafe { MmapOptions::new().map(&file.0)? };
+        // Here we are copying the footer, as accessing any data in a
+        // TieredStorage instance requires accessing its Footer.
+        let footer = *TieredStorageFooter::new_from_mmap(&mmap)?;
+
+        Ok(Self { mmap, footer })
+    }
+
+    /// Returns the size of the underlying storage.
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Returns whether the underlying storage is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.len() as u64
+    }
+
+    /// Returns the footer of the underlying tiered-storage accounts file.
+    pub fn footer(&self) -> &TieredStorageFooter {
+        &self.footer
+    }
+
+    /// Returns the number of accounts inside the underlying tiered-storage
+    /// accounts file.
+    pub fn num_accounts(&self) -> usize {
+        self.footer.account_entry_count as usize
+    }
+
+    /// Returns the offset to the account given the specified index.
+    fn get_account_offset(
+        &self,
+        index_offset: IndexOffset,
+    ) -> TieredStorageResult<ColdAccountOffset> {
+        self.footer
+            .index_block_format
+            .get_account_offset::<ColdAccountOffset>(&self.mmap, &self.footer, index_offset)
+    }
+
+    /// Returns the address of the account associated with the specified index.
+    fn get_account_address(&self, index: IndexOffset) -> TieredStorageResult<&Pubkey> {
+        self.footer
+            .index_block_format
+            .get_account_address(&self.mmap, &self.footer, index)
+    }
+
+    /// Returns the address of the account owner given the specified
+    /// owner_offset.
+    fn get_owner_address(&self, owner_offset: OwnerOffset) -> TieredStorageResult<&Pubkey> {
+        self.footer
+            .owners_block_format
+            .get_owner_address(&self.mmap, &self.footer, owner_offset)
+    }
+
+    /// Returns the offset of the account block offsets, which directly
+    /// follow the index block.
+    fn account_block_offsets_offset(&self) -> usize {
+        self.footer.index_block_offset as usize
+            + self
+                .footer
+                .index_block_format
+                .entry_size::<ColdAccountOffset>()
+                * self.footer.account_entry_count as usize
+    }
+
+    /// Returns the number of account blocks.
+    fn num_account_blocks(&self) -> usize {
+        let num_account_block_offsets = (self.footer.owners_block_offset as usize)
+            .saturating_sub(self.account_block_offsets_offset())
+            / std::mem::size_of::<u64>();
+        // the last offset is where the last account block ends
+        num_account_block_offsets.saturating_sub(1)
+    }
+
+    /// Returns the offset of the specified account block.  Passing the number
+    /// of account blocks returns where the last account block ends.
+    fn get_account_block_offset(&self, block: u32) -> TieredStorageResult<usize> {
+        let offset =
+            self.account_block_offsets_offset() + std::mem::size_of::<u64>() * block as usize;
+        let (block_offset, _) = get_pod::<u64>(&self.mmap, offset)?;
+        Ok(*block_offset as usize)
+    }
+
+    /// Decompresses the specified account block.
+    fn decode_account_block(&self, block: u32) -> TieredStorageResult<Vec<u8>> {
+        if block as usize >= self.num_account_blocks() {
+            return Err(TieredStorageError::InvalidAccountBlock(block));
+        }
+        let start = self.get_account_block_offset(block)?;
+        let end = self.get_account_block_offset(block + 1)?;
+        if end < start {
+            return Err(TieredStorageError::InvalidAccountBlock(block));
+        }
+        let (encoded_block, _) = get_slice(&self.mmap, start, end - start)?;
+
+        match self.footer.account_block_format {
+            AccountBlockFormat::AlignedRaw => Ok(encoded_block.to_vec()),
+            format => Ok(ByteBlockReader::decode(format, encoded_block)?),
+        }
+    }
+
+    /// Returns the meta and the account block of the account associated with
+    /// the specified index.  The account block starts right after the meta.
+    ///
+    /// The account block containing the account is decoded into `decoded`,
+    /// unless it is the one already there.
+    fn get_account<'a>(
+        &self,
+        index_offset: IndexOffset,
+        decoded: &'a mut DecodedAccountBlock,
+    ) -> TieredStorageResult<(ColdAccountMeta, &'a [u8])> {
+        let ColdAccountOffset { block, offset } = self.get_account_offset(index_offset)?;
+        if decoded
+            .as_ref()
+            .is_none_or(|(decoded_block, _)| *decoded_block != block)
+        {
+            *decoded = Some((block, self.decode_account_block(block)?));
+        }
+        let Some((_, decoded_block)) = decoded.as_ref() else {
+            unreachable!("the account block was just decoded");
+        };
+
+        let meta_start = offset as usize;
+        let meta_end = meta_start.saturating_add(std::mem::size_of::<ColdAccountMeta>());
+        let meta = decoded_block
+            .get(meta_start..meta_end)
+            .map(bytemuck::pod_read_unaligned::<ColdAccountMeta>)
+            .ok_or(TieredStorageError::InvalidAccountBlock(block))?;
+        let account_block = &decoded_block[meta_end..];
+        let account_size = meta
+            .account_data_size(account_block)
+            .saturating_add(AccountMetaOptionalFields::size_from_flags(meta.flags()));
+        if account_size > account_block.len() {
+            return Err(TieredStorageError::InvalidAccountBlock(block));
+        }
+
+        Ok((meta, account_block))
+    }
+
+    /// Calls `callback` with the stored account at `offset`.
+    ///
+    /// Returns `None` if there is no account at `offset`, otherwise returns the result of
+    /// `callback` in `Some`.
+    ///
+    /// Note that the whole account block containing the account is decoded
+    /// even though the account data is not passed to `callback`.
+    pub fn get_stored_account_without_data_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        callback: impl for<'local> FnMut(StoredAccountInfoWithoutData<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        self.do_get_stored_account_without_data_callback(index_offset, &mut None, callback)
+    }
+
+    fn do_get_stored_account_without_data_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        decoded: &mut DecodedAccountBlock,
+        mut callback: impl for<'local> FnMut(StoredAccountInfoWithoutData<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        if index_offset.0 >= self.footer.account_entry_count {
+            return Ok(None);
+        }
+
+        let (meta, account_block) = self.get_account(index_offset, decoded)?;
+        let stored_account = StoredAccountInfoWithoutData {
+            pubkey: self.get_account_address(index_offset)?,
+            lamports: meta.lamports(),
+            owner: self.get_owner_address(meta.owner_offset())?,
+            data_len: meta.account_data_size(account_block),
+            executable: meta.flags().executable(),
+            rent_epoch: meta.final_rent_epoch(account_block),
+        };
+
+        Ok(Some(callback(stored_account)))
+    }
+
+    /// Calls `callback` with the stored account at `offset`.
+    ///
+    /// Returns `None` if there is no account at `offset`, otherwise returns the result of
+    /// `callback` in `Some`.
+    ///
+    /// This fn *does* load the account's data.
+    pub fn get_stored_account_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        callback: impl for<'local> FnMut(StoredAccountInfo<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        self.do_get_stored_account_callback(index_offset, &mut None, callback)
+    }
+
+    fn do_get_stored_account_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        decoded: &mut DecodedAccountBlock,
+        mut callback: impl for<'local> FnMut(StoredAccountInfo<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        if index_offset.0 >= self.footer.account_entry_count {
+            return Ok(None);
+        }
+
+        let (meta, account_block) = self.get_account(index_offset, decoded)?;
+        let stored_account = StoredAccountInfo {
+            pubkey: self.get_account_address(index_offset)?,
+            lamports: meta.lamports(),
+            owner: self.get_owner_address(meta.owner_offset())?,
+            data: meta.account_data(account_block),
+            executable: meta.flags().executable(),
+            rent_epoch: meta.final_rent_epoch(account_block),
+        };
+
+        Ok(Some(callback(stored_account)))
+    }
+
+    /// Returns the account located at the specified index offset.
+    pub fn get_account_shared_data(
+        &self,
+        index_offset: IndexOffset,
+    ) -> TieredStorageResult<Option<AccountSharedData>> {
+        self.get_stored_account_callback(index_offset, |stored_account| {
+            AccountSharedData::create(
+                stored_account.lamports,
+                stored_account.data.to_vec(),
+                *stored_account.owner,
+                stored_account.executable,
+                stored_account.rent_epoch,
+            )
+        })
+    }
+
+    /// iterate over all pubkeys
+    pub fn scan_pubkeys(&self, mut callback: impl FnMut(&Pubkey)) -> TieredStorageResult<()> {
+        for i in 0..self.footer.account_entry_count {
+            let address = self.get_account_address(IndexOffset(i))?;
+            callback(address);
+        }
+        Ok(())
+    }
+
+    /// Calculate the amount of storage required for an account with the passed
+    /// in data_len
+    pub(crate) fn calculate_stored_size(data_len: usize) -> usize {
+        stored_size(data_len)
+    }
+
+    /// for each offset in `sorted_offsets`, return the length of data stored in the account
+    pub(crate) fn get_account_data_lens(
+        &self,
+        sorted_offsets: &[usize],
+    ) -> TieredStorageResult<Vec<usize>> {
+        let mut decoded = None;
+        let mut result = Vec::with_capacity(sorted_offsets.len());
+        for &offset in sorted_offsets {
+            let index_offset = IndexOffset(AccountInfo::get_reduced_offset(offset));
+            let (meta, account_block) = self.get_account(index_offset, &mut decoded)?;
+            result.push(meta.account_data_size(account_block));
+        }
+        Ok(result)
+    }
+
+    /// Iterate over all accounts and call `callback` with each account.
+    ///
+    /// `callback` parameters:
+    /// * Offset: the offset within the file of this account
+    /// * StoredAccountInfoWithoutData: the account itself, without account data
+    ///
+    /// Note that account data is not read/passed to the callback.
+    pub fn scan_accounts_without_data(
+        &self,
+        mut callback: impl for<'local> FnMut(Offset, StoredAccountInfoWithoutData<'local>),
+    ) -> TieredStorageResult<()> {
+        let mut decoded = None;
+        for i in 0..self.footer.account_entry_count {
+            self.do_get_stored_account_without_data_callback(
+                IndexOffset(i),
+                &mut decoded,
+                |account| callback(AccountInfo::reduced_offset_to_offset(i), account),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Iterate over all accounts and call `callback` with each account.
+    ///
+    /// `callback` parameters:
+    /// * Offset: the offset within the file of this account
+    /// * StoredAccountInfo: the account itself, with account data
+    ///
+    /// Each account block is only decoded once, as accounts are stored in the
+    /// same order as they are indexed.
+    pub fn scan_accounts(
+        &self,
+        mut callback: impl for<'local> FnMut(Offset, StoredAccountInfo<'local>),
+    ) -> TieredStorageResult<()> {
+        let mut decoded = None;
+        for i in 0..self.footer.account_entry_count {
+            self.do_get_stored_account_callback(IndexOffset(i), &mut decoded, |account| {
+                callback(AccountInfo::reduced_offset_to_offset(i), account)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns a slice suitable for use when archiving cold storages
+    pub fn data_for_archive(&self) -> &[u8] {
+        self.mmap.as_ref()
+    }
+}
+
+/// return an approximation of the cost to store an account.
+/// Some fields like owner are shared across multiple accounts.
+fn stored_size(data_len: usize) -> usize {
+    data_len + std::mem::size_of::<Pubkey>()
+}
+
+/// The writer that creates a cold accounts file.
+#[derive(Debug)]
+pub struct ColdStorageWriter {
+    storage: TieredWritableFile,
+}
+
+impl ColdStorageWriter {
+    /// Create a new ColdStorageWriter with the specified path.
+    pub fn new(file_path: impl AsRef<Path>) -> TieredStorageResult<Self> {
+        Ok(Self {
+            storage: TieredWritableFile::new(file_path)?,
+        })
+    }
+
+    /// Appends an account with the specified information to the account
+    /// block being built, and returns its offset inside the account block.
+    fn write_account(
+        block_writer: &mut ByteBlockWriter,
+        lamports: u64,
+        owner_offset: OwnerOffset,
+        account_data: &[u8],
+        executable: bool,
+        rent_epoch: Option<Epoch>,
+    ) -> TieredStorageResult<usize> {
+        let optional_fields = AccountMetaOptionalFields { rent_epoch };
+
+        let mut flags = AccountMetaFlags::new_from(&optional_fields);
+        flags.set_executable(executable);
+
+        let meta = ColdAccountMeta::new()
+            .with_lamports(lamports)
+            .with_owner_offset(owner_offset)
+            .with_account_data_size(account_data.len() as u64)
+            .with_flags(&flags);
+
+        let offset = block_writer.raw_len();
+        block_writer.write_pod(&meta)?;
+        block_writer.write(account_data)?;
+        block_writer.write_optional_fields(&optional_fields)?;
+
+        Ok(offset)
+    }
+
+    /// Compresses and persists the accounts collected by `block_writer`, and
+    /// returns the number of bytes written.
+    fn write_account_block(&mut self, block_writer: ByteBlockWriter) -> TieredStorageResult<usize> {
+        let account_block = block_writer.finish()?;
+        Ok(self.storage.write_bytes(&account_block)?)
+    }
+
+    /// Persists `accounts` into the underlying cold accounts file associated
+    /// with this ColdStorageWriter.  The first `skip` number of accounts are
+    /// *not* persisted.
+    pub fn write_accounts<'a>(
+        &mut self,
+        accounts: &impl StorableAccounts<'a>,
+        skip: usize,
+    ) -> TieredStorageResult<StoredAccountsInfo> {
+        let mut footer = new_cold_footer();
+        let mut index = vec![];
+        let mut owners_table = OwnersTable::default();
+        let mut account_block_offsets = vec![];
+        let mut block_writer = ByteBlockWriter::new(footer.account_block_format);
+        let mut cursor = 0;
+        let mut address_range = AccountAddressRange::default();
+
+        let len = accounts.len();
+        let total_input_accounts = len.saturating_sub(skip);
+        let mut offsets = Vec::with_capacity(total_input_accounts);
+
+        // writing account blocks
+        for i in skip..len {
+            accounts.account_default_if_zero_lamport::<TieredStorageResult<()>>(i, |account| {
+                address_range.update(account.pubkey());
+
+                // only persist rent_epoch for those rent-paying accounts
+                let rent_epoch = (account.rent_epoch() != RENT_EXEMPT_RENT_EPOCH)
+                    .then_some(account.rent_epoch());
+                let account_size = std::mem::size_of::<ColdAccountMeta>()
+                    + account.data().len()
+                    + AccountMetaOptionalFields { rent_epoch }.size();
+
+                // Start a new account block if this account does not fit in
+                // the current one.  An account bigger than the account block
+                // size ends up in an account block of its own.
+                if block_writer.raw_len() > 0
+                    && block_writer.raw_len() + account_size > COLD_ACCOUNT_BLOCK_SIZE
+                {
+                    let full_block_writer = mem::replace(
+                        &mut block_writer,
+                        ByteBlockWriter::new(footer.account_block_format),
+                    );
+                    account_block_offsets.push(cursor as u64);
+                    cursor += self.write_account_block(full_block_writer)?;
+                }
+
+                let owner_offset = owners_table.insert(account.owner());
+                let offset = Self::write_account(
+                    &mut block_writer,
+                    account.lamports(),
+                    owner_offset,
+                    account.data(),
+                    account.executable(),
+                    rent_epoch,
+                )?;
+                let index_entry = AccountIndexWriterEntry {
+                    address: *account.pubkey(),
+                    offset: ColdAccountOffset {
+                        block: account_block_offsets.len() as u32,
+                        offset: offset as u32,
+                    },
+                };
+
+                offsets.push(index.len());
+                index.push(index_entry);
+                Ok(())
+            })?;
+        }
+        if block_writer.raw_len() > 0 {
+            account_block_offsets.push(cursor as u64);
+            cursor += self.write_account_block(block_writer)?;
+        }
+        account_block_offsets.push(cursor as u64);
+        footer.account_entry_count = total_input_accounts as u32;
+
+        // writing index block
+        // as account blocks are compressed, padding is needed to align it.
+        let padding_len = cursor.next_multiple_of(COLD_BLOCK_ALIGNMENT) - cursor;
+        cursor += self.storage.write_bytes(&PADDING_BUFFER[..padding_len])?;
+        footer.index_block_offset = cursor as u64;
+        cursor += footer
+            .index_block_format
+            .write_index_block(&mut self.storage, &index)?;
+
+        // writing account block offsets
+        assert!(cursor % COLD_BLOCK_ALIGNMENT == 0);
+        for account_block_offset in &account_block_offsets {
+            cursor += self.storage.write_pod(account_block_offset)?;
+        }
+
+        // writing owners block
+        footer.owners_block_offset = cursor as u64;
+        footer.owner_count = owners_table.len() as u32;
+        cursor += footer
+            .owners_block_format
+            .write_owners_block(&mut self.storage, &owners_table)?;
+
+        // writing footer
+        footer.min_account_address = address_range.min;
+        footer.max_account_address = address_range.max;
+        cursor += footer.write_footer_block(&mut self.storage)?;
+
+        Ok(StoredAccountsInfo {
+            offsets,
+            size: cursor,
+        })
+    }
+
+    /// Flushes any buffered data to the file
+    pub fn flush(&mut self) -> TieredStorageResult<()> {
+        self.storage
+            .0
+            .flush()
+            .map_err(TieredStorageError::FlushColdWriter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tiered_storage::{
+            file::TieredStorageMagicNumber,
+            test_utils::{create_test_account, verify_test_account},
+        },
+        assert_matches::assert_matches,
+        memoffset::offset_of,
+        solana_clock::Slot,
+        tempfile::TempDir,
+    };
+
+    #[test]
+    fn test_cold_account_meta_layout() {
+        assert_eq!(offset_of!(ColdAccountMeta, lamports), 0x00);
+        assert_eq!(offset_of!(ColdAccountMeta, account_data_size), 0x08);
+        assert_eq!(offset_of!(ColdAccountMeta, owner_offset), 0x10);
+        assert_eq!(offset_of!(ColdAccountMeta, flags), 0x14);
+        assert_eq!(std::mem::size_of::<ColdAccountMeta>(), 24);
+    }
+
+    #[test]
+    fn test_cold_account_meta() {
+        const TEST_LAMPORTS: u64 = 2314232137;
+        const TEST_OWNER_OFFSET: u32 = 0x1fef_1234;
+        const TEST_RENT_EPOCH: Epoch = 7;
+        let data = [0x22u8; 100];
+
+        let optional_fields = AccountMetaOptionalFields {
+            rent_epoch: Some(TEST_RENT_EPOCH),
+        };
+        let flags = AccountMetaFlags::new_from(&optional_fields);
+        let meta = ColdAccountMeta::new()
+            .with_lamports(TEST_LAMPORTS)
+            .with_owner_offset(OwnerOffset(TEST_OWNER_OFFSET))
+            .with_account_data_size(data.len() as u64)
+            .with_flags(&flags);
+
+        // The account block of a cold account may be followed by other
+        // accounts sharing the same account block.
+        let mut account_block = data.to_vec();
+        account_block.extend_from_slice(&TEST_RENT_EPOCH.to_le_bytes());
+        account_block.extend_from_slice(&[0xFFu8; 64]);
+
+        assert_eq!(meta.lamports(), TEST_LAMPORTS);
+        assert_eq!(meta.owner_offset(), OwnerOffset(TEST_OWNER_OFFSET));
+        assert_eq!(meta.account_data_padding(), 0);
+        assert_eq!(meta.account_data_size(&account_block), data.len());
+        assert_eq!(meta.account_data(&account_block), data);
+        assert_eq!(meta.rent_epoch(&account_block), Some(TEST_RENT_EPOCH));
+        assert_eq!(meta.final_rent_epoch(&account_block), TEST_RENT_EPOCH);
+        assert!(ColdAccountMeta::supports_shared_account_block());
+    }
+
+    /// Writes the accounts with the specified data sizes into a cold storage
+    /// file and verifies every account can be read back.
+    fn do_test_write_accounts(account_data_sizes: &[u64]) -> ColdStorageReader {
+        let accounts: Vec<_> = account_data_sizes
+            .iter()
+            .map(|size| create_test_account(*size))
+            .collect();
+
+        let account_refs: Vec<_> = accounts
+            .iter()
+            .map(|account| (&account.0.pubkey, &account.1))
+            .collect();
+
+        // Slot information is not used here
+        let storable_accounts = (Slot::MAX, &account_refs[..]);
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test_cold_write_accounts");
+        let stored_accounts_info = {
+            let mut writer = ColdStorageWriter::new(&path).unwrap();
+            let stored_accounts_info = writer.write_accounts(&storable_accounts, 0).unwrap();
+            writer.flush().unwrap();
+            stored_accounts_info
+        };
+
+        let file = TieredReadableFile::new(&path).unwrap();
+        let cold_storage = ColdStorageReader::new(file).unwrap();
+        assert_eq!(cold_storage.len(), stored_accounts_info.size);
+        assert_eq!(cold_storage.num_accounts(), account_data_sizes.len());
+
+        for offset in stored_accounts_info.offsets {
+            cold_storage
+                .get_stored_account_callback(IndexOffset(offset as u32), |stored_account| {
+                    storable_accounts.account_default_if_zero_lamport(offset, |account| {
+                        verify_test_account(
+                            &stored_account,
+                            &account.to_account_shared_data(),
+                            account.pubkey(),
+                        );
+                        assert_eq!(stored_account.rent_epoch(), account.rent_epoch());
+                    });
+                })
+                .unwrap()
+                .unwrap();
+        }
+        // Make sure it returns None on num_accounts to allow termination on
+        // while loop in actual accounts-db read case.
+        assert_matches!(
+            cold_storage.get_stored_account_callback(
+                IndexOffset(account_data_sizes.len() as u32),
+                |_| {
+                    panic!("unexpected");
+                }
+            ),
+            Ok(None)
+        );
+
+        let mut i = 0;
+        cold_storage
+            .scan_accounts(|offset, stored_account| {
+                assert_eq!(offset, AccountInfo::reduced_offset_to_offset(i as u32));
+                storable_accounts.account_default_if_zero_lamport(i, |account| {
+                    verify_test_account(
+                        &stored_account,
+                        &account.to_account_shared_data(),
+                        account.pubkey(),
+                    );
+                });
+                i += 1;
+            })
+            .unwrap();
+        assert_eq!(i, account_data_sizes.len());
+
+        let mut i = 0;
+        cold_storage
+            .scan_accounts_without_data(|_offset, stored_account| {
+                assert_eq!(
+                    stored_account.data_len, account_data_sizes[i] as usize,
+                    "account {i}"
+                );
+                i += 1;
+            })
+            .unwrap();
+        assert_eq!(i, account_data_sizes.len());
+
+        let offsets: Vec<_> = (0..account_data_sizes.len())
+            .map(|i| AccountInfo::reduced_offset_to_offset(i as u32))
+            .collect();
+        assert_eq!(
+            cold_storage.get_account_data_lens(&offsets).unwrap(),
+            account_data_sizes
+                .iter()
+                .map(|size| *size as usize)
+                .collect::<Vec<_>>(),
+        );
+
+        cold_storage
+    }
+
+    #[test]
+    fn test_write_accounts_shared_account_block() {
+        let account_data_sizes = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0, 100, 200];
+        let cold_storage = do_test_write_accounts(account_data_sizes);
+
+        // All the accounts fit in one account block
+        assert_eq!(cold_storage.num_account_blocks(), 1);
+        let footer = cold_storage.footer();
+        assert_eq!(footer.account_meta_format, AccountMetaFormat::Cold);
+        assert_eq!(footer.account_block_format, AccountBlockFormat::Lz4);
+        assert_eq!(footer.account_block_size, COLD_ACCOUNT_BLOCK_SIZE as u64);
+        assert_eq!(footer.index_block_offset as usize % COLD_BLOCK_ALIGNMENT, 0);
+
+        let expected_size = footer.owners_block_offset as usize
+            + std::mem::size_of::<Pubkey>() * footer.owner_count as usize
+            + std::mem::size_of::<TieredStorageFooter>()
+            + std::mem::size_of::<TieredStorageMagicNumber>();
+        assert_eq!(expected_size, cold_storage.len());
+    }
+
+    #[test]
+    fn test_write_accounts_multiple_account_blocks() {
+        // Accounts bigger than the account block size get an account block
+        // of their own, and the smaller ones are packed together.
+        let account_data_sizes = &[
+            10,
+            COLD_ACCOUNT_BLOCK_SIZE as u64 / 2,
+            COLD_ACCOUNT_BLOCK_SIZE as u64 / 2,
+            20,
+            COLD_ACCOUNT_BLOCK_SIZE as u64 * 2,
+            30,
+            40,
+        ];
+        let cold_storage = do_test_write_accounts(account_data_sizes);
+        assert_eq!(cold_storage.num_account_blocks(), 4);
+
+        let account_blocks: Vec<_> = (0..account_data_sizes.len())
+            .map(|i| {
+                cold_storage
+                    .get_account_offset(IndexOffset(i as u32))
+                    .unwrap()
+                    .block
+            })
+            .collect();
+        assert_eq!(account_blocks, vec![0, 0, 1, 1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_write_zero_accounts() {
+        let cold_storage = do_test_write_accounts(&[]);
+        assert_eq!(cold_storage.num_account_blocks(), 0);
+        assert_eq!(cold_storage.num_accounts(), 0);
+        assert_matches!(
+            cold_storage.get_account_shared_data(IndexOffset(0)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_compressed_account_blocks() {
+        // Cold account blocks are compressed, so highly compressible accounts
+        // take far less space than their data.
+        let account_data_sizes = vec![1000; 32];
+        let cold_storage = do_test_write_accounts(&account_data_sizes);
+        let total_data_size: u64 = account_data_sizes.iter().sum();
+        assert!((cold_storage.footer().index_block_offset) < total_data_size / 4);
+
+        let account = cold_storage
+            .get_account_shared_data(IndexOffset(5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.data(), vec![232u8; 1000]);
+    }
+
+    #[test]
+    fn test_cold_storage_writer_twice_on_same_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir
+            .path()
+            .join("test_cold_storage_writer_twice_on_same_path");
+
+        // Expect the first returns Ok
+        assert_matches!(ColdStorageWriter::new(&path), Ok(_));
+        // Expect the second call on the same path returns Err, as the
+        // ColdStorageWriter only writes once.
+        assert_matches!(ColdStorageWriter::new(&path), Err(_));
+    }
+}
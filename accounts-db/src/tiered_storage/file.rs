@@ -52,6 +52,19 @@ impl TieredReadableFile {
         ))
     }
 
+    /// Returns whether the file at `file_path` ends with [`FILE_MAGIC_NUMBER`], without
+    /// validating the rest of the file.
+    pub fn has_magic_number(file_path: impl AsRef<Path>) -> io::Result<bool> {
+        let file = Self(File::open(file_path)?);
+        if file.0.metadata()?.len() < mem::size_of::<TieredStorageMagicNumber>() as u64 {
+            return Ok(false);
+        }
+        file.seek_from_end(-(mem::size_of::<TieredStorageMagicNumber>() as i64))?;
+        let mut magic_number = TieredStorageMagicNumber::zeroed();
+        file.read_pod(&mut magic_number)?;
+        Ok(magic_number == TieredStorageMagicNumber::default())
+    }
+
     fn check_magic_number(&self) -> TieredStorageResult<()> {
         self.seek_from_end(-(std::mem::size_of::<TieredStorageMagicNumber>() as i64))?;
         let mut magic_number = TieredStorageMagicNumber::zeroed();
@@ -204,4 +217,21 @@ mod tests {
             Err(TieredStorageError::MagicNumberMismatch(_, _))
         ));
     }
+
+    #[test]
+    fn test_has_magic_number() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test_has_magic_number");
+        generate_test_file_with_number(&path, FILE_MAGIC_NUMBER);
+        assert!(TieredReadableFile::has_magic_number(&path).unwrap());
+
+        let path = temp_dir.path().join("test_has_magic_number_mismatch");
+        generate_test_file_with_number(&path, !FILE_MAGIC_NUMBER);
+        assert!(!TieredReadableFile::has_magic_number(&path).unwrap());
+
+        // Files too short to hold a magic number, like empty append vecs
+        let path = temp_dir.path().join("test_has_magic_number_empty");
+        std::fs::write(&path, []).unwrap();
+        assert!(!TieredReadableFile::has_magic_number(&path).unwrap());
+    }
 }
@@ -187,6 +187,7 @@ fn simulate_transaction(
         result,
         logs,
         post_simulation_accounts: _,
+        rollback_accounts: _,
         units_consumed,
         loaded_accounts_data_size,
         return_data,
@@ -196,6 +197,7 @@ fn simulate_transaction(
         post_balances: _,
         pre_token_balances: _,
         post_token_balances: _,
+        instruction_trace: _,
     } = bank.simulate_transaction_unchecked(&sanitized_transaction, true);
 
     let simulation_details = TransactionSimulationDetails {
@@ -18,7 +18,7 @@ use {
     solana_rpc_client_api::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig,
+            RpcProgramAccountsConfig, RpcTransactionSubscribeConfig, RpcTransactionSubscribeFilter,
         },
         response::SlotInfo,
     },
@@ -32,7 +32,7 @@ use {
     solana_streamer::socket::SocketAddrSpace,
     solana_system_interface::program as system_program,
     solana_system_transaction as system_transaction,
-    solana_test_validator::TestValidator,
+    solana_test_validator::{TestValidator, TestValidatorGenesis},
     solana_transaction_status::{
         BlockEncodingOptions, ConfirmedBlock, TransactionDetails, UiTransactionEncoding,
     },
@@ -319,6 +319,52 @@ fn test_block_subscription() {
     pubsub_service.close().unwrap();
 }
 
+#[test]
+fn test_transaction_subscription() {
+    solana_logger::setup();
+
+    let (test_validator, alice) = TestValidatorGenesis::default()
+        .pubsub_config(PubSubConfig {
+            enable_transaction_subscription: true,
+            ..PubSubConfig::default()
+        })
+        .start();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+
+    let (mut client, receiver) = PubsubClient::transaction_subscribe(
+        &test_validator.rpc_pubsub_url(),
+        RpcTransactionSubscribeFilter {
+            account_include: vec![bob.pubkey().to_string()],
+            ..RpcTransactionSubscribeFilter::default()
+        },
+        Some(RpcTransactionSubscribeConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+            encoding: Some(UiTransactionEncoding::Base64),
+            max_supported_transaction_version: Some(0),
+        }),
+    )
+    .unwrap();
+
+    let rpc_client = RpcClient::new(test_validator.rpc_url());
+    let blockhash = rpc_client.get_latest_blockhash().unwrap();
+    // Only the transfer to bob passes the filter
+    let carol_tx =
+        system_transaction::transfer(&alice, &carol.pubkey(), LAMPORTS_PER_SOL, blockhash);
+    rpc_client.send_transaction(&carol_tx).unwrap();
+    let bob_tx = system_transaction::transfer(&alice, &bob.pubkey(), LAMPORTS_PER_SOL, blockhash);
+    let signature = rpc_client.send_transaction(&bob_tx).unwrap();
+
+    let response = receiver.recv_timeout(Duration::from_secs(20)).unwrap();
+    assert_eq!(response.value.signature, signature.to_string());
+    assert!(!response.value.is_vote);
+    let transaction = response.value.transaction.transaction.decode().unwrap();
+    assert_eq!(transaction.signatures, bob_tx.signatures);
+    assert!(response.context.slot > 0);
+
+    client.shutdown().unwrap();
+}
+
 #[test]
 fn test_program_subscription() {
     let pubsub_addr = pubsub_addr();
@@ -605,6 +651,120 @@ async fn test_slot_subscription_async() {
     unsubscribe().await;
 }
 
+#[tokio::test]
+async fn test_multi_account_subscription_async() {
+    let sync_service = Arc::new(AtomicU64::new(0));
+    let sync_client = Arc::clone(&sync_service);
+
+    fn wait_until(atomic: &Arc<AtomicU64>, value: u64) {
+        let now = Instant::now();
+        while atomic.load(Ordering::Relaxed) != value {
+            if now.elapsed() > Duration::from_secs(5) {
+                panic!("wait for too long")
+            }
+            sleep(Duration::from_millis(1))
+        }
+    }
+
+    let pubsub_addr = pubsub_addr();
+    let bob = Keypair::new();
+    let bob_pubkey = bob.pubkey();
+
+    tokio::task::spawn_blocking(move || {
+        let exit = Arc::new(AtomicBool::new(false));
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair: alice,
+            ..
+        } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit.clone(),
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::default())),
+            optimistically_confirmed_bank,
+        ));
+        let (trigger, pubsub_service) =
+            PubSubService::new(PubSubConfig::default(), &subscriptions, pubsub_addr);
+
+        check_server_is_ready_or_panic(&pubsub_addr, 10, Duration::from_millis(100));
+
+        sync_service.store(1, Ordering::Relaxed);
+
+        wait_until(&sync_service, 2);
+        let tx = system_transaction::transfer(&alice, &bob_pubkey, 100, blockhash);
+        bank_forks
+            .read()
+            .unwrap()
+            .get(1)
+            .unwrap()
+            .process_transaction(&tx)
+            .unwrap();
+        subscriptions.notify_subscribers(CommitmentSlots {
+            slot: 1,
+            ..CommitmentSlots::default()
+        });
+        sync_service.store(3, Ordering::Relaxed);
+
+        wait_until(&sync_service, 4);
+        exit.store(true, Ordering::Relaxed);
+        trigger.cancel();
+        pubsub_service.close().unwrap();
+    });
+
+    wait_until(&sync_client, 1);
+    let url = format!("ws://0.0.0.0:{}/", pubsub_addr.port());
+    let pubsub_client = nonblocking::pubsub_client::PubsubClient::new(&url)
+        .await
+        .unwrap();
+    let config = Some(RpcAccountInfoConfig {
+        commitment: Some(CommitmentConfig::processed()),
+        encoding: None,
+        data_slice: None,
+        min_context_slot: None,
+    });
+    let (mut notifications, subscription_id, unsubscribe) = pubsub_client
+        .multi_account_subscribe(&[], config)
+        .await
+        .unwrap();
+    assert_eq!(
+        pubsub_client
+            .multi_account_add(subscription_id, &[bob_pubkey])
+            .await
+            .unwrap(),
+        1
+    );
+    sync_client.store(2, Ordering::Relaxed);
+
+    wait_until(&sync_client, 3);
+    let notification = tokio::time::timeout(Duration::from_secs(5), notifications.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(notification.context.slot, 1);
+    assert_eq!(notification.value.pubkey, bob_pubkey.to_string());
+    assert_eq!(notification.value.account.lamports, 100);
+    assert_eq!(
+        pubsub_client
+            .multi_account_remove(subscription_id, &[bob_pubkey])
+            .await
+            .unwrap(),
+        0
+    );
+    sync_client.store(4, Ordering::Relaxed);
+
+    unsubscribe().await;
+}
+
 fn check_server_is_ready_or_panic(
     socket_addr: &SocketAddr,
     mut retry: u8,
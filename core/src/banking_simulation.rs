@@ -48,6 +48,7 @@ use {
         fs::File,
         io::{self, BufRead, BufReader},
         net::{IpAddr, Ipv4Addr},
+        num::NonZeroUsize,
         path::PathBuf,
         sync::{
             atomic::{AtomicBool, Ordering},
@@ -143,7 +144,7 @@ type TimedBatchesToSend = Vec<(
 
 type EventSenderThread = JoinHandle<(TracedSender, TracedSender, TracedSender)>;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct BankingTraceEvents {
     packet_batches_by_time: PacketBatchesByTime,
     freeze_time_by_slot: FreezeTimeBySlot,
@@ -167,13 +168,25 @@ impl BankingTraceEvents {
     }
 
     pub fn load(event_file_paths: &[PathBuf]) -> Result<Self, SimulateError> {
-        let mut event_count = 0;
         let mut events = Self::default();
+        Self::read_event_files(event_file_paths, |event| events.load_event(event))?;
+        Ok(events)
+    }
+
+    /// Calls `callback` with each event of `event_file_paths`, in the order they are stored in
+    /// the files.
+    ///
+    /// Files truncated by an unclean validator shutdown are read up to their last complete event.
+    pub fn read_event_files(
+        event_file_paths: &[PathBuf],
+        mut callback: impl FnMut(TimedTracedEvent),
+    ) -> Result<(), SimulateError> {
+        let mut event_count = 0;
         for event_file_path in event_file_paths {
             let old_event_count = event_count;
             let read_result = Self::read_event_file(event_file_path, |event| {
                 event_count += 1;
-                events.load_event(event);
+                callback(event);
             });
             info!(
                 "Read {} events from {:?}",
@@ -200,7 +213,7 @@ impl BankingTraceEvents {
             }
         }
 
-        Ok(events)
+        Ok(())
     }
 
     fn load_event(&mut self, TimedTracedEvent(event_time, event): TimedTracedEvent) {
@@ -234,6 +247,42 @@ impl BankingTraceEvents {
     }
 }
 
+/// The block produced by the simulated leader for one of its slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedSlot {
+    pub slot: Slot,
+    pub transaction_count: u64,
+    /// Transaction fees collected, including priority fees
+    pub fees: u64,
+    pub priority_fees: u64,
+    pub block_cost: u64,
+    pub vote_cost: u64,
+    /// The writable account with the highest cost in the block
+    pub costliest_account: Pubkey,
+    pub costliest_account_cost: u64,
+    /// Number of writable accounts whose cost is close to the account cost limit
+    pub contended_account_count: usize,
+}
+
+impl SimulatedSlot {
+    fn new(bank: &Bank) -> Self {
+        let collector_fee_details = bank.get_collector_fee_details();
+        let cost_tracker = bank.read_cost_tracker().unwrap();
+        let (costliest_account, costliest_account_cost) = cost_tracker.find_costliest_account();
+        Self {
+            slot: bank.slot(),
+            transaction_count: bank.executed_transaction_count(),
+            fees: collector_fee_details.total_transaction_fee(),
+            priority_fees: collector_fee_details.total_priority_fee(),
+            block_cost: cost_tracker.block_cost(),
+            vote_cost: cost_tracker.vote_cost(),
+            costliest_account,
+            costliest_account_cost,
+            contended_account_count: cost_tracker.find_number_of_contended_accounts(),
+        }
+    }
+}
+
 struct DummyClusterInfo {
     // Artificially wrap Pubkey with RwLock to induce lock contention if any to mimic the real
     // ClusterInfo
@@ -425,7 +474,7 @@ impl SimulatorLoop {
         self,
         base_simulation_time: SystemTime,
         sender_thread: EventSenderThread,
-    ) -> (EventSenderThread, Sender<Slot>) {
+    ) -> (EventSenderThread, Sender<Slot>, Vec<SimulatedSlot>) {
         sleep(WARMUP_DURATION);
         info!("warmup done!");
         self.start(base_simulation_time, sender_thread)
@@ -435,7 +484,7 @@ impl SimulatorLoop {
         mut self,
         base_simulation_time: SystemTime,
         sender_thread: EventSenderThread,
-    ) -> (EventSenderThread, Sender<Slot>) {
+    ) -> (EventSenderThread, Sender<Slot>, Vec<SimulatedSlot>) {
         let logger = SimulatorLoopLogger {
             simulated_leader: self.simulated_leader,
             base_event_time: self.base_event_time,
@@ -443,6 +492,7 @@ impl SimulatorLoop {
             freeze_time_by_slot: self.freeze_time_by_slot,
         };
         let (mut bank, mut bank_created) = (self.bank, Instant::now());
+        let mut simulated_slots = vec![];
         loop {
             if self.poh_recorder.read().unwrap().bank().is_none() {
                 let next_leader_slot = self.leader_schedule_cache.next_leader_slot(
@@ -463,6 +513,10 @@ impl SimulatorLoop {
                     assert_matches!(result, Ok(()));
                 }
                 bank.freeze();
+                if bank.slot() != self.parent_slot && *bank.collector_id() == self.simulated_leader
+                {
+                    simulated_slots.push(SimulatedSlot::new(&bank));
+                }
                 let new_slot = if bank.slot() == self.parent_slot {
                     info!("initial leader block!");
                     self.first_simulated_slot
@@ -516,7 +570,7 @@ impl SimulatorLoop {
             sleep(Duration::from_millis(10));
         }
 
-        (sender_thread, self.retransmit_slots_sender)
+        (sender_thread, self.retransmit_slots_sender, simulated_slots)
     }
 }
 
@@ -692,6 +746,7 @@ impl BankingSimulator {
         blockstore: Arc<Blockstore>,
         block_production_method: BlockProductionMethod,
         transaction_struct: TransactionStructure,
+        num_workers: NonZeroUsize,
     ) -> (SenderLoop, SimulatorLoop, SimulatorThreads) {
         let parent_slot = self.parent_slot().unwrap();
         let mut packet_batches_by_time = self.banking_trace_events.packet_batches_by_time;
@@ -831,7 +886,7 @@ impl BankingSimulator {
             non_vote_receiver,
             tpu_vote_receiver,
             gossip_vote_receiver,
-            BankingStage::default_num_workers(),
+            num_workers,
             None,
             replay_vote_sender,
             None,
@@ -905,6 +960,7 @@ impl BankingSimulator {
         (sender_loop, simulator_loop, simulator_threads)
     }
 
+    /// Runs the simulation, returning the blocks produced by the simulated leader.
     pub fn start(
         self,
         genesis_config: GenesisConfig,
@@ -912,13 +968,15 @@ impl BankingSimulator {
         blockstore: Arc<Blockstore>,
         block_production_method: BlockProductionMethod,
         transaction_struct: TransactionStructure,
-    ) -> Result<(), SimulateError> {
+        num_workers: NonZeroUsize,
+    ) -> Result<Vec<SimulatedSlot>, SimulateError> {
         let (sender_loop, simulator_loop, simulator_threads) = self.prepare_simulation(
             genesis_config,
             bank_forks,
             blockstore,
             block_production_method,
             transaction_struct,
+            num_workers,
         );
 
         sender_loop.log_starting();
@@ -926,12 +984,12 @@ impl BankingSimulator {
         // Spawning and entering these two loops must be done at the same time as they're timed.
         // So, all the mundane setup must be done in advance.
         let sender_thread = sender_loop.spawn(base_simulation_time)?;
-        let (sender_thread, retransmit_slots_sender) =
+        let (sender_thread, retransmit_slots_sender, simulated_slots) =
             simulator_loop.enter(base_simulation_time, sender_thread);
 
         simulator_threads.finish(sender_thread, retransmit_slots_sender);
 
-        Ok(())
+        Ok(simulated_slots)
     }
 
     pub fn event_file_name(index: usize) -> String {
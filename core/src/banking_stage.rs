@@ -68,7 +68,11 @@ pub mod vote_storage;
 mod consume_worker;
 mod vote_worker;
 conditional_vis_mod!(decision_maker, feature = "dev-context-only-utils", pub);
-mod immutable_deserialized_packet;
+conditional_vis_mod!(
+    immutable_deserialized_packet,
+    feature = "dev-context-only-utils",
+    pub
+);
 mod latest_validator_vote_packet;
 mod leader_slot_timing_metrics;
 conditional_vis_mod!(packet_deserializer, feature = "dev-context-only-utils", pub);
@@ -100,8 +100,8 @@ use {
         rpc_completed_slots_service::RpcCompletedSlotsService,
         rpc_pubsub_service::{PubSubConfig, PubSubService},
         rpc_service::{JsonRpcService, JsonRpcServiceConfig},
-        rpc_subscriptions::RpcSubscriptions,
-        transaction_notifier_interface::TransactionNotifierArc,
+        rpc_subscriptions::{RpcSubscriptions, RpcTransactionNotifier},
+        transaction_notifier_interface::{TransactionNotifierArc, TransactionNotifiers},
         transaction_status_service::TransactionStatusService,
     },
     solana_runtime::{
@@ -778,6 +778,23 @@ impl Validator {
             entry_notifier.is_some()
         );
 
+        // `transactionSubscribe` is fed by the transaction status service, alongside geyser
+        let rpc_transaction_notifier = (config.rpc_addrs.is_some()
+            && config.rpc_config.full_api
+            && config.pubsub_config.enable_transaction_subscription)
+            .then(|| Arc::new(RpcTransactionNotifier::default()));
+        let transaction_notifier: Option<TransactionNotifierArc> =
+            match (transaction_notifier, &rpc_transaction_notifier) {
+                (Some(transaction_notifier), Some(rpc_transaction_notifier)) => {
+                    Some(Arc::new(TransactionNotifiers(vec![
+                        transaction_notifier,
+                        rpc_transaction_notifier.clone(),
+                    ])))
+                }
+                (None, Some(rpc_transaction_notifier)) => Some(rpc_transaction_notifier.clone()),
+                (transaction_notifier, None) => transaction_notifier,
+            };
+
         let system_monitor_service = Some(SystemMonitorService::new(
             exit.clone(),
             SystemMonitorStatsReportConfig {
@@ -1220,6 +1237,9 @@ impl Validator {
                 &config.pubsub_config,
                 None,
             ));
+            if let Some(rpc_transaction_notifier) = &rpc_transaction_notifier {
+                rpc_transaction_notifier.attach(&rpc_subscriptions);
+            }
             let pubsub_service = if !config.rpc_config.full_api {
                 None
             } else {
@@ -2135,9 +2155,9 @@ fn load_blockstore(
 
     let enable_rpc_transaction_history =
         config.rpc_addrs.is_some() && config.rpc_config.enable_rpc_transaction_history;
-    let is_plugin_transaction_history_required = transaction_notifier.as_ref().is_some();
+    let is_transaction_notifier_required = transaction_notifier.as_ref().is_some();
     let transaction_history_services =
-        if enable_rpc_transaction_history || is_plugin_transaction_history_required {
+        if enable_rpc_transaction_history || is_transaction_notifier_required {
             initialize_rpc_transaction_history_services(
                 blockstore.clone(),
                 exit.clone(),
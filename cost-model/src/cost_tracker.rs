@@ -290,7 +290,8 @@ impl CostTracker {
         );
     }
 
-    fn find_costliest_account(&self) -> (Pubkey, u64) {
+    /// Returns the writable account with the highest cost, and its cost.
+    pub fn find_costliest_account(&self) -> (Pubkey, u64) {
         self.cost_by_writable_accounts
             .iter()
             .max_by_key(|(_, &cost)| cost)
@@ -298,7 +299,8 @@ impl CostTracker {
             .unwrap_or_default()
     }
 
-    fn find_number_of_contended_accounts(&self) -> usize {
+    /// Returns the number of writable accounts whose cost is close to the account limit.
+    pub fn find_number_of_contended_accounts(&self) -> usize {
         // accounts has more than 95% of account_cu_limit is considered as highly contended
         let contended_cost_mark: u64 = self
             .account_cost_limit
@@ -72,6 +72,9 @@ pub fn download_snapshot_archive(
             config: ZstdConfig::default(),
         },
         ArchiveFormat::TarLz4,
+        ArchiveFormat::TarZstdSeekable {
+            config: ZstdConfig::default(),
+        },
     ] {
         let destination_path = match snapshot_kind {
             SnapshotKind::FullSnapshot => snapshot_utils::build_full_snapshot_archive_path(
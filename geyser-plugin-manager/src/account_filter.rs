@@ -0,0 +1,263 @@
+//! Module implementing the account filter a plugin may declare in its config
+//! file, so that account updates are filtered once by the plugin manager
+//! rather than by every plugin.
+//!
+//! The filter lives under the `account_filter` key of the plugin config, e.g.
+//!
+//! ```json5
+//! {
+//!     libpath: "libmy_geyser_plugin.so",
+//!     account_filter: {
+//!         owners: ["TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"],
+//!         pubkeys: ["SysvarC1ock11111111111111111111111111111111"],
+//!         filters: [{ dataSize: 165 }, { memcmp: { offset: 32, bytes: "..." } }],
+//!     },
+//! }
+//! ```
+//!
+//! An account is selected if its pubkey is in `pubkeys` or its owner is in
+//! `owners`; when both lists are empty every account is selected. Selected
+//! accounts must then pass all of the `filters`, which are the same filters
+//! accepted by the `getProgramAccounts` RPC method.
+
+use {
+    crate::geyser_plugin_manager::GeyserPluginManagerError,
+    agave_geyser_plugin_interface::geyser_plugin_interface::ReplicaAccountInfoV3,
+    serde_derive::Deserialize,
+    solana_pubkey::Pubkey,
+    solana_rpc_client_api::filter::{RpcFilterError, RpcFilterType},
+    spl_generic_token::{token::GenericTokenAccount, token_2022::Account},
+    std::{collections::HashSet, str::FromStr},
+};
+
+/// The config file key holding the account filter
+const ACCOUNT_FILTER_KEY: &str = "account_filter";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AccountFilterConfig {
+    owners: Vec<String>,
+    pubkeys: Vec<String>,
+    filters: Vec<RpcFilterType>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccountFilter {
+    owners: HashSet<Pubkey>,
+    pubkeys: HashSet<Pubkey>,
+    filters: Vec<RpcFilterType>,
+}
+
+impl AccountFilter {
+    /// Parses the account filter out of a plugin config, returning `None` if
+    /// the config does not declare one.
+    pub(crate) fn from_config(
+        config: &serde_json::Value,
+    ) -> Result<Option<Self>, GeyserPluginManagerError> {
+        let Some(value) = config.get(ACCOUNT_FILTER_KEY) else {
+            return Ok(None);
+        };
+        let config = serde_json::from_value::<AccountFilterConfig>(value.clone())
+            .map_err(|err| GeyserPluginManagerError::InvalidAccountFilter(err.to_string()))?;
+
+        let parse_pubkeys = |pubkeys: Vec<String>| {
+            pubkeys
+                .into_iter()
+                .map(|pubkey| {
+                    Pubkey::from_str(&pubkey).map_err(|err| {
+                        GeyserPluginManagerError::InvalidAccountFilter(format!(
+                            "invalid pubkey {pubkey}: {err}"
+                        ))
+                    })
+                })
+                .collect::<Result<HashSet<_>, _>>()
+        };
+        let owners = parse_pubkeys(config.owners)?;
+        let pubkeys = parse_pubkeys(config.pubkeys)?;
+
+        let mut filters = config.filters;
+        for filter in filters.iter_mut() {
+            filter
+                .verify()
+                .and_then(|()| convert_to_raw_bytes(filter))
+                .map_err(|err| GeyserPluginManagerError::InvalidAccountFilter(err.to_string()))?;
+        }
+
+        Ok(Some(Self {
+            owners,
+            pubkeys,
+            filters,
+        }))
+    }
+
+    /// Check if the account update should be passed on to the plugin
+    pub fn allows(&self, account: &ReplicaAccountInfoV3) -> bool {
+        self.is_selected(account)
+            && self
+                .filters
+                .iter()
+                .all(|filter| filter_allows(filter, account))
+    }
+
+    fn is_selected(&self, account: &ReplicaAccountInfoV3) -> bool {
+        if self.owners.is_empty() && self.pubkeys.is_empty() {
+            return true;
+        }
+        let contains = |pubkeys: &HashSet<Pubkey>, key: &[u8]| {
+            Pubkey::try_from(key).is_ok_and(|key| pubkeys.contains(&key))
+        };
+        contains(&self.pubkeys, account.pubkey) || contains(&self.owners, account.owner)
+    }
+}
+
+/// Decode memcmp bytes once here rather than for every account update
+fn convert_to_raw_bytes(filter: &mut RpcFilterType) -> Result<(), RpcFilterError> {
+    match filter {
+        RpcFilterType::Memcmp(compare) => compare.convert_to_raw_bytes(),
+        RpcFilterType::Or(filters) => filters.iter_mut().try_for_each(convert_to_raw_bytes),
+        _ => Ok(()),
+    }
+}
+
+fn filter_allows(filter: &RpcFilterType, account: &ReplicaAccountInfoV3) -> bool {
+    match filter {
+        RpcFilterType::DataSize(size) => account.data.len() as u64 == *size,
+        RpcFilterType::Memcmp(compare) => compare.bytes_match(account.data),
+        RpcFilterType::TokenAccountState => Account::valid_account_data(account.data),
+        RpcFilterType::ValueCmp(compare) => compare.value_matches(account.data),
+        RpcFilterType::DataSizeRange(range) => range.contains(account.data.len() as u64),
+        RpcFilterType::LamportsRange(range) => range.contains(account.lamports),
+        RpcFilterType::Or(filters) => filters.iter().any(|filter| filter_allows(filter, account)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        solana_rpc_client_api::filter::{Memcmp, RangeFilter},
+    };
+
+    fn account_info<'a>(
+        pubkey: &'a Pubkey,
+        owner: &'a Pubkey,
+        data: &'a [u8],
+    ) -> ReplicaAccountInfoV3<'a> {
+        ReplicaAccountInfoV3 {
+            pubkey: pubkey.as_ref(),
+            lamports: 1,
+            owner: owner.as_ref(),
+            executable: false,
+            rent_epoch: 0,
+            data,
+            write_version: 0,
+            txn: None,
+        }
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(
+            AccountFilter::from_config(&json!({ "libpath": "libplugin.so" })).unwrap(),
+            None
+        );
+
+        let owner = Pubkey::new_unique();
+        let filter = AccountFilter::from_config(&json!({
+            "libpath": "libplugin.so",
+            "account_filter": {
+                "owners": [owner.to_string()],
+                "filters": [
+                    { "dataSize": 3 },
+                    { "memcmp": { "offset": 1, "bytes": bs58::encode([2]).into_string() } },
+                ],
+            },
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            filter,
+            AccountFilter {
+                owners: HashSet::from([owner]),
+                pubkeys: HashSet::new(),
+                filters: vec![
+                    RpcFilterType::DataSize(3),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(1, vec![2])),
+                ],
+            }
+        );
+
+        // Memcmp bytes within or groups are decoded too
+        let filter = AccountFilter::from_config(&json!({
+            "account_filter": {
+                "filters": [{ "or": [
+                    { "lamportsRange": { "min": 1 } },
+                    { "memcmp": { "offset": 1, "bytes": bs58::encode([2]).into_string() } },
+                ] }],
+            },
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            filter.filters,
+            vec![RpcFilterType::Or(vec![
+                RpcFilterType::LamportsRange(RangeFilter::new(Some(1), None)),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(1, vec![2])),
+            ])]
+        );
+
+        for invalid in [
+            json!({ "account_filter": { "owners": ["not a pubkey"] } }),
+            json!({ "account_filter": { "owner": [] } }),
+            json!({ "account_filter": { "filters": [{ "dataSize": "three" }] } }),
+            json!({ "account_filter": { "filters": [{ "or": [] }] } }),
+            json!({
+                "account_filter": {
+                    "filters": [{ "memcmp": { "offset": 0, "bytes": "0OIl" } }],
+                },
+            }),
+        ] {
+            assert!(matches!(
+                AccountFilter::from_config(&invalid),
+                Err(GeyserPluginManagerError::InvalidAccountFilter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_allows() {
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let data = [1, 2, 3];
+
+        // An empty filter allows everything
+        let filter = AccountFilter::default();
+        assert!(filter.allows(&account_info(&pubkey, &owner, &data)));
+
+        // Either a matching pubkey or a matching owner selects the account
+        let filter = AccountFilter {
+            owners: HashSet::from([owner]),
+            pubkeys: HashSet::from([pubkey]),
+            filters: vec![],
+        };
+        assert!(filter.allows(&account_info(&pubkey, &other, &data)));
+        assert!(filter.allows(&account_info(&other, &owner, &data)));
+        assert!(!filter.allows(&account_info(&other, &other, &data)));
+
+        // Selected accounts must also pass every filter
+        let filter = AccountFilter {
+            owners: HashSet::from([owner]),
+            pubkeys: HashSet::new(),
+            filters: vec![
+                RpcFilterType::DataSize(3),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(1, vec![2, 3])),
+            ],
+        };
+        assert!(filter.allows(&account_info(&pubkey, &owner, &data)));
+        assert!(!filter.allows(&account_info(&pubkey, &owner, &[1, 2, 4])));
+        assert!(!filter.allows(&account_info(&pubkey, &owner, &[1, 2, 3, 4])));
+        assert!(!filter.allows(&account_info(&pubkey, &other, &data)));
+    }
+}
@@ -1,4 +1,5 @@
 use {
+    crate::account_filter::AccountFilter,
     agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
     jsonrpc_core::{ErrorCode, Result as JsonRpcResult},
     libloading::Library,
@@ -14,6 +15,7 @@ use {
 pub struct LoadedGeyserPlugin {
     name: String,
     plugin: Box<dyn GeyserPlugin>,
+    account_filter: Option<AccountFilter>,
     // NOTE: While we do not access the library, the plugin we have loaded most
     // certainly does. To ensure we don't SIGSEGV we must declare the library
     // after the plugin so the plugin is dropped first.
@@ -31,6 +33,7 @@ impl LoadedGeyserPlugin {
         Self {
             name: name.unwrap_or_else(|| plugin.name().to_owned()),
             plugin,
+            account_filter: None,
             library,
         }
     }
@@ -38,6 +41,15 @@ impl LoadedGeyserPlugin {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// The account filter declared in the plugin config, if any
+    pub fn account_filter(&self) -> Option<&AccountFilter> {
+        self.account_filter.as_ref()
+    }
+
+    pub fn set_account_filter(&mut self, account_filter: Option<AccountFilter>) {
+        self.account_filter = account_filter;
+    }
 }
 
 impl Deref for LoadedGeyserPlugin {
@@ -331,6 +343,9 @@ pub enum GeyserPluginManagerError {
 
     #[error("The GeyserPlugin on_load method failed (error: {0})")]
     PluginStartError(String),
+
+    #[error("Invalid account filter in the plugin config file (error: {0})")]
+    InvalidAccountFilter(String),
 }
 
 /// # Safety
@@ -389,6 +404,7 @@ pub(crate) fn load_plugin_from_config(
     }
 
     let plugin_name = result["name"].as_str().map(|s| s.to_owned());
+    let account_filter = AccountFilter::from_config(&result)?;
 
     let config_file = geyser_plugin_config_file
         .as_os_str()
@@ -404,10 +420,9 @@ pub(crate) fn load_plugin_from_config(
         let plugin_raw = constructor();
         (Box::from_raw(plugin_raw), lib)
     };
-    Ok((
-        LoadedGeyserPlugin::new(lib, plugin, plugin_name),
-        config_file,
-    ))
+    let mut plugin = LoadedGeyserPlugin::new(lib, plugin, plugin_name);
+    plugin.set_account_filter(account_filter);
+    Ok((plugin, config_file))
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+pub mod account_filter;
 pub mod accounts_update_notifier;
 pub mod block_metadata_notifier;
 pub mod block_metadata_notifier_interface;
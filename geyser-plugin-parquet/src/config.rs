@@ -0,0 +1,138 @@
+//! Configuration of the Parquet plugin.
+//!
+//! The config file is read with the same JSON5 parser the plugin manager uses,
+//! so the `libpath` and `name` fields the manager needs may live next to the
+//! plugin's own settings, e.g.
+//!
+//! ```json5
+//! {
+//!     libpath: "libagave_geyser_plugin_parquet.so",
+//!     name: "parquet",
+//!     output_dir: "/mnt/geyser-parquet",
+//!     slots_per_file: 1000,
+//!     accounts: true,
+//!     startup_accounts: false,
+//!     transactions: true,
+//!     entries: false,
+//!     blocks: true,
+//! }
+//! ```
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::{GeyserPluginError, Result},
+    serde_derive::Deserialize,
+    std::{fs, path::PathBuf},
+};
+
+const DEFAULT_SLOTS_PER_FILE: u64 = 1_000;
+const DEFAULT_ROW_GROUP_SIZE: usize = 16_384;
+const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub struct ParquetPluginConfig {
+    /// Directory the per-table sub-directories and Parquet files are written to
+    pub output_dir: PathBuf,
+
+    /// Number of consecutive slots covered by one file
+    pub slots_per_file: u64,
+
+    /// Maximum number of rows buffered in memory before a row group is written
+    pub row_group_size: usize,
+
+    /// Zstd level used to compress column chunks, or `None` to store them uncompressed
+    pub zstd_level: Option<i32>,
+
+    /// Write account updates
+    pub accounts: bool,
+
+    /// Also write the accounts restored from the snapshot at startup
+    pub startup_accounts: bool,
+
+    /// Write transactions
+    pub transactions: bool,
+
+    /// Write entries
+    pub entries: bool,
+
+    /// Write block metadata
+    pub blocks: bool,
+}
+
+impl Default for ParquetPluginConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("geyser-parquet"),
+            slots_per_file: DEFAULT_SLOTS_PER_FILE,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            zstd_level: Some(DEFAULT_ZSTD_LEVEL),
+            accounts: true,
+            startup_accounts: false,
+            transactions: true,
+            entries: false,
+            blocks: true,
+        }
+    }
+}
+
+impl ParquetPluginConfig {
+    pub fn load(config_file: &str) -> Result<Self> {
+        let contents = fs::read_to_string(config_file)?;
+        Self::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let config: Self =
+            json5::from_str(contents).map_err(|err| GeyserPluginError::ConfigFileReadError {
+                msg: format!("Failed to parse the plugin config: {err}"),
+            })?;
+        if config.slots_per_file == 0 {
+            return Err(GeyserPluginError::ConfigFileReadError {
+                msg: "slots_per_file must be greater than zero".to_string(),
+            });
+        }
+        if config.row_group_size == 0 {
+            return Err(GeyserPluginError::ConfigFileReadError {
+                msg: "row_group_size must be greater than zero".to_string(),
+            });
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = ParquetPluginConfig::parse(
+            r#"{
+                // fields consumed by the plugin manager are ignored
+                libpath: "libagave_geyser_plugin_parquet.so",
+                name: "parquet",
+                output_dir: "/tmp/parquet",
+                slots_per_file: 100,
+                zstd_level: null,
+                entries: true,
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            ParquetPluginConfig {
+                output_dir: PathBuf::from("/tmp/parquet"),
+                slots_per_file: 100,
+                zstd_level: None,
+                entries: true,
+                ..ParquetPluginConfig::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_config_invalid() {
+        assert!(ParquetPluginConfig::parse("{ slots_per_file: 0 }").is_err());
+        assert!(ParquetPluginConfig::parse("{ row_group_size: 0 }").is_err());
+        assert!(ParquetPluginConfig::parse("{ accounts: 1 }").is_err());
+    }
+}
@@ -0,0 +1,22 @@
+pub mod config;
+pub mod parquet_plugin;
+mod tables;
+mod writer;
+
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
+    parquet_plugin::ParquetPlugin,
+};
+
+/// Entry point looked up by the plugin manager when it loads this library.
+///
+/// # Safety
+///
+/// The returned pointer is owned by the caller, which must eventually
+/// reconstruct the `Box` with `Box::from_raw` to drop the plugin.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::<ParquetPlugin>::default();
+    Box::into_raw(plugin)
+}
@@ -0,0 +1,352 @@
+//! The Geyser plugin writing account updates, transactions, entries and block
+//! metadata to rolling Parquet files.
+use {
+    crate::{
+        config::ParquetPluginConfig,
+        tables::{AccountsTable, BlocksTable, EntriesTable, TransactionsTable},
+        writer::{SlotRangeWriter, Table, WriterOptions},
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, ReplicaBlockInfoVersions,
+        ReplicaEntryInfoVersions, ReplicaTransactionInfoVersions, Result, SlotStatus,
+    },
+    log::*,
+    parquet::{basic::Compression, errors::ParquetError},
+    solana_clock::Slot,
+    std::{fmt, sync::Mutex},
+};
+
+/// Table writers created by `on_load`, one per enabled table.
+struct Writers {
+    accounts: Option<Mutex<SlotRangeWriter<AccountsTable>>>,
+    transactions: Option<Mutex<SlotRangeWriter<TransactionsTable>>>,
+    entries: Option<Mutex<SlotRangeWriter<EntriesTable>>>,
+    blocks: Option<Mutex<SlotRangeWriter<BlocksTable>>>,
+}
+
+impl Writers {
+    fn new(config: &ParquetPluginConfig) -> std::result::Result<Self, ParquetError> {
+        let options = WriterOptions {
+            slots_per_file: config.slots_per_file,
+            row_group_size: config.row_group_size,
+            compression: match config.zstd_level {
+                Some(level) => WriterOptions::zstd_compression(level)?,
+                None => Compression::UNCOMPRESSED,
+            },
+        };
+        fn new_writer<T: Table>(
+            enabled: bool,
+            config: &ParquetPluginConfig,
+            options: &WriterOptions,
+        ) -> std::result::Result<Option<Mutex<SlotRangeWriter<T>>>, ParquetError> {
+            enabled
+                .then(|| SlotRangeWriter::new(&config.output_dir, options).map(Mutex::new))
+                .transpose()
+        }
+        Ok(Self {
+            accounts: new_writer(config.accounts, config, &options)?,
+            transactions: new_writer(config.transactions, config, &options)?,
+            entries: new_writer(config.entries, config, &options)?,
+            blocks: new_writer(config.blocks, config, &options)?,
+        })
+    }
+
+    fn close_if_rooted(&self, root: Slot) -> std::result::Result<(), ParquetError> {
+        fn close<T: Table>(
+            writer: &Option<Mutex<SlotRangeWriter<T>>>,
+            root: Slot,
+        ) -> std::result::Result<(), ParquetError> {
+            match writer {
+                Some(writer) => writer.lock().unwrap().close_if_rooted(root),
+                None => Ok(()),
+            }
+        }
+        close(&self.accounts, root)?;
+        close(&self.transactions, root)?;
+        close(&self.entries, root)?;
+        close(&self.blocks, root)
+    }
+
+    fn close(&self) {
+        fn close<T: Table>(writer: &Option<Mutex<SlotRangeWriter<T>>>) {
+            if let Some(writer) = writer {
+                if let Err(err) = writer.lock().unwrap().close() {
+                    error!("Failed to close {} parquet file: {err}", T::NAME);
+                }
+            }
+        }
+        close(&self.accounts);
+        close(&self.transactions);
+        close(&self.entries);
+        close(&self.blocks);
+    }
+}
+
+#[derive(Default)]
+pub struct ParquetPlugin {
+    config: ParquetPluginConfig,
+    writers: Option<Writers>,
+}
+
+impl fmt::Debug for ParquetPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetPlugin")
+            .field("config", &self.config)
+            .field("loaded", &self.writers.is_some())
+            .finish()
+    }
+}
+
+impl ParquetPlugin {
+    fn writers(&self) -> Result<&Writers> {
+        self.writers
+            .as_ref()
+            .ok_or_else(|| GeyserPluginError::Custom("the parquet plugin is not loaded".into()))
+    }
+}
+
+impl GeyserPlugin for ParquetPlugin {
+    fn setup_logger(&self, logger: &'static dyn log::Log, level: log::LevelFilter) -> Result<()> {
+        log::set_max_level(level);
+        if let Err(err) = log::set_logger(logger) {
+            return Err(GeyserPluginError::Custom(Box::new(err)));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "parquet"
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config = ParquetPluginConfig::load(config_file)?;
+        info!("Loading parquet plugin with config {config:?}");
+        let writers = Writers::new(&config).map_err(|err| GeyserPluginError::Custom(err.into()))?;
+        self.config = config;
+        self.writers = Some(writers);
+        Ok(())
+    }
+
+    fn on_unload(&mut self) {
+        if let Some(writers) = self.writers.take() {
+            writers.close();
+        }
+    }
+
+    fn update_account(
+        &self,
+        account: ReplicaAccountInfoVersions,
+        slot: Slot,
+        is_startup: bool,
+    ) -> Result<()> {
+        if is_startup && !self.config.startup_accounts {
+            return Ok(());
+        }
+        let Some(writer) = &self.writers()?.accounts else {
+            return Ok(());
+        };
+        writer
+            .lock()
+            .unwrap()
+            .append(slot, |table| {
+                table.push(slot, &account, is_startup);
+                Ok(())
+            })
+            .map_err(|err| GeyserPluginError::AccountsUpdateError {
+                msg: err.to_string(),
+            })
+    }
+
+    fn update_slot_status(
+        &self,
+        slot: Slot,
+        _parent: Option<u64>,
+        status: &SlotStatus,
+    ) -> Result<()> {
+        if *status != SlotStatus::Rooted {
+            return Ok(());
+        }
+        self.writers()?.close_if_rooted(slot).map_err(|err| {
+            GeyserPluginError::SlotStatusUpdateError {
+                msg: err.to_string(),
+            }
+        })
+    }
+
+    fn notify_transaction(
+        &self,
+        transaction: ReplicaTransactionInfoVersions,
+        slot: Slot,
+    ) -> Result<()> {
+        let Some(writer) = &self.writers()?.transactions else {
+            return Ok(());
+        };
+        writer
+            .lock()
+            .unwrap()
+            .append(slot, |table| table.push(slot, &transaction))
+            .map_err(|err| GeyserPluginError::TransactionUpdateError {
+                msg: err.to_string(),
+            })
+    }
+
+    fn notify_entry(&self, entry: ReplicaEntryInfoVersions) -> Result<()> {
+        let Some(writer) = &self.writers()?.entries else {
+            return Ok(());
+        };
+        writer
+            .lock()
+            .unwrap()
+            .append(EntriesTable::slot(&entry), |table| {
+                table.push(&entry);
+                Ok(())
+            })
+            .map_err(|err| GeyserPluginError::Custom(err.into()))
+    }
+
+    fn notify_block_metadata(&self, blockinfo: ReplicaBlockInfoVersions) -> Result<()> {
+        let Some(writer) = &self.writers()?.blocks else {
+            return Ok(());
+        };
+        writer
+            .lock()
+            .unwrap()
+            .append(BlocksTable::slot(&blockinfo), |table| {
+                table.push(&blockinfo);
+                Ok(())
+            })
+            .map_err(|err| GeyserPluginError::Custom(err.into()))
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        self.config.accounts
+    }
+
+    fn account_data_snapshot_notifications_enabled(&self) -> bool {
+        self.config.accounts && self.config.startup_accounts
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        self.config.transactions
+    }
+
+    fn entry_notifications_enabled(&self) -> bool {
+        self.config.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        agave_geyser_plugin_interface::geyser_plugin_interface::{
+            ReplicaAccountInfoV3, ReplicaBlockInfoV4,
+        },
+        solana_pubkey::Pubkey,
+        solana_transaction_status::RewardsAndNumPartitions,
+        std::{fs, path::Path},
+        tempfile::TempDir,
+    };
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parquet_plugin() {
+        let output_dir = TempDir::new().unwrap();
+        let config_file = output_dir.path().join("config.json");
+        fs::write(
+            &config_file,
+            format!(
+                r#"{{
+                    libpath: "libagave_geyser_plugin_parquet.so",
+                    output_dir: {:?},
+                    slots_per_file: 10,
+                }}"#,
+                output_dir.path().join("out")
+            ),
+        )
+        .unwrap();
+
+        let mut plugin = ParquetPlugin::default();
+        plugin
+            .on_load(config_file.to_str().unwrap(), false)
+            .unwrap();
+        assert!(plugin.account_data_notifications_enabled());
+        assert!(!plugin.account_data_snapshot_notifications_enabled());
+        assert!(!plugin.entry_notifications_enabled());
+
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        for slot in [1, 2, 11] {
+            let account = ReplicaAccountInfoV3 {
+                pubkey: pubkey.as_ref(),
+                lamports: 42,
+                owner: owner.as_ref(),
+                executable: false,
+                rent_epoch: u64::MAX,
+                data: &[1, 2, 3],
+                write_version: slot,
+                txn: None,
+            };
+            plugin
+                .update_account(ReplicaAccountInfoVersions::V0_0_3(&account), slot, false)
+                .unwrap();
+            // Startup accounts are not enabled in the config and are skipped
+            plugin
+                .update_account(ReplicaAccountInfoVersions::V0_0_3(&account), slot, true)
+                .unwrap();
+        }
+        let rewards = RewardsAndNumPartitions {
+            rewards: vec![],
+            num_partitions: None,
+        };
+        let block = ReplicaBlockInfoV4 {
+            parent_slot: 1,
+            parent_blockhash: "parent",
+            slot: 2,
+            blockhash: "block",
+            rewards: &rewards,
+            block_time: Some(1_700_000_000),
+            block_height: Some(2),
+            executed_transaction_count: 0,
+            entry_count: 1,
+        };
+        plugin
+            .notify_block_metadata(ReplicaBlockInfoVersions::V0_0_4(&block))
+            .unwrap();
+
+        // Rooting the last slot of the first range finalizes its files
+        plugin
+            .update_slot_status(9, Some(8), &SlotStatus::Rooted)
+            .unwrap();
+        let out_dir = output_dir.path().join("out");
+        assert_eq!(
+            file_names(&out_dir.join("accounts")),
+            vec![
+                "accounts-000000000000-000000000009-0.parquet",
+                "accounts-000000000010-000000000019-0.parquet.tmp",
+            ]
+        );
+        assert_eq!(
+            file_names(&out_dir.join("blocks")),
+            vec!["blocks-000000000000-000000000009-0.parquet"]
+        );
+        assert!(!out_dir.join("entries").exists());
+
+        plugin.on_unload();
+        assert_eq!(
+            file_names(&out_dir.join("accounts")),
+            vec![
+                "accounts-000000000000-000000000009-0.parquet",
+                "accounts-000000000010-000000000019-0.parquet",
+            ]
+        );
+    }
+}
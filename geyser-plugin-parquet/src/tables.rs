@@ -0,0 +1,447 @@
+//! Columnar row buffers for each of the tables written by the plugin.
+use {
+    crate::writer::{
+        string_value, unsigned_value, write_optional, write_required, OptionalColumn, Table,
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        ReplicaAccountInfoVersions, ReplicaBlockInfoVersions, ReplicaEntryInfoVersions,
+        ReplicaTransactionInfoVersions,
+    },
+    parquet::{
+        data_type::{BoolType, ByteArray, ByteArrayType, Int64Type},
+        errors::{ParquetError, Result},
+        file::writer::SerializedRowGroupWriter,
+    },
+    solana_clock::Slot,
+    solana_transaction::versioned::VersionedTransaction,
+    solana_transaction_status::TransactionStatusMeta,
+    std::fs::File,
+};
+
+fn base58_value(bytes: &[u8]) -> ByteArray {
+    string_value(bs58::encode(bytes).into_string())
+}
+
+/// One row per account update.
+#[derive(Default)]
+pub(crate) struct AccountsTable {
+    slot: Vec<i64>,
+    pubkey: Vec<ByteArray>,
+    owner: Vec<ByteArray>,
+    lamports: Vec<i64>,
+    executable: Vec<bool>,
+    rent_epoch: Vec<i64>,
+    data: Vec<ByteArray>,
+    write_version: Vec<i64>,
+    txn_signature: OptionalColumn<ByteArray>,
+    is_startup: Vec<bool>,
+}
+
+impl AccountsTable {
+    pub(crate) fn push(
+        &mut self,
+        slot: Slot,
+        account: &ReplicaAccountInfoVersions,
+        is_startup: bool,
+    ) {
+        let (pubkey, lamports, owner, executable, rent_epoch, data, write_version, signature) =
+            match account {
+                ReplicaAccountInfoVersions::V0_0_1(info) => (
+                    info.pubkey,
+                    info.lamports,
+                    info.owner,
+                    info.executable,
+                    info.rent_epoch,
+                    info.data,
+                    info.write_version,
+                    None,
+                ),
+                ReplicaAccountInfoVersions::V0_0_2(info) => (
+                    info.pubkey,
+                    info.lamports,
+                    info.owner,
+                    info.executable,
+                    info.rent_epoch,
+                    info.data,
+                    info.write_version,
+                    info.txn_signature.copied(),
+                ),
+                ReplicaAccountInfoVersions::V0_0_3(info) => (
+                    info.pubkey,
+                    info.lamports,
+                    info.owner,
+                    info.executable,
+                    info.rent_epoch,
+                    info.data,
+                    info.write_version,
+                    info.txn.map(|txn| *txn.signature()),
+                ),
+            };
+        self.slot.push(unsigned_value(slot));
+        self.pubkey.push(base58_value(pubkey));
+        self.owner.push(base58_value(owner));
+        self.lamports.push(unsigned_value(lamports));
+        self.executable.push(executable);
+        self.rent_epoch.push(unsigned_value(rent_epoch));
+        self.data.push(ByteArray::from(data.to_vec()));
+        self.write_version.push(unsigned_value(write_version));
+        self.txn_signature
+            .push(signature.map(|signature| string_value(signature.to_string())));
+        self.is_startup.push(is_startup);
+    }
+}
+
+impl Table for AccountsTable {
+    const NAME: &'static str = "accounts";
+    const SCHEMA: &'static str = "
+        message accounts {
+            required int64 slot (INTEGER(64,false));
+            required binary pubkey (STRING);
+            required binary owner (STRING);
+            required int64 lamports (INTEGER(64,false));
+            required boolean executable;
+            required int64 rent_epoch (INTEGER(64,false));
+            required binary data;
+            required int64 write_version (INTEGER(64,false));
+            optional binary txn_signature (STRING);
+            required boolean is_startup;
+        }
+    ";
+
+    fn len(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn write_row_group(
+        &mut self,
+        row_group: &mut SerializedRowGroupWriter<'_, File>,
+    ) -> Result<()> {
+        write_required::<Int64Type>(row_group, &mut self.slot)?;
+        write_required::<ByteArrayType>(row_group, &mut self.pubkey)?;
+        write_required::<ByteArrayType>(row_group, &mut self.owner)?;
+        write_required::<Int64Type>(row_group, &mut self.lamports)?;
+        write_required::<BoolType>(row_group, &mut self.executable)?;
+        write_required::<Int64Type>(row_group, &mut self.rent_epoch)?;
+        write_required::<ByteArrayType>(row_group, &mut self.data)?;
+        write_required::<Int64Type>(row_group, &mut self.write_version)?;
+        write_optional::<ByteArrayType>(row_group, &mut self.txn_signature)?;
+        write_required::<BoolType>(row_group, &mut self.is_startup)
+    }
+}
+
+/// One row per transaction; the full transaction is kept as its bincode
+/// serialized `VersionedTransaction`.
+#[derive(Default)]
+pub(crate) struct TransactionsTable {
+    slot: Vec<i64>,
+    index: OptionalColumn<i64>,
+    signature: Vec<ByteArray>,
+    is_vote: Vec<bool>,
+    err: OptionalColumn<ByteArray>,
+    fee: Vec<i64>,
+    compute_units_consumed: OptionalColumn<i64>,
+    transaction: Vec<ByteArray>,
+}
+
+impl TransactionsTable {
+    pub(crate) fn push(
+        &mut self,
+        slot: Slot,
+        transaction: &ReplicaTransactionInfoVersions,
+    ) -> Result<()> {
+        let (signature, is_vote, meta, index, serialized_transaction) = match transaction {
+            ReplicaTransactionInfoVersions::V0_0_1(info) => (
+                info.signature,
+                info.is_vote,
+                info.transaction_status_meta,
+                None,
+                serialize_transaction(&info.transaction.to_versioned_transaction())?,
+            ),
+            ReplicaTransactionInfoVersions::V0_0_2(info) => (
+                info.signature,
+                info.is_vote,
+                info.transaction_status_meta,
+                Some(info.index),
+                serialize_transaction(&info.transaction.to_versioned_transaction())?,
+            ),
+            ReplicaTransactionInfoVersions::V0_0_3(info) => (
+                info.signature,
+                info.is_vote,
+                info.transaction_status_meta,
+                Some(info.index),
+                serialize_transaction(info.transaction)?,
+            ),
+        };
+        let TransactionStatusMeta {
+            status,
+            fee,
+            compute_units_consumed,
+            ..
+        } = meta;
+
+        self.slot.push(unsigned_value(slot));
+        self.index.push(index.map(|index| index as i64));
+        self.signature.push(string_value(signature.to_string()));
+        self.is_vote.push(is_vote);
+        self.err.push(
+            status
+                .as_ref()
+                .err()
+                .map(|err| string_value(err.to_string())),
+        );
+        self.fee.push(unsigned_value(*fee));
+        self.compute_units_consumed
+            .push(compute_units_consumed.map(unsigned_value));
+        self.transaction
+            .push(ByteArray::from(serialized_transaction));
+        Ok(())
+    }
+}
+
+fn serialize_transaction(transaction: &VersionedTransaction) -> Result<Vec<u8>> {
+    bincode::serialize(transaction).map_err(|err| ParquetError::External(Box::new(err)))
+}
+
+impl Table for TransactionsTable {
+    const NAME: &'static str = "transactions";
+    const SCHEMA: &'static str = "
+        message transactions {
+            required int64 slot (INTEGER(64,false));
+            optional int64 index (INTEGER(64,false));
+            required binary signature (STRING);
+            required boolean is_vote;
+            optional binary err (STRING);
+            required int64 fee (INTEGER(64,false));
+            optional int64 compute_units_consumed (INTEGER(64,false));
+            required binary transaction;
+        }
+    ";
+
+    fn len(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn write_row_group(
+        &mut self,
+        row_group: &mut SerializedRowGroupWriter<'_, File>,
+    ) -> Result<()> {
+        write_required::<Int64Type>(row_group, &mut self.slot)?;
+        write_optional::<Int64Type>(row_group, &mut self.index)?;
+        write_required::<ByteArrayType>(row_group, &mut self.signature)?;
+        write_required::<BoolType>(row_group, &mut self.is_vote)?;
+        write_optional::<ByteArrayType>(row_group, &mut self.err)?;
+        write_required::<Int64Type>(row_group, &mut self.fee)?;
+        write_optional::<Int64Type>(row_group, &mut self.compute_units_consumed)?;
+        write_required::<ByteArrayType>(row_group, &mut self.transaction)
+    }
+}
+
+/// One row per entry.
+#[derive(Default)]
+pub(crate) struct EntriesTable {
+    slot: Vec<i64>,
+    index: Vec<i64>,
+    num_hashes: Vec<i64>,
+    hash: Vec<ByteArray>,
+    executed_transaction_count: Vec<i64>,
+    starting_transaction_index: OptionalColumn<i64>,
+}
+
+impl EntriesTable {
+    /// Returns the slot of the entry, which is not passed separately to
+    /// `notify_entry`.
+    pub(crate) fn slot(entry: &ReplicaEntryInfoVersions) -> Slot {
+        match entry {
+            ReplicaEntryInfoVersions::V0_0_1(info) => info.slot,
+            ReplicaEntryInfoVersions::V0_0_2(info) => info.slot,
+        }
+    }
+
+    pub(crate) fn push(&mut self, entry: &ReplicaEntryInfoVersions) {
+        let (slot, index, num_hashes, hash, executed_transaction_count, starting_index) =
+            match entry {
+                ReplicaEntryInfoVersions::V0_0_1(info) => (
+                    info.slot,
+                    info.index,
+                    info.num_hashes,
+                    info.hash,
+                    info.executed_transaction_count,
+                    None,
+                ),
+                ReplicaEntryInfoVersions::V0_0_2(info) => (
+                    info.slot,
+                    info.index,
+                    info.num_hashes,
+                    info.hash,
+                    info.executed_transaction_count,
+                    Some(info.starting_transaction_index),
+                ),
+            };
+        self.slot.push(unsigned_value(slot));
+        self.index.push(index as i64);
+        self.num_hashes.push(unsigned_value(num_hashes));
+        self.hash.push(base58_value(hash));
+        self.executed_transaction_count
+            .push(unsigned_value(executed_transaction_count));
+        self.starting_transaction_index
+            .push(starting_index.map(|index| index as i64));
+    }
+}
+
+impl Table for EntriesTable {
+    const NAME: &'static str = "entries";
+    const SCHEMA: &'static str = "
+        message entries {
+            required int64 slot (INTEGER(64,false));
+            required int64 index (INTEGER(64,false));
+            required int64 num_hashes (INTEGER(64,false));
+            required binary hash (STRING);
+            required int64 executed_transaction_count (INTEGER(64,false));
+            optional int64 starting_transaction_index (INTEGER(64,false));
+        }
+    ";
+
+    fn len(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn write_row_group(
+        &mut self,
+        row_group: &mut SerializedRowGroupWriter<'_, File>,
+    ) -> Result<()> {
+        write_required::<Int64Type>(row_group, &mut self.slot)?;
+        write_required::<Int64Type>(row_group, &mut self.index)?;
+        write_required::<Int64Type>(row_group, &mut self.num_hashes)?;
+        write_required::<ByteArrayType>(row_group, &mut self.hash)?;
+        write_required::<Int64Type>(row_group, &mut self.executed_transaction_count)?;
+        write_optional::<Int64Type>(row_group, &mut self.starting_transaction_index)
+    }
+}
+
+/// One row per block.
+#[derive(Default)]
+pub(crate) struct BlocksTable {
+    slot: Vec<i64>,
+    parent_slot: OptionalColumn<i64>,
+    blockhash: Vec<ByteArray>,
+    parent_blockhash: OptionalColumn<ByteArray>,
+    block_time: OptionalColumn<i64>,
+    block_height: OptionalColumn<i64>,
+    executed_transaction_count: OptionalColumn<i64>,
+    entry_count: OptionalColumn<i64>,
+    rewards_count: Vec<i64>,
+}
+
+impl BlocksTable {
+    /// Returns the slot of the block, which is not passed separately to
+    /// `notify_block_metadata`.
+    pub(crate) fn slot(block: &ReplicaBlockInfoVersions) -> Slot {
+        match block {
+            ReplicaBlockInfoVersions::V0_0_1(info) => info.slot,
+            ReplicaBlockInfoVersions::V0_0_2(info) => info.slot,
+            ReplicaBlockInfoVersions::V0_0_3(info) => info.slot,
+            ReplicaBlockInfoVersions::V0_0_4(info) => info.slot,
+        }
+    }
+
+    pub(crate) fn push(&mut self, block: &ReplicaBlockInfoVersions) {
+        let (slot, blockhash, rewards_count, block_time, block_height) = match block {
+            ReplicaBlockInfoVersions::V0_0_1(info) => (
+                info.slot,
+                info.blockhash,
+                info.rewards.len(),
+                info.block_time,
+                info.block_height,
+            ),
+            ReplicaBlockInfoVersions::V0_0_2(info) => (
+                info.slot,
+                info.blockhash,
+                info.rewards.len(),
+                info.block_time,
+                info.block_height,
+            ),
+            ReplicaBlockInfoVersions::V0_0_3(info) => (
+                info.slot,
+                info.blockhash,
+                info.rewards.len(),
+                info.block_time,
+                info.block_height,
+            ),
+            ReplicaBlockInfoVersions::V0_0_4(info) => (
+                info.slot,
+                info.blockhash,
+                info.rewards.rewards.len(),
+                info.block_time,
+                info.block_height,
+            ),
+        };
+        let (parent_slot, parent_blockhash, executed_transaction_count, entry_count) = match block {
+            ReplicaBlockInfoVersions::V0_0_1(_) => (None, None, None, None),
+            ReplicaBlockInfoVersions::V0_0_2(info) => (
+                Some(info.parent_slot),
+                Some(info.parent_blockhash),
+                Some(info.executed_transaction_count),
+                None,
+            ),
+            ReplicaBlockInfoVersions::V0_0_3(info) => (
+                Some(info.parent_slot),
+                Some(info.parent_blockhash),
+                Some(info.executed_transaction_count),
+                Some(info.entry_count),
+            ),
+            ReplicaBlockInfoVersions::V0_0_4(info) => (
+                Some(info.parent_slot),
+                Some(info.parent_blockhash),
+                Some(info.executed_transaction_count),
+                Some(info.entry_count),
+            ),
+        };
+        self.slot.push(unsigned_value(slot));
+        self.parent_slot.push(parent_slot.map(unsigned_value));
+        self.blockhash.push(string_value(blockhash));
+        self.parent_blockhash
+            .push(parent_blockhash.map(string_value));
+        self.block_time.push(block_time);
+        self.block_height.push(block_height.map(unsigned_value));
+        self.executed_transaction_count
+            .push(executed_transaction_count.map(unsigned_value));
+        self.entry_count.push(entry_count.map(unsigned_value));
+        self.rewards_count.push(rewards_count as i64);
+    }
+}
+
+impl Table for BlocksTable {
+    const NAME: &'static str = "blocks";
+    const SCHEMA: &'static str = "
+        message blocks {
+            required int64 slot (INTEGER(64,false));
+            optional int64 parent_slot (INTEGER(64,false));
+            required binary blockhash (STRING);
+            optional binary parent_blockhash (STRING);
+            optional int64 block_time;
+            optional int64 block_height (INTEGER(64,false));
+            optional int64 executed_transaction_count (INTEGER(64,false));
+            optional int64 entry_count (INTEGER(64,false));
+            required int64 rewards_count (INTEGER(64,false));
+        }
+    ";
+
+    fn len(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn write_row_group(
+        &mut self,
+        row_group: &mut SerializedRowGroupWriter<'_, File>,
+    ) -> Result<()> {
+        write_required::<Int64Type>(row_group, &mut self.slot)?;
+        write_optional::<Int64Type>(row_group, &mut self.parent_slot)?;
+        write_required::<ByteArrayType>(row_group, &mut self.blockhash)?;
+        write_optional::<ByteArrayType>(row_group, &mut self.parent_blockhash)?;
+        write_optional::<Int64Type>(row_group, &mut self.block_time)?;
+        write_optional::<Int64Type>(row_group, &mut self.block_height)?;
+        write_optional::<Int64Type>(row_group, &mut self.executed_transaction_count)?;
+        write_optional::<Int64Type>(row_group, &mut self.entry_count)?;
+        write_required::<Int64Type>(row_group, &mut self.rewards_count)
+    }
+}
@@ -0,0 +1,454 @@
+//! Rolling Parquet writers, one file per table per slot range.
+//!
+//! Rows are buffered in memory in columnar form and written out as a row group
+//! once `row_group_size` rows have accumulated. A file covers the slots
+//! `[first_slot, first_slot + slots_per_file)`; it is written to a temporary
+//! `.parquet.tmp` path and only renamed to its final `.parquet` name once it is
+//! closed, so readers never observe a partially written file.
+use {
+    log::*,
+    parquet::{
+        basic::{Compression, ZstdLevel},
+        data_type::{ByteArray, DataType},
+        errors::{ParquetError, Result},
+        file::{
+            properties::WriterProperties,
+            writer::{SerializedFileWriter, SerializedRowGroupWriter},
+        },
+        schema::{parser::parse_message_type, types::Type},
+    },
+    solana_clock::Slot,
+    std::{
+        fs::{self, File},
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+/// A set of buffered rows that can be written out as a Parquet row group.
+pub(crate) trait Table: Default {
+    /// Name of the table; used as the output sub-directory and file prefix.
+    const NAME: &'static str;
+
+    /// Parquet message type describing the table's columns.
+    const SCHEMA: &'static str;
+
+    /// Number of rows currently buffered.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes all buffered columns into `row_group`, in schema order, and
+    /// clears the buffer.
+    fn write_row_group(&mut self, row_group: &mut SerializedRowGroupWriter<'_, File>)
+        -> Result<()>;
+}
+
+/// The values of an optional column, together with their definition levels.
+#[derive(Debug)]
+pub(crate) struct OptionalColumn<T> {
+    values: Vec<T>,
+    def_levels: Vec<i16>,
+}
+
+impl<T> Default for OptionalColumn<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::default(),
+            def_levels: Vec::default(),
+        }
+    }
+}
+
+impl<T> OptionalColumn<T> {
+    pub(crate) fn push(&mut self, value: Option<T>) {
+        match value {
+            Some(value) => {
+                self.values.push(value);
+                self.def_levels.push(1);
+            }
+            None => self.def_levels.push(0),
+        }
+    }
+}
+
+/// Writes the next column of `row_group` from `values` and clears them.
+pub(crate) fn write_required<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    values: &mut Vec<T::T>,
+) -> Result<()> {
+    write_column::<T>(row_group, values, None)?;
+    values.clear();
+    Ok(())
+}
+
+/// Writes the next, nullable, column of `row_group` from `column` and clears it.
+pub(crate) fn write_optional<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    column: &mut OptionalColumn<T::T>,
+) -> Result<()> {
+    write_column::<T>(row_group, &column.values, Some(&column.def_levels))?;
+    column.values.clear();
+    column.def_levels.clear();
+    Ok(())
+}
+
+fn write_column<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    values: &[T::T],
+    def_levels: Option<&[i16]>,
+) -> Result<()> {
+    let mut column = row_group
+        .next_column()?
+        .ok_or_else(|| ParquetError::General("more columns written than in schema".to_string()))?;
+    column.typed::<T>().write_batch(values, def_levels, None)?;
+    column.close()
+}
+
+/// Converts a string into a Parquet `BYTE_ARRAY` value.
+pub(crate) fn string_value(value: impl Into<String>) -> ByteArray {
+    ByteArray::from(value.into().into_bytes())
+}
+
+/// Unsigned 64-bit values are stored as `INT64` annotated with
+/// `INTEGER(64,false)`, so they are written bit-for-bit as `i64`.
+pub(crate) fn unsigned_value(value: u64) -> i64 {
+    value as i64
+}
+
+/// Options shared by all table writers.
+#[derive(Clone, Debug)]
+pub(crate) struct WriterOptions {
+    pub(crate) slots_per_file: u64,
+    pub(crate) row_group_size: usize,
+    pub(crate) compression: Compression,
+}
+
+impl WriterOptions {
+    pub(crate) fn zstd_compression(level: i32) -> Result<Compression> {
+        Ok(Compression::ZSTD(ZstdLevel::try_new(level)?))
+    }
+}
+
+struct OpenFile {
+    first_slot: Slot,
+    tmp_path: PathBuf,
+    path: PathBuf,
+    writer: SerializedFileWriter<File>,
+}
+
+/// Writes the rows of one table into files partitioned by slot range.
+pub(crate) struct SlotRangeWriter<T: Table> {
+    directory: PathBuf,
+    schema: Arc<Type>,
+    properties: Arc<WriterProperties>,
+    slots_per_file: u64,
+    row_group_size: usize,
+    rows: T,
+    current: Option<OpenFile>,
+}
+
+impl<T: Table> SlotRangeWriter<T> {
+    pub(crate) fn new(output_dir: &Path, options: &WriterOptions) -> Result<Self> {
+        let directory = output_dir.join(T::NAME);
+        fs::create_dir_all(&directory)?;
+        let properties = WriterProperties::builder()
+            .set_compression(options.compression)
+            .set_max_row_group_size(options.row_group_size)
+            .build();
+        Ok(Self {
+            directory,
+            schema: Arc::new(parse_message_type(T::SCHEMA)?),
+            properties: Arc::new(properties),
+            slots_per_file: options.slots_per_file.max(1),
+            row_group_size: options.row_group_size.max(1),
+            rows: T::default(),
+            current: None,
+        })
+    }
+
+    /// Appends one row belonging to `slot`, rolling over to a new file first if
+    /// `slot` falls outside of the slot range of the currently open file.
+    ///
+    /// `push_row` must either push a value to every column or, on error, to none.
+    pub(crate) fn append(
+        &mut self,
+        slot: Slot,
+        push_row: impl FnOnce(&mut T) -> Result<()>,
+    ) -> Result<()> {
+        let first_slot = self.first_slot_of_range(slot);
+        if self.current.as_ref().map(|file| file.first_slot) != Some(first_slot) {
+            self.close()?;
+            self.open(first_slot)?;
+        }
+        push_row(&mut self.rows)?;
+        if self.rows.len() >= self.row_group_size {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Closes the currently open file if every slot in its range is at or below
+    /// `root`, which means no more rows are expected for it.
+    pub(crate) fn close_if_rooted(&mut self, root: Slot) -> Result<()> {
+        let last_slot = self
+            .current
+            .as_ref()
+            .map(|file| file.first_slot.saturating_add(self.slots_per_file - 1));
+        match last_slot {
+            Some(last_slot) if last_slot <= root => self.close(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes out any buffered rows and finalizes the currently open file.
+    pub(crate) fn close(&mut self) -> Result<()> {
+        self.flush_row_group()?;
+        let Some(OpenFile {
+            tmp_path,
+            path,
+            writer,
+            ..
+        }) = self.current.take()
+        else {
+            return Ok(());
+        };
+        let metadata = writer.close()?;
+        fs::rename(&tmp_path, &path)?;
+        info!(
+            "Wrote {} rows in {} row groups to {path:?}",
+            metadata.num_rows,
+            metadata.row_groups.len(),
+        );
+        Ok(())
+    }
+
+    fn first_slot_of_range(&self, slot: Slot) -> Slot {
+        slot - slot % self.slots_per_file
+    }
+
+    fn open(&mut self, first_slot: Slot) -> Result<()> {
+        let last_slot = first_slot.saturating_add(self.slots_per_file - 1);
+        let (tmp_path, path) = self.unused_file_paths(first_slot, last_slot);
+        let file = File::create(&tmp_path)?;
+        let writer = SerializedFileWriter::new(
+            file,
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+        self.current = Some(OpenFile {
+            first_slot,
+            tmp_path,
+            path,
+            writer,
+        });
+        Ok(())
+    }
+
+    /// A slot range may be reopened after it was closed, e.g. when a late
+    /// update arrives for a slot in an earlier range. Each reopening gets its
+    /// own file so that already finalized files are never overwritten.
+    fn unused_file_paths(&self, first_slot: Slot, last_slot: Slot) -> (PathBuf, PathBuf) {
+        let mut sequence = 0u32;
+        loop {
+            let file_name = format!(
+                "{}-{first_slot:012}-{last_slot:012}-{sequence}.parquet",
+                T::NAME
+            );
+            let path = self.directory.join(file_name);
+            let tmp_path = path.with_extension("parquet.tmp");
+            if !path.exists() && !tmp_path.exists() {
+                return (tmp_path, path);
+            }
+            sequence = sequence.saturating_add(1);
+        }
+    }
+
+    fn flush_row_group(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let Some(file) = self.current.as_mut() else {
+            return Err(ParquetError::General(format!(
+                "{} rows buffered without an open file",
+                T::NAME
+            )));
+        };
+        let mut row_group = file.writer.next_row_group()?;
+        self.rows.write_row_group(&mut row_group)?;
+        row_group.close()?;
+        Ok(())
+    }
+}
+
+impl<T: Table> Drop for SlotRangeWriter<T> {
+    fn drop(&mut self) {
+        if let Err(err) = self.close() {
+            error!("Failed to close {} parquet file: {err}", T::NAME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        parquet::{
+            data_type::Int64Type,
+            file::reader::{FileReader, SerializedFileReader},
+            record::Field,
+        },
+        tempfile::TempDir,
+    };
+
+    #[derive(Default)]
+    struct TestTable {
+        slot: Vec<i64>,
+        label: OptionalColumn<ByteArray>,
+    }
+
+    impl Table for TestTable {
+        const NAME: &'static str = "test";
+        const SCHEMA: &'static str = "
+            message test {
+                required int64 slot (INTEGER(64,false));
+                optional binary label (STRING);
+            }
+        ";
+
+        fn len(&self) -> usize {
+            self.slot.len()
+        }
+
+        fn write_row_group(
+            &mut self,
+            row_group: &mut SerializedRowGroupWriter<'_, File>,
+        ) -> Result<()> {
+            write_required::<Int64Type>(row_group, &mut self.slot)?;
+            write_optional::<parquet::data_type::ByteArrayType>(row_group, &mut self.label)
+        }
+    }
+
+    fn options() -> WriterOptions {
+        WriterOptions {
+            slots_per_file: 10,
+            row_group_size: 2,
+            compression: WriterOptions::zstd_compression(1).unwrap(),
+        }
+    }
+
+    fn append(writer: &mut SlotRangeWriter<TestTable>, slot: Slot, label: Option<&str>) {
+        writer
+            .append(slot, |table| {
+                table.slot.push(unsigned_value(slot));
+                table.label.push(label.map(string_value));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    fn parquet_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn read_rows(path: &Path) -> Vec<(u64, Option<String>)> {
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                let row = row.unwrap();
+                let mut columns = row.get_column_iter();
+                let slot = match columns.next().unwrap().1 {
+                    Field::ULong(slot) => *slot,
+                    field => panic!("unexpected slot field {field:?}"),
+                };
+                let label = match columns.next().unwrap().1 {
+                    Field::Str(label) => Some(label.clone()),
+                    Field::Null => None,
+                    field => panic!("unexpected label field {field:?}"),
+                };
+                (slot, label)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolls_over_by_slot_range() {
+        let output_dir = TempDir::new().unwrap();
+        let mut writer = SlotRangeWriter::<TestTable>::new(output_dir.path(), &options()).unwrap();
+        append(&mut writer, 3, Some("a"));
+        append(&mut writer, 4, None);
+        append(&mut writer, 9, Some("b"));
+        append(&mut writer, 12, Some("c"));
+
+        // The first range was closed on rollover, the second one is still in progress
+        let table_dir = output_dir.path().join("test");
+        assert_eq!(
+            parquet_files(&table_dir),
+            vec![
+                table_dir.join("test-000000000000-000000000009-0.parquet"),
+                table_dir.join("test-000000000010-000000000019-0.parquet.tmp"),
+            ]
+        );
+
+        writer.close().unwrap();
+        let files = parquet_files(&table_dir);
+        assert_eq!(
+            read_rows(&files[0]),
+            vec![
+                (3, Some("a".to_string())),
+                (4, None),
+                (9, Some("b".to_string())),
+            ]
+        );
+        assert_eq!(read_rows(&files[1]), vec![(12, Some("c".to_string()))]);
+    }
+
+    #[test]
+    fn test_reopened_range_does_not_overwrite() {
+        let output_dir = TempDir::new().unwrap();
+        let mut writer = SlotRangeWriter::<TestTable>::new(output_dir.path(), &options()).unwrap();
+        append(&mut writer, 1, Some("a"));
+        append(&mut writer, 11, Some("b"));
+        append(&mut writer, 2, Some("c"));
+        drop(writer);
+
+        let table_dir = output_dir.path().join("test");
+        let files = parquet_files(&table_dir);
+        assert_eq!(
+            files,
+            vec![
+                table_dir.join("test-000000000000-000000000009-0.parquet"),
+                table_dir.join("test-000000000000-000000000009-1.parquet"),
+                table_dir.join("test-000000000010-000000000019-0.parquet"),
+            ]
+        );
+        assert_eq!(read_rows(&files[1]), vec![(2, Some("c".to_string()))]);
+    }
+
+    #[test]
+    fn test_close_if_rooted() {
+        let output_dir = TempDir::new().unwrap();
+        let mut writer = SlotRangeWriter::<TestTable>::new(output_dir.path(), &options()).unwrap();
+        append(&mut writer, 5, Some("a"));
+
+        writer.close_if_rooted(8).unwrap();
+        assert!(writer.current.is_some());
+
+        writer.close_if_rooted(9).unwrap();
+        assert!(writer.current.is_none());
+        let table_dir = output_dir.path().join("test");
+        assert_eq!(
+            parquet_files(&table_dir),
+            vec![table_dir.join("test-000000000000-000000000009-0.parquet")]
+        );
+    }
+}
@@ -0,0 +1,905 @@
+//! The `banking-trace` subcommand
+
+use {
+    crate::{
+        banking_trace_scenario::{generate_trace, GeneratedTrace, Scenario},
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+        output::{
+            CliBankingTraceChannel, CliBankingTraceSummary, CliComputeUnitPriceDistribution,
+            CliFeePayerCount,
+        },
+        read_banking_trace_event_file_paths_or_exit,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    chrono::{DateTime, SecondsFormat, Utc},
+    clap::{value_t, value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_accounts_db::hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
+    solana_clap_utils::{
+        input_parsers::pubkeys_of,
+        input_validators::{is_parsable, is_pubkey},
+    },
+    solana_cli_output::OutputFormat,
+    solana_clock::Slot,
+    solana_core::{
+        banking_simulation::{BankingSimulator, BankingTraceEvents},
+        banking_stage::immutable_deserialized_packet::ImmutableDeserializedPacket,
+        banking_trace::{ChannelLabel, TimedTracedEvent, TracedEvent},
+    },
+    solana_genesis_config::DEFAULT_GENESIS_FILE,
+    solana_ledger::{
+        blockstore::{banking_trace_path, create_new_ledger},
+        blockstore_options::LedgerColumnOptions,
+    },
+    solana_perf::packet::{BytesPacket, PacketBatch, PacketRef},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_transaction::versioned::VersionedTransaction,
+    std::{
+        collections::{HashMap, HashSet},
+        fs::{self, File},
+        io::{self, BufWriter, Write},
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::SystemTime,
+    },
+};
+
+const CHANNEL_NAMES: &[&str] = &["non-vote", "tpu-vote", "gossip-vote"];
+
+pub fn banking_trace_subcommand<'a, 'b>() -> App<'a, 'b> {
+    let banking_trace_dir_arg = Arg::with_name("banking_trace_dir")
+        .long("banking-trace-dir")
+        .value_name("DIR")
+        .takes_value(true)
+        .help(
+            "Read the banking trace event files from DIR [default: the banking_trace directory of \
+             the ledger]",
+        );
+
+    SubCommand::with_name("banking-trace")
+        .about("Inspect, convert and generate banking trace event files")
+        .setting(AppSettings::InferSubcommands)
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("summarize")
+                .about(
+                    "Summarize packet rates per channel, duplicates, compute unit prices and fee \
+                     payers",
+                )
+                .arg(&banking_trace_dir_arg)
+                .arg(
+                    Arg::with_name("top")
+                        .long("top")
+                        .value_name("NUM")
+                        .takes_value(true)
+                        .validator(is_parsable::<usize>)
+                        .default_value("10")
+                        .help("Number of top fee payers to report"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Export the traced transactions, one record per packet")
+                .arg(&banking_trace_dir_arg)
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .takes_value(true)
+                        .possible_values(&["json", "csv"])
+                        .default_value("json")
+                        .help("Export as newline-delimited JSON or as CSV"),
+                )
+                .arg(
+                    Arg::with_name("output_file")
+                        .long("output-file")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .help("Write to FILE instead of stdout"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("filter")
+                .about("Write a reduced trace that only keeps the matching packets")
+                .long_about(
+                    "Write a reduced trace that only keeps the matching packets. Block and bank \
+                     hash events are always kept, so that the reduced trace can still be used by \
+                     simulate-block-production.",
+                )
+                .arg(&banking_trace_dir_arg)
+                .arg(
+                    Arg::with_name("output_dir")
+                        .long("output-dir")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Directory to write the reduced trace event file to"),
+                )
+                .arg(
+                    Arg::with_name("channel")
+                        .long("channel")
+                        .value_name("CHANNEL")
+                        .takes_value(true)
+                        .multiple(true)
+                        .possible_values(CHANNEL_NAMES)
+                        .help("Only keep the packets sent through CHANNEL"),
+                )
+                .arg(
+                    Arg::with_name("fee_payer")
+                        .long("fee-payer")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .multiple(true)
+                        .validator(is_pubkey)
+                        .help("Only keep the transactions paid by PUBKEY"),
+                )
+                .arg(
+                    Arg::with_name("account")
+                        .long("account")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .multiple(true)
+                        .validator(is_pubkey)
+                        .help(
+                            "Only keep the transactions which statically reference PUBKEY. \
+                             Accounts loaded from address lookup tables are not considered",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("no_votes")
+                        .long("no-votes")
+                        .takes_value(false)
+                        .help("Drop simple vote transactions"),
+                )
+                .arg(
+                    Arg::with_name("dedup")
+                        .long("dedup")
+                        .takes_value(false)
+                        .help("Drop the transactions which were already sent earlier"),
+                )
+                .arg(
+                    Arg::with_name("drop_invalid")
+                        .long("drop-invalid")
+                        .takes_value(false)
+                        .help(
+                            "Drop the packets discarded by sigverify and those which aren't valid \
+                             transactions",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("generate")
+                .about("Create a new ledger with a synthetic banking trace")
+                .long_about(
+                    "Create a new ledger at the --ledger path, along with a banking trace \
+                     generated from a JSON load scenario. The scenario describes the arrival \
+                     rate, account contention, compute unit prices and limits of the transactions \
+                     and the number of voters. The genesis only has one staked validator, so that \
+                     the trace can be replayed with simulate-block-production \
+                     --first-simulated-slot 1.",
+                )
+                .arg(
+                    Arg::with_name("scenario")
+                        .long("scenario")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .help("JSON load scenario [default: the default scenario]"),
+                ),
+        )
+}
+
+pub fn banking_trace_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    do_banking_trace_process_command(ledger_path, matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_banking_trace_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -> Result<()> {
+    match matches.subcommand() {
+        ("summarize", Some(arg_matches)) => {
+            let event_file_paths = event_file_paths(ledger_path, arg_matches);
+            let top = value_t_or_exit!(arg_matches, "top", usize);
+            let output_format = OutputFormat::from_matches(arg_matches, "output_format", false);
+
+            let mut summary = SummaryBuilder::default();
+            read_event_files(&event_file_paths, |event| summary.add_event(&event))?;
+            println!("{}", output_format.formatted_string(&summary.finish(top)));
+        }
+        ("export", Some(arg_matches)) => {
+            let event_file_paths = event_file_paths(ledger_path, arg_matches);
+            let output: Box<dyn Write> = match value_t!(arg_matches, "output_file", PathBuf) {
+                Ok(output_file) => Box::new(File::create(output_file)?),
+                Err(_) => Box::new(io::stdout().lock()),
+            };
+            let mut exporter = match arg_matches.value_of("format") {
+                Some("csv") => Exporter::Csv(csv::Writer::from_writer(output)),
+                _ => Exporter::Json(BufWriter::new(output)),
+            };
+
+            let mut stats = ExportStats::default();
+            let mut result = Ok(());
+            read_event_files(&event_file_paths, |event| {
+                if result.is_ok() {
+                    result = exporter.export_event(&event, &mut stats);
+                }
+            })?;
+            result?;
+            exporter.flush()?;
+            eprintln!(
+                "Exported {} transactions, skipped {} discarded and {} invalid packets",
+                stats.num_exported, stats.num_discarded, stats.num_invalid,
+            );
+        }
+        ("filter", Some(arg_matches)) => {
+            let event_file_paths = event_file_paths(ledger_path, arg_matches);
+            let output_dir = value_t_or_exit!(arg_matches, "output_dir", PathBuf);
+            let mut filter = PacketFilter {
+                channels: arg_matches
+                    .values_of("channel")
+                    .map(|channels| channels.map(parse_channel_label).collect()),
+                fee_payers: pubkeys_of(arg_matches, "fee_payer")
+                    .map(|pubkeys| pubkeys.into_iter().collect()),
+                accounts: pubkeys_of(arg_matches, "account")
+                    .map(|pubkeys| pubkeys.into_iter().collect()),
+                no_votes: arg_matches.is_present("no_votes"),
+                dedup: arg_matches.is_present("dedup"),
+                drop_invalid: arg_matches.is_present("drop_invalid"),
+                seen_signatures: HashSet::default(),
+            };
+
+            fs::create_dir_all(&output_dir)?;
+            let output_path = output_dir.join(BankingSimulator::event_file_name(0));
+            if output_path.exists() {
+                return Err(LedgerToolError::BadArgument(format!(
+                    "{} already exists",
+                    output_path.display()
+                )));
+            }
+            let mut writer = BufWriter::new(File::create(&output_path)?);
+
+            let mut stats = FilterStats::default();
+            let mut result = Ok(());
+            read_event_files(&event_file_paths, |event| {
+                if result.is_ok() {
+                    if let Some(event) = filter.filter_event(event, &mut stats) {
+                        result = bincode::serialize_into(&mut writer, &event);
+                    }
+                }
+            })?;
+            result.map_err(|err| {
+                LedgerToolError::Generic(format!("Failed to write trace event: {err}"))
+            })?;
+            writer.flush()?;
+            println!(
+                "Wrote {} of {} packets and {} block events to {}",
+                stats.num_kept_packets,
+                stats.num_packets,
+                stats.num_block_events,
+                output_path.display(),
+            );
+        }
+        ("generate", Some(arg_matches)) => {
+            if ledger_path.join(DEFAULT_GENESIS_FILE).exists() {
+                return Err(LedgerToolError::BadArgument(format!(
+                    "{} already contains a ledger",
+                    ledger_path.display()
+                )));
+            }
+            let scenario = match value_t!(arg_matches, "scenario", PathBuf) {
+                Ok(scenario_path) => serde_json::from_reader(File::open(scenario_path)?)?,
+                Err(_) => Scenario::default(),
+            };
+            scenario.verify().map_err(LedgerToolError::BadArgument)?;
+
+            let GeneratedTrace {
+                genesis_config,
+                leader,
+                events,
+                num_transactions,
+                num_votes,
+            } = generate_trace(&scenario);
+
+            fs::create_dir_all(ledger_path)?;
+            create_new_ledger(
+                ledger_path,
+                &genesis_config,
+                MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
+                LedgerColumnOptions::default(),
+            )?;
+            let banking_trace_dir = banking_trace_path(ledger_path);
+            fs::create_dir_all(&banking_trace_dir)?;
+            let output_path = banking_trace_dir.join(BankingSimulator::event_file_name(0));
+            let mut writer = BufWriter::new(File::create(&output_path)?);
+            for event in &events {
+                bincode::serialize_into(&mut writer, event).map_err(|err| {
+                    LedgerToolError::Generic(format!("Failed to write trace event: {err}"))
+                })?;
+            }
+            writer.flush()?;
+            println!(
+                "Created ledger {} with genesis hash {} and leader {leader}",
+                ledger_path.display(),
+                genesis_config.hash(),
+            );
+            println!(
+                "Wrote {num_transactions} transactions and {num_votes} votes for slots 1 to {} to \
+                 {}",
+                scenario.num_slots,
+                output_path.display(),
+            );
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+fn event_file_paths(ledger_path: &Path, arg_matches: &ArgMatches<'_>) -> Vec<PathBuf> {
+    let banking_trace_dir = value_t!(arg_matches, "banking_trace_dir", PathBuf)
+        .unwrap_or_else(|_| banking_trace_path(&canonicalize_ledger_path(ledger_path)));
+    read_banking_trace_event_file_paths_or_exit(banking_trace_dir)
+}
+
+fn read_event_files(
+    event_file_paths: &[PathBuf],
+    callback: impl FnMut(TimedTracedEvent),
+) -> Result<()> {
+    BankingTraceEvents::read_event_files(event_file_paths, callback).map_err(|err| {
+        LedgerToolError::Generic(format!("Failed to read banking trace events: {err}"))
+    })
+}
+
+fn parse_channel_label(name: &str) -> ChannelLabel {
+    match name {
+        "non-vote" => ChannelLabel::NonVote,
+        "tpu-vote" => ChannelLabel::TpuVote,
+        "gossip-vote" => ChannelLabel::GossipVote,
+        _ => unreachable!(),
+    }
+}
+
+fn channel_name(label: ChannelLabel) -> &'static str {
+    match label {
+        ChannelLabel::NonVote => "non-vote",
+        ChannelLabel::TpuVote => "tpu-vote",
+        ChannelLabel::GossipVote => "gossip-vote",
+        ChannelLabel::Dummy => "dummy",
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// A traced packet deserialized the same way banking stage does
+struct TracedTransaction {
+    signature: Signature,
+    packet: ImmutableDeserializedPacket,
+}
+
+impl TracedTransaction {
+    /// Returns `None` if the packet isn't a valid transaction, or if its compute budget
+    /// instructions are invalid
+    fn new(packet: PacketRef) -> Option<Self> {
+        let transaction: VersionedTransaction = packet.deserialize_slice(..).ok()?;
+        let signature = *transaction.signatures.first()?;
+        let packet = ImmutableDeserializedPacket::new(packet).ok()?;
+        Some(Self { signature, packet })
+    }
+
+    fn account_keys(&self) -> &[Pubkey] {
+        self.packet
+            .transaction()
+            .get_message()
+            .message
+            .static_account_keys()
+    }
+
+    fn fee_payer(&self) -> &Pubkey {
+        // Sanitized messages have at least one account key
+        &self.account_keys()[0]
+    }
+}
+
+#[derive(Default)]
+struct ChannelStats {
+    num_batches: usize,
+    num_packets: usize,
+    num_discarded: usize,
+    num_invalid: usize,
+}
+
+#[derive(Default)]
+struct SummaryBuilder {
+    first_event_time: Option<SystemTime>,
+    last_event_time: Option<SystemTime>,
+    slots: Vec<Slot>,
+    channels: HashMap<ChannelLabel, ChannelStats>,
+    seen_signatures: HashSet<Signature>,
+    num_transactions: usize,
+    num_vote_transactions: usize,
+    num_duplicates: usize,
+    compute_unit_prices: Vec<u64>,
+    fee_payers: HashMap<Pubkey, usize>,
+}
+
+impl SummaryBuilder {
+    fn add_event(&mut self, TimedTracedEvent(event_time, event): &TimedTracedEvent) {
+        // Events are mostly, but not strictly, ordered by time
+        self.first_event_time = Some(
+            self.first_event_time
+                .map_or(*event_time, |first| first.min(*event_time)),
+        );
+        self.last_event_time = Some(
+            self.last_event_time
+                .map_or(*event_time, |last| last.max(*event_time)),
+        );
+
+        match event {
+            TracedEvent::PacketBatch(label, batches) => {
+                let channel = self.channels.entry(*label).or_default();
+                channel.num_batches += 1;
+                for packet in batches.iter().flat_map(|batch| batch.iter()) {
+                    channel.num_packets += 1;
+                    if packet.meta().discard() {
+                        channel.num_discarded += 1;
+                        continue;
+                    }
+                    let Some(transaction) = TracedTransaction::new(packet) else {
+                        channel.num_invalid += 1;
+                        continue;
+                    };
+
+                    self.num_transactions += 1;
+                    if !self.seen_signatures.insert(transaction.signature) {
+                        self.num_duplicates += 1;
+                    }
+                    if transaction.packet.is_simple_vote() {
+                        self.num_vote_transactions += 1;
+                    } else {
+                        self.compute_unit_prices
+                            .push(transaction.packet.compute_unit_price());
+                        *self.fee_payers.entry(*transaction.fee_payer()).or_default() += 1;
+                    }
+                }
+            }
+            TracedEvent::BlockAndBankHash(slot, _blockhash, _bank_hash) => {
+                self.slots.push(*slot);
+            }
+        }
+    }
+
+    fn finish(self, top: usize) -> CliBankingTraceSummary {
+        let Self {
+            first_event_time,
+            last_event_time,
+            slots,
+            channels,
+            num_transactions,
+            num_vote_transactions,
+            num_duplicates,
+            mut compute_unit_prices,
+            fee_payers,
+            ..
+        } = self;
+        let duration_secs = first_event_time
+            .zip(last_event_time)
+            .and_then(|(first, last)| last.duration_since(first).ok())
+            .unwrap_or_default()
+            .as_secs_f64();
+        let per_second = |count: usize| {
+            if duration_secs > 0.0 {
+                count as f64 / duration_secs
+            } else {
+                0.0
+            }
+        };
+
+        let mut channels = channels
+            .into_iter()
+            .map(|(label, stats)| CliBankingTraceChannel {
+                label: channel_name(label).to_string(),
+                num_batches: stats.num_batches,
+                num_packets: stats.num_packets,
+                num_discarded: stats.num_discarded,
+                num_invalid: stats.num_invalid,
+                packets_per_second: per_second(stats.num_packets),
+            })
+            .collect::<Vec<_>>();
+        channels.sort_by(|a, b| a.label.cmp(&b.label));
+
+        compute_unit_prices.sort_unstable();
+        let compute_unit_prices = (!compute_unit_prices.is_empty()).then(|| {
+            let percentile =
+                |p: usize| compute_unit_prices[(compute_unit_prices.len() - 1) * p / 100];
+            CliComputeUnitPriceDistribution {
+                num_transactions: compute_unit_prices.len(),
+                num_with_priority_fee: compute_unit_prices
+                    .iter()
+                    .filter(|price| **price > 0)
+                    .count(),
+                min: percentile(0),
+                p25: percentile(25),
+                median: percentile(50),
+                p75: percentile(75),
+                p90: percentile(90),
+                p99: percentile(99),
+                max: percentile(100),
+            }
+        });
+
+        let mut top_fee_payers = fee_payers
+            .into_iter()
+            .map(|(fee_payer, num_transactions)| CliFeePayerCount {
+                fee_payer: fee_payer.to_string(),
+                num_transactions,
+            })
+            .collect::<Vec<_>>();
+        top_fee_payers.sort_by(|a, b| {
+            b.num_transactions
+                .cmp(&a.num_transactions)
+                .then_with(|| a.fee_payer.cmp(&b.fee_payer))
+        });
+        top_fee_payers.truncate(top);
+
+        CliBankingTraceSummary {
+            first_event_time: first_event_time.map(format_time),
+            last_event_time: last_event_time.map(format_time),
+            duration_secs,
+            first_slot: slots.iter().min().copied(),
+            last_slot: slots.iter().max().copied(),
+            num_slots: slots.len(),
+            channels,
+            num_transactions,
+            num_vote_transactions,
+            num_duplicates,
+            duplicate_ratio: if num_transactions > 0 {
+                num_duplicates as f64 / num_transactions as f64
+            } else {
+                0.0
+            },
+            compute_unit_prices,
+            top_fee_payers,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ExportedTransaction {
+    time: String,
+    channel: &'static str,
+    signature: String,
+    fee_payer: String,
+    num_account_keys: usize,
+    is_simple_vote: bool,
+    forwarded: bool,
+    compute_unit_limit: u64,
+    compute_unit_price: u64,
+    /// The base64 encoded wire transaction
+    transaction: String,
+}
+
+#[derive(Default)]
+struct ExportStats {
+    num_exported: usize,
+    num_discarded: usize,
+    num_invalid: usize,
+}
+
+enum Exporter<W: Write> {
+    Json(BufWriter<W>),
+    Csv(csv::Writer<W>),
+}
+
+impl<W: Write> Exporter<W> {
+    fn export_event(
+        &mut self,
+        TimedTracedEvent(event_time, event): &TimedTracedEvent,
+        stats: &mut ExportStats,
+    ) -> Result<()> {
+        let TracedEvent::PacketBatch(label, batches) = event else {
+            return Ok(());
+        };
+        for packet in batches.iter().flat_map(|batch| batch.iter()) {
+            let Some(data) = packet.data(..) else {
+                stats.num_discarded += 1;
+                continue;
+            };
+            let Some(transaction) = TracedTransaction::new(packet) else {
+                stats.num_invalid += 1;
+                continue;
+            };
+            let record = ExportedTransaction {
+                time: format_time(*event_time),
+                channel: channel_name(*label),
+                signature: transaction.signature.to_string(),
+                fee_payer: transaction.fee_payer().to_string(),
+                num_account_keys: transaction.account_keys().len(),
+                is_simple_vote: transaction.packet.is_simple_vote(),
+                forwarded: transaction.packet.forwarded(),
+                compute_unit_limit: transaction.packet.compute_unit_limit(),
+                compute_unit_price: transaction.packet.compute_unit_price(),
+                transaction: BASE64_STANDARD.encode(data),
+            };
+            match self {
+                Self::Json(writer) => {
+                    serde_json::to_writer(&mut *writer, &record)?;
+                    writeln!(writer)?;
+                }
+                Self::Csv(writer) => writer.serialize(&record).map_err(|err| {
+                    LedgerToolError::Generic(format!("Failed to write CSV record: {err}"))
+                })?,
+            }
+            stats.num_exported += 1;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Json(writer) => writer.flush(),
+            Self::Csv(writer) => writer.flush(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FilterStats {
+    num_packets: usize,
+    num_kept_packets: usize,
+    num_block_events: usize,
+}
+
+struct PacketFilter {
+    channels: Option<HashSet<ChannelLabel>>,
+    fee_payers: Option<HashSet<Pubkey>>,
+    accounts: Option<HashSet<Pubkey>>,
+    no_votes: bool,
+    dedup: bool,
+    drop_invalid: bool,
+    seen_signatures: HashSet<Signature>,
+}
+
+impl PacketFilter {
+    fn needs_transaction(&self) -> bool {
+        self.fee_payers.is_some()
+            || self.accounts.is_some()
+            || self.no_votes
+            || self.dedup
+            || self.drop_invalid
+    }
+
+    fn is_kept(&mut self, packet: PacketRef) -> bool {
+        if !self.needs_transaction() {
+            return true;
+        }
+        if packet.meta().discard() {
+            return false;
+        }
+        let Some(transaction) = TracedTransaction::new(packet) else {
+            return false;
+        };
+        if self.no_votes && transaction.packet.is_simple_vote() {
+            return false;
+        }
+        if let Some(fee_payers) = &self.fee_payers {
+            if !fee_payers.contains(transaction.fee_payer()) {
+                return false;
+            }
+        }
+        if let Some(accounts) = &self.accounts {
+            if !transaction
+                .account_keys()
+                .iter()
+                .any(|key| accounts.contains(key))
+            {
+                return false;
+            }
+        }
+        // Checked last, so that only the kept transactions are recorded
+        !self.dedup || self.seen_signatures.insert(transaction.signature)
+    }
+
+    /// Returns the event with only the kept packets, or `None` if no packet is kept
+    fn filter_event(
+        &mut self,
+        TimedTracedEvent(event_time, event): TimedTracedEvent,
+        stats: &mut FilterStats,
+    ) -> Option<TimedTracedEvent> {
+        let (label, batches) = match event {
+            TracedEvent::PacketBatch(label, batches) => (label, batches),
+            TracedEvent::BlockAndBankHash(..) => {
+                stats.num_block_events += 1;
+                return Some(TimedTracedEvent(event_time, event));
+            }
+        };
+        let num_packets = batches.iter().map(PacketBatch::len).sum::<usize>();
+        stats.num_packets += num_packets;
+        if let Some(channels) = &self.channels {
+            if !channels.contains(&label) {
+                return None;
+            }
+        }
+
+        let mut filtered_batches = Vec::with_capacity(batches.len());
+        for batch in batches.iter() {
+            let kept_packets = batch
+                .iter()
+                .filter(|packet| self.is_kept(*packet))
+                .map(|packet| packet.to_bytes_packet())
+                .collect::<Vec<BytesPacket>>();
+            stats.num_kept_packets += kept_packets.len();
+            if kept_packets.len() == batch.len() {
+                filtered_batches.push(batch.clone());
+            } else if !kept_packets.is_empty() {
+                filtered_batches.push(PacketBatch::from(kept_packets));
+            }
+        }
+        (!filtered_batches.is_empty()).then(|| {
+            TimedTracedEvent(
+                event_time,
+                TracedEvent::PacketBatch(label, Arc::new(filtered_batches)),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_compute_budget_interface::ComputeBudgetInstruction,
+        solana_hash::Hash,
+        solana_keypair::Keypair,
+        solana_perf::packet::{Packet, PinnedPacketBatch},
+        solana_signer::Signer,
+        solana_system_interface::instruction as system_instruction,
+        solana_transaction::Transaction,
+        std::time::Duration,
+    };
+
+    fn transfer(payer: &Keypair, compute_unit_price: u64) -> Packet {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            ],
+            Some(&payer.pubkey()),
+            &[payer],
+            Hash::new_unique(),
+        );
+        Packet::from_data(None, transaction).unwrap()
+    }
+
+    fn packet_event(secs: u64, label: ChannelLabel, packets: Vec<Packet>) -> TimedTracedEvent {
+        TimedTracedEvent(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            TracedEvent::PacketBatch(
+                label,
+                Arc::new(vec![PacketBatch::from(PinnedPacketBatch::new(packets))]),
+            ),
+        )
+    }
+
+    fn block_event(secs: u64, slot: u64) -> TimedTracedEvent {
+        TimedTracedEvent(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            TracedEvent::BlockAndBankHash(slot, Hash::new_unique(), Hash::new_unique()),
+        )
+    }
+
+    #[test]
+    fn test_summarize() {
+        let payer = Keypair::new();
+        let other_payer = Keypair::new();
+        let duplicate = transfer(&payer, 10);
+        let mut discarded = transfer(&payer, 0);
+        discarded.meta_mut().set_discard(true);
+        let mut invalid = Packet::default();
+        invalid.meta_mut().size = 1;
+
+        let mut summary = SummaryBuilder::default();
+        for event in [
+            packet_event(
+                2,
+                ChannelLabel::NonVote,
+                vec![duplicate.clone(), transfer(&payer, 20), discarded],
+            ),
+            block_event(3, 7),
+            packet_event(
+                6,
+                ChannelLabel::NonVote,
+                vec![duplicate, transfer(&other_payer, 0), invalid],
+            ),
+            block_event(4, 8),
+        ] {
+            summary.add_event(&event);
+        }
+        let summary = summary.finish(1);
+
+        assert_eq!(summary.duration_secs, 4.0);
+        assert_eq!((summary.first_slot, summary.last_slot), (Some(7), Some(8)));
+        assert_eq!(
+            summary.channels,
+            vec![CliBankingTraceChannel {
+                label: "non-vote".to_string(),
+                num_batches: 2,
+                num_packets: 6,
+                num_discarded: 1,
+                num_invalid: 1,
+                packets_per_second: 1.5,
+            }]
+        );
+        assert_eq!(summary.num_transactions, 4);
+        assert_eq!(summary.num_duplicates, 1);
+        assert_eq!(summary.duplicate_ratio, 0.25);
+        let compute_unit_prices = summary.compute_unit_prices.unwrap();
+        assert_eq!(compute_unit_prices.num_transactions, 4);
+        assert_eq!(compute_unit_prices.num_with_priority_fee, 3);
+        assert_eq!((compute_unit_prices.min, compute_unit_prices.max), (0, 20));
+        assert_eq!(
+            summary.top_fee_payers,
+            vec![CliFeePayerCount {
+                fee_payer: payer.pubkey().to_string(),
+                num_transactions: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_filter_event() {
+        let payer = Keypair::new();
+        let other_payer = Keypair::new();
+        let kept = transfer(&payer, 0);
+        let mut filter = PacketFilter {
+            channels: Some(HashSet::from([ChannelLabel::NonVote])),
+            fee_payers: Some(HashSet::from([payer.pubkey()])),
+            accounts: None,
+            no_votes: false,
+            dedup: true,
+            drop_invalid: false,
+            seen_signatures: HashSet::default(),
+        };
+        let mut stats = FilterStats::default();
+
+        let event = filter
+            .filter_event(
+                packet_event(
+                    1,
+                    ChannelLabel::NonVote,
+                    vec![kept.clone(), transfer(&other_payer, 0), kept.clone()],
+                ),
+                &mut stats,
+            )
+            .unwrap();
+        let TimedTracedEvent(_, TracedEvent::PacketBatch(_, batches)) = event else {
+            panic!("unexpected event");
+        };
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 1);
+        assert_eq!(batches[0].get(0).unwrap().data(..), kept.data(..));
+
+        // Other channels and packets without a match are dropped entirely, while block events
+        // are always kept
+        assert!(filter
+            .filter_event(
+                packet_event(2, ChannelLabel::TpuVote, vec![transfer(&payer, 0)]),
+                &mut stats
+            )
+            .is_none());
+        assert!(filter
+            .filter_event(
+                packet_event(3, ChannelLabel::NonVote, vec![kept]),
+                &mut stats
+            )
+            .is_none());
+        assert!(filter.filter_event(block_event(4, 1), &mut stats).is_some());
+
+        assert_eq!(stats.num_packets, 5);
+        assert_eq!(stats.num_kept_packets, 1);
+        assert_eq!(stats.num_block_events, 1);
+    }
+}
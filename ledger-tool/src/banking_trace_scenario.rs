@@ -0,0 +1,627 @@
+//! Synthetic banking traces, generated from a declarative load scenario
+//!
+//! A scenario is a JSON file describing the load offered to the leader. Every field is optional:
+//!
+//! ```json
+//! {
+//!   "seed": 0,
+//!   "numSlots": 32,
+//!   "warmupMs": 2000,
+//!   "transactionsPerSecond": 5000,
+//!   "batchIntervalUs": 1000,
+//!   "batchSize": 64,
+//!   "numPayers": 1000,
+//!   "contention": {
+//!     "numAccounts": 1000,
+//!     "zipfExponent": 1.0,
+//!     "writableAccountsPerTransaction": 1
+//!   },
+//!   "computeUnitPrice": { "type": "exponential", "mean": 10000 },
+//!   "computeUnitLimit": { "type": "uniform", "min": 1000, "max": 200000 },
+//!   "votes": {
+//!     "numVoters": 20,
+//!     "latencyMs": { "type": "uniform", "min": 50, "max": 400 }
+//!   }
+//! }
+//! ```
+//!
+//! The generated genesis has a single staked validator, which is the leader of every slot, so
+//! the trace can be replayed with `simulate-block-production --first-simulated-slot 1`.
+
+use {
+    rand::{Rng, SeedableRng},
+    rand_chacha::ChaChaRng,
+    serde_derive::Deserialize,
+    solana_account::AccountSharedData,
+    solana_clock::{Slot, DEFAULT_MS_PER_SLOT},
+    solana_cluster_type::ClusterType,
+    solana_compute_budget_interface::ComputeBudgetInstruction,
+    solana_core::{
+        banking_trace::{ChannelLabel, TimedTracedEvent, TracedEvent},
+        consensus::Tower,
+    },
+    solana_fee_calculator::FeeRateGovernor,
+    solana_genesis_config::GenesisConfig,
+    solana_hash::Hash,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_perf::packet::{BytesPacket, PacketBatch, PacketFlags},
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_runtime::{
+        bank::Bank,
+        genesis_utils::{bootstrap_validator_stake_lamports, create_genesis_config_with_leader_ex},
+    },
+    solana_signer::Signer,
+    solana_system_interface::{instruction as system_instruction, program as system_program},
+    solana_transaction::Transaction,
+    solana_vote::vote_transaction::{new_tower_sync_transaction, VoteTransaction},
+    solana_vote_program::vote_state,
+    std::{
+        collections::{BTreeMap, HashSet},
+        sync::Arc,
+        time::{Duration, SystemTime},
+    },
+};
+
+/// Generated traces start at an arbitrary fixed time so that they are reproducible byte for byte
+const TRACE_START_TIME: Duration = Duration::from_secs(1_700_000_000);
+
+/// Transactions reference the blockhash of a slot frozen this many slots earlier, so that they
+/// stay valid even if the simulated leader runs slightly behind the trace
+const BLOCKHASH_LAG_SLOTS: Slot = 2;
+
+const ACCOUNT_LAMPORTS: u64 = 1_000 * LAMPORTS_PER_SOL;
+const MAX_WRITABLE_ACCOUNTS_PER_TRANSACTION: usize = 16;
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Scenario {
+    pub seed: u64,
+    /// Number of leader slots to generate, starting at slot 1
+    pub num_slots: u64,
+    /// Duration of the traffic sent before the first leader slot
+    pub warmup_ms: u64,
+    /// Arrival rate of the non-vote transactions
+    pub transactions_per_second: f64,
+    /// Transactions arriving within the same interval are sent in the same packet batches
+    pub batch_interval_us: u64,
+    pub batch_size: usize,
+    pub num_payers: usize,
+    pub contention: Contention,
+    /// Compute unit price of the non-vote transactions, in micro-lamports
+    pub compute_unit_price: Distribution,
+    /// Requested compute unit limit of the non-vote transactions
+    pub compute_unit_limit: Distribution,
+    pub votes: Votes,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            num_slots: 32,
+            warmup_ms: 2_000,
+            transactions_per_second: 5_000.0,
+            batch_interval_us: 1_000,
+            batch_size: 64,
+            num_payers: 1_000,
+            contention: Contention::default(),
+            compute_unit_price: Distribution::Exponential { mean: 10_000.0 },
+            compute_unit_limit: Distribution::Uniform {
+                min: 1_000,
+                max: 200_000,
+            },
+            votes: Votes::default(),
+        }
+    }
+}
+
+/// Which accounts are write-locked by the non-vote transactions
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Contention {
+    pub num_accounts: usize,
+    /// Exponent of the Zipf distribution the write-locked accounts are drawn from. 0 spreads the
+    /// writes evenly, higher values concentrate them on fewer accounts.
+    pub zipf_exponent: f64,
+    pub writable_accounts_per_transaction: usize,
+}
+
+impl Default for Contention {
+    fn default() -> Self {
+        Self {
+            num_accounts: 1_000,
+            zipf_exponent: 1.0,
+            writable_accounts_per_transaction: 1,
+        }
+    }
+}
+
+/// Each voter votes once for every slot, after the slot is frozen
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Votes {
+    pub num_voters: usize,
+    /// Delay between freezing a slot and sending the votes for it
+    pub latency_ms: Distribution,
+}
+
+impl Default for Votes {
+    fn default() -> Self {
+        Self {
+            num_voters: 20,
+            latency_ms: Distribution::Uniform { min: 50, max: 400 },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Distribution {
+    Constant { value: u64 },
+    Uniform { min: u64, max: u64 },
+    Exponential { mean: f64 },
+}
+
+impl Distribution {
+    fn sample(&self, rng: &mut impl Rng) -> u64 {
+        match self {
+            Self::Constant { value } => *value,
+            Self::Uniform { min, max } => rng.gen_range(*min..=*max),
+            Self::Exponential { mean } => sample_exponential(rng, *mean) as u64,
+        }
+    }
+
+    fn verify(&self, name: &str) -> Result<(), String> {
+        match self {
+            Self::Constant { .. } => Ok(()),
+            Self::Uniform { min, max } if min > max => {
+                Err(format!("{name}: min {min} is greater than max {max}"))
+            }
+            Self::Uniform { .. } => Ok(()),
+            Self::Exponential { mean } if !(mean.is_finite() && *mean >= 0.0) => {
+                Err(format!("{name}: invalid mean {mean}"))
+            }
+            Self::Exponential { .. } => Ok(()),
+        }
+    }
+}
+
+fn sample_exponential(rng: &mut impl Rng, mean: f64) -> f64 {
+    -mean * (1.0 - rng.gen::<f64>()).ln()
+}
+
+impl Scenario {
+    pub fn verify(&self) -> Result<(), String> {
+        if self.num_slots == 0 {
+            return Err("numSlots must be at least 1".to_string());
+        }
+        if !(self.transactions_per_second.is_finite() && self.transactions_per_second >= 0.0) {
+            return Err(format!(
+                "invalid transactionsPerSecond {}",
+                self.transactions_per_second
+            ));
+        }
+        if self.batch_interval_us == 0 || self.batch_size == 0 {
+            return Err("batchIntervalUs and batchSize must be at least 1".to_string());
+        }
+        if self.num_payers == 0 {
+            return Err("numPayers must be at least 1".to_string());
+        }
+        let Contention {
+            num_accounts,
+            zipf_exponent,
+            writable_accounts_per_transaction,
+        } = self.contention;
+        if !(1..=MAX_WRITABLE_ACCOUNTS_PER_TRANSACTION).contains(&writable_accounts_per_transaction)
+        {
+            return Err(format!(
+                "contention.writableAccountsPerTransaction must be between 1 and \
+                 {MAX_WRITABLE_ACCOUNTS_PER_TRANSACTION}"
+            ));
+        }
+        if num_accounts < writable_accounts_per_transaction {
+            return Err(
+                "contention.numAccounts must be at least writableAccountsPerTransaction"
+                    .to_string(),
+            );
+        }
+        if !(zipf_exponent.is_finite() && zipf_exponent >= 0.0) {
+            return Err(format!("invalid contention.zipfExponent {zipf_exponent}"));
+        }
+        self.compute_unit_price.verify("computeUnitPrice")?;
+        self.compute_unit_limit.verify("computeUnitLimit")?;
+        self.votes.latency_ms.verify("votes.latencyMs")
+    }
+}
+
+/// Zipf distribution over `0..n`, sampled by inverting its cumulative weights
+struct Zipf {
+    cumulative_weights: Vec<f64>,
+}
+
+impl Zipf {
+    fn new(n: usize, exponent: f64) -> Self {
+        let cumulative_weights = (1..=n)
+            .scan(0.0, |total, rank| {
+                *total += (rank as f64).powf(-exponent);
+                Some(*total)
+            })
+            .collect();
+        Self { cumulative_weights }
+    }
+
+    fn sample(&self, rng: &mut impl Rng) -> usize {
+        let total = self.cumulative_weights.last().copied().unwrap_or_default();
+        let target = rng.gen::<f64>() * total;
+        self.cumulative_weights
+            .partition_point(|weight| *weight <= target)
+            .min(self.cumulative_weights.len() - 1)
+    }
+}
+
+struct Voter {
+    node_keypair: Keypair,
+    vote_keypair: Keypair,
+    tower: Tower,
+}
+
+pub struct GeneratedTrace {
+    pub genesis_config: GenesisConfig,
+    pub leader: Pubkey,
+    /// Events ordered by time
+    pub events: Vec<TimedTracedEvent>,
+    pub num_transactions: usize,
+    pub num_votes: usize,
+}
+
+fn new_keypair(rng: &mut ChaChaRng) -> Keypair {
+    Keypair::new_from_array(rng.gen())
+}
+
+pub fn generate_trace(scenario: &Scenario) -> GeneratedTrace {
+    let mut rng = ChaChaRng::seed_from_u64(scenario.seed);
+
+    // The leader is always the first voter, even if it doesn't vote
+    let mut voters = (0..scenario.votes.num_voters.max(1))
+        .map(|_| Voter {
+            node_keypair: new_keypair(&mut rng),
+            vote_keypair: new_keypair(&mut rng),
+            tower: Tower::default(),
+        })
+        .collect::<Vec<_>>();
+    let payers = (0..scenario.num_payers)
+        .map(|_| new_keypair(&mut rng))
+        .collect::<Vec<_>>();
+    let accounts = (0..scenario.contention.num_accounts)
+        .map(|_| Pubkey::new_from_array(rng.gen()))
+        .collect::<Vec<_>>();
+
+    let rent = Rent::default();
+    let initial_accounts = voters[1..]
+        .iter()
+        .flat_map(|voter| {
+            let node_pubkey = voter.node_keypair.pubkey();
+            let vote_pubkey = voter.vote_keypair.pubkey();
+            [
+                (
+                    node_pubkey,
+                    AccountSharedData::new(ACCOUNT_LAMPORTS, 0, &system_program::id()),
+                ),
+                (
+                    vote_pubkey,
+                    vote_state::create_account(
+                        &vote_pubkey,
+                        &node_pubkey,
+                        0,
+                        rent.minimum_balance(vote_state::VoteStateV3::size_of()),
+                    ),
+                ),
+            ]
+        })
+        .chain(payers.iter().map(|payer| {
+            (
+                payer.pubkey(),
+                AccountSharedData::new(ACCOUNT_LAMPORTS, 0, &system_program::id()),
+            )
+        }))
+        .chain(accounts.iter().map(|account| {
+            (
+                *account,
+                AccountSharedData::new(rent.minimum_balance(0), 0, &system_program::id()),
+            )
+        }))
+        .collect();
+    let genesis_config = create_genesis_config_with_leader_ex(
+        ACCOUNT_LAMPORTS,
+        &new_keypair(&mut rng).pubkey(),
+        &voters[0].node_keypair.pubkey(),
+        &voters[0].vote_keypair.pubkey(),
+        &Pubkey::new_from_array(rng.gen()),
+        None,
+        bootstrap_validator_stake_lamports(),
+        ACCOUNT_LAMPORTS,
+        FeeRateGovernor::default(),
+        rent,
+        ClusterType::Development,
+        initial_accounts,
+    );
+
+    // Slot 0 is the genesis bank, which is replayed rather than simulated. The later slots are
+    // given arbitrary hashes, which the simulated banks are overridden with.
+    let genesis_bank = Bank::new_for_benches(&genesis_config);
+    genesis_bank.freeze();
+    let hashes = (0..=scenario.num_slots)
+        .map(|slot| {
+            if slot == 0 {
+                (genesis_bank.last_blockhash(), genesis_bank.hash())
+            } else {
+                (
+                    Hash::new_from_array(rng.gen()),
+                    Hash::new_from_array(rng.gen()),
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let start_time = SystemTime::UNIX_EPOCH + TRACE_START_TIME;
+    let slot_duration = Duration::from_millis(DEFAULT_MS_PER_SLOT);
+    let freeze_time = |slot: Slot| start_time + slot_duration * slot as u32;
+    let end_time = freeze_time(scenario.num_slots);
+    let blockhash_at = |time: SystemTime| {
+        let frozen_slot = time
+            .duration_since(start_time)
+            .map(|elapsed| elapsed.as_millis() as u64 / DEFAULT_MS_PER_SLOT)
+            .unwrap_or_default();
+        hashes[frozen_slot.saturating_sub(BLOCKHASH_LAG_SLOTS) as usize].0
+    };
+
+    let mut packets = vec![];
+
+    // Non-vote transactions arrive as a Poisson process
+    let accounts_zipf = Zipf::new(accounts.len(), scenario.contention.zipf_exponent);
+    if scenario.transactions_per_second > 0.0 {
+        let mean_interval_secs = 1.0 / scenario.transactions_per_second;
+        let mut time = start_time - Duration::from_millis(scenario.warmup_ms);
+        loop {
+            time += Duration::from_secs_f64(sample_exponential(&mut rng, mean_interval_secs));
+            if time >= end_time {
+                break;
+            }
+            let payer = &payers[rng.gen_range(0..payers.len())];
+            let mut writable_accounts = HashSet::new();
+            while writable_accounts.len() < scenario.contention.writable_accounts_per_transaction {
+                writable_accounts.insert(accounts_zipf.sample(&mut rng));
+            }
+            let compute_unit_limit = scenario
+                .compute_unit_limit
+                .sample(&mut rng)
+                .min(MAX_COMPUTE_UNIT_LIMIT);
+            let compute_unit_price = scenario.compute_unit_price.sample(&mut rng);
+
+            let mut instructions = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit as u32),
+                ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+            ];
+            let mut writable_accounts = writable_accounts.into_iter().collect::<Vec<_>>();
+            writable_accounts.sort_unstable();
+            instructions.extend(
+                writable_accounts.into_iter().map(|index| {
+                    system_instruction::transfer(&payer.pubkey(), &accounts[index], 1)
+                }),
+            );
+            let transaction = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &[payer],
+                blockhash_at(time),
+            );
+            let packet = BytesPacket::from_data(None, transaction).unwrap();
+            packets.push((time, ChannelLabel::NonVote, packet));
+        }
+    }
+    let num_transactions = packets.len();
+
+    // Votes for the simulated slots, sent by every voter after each slot is frozen
+    for slot in 1..scenario.num_slots {
+        let (_blockhash, bank_hash) = hashes[slot as usize];
+        for voter in voters.iter_mut().take(scenario.votes.num_voters) {
+            let time = freeze_time(slot)
+                + Duration::from_millis(scenario.votes.latency_ms.sample(&mut rng));
+            if time >= end_time {
+                continue;
+            }
+            voter.tower.record_vote(slot, bank_hash);
+            let VoteTransaction::TowerSync(mut tower_sync) = voter.tower.last_vote() else {
+                unreachable!("Tower::record_vote() always creates tower sync votes");
+            };
+            // The tower timestamps votes with the wall clock time
+            tower_sync.timestamp = None;
+            let transaction = new_tower_sync_transaction(
+                tower_sync,
+                blockhash_at(time),
+                &voter.node_keypair,
+                &voter.vote_keypair,
+                &voter.vote_keypair,
+                None,
+            );
+            let mut packet = BytesPacket::from_data(None, transaction).unwrap();
+            packet
+                .meta_mut()
+                .flags
+                .set(PacketFlags::SIMPLE_VOTE_TX, true);
+            packets.push((time, ChannelLabel::TpuVote, packet));
+        }
+    }
+    let num_votes = packets.len() - num_transactions;
+
+    // Group the packets sent through the same channel within the same batch interval
+    let batch_interval = Duration::from_micros(scenario.batch_interval_us);
+    let mut packets_by_interval = BTreeMap::<_, Vec<_>>::new();
+    for (time, label, packet) in packets {
+        let interval = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+            / batch_interval.as_nanos();
+        packets_by_interval
+            .entry((interval, label == ChannelLabel::TpuVote))
+            .or_default()
+            .push((time, label, packet));
+    }
+
+    let mut events = BTreeMap::new();
+    for (_interval, mut packets) in packets_by_interval {
+        packets.sort_by_key(|(time, _label, _packet)| *time);
+        let (mut time, label) = packets
+            .last()
+            .map(|(time, label, _packet)| (*time, *label))
+            .unwrap();
+        let batches = packets
+            .chunks(scenario.batch_size)
+            .map(|chunk| {
+                PacketBatch::from(
+                    chunk
+                        .iter()
+                        .map(|(_time, _label, packet)| packet.clone())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        // Event times must be unique, as the simulator orders packet batches by time
+        while events.contains_key(&time) {
+            time += Duration::from_nanos(1);
+        }
+        events.insert(
+            time,
+            TimedTracedEvent(time, TracedEvent::PacketBatch(label, Arc::new(batches))),
+        );
+    }
+    let mut events = events.into_values().collect::<Vec<_>>();
+    events.extend(
+        hashes
+            .iter()
+            .enumerate()
+            .map(|(slot, (blockhash, bank_hash))| {
+                TimedTracedEvent(
+                    freeze_time(slot as Slot),
+                    TracedEvent::BlockAndBankHash(slot as Slot, *blockhash, *bank_hash),
+                )
+            }),
+    );
+    events.sort_by_key(|TimedTracedEvent(time, _event)| *time);
+
+    GeneratedTrace {
+        genesis_config,
+        leader: voters[0].node_keypair.pubkey(),
+        events,
+        num_transactions,
+        num_votes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario: Scenario = serde_json::from_str(
+            r#"{
+                "seed": 7,
+                "numSlots": 4,
+                "contention": { "numAccounts": 10, "zipfExponent": 2.0 },
+                "computeUnitPrice": { "type": "constant", "value": 100 },
+                "votes": { "numVoters": 3 }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(scenario.seed, 7);
+        assert_eq!(scenario.num_slots, 4);
+        assert_eq!(scenario.batch_size, Scenario::default().batch_size);
+        assert_eq!(
+            scenario.contention,
+            Contention {
+                num_accounts: 10,
+                zipf_exponent: 2.0,
+                writable_accounts_per_transaction: 1,
+            }
+        );
+        assert_eq!(
+            scenario.compute_unit_price,
+            Distribution::Constant { value: 100 }
+        );
+        assert_eq!(scenario.votes.latency_ms, Votes::default().latency_ms);
+        assert!(scenario.verify().is_ok());
+
+        assert!(serde_json::from_str::<Scenario>(r#"{ "numSlot": 4 }"#).is_err());
+        let scenario: Scenario = serde_json::from_str(
+            r#"{ "contention": { "numAccounts": 1, "writableAccountsPerTransaction": 2 } }"#,
+        )
+        .unwrap();
+        assert!(scenario.verify().is_err());
+    }
+
+    #[test]
+    fn test_generate_trace() {
+        let scenario = Scenario {
+            num_slots: 4,
+            warmup_ms: 100,
+            transactions_per_second: 1_000.0,
+            num_payers: 10,
+            contention: Contention {
+                num_accounts: 5,
+                zipf_exponent: 1.0,
+                writable_accounts_per_transaction: 2,
+            },
+            votes: Votes {
+                num_voters: 3,
+                latency_ms: Distribution::Constant { value: 100 },
+            },
+            ..Scenario::default()
+        };
+        let trace = generate_trace(&scenario);
+
+        // 3 voters vote for slots 1 to 3
+        assert_eq!(trace.num_votes, 9);
+        assert!(trace.num_transactions > 0);
+        assert!(trace
+            .events
+            .windows(2)
+            .all(|events| events[0].0 < events[1].0));
+
+        let mut num_packets = 0;
+        let mut slots = vec![];
+        for TimedTracedEvent(_time, event) in &trace.events {
+            match event {
+                TracedEvent::PacketBatch(label, batches) => {
+                    for packet in batches.iter().flat_map(|batch| batch.iter()) {
+                        let is_vote = packet.meta().is_simple_vote_tx();
+                        assert_eq!(is_vote, *label == ChannelLabel::TpuVote);
+                        num_packets += 1;
+                    }
+                }
+                TracedEvent::BlockAndBankHash(slot, _blockhash, _bank_hash) => slots.push(*slot),
+            }
+        }
+        assert_eq!(num_packets, trace.num_transactions + trace.num_votes);
+        assert_eq!(slots, vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            trace.events.iter().find_map(|event| match &event.1 {
+                TracedEvent::BlockAndBankHash(0, blockhash, _bank_hash) => Some(*blockhash),
+                _ => None,
+            }),
+            Some(trace.genesis_config.hash())
+        );
+
+        // The same scenario always generates the same trace
+        let other_trace = generate_trace(&scenario);
+        assert_eq!(trace.leader, other_trace.leader);
+        assert_eq!(
+            bincode::serialize(&trace.events).unwrap(),
+            bincode::serialize(&other_trace.events).unwrap()
+        );
+    }
+}
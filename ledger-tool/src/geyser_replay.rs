@@ -0,0 +1,382 @@
+//! The `geyser-replay` subcommand
+
+use {
+    crate::{
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+        ledger_utils::open_blockstore,
+    },
+    clap::{value_t, value_t_or_exit, values_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    crossbeam_channel::unbounded,
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_clap_utils::input_validators::is_slot,
+    solana_clock::Slot,
+    solana_geyser_plugin_manager::{
+        block_metadata_notifier_interface::BlockMetadataNotifierArc,
+        geyser_plugin_service::GeyserPluginService,
+    },
+    solana_ledger::{
+        blockstore::{Blockstore, BlockstoreError},
+        blockstore_options::AccessType,
+    },
+    solana_pubkey::Pubkey,
+    solana_reward_info::RewardInfo,
+    solana_rpc::{
+        slot_status_notifier::SlotStatusNotifier,
+        transaction_notifier_interface::TransactionNotifierArc,
+    },
+    solana_runtime::bank::KeyedRewardsAndNumPartitions,
+    solana_transaction::{
+        simple_vote_transaction_checker::is_simple_vote_transaction,
+        versioned::sanitized::SanitizedVersionedTransaction,
+    },
+    solana_transaction_status::{Reward, VersionedConfirmedBlockWithEntries},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        str::FromStr,
+    },
+};
+
+/// Progress of a replay, persisted after every slot so that an interrupted
+/// replay can be resumed with the same `--checkpoint-file`
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Checkpoint {
+    last_replayed_slot: Slot,
+}
+
+impl Checkpoint {
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read(path)?;
+        Ok(Some(serde_json::from_slice(&contents)?))
+    }
+
+    /// Write the checkpoint to a temporary file first and rename it into place
+    /// so that a crash never leaves a truncated checkpoint behind
+    fn store(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+pub fn geyser_replay_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("geyser-replay")
+        .about(
+            "Replay the transactions and block metadata stored in the ledger to Geyser plugins, \
+             without replaying the banks",
+        )
+        .long_about(
+            "Replay the transactions and block metadata stored in the ledger to Geyser plugins, \
+             without replaying the banks. Rooted slots are replayed in slot order; for each slot \
+             the plugins are notified of every transaction, then of the block metadata, and \
+             finally of the slot becoming processed, confirmed and rooted. Account updates are \
+             not replayed. The ledger must have been populated with transaction status metadata, \
+             ie with --enable-rpc-transaction-history.",
+        )
+        .arg(
+            Arg::with_name("geyser_plugin_config")
+                .long("geyser-plugin-config")
+                .value_name("FILE")
+                .takes_value(true)
+                .multiple(true)
+                .required(true)
+                .help("Specify the configuration file for the Geyser plugin."),
+        )
+        .arg(
+            Arg::with_name("starting_slot")
+                .long("starting-slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .default_value("0")
+                .help("Start at this slot"),
+        )
+        .arg(
+            Arg::with_name("ending_slot")
+                .long("ending-slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .help("The last slot to replay [default: the highest root]"),
+        )
+        .arg(
+            Arg::with_name("checkpoint_file")
+                .long("checkpoint-file")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "Record the last replayed slot to this file after every slot. If the file \
+                     already exists, resume the replay from the slot following the recorded one",
+                ),
+        )
+}
+
+pub fn geyser_replay_process_command(ledger_path: &Path, arg_matches: &ArgMatches<'_>) {
+    do_geyser_replay_process_command(ledger_path, arg_matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_geyser_replay_process_command(
+    ledger_path: &Path,
+    arg_matches: &ArgMatches<'_>,
+) -> Result<()> {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let geyser_config_files = values_t_or_exit!(arg_matches, "geyser_plugin_config", String)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    let mut starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+    let checkpoint_file = value_t!(arg_matches, "checkpoint_file", PathBuf).ok();
+
+    if let Some(checkpoint_file) = &checkpoint_file {
+        if let Some(checkpoint) = Checkpoint::load(checkpoint_file)? {
+            let resume_slot = checkpoint.last_replayed_slot.saturating_add(1);
+            if resume_slot > starting_slot {
+                info!(
+                    "Resuming from checkpoint {}, last replayed slot: {}",
+                    checkpoint_file.display(),
+                    checkpoint.last_replayed_slot,
+                );
+                starting_slot = resume_slot;
+            }
+        }
+    }
+
+    let blockstore = open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
+    let ending_slot =
+        value_t!(arg_matches, "ending_slot", Slot).unwrap_or_else(|_| blockstore.max_root());
+    if starting_slot > ending_slot {
+        println!(
+            "Nothing to replay, starting slot {starting_slot} is past ending slot {ending_slot}"
+        );
+        return Ok(());
+    }
+
+    // Slot status is pushed to the plugins directly rather than through the
+    // confirmed bank channel, so the sender can be dropped right away
+    let (confirmed_bank_sender, confirmed_bank_receiver) = unbounded();
+    drop(confirmed_bank_sender);
+    let geyser_service =
+        GeyserPluginService::new(confirmed_bank_receiver, false, &geyser_config_files).map_err(
+            |err| LedgerToolError::Generic(format!("Failed to start the Geyser plugins: {err}")),
+        )?;
+    let replayer = GeyserReplayer {
+        transaction_notifier: geyser_service.get_transaction_notifier(),
+        block_metadata_notifier: geyser_service.get_block_metadata_notifier(),
+        slot_status_notifier: geyser_service.get_slot_status_notifier(),
+    };
+
+    let mut num_slots = 0;
+    let mut num_transactions = 0;
+    for slot in blockstore.rooted_slot_iterator(starting_slot)? {
+        if slot > ending_slot {
+            break;
+        }
+        // A rooted slot whose data is missing, incomplete or already cleaned up
+        // is skipped rather than aborting the whole replay
+        if let Some(slot_transactions) = replayer.replay_slot(&blockstore, slot)? {
+            num_transactions += slot_transactions;
+            num_slots += 1;
+        }
+        if let Some(checkpoint_file) = &checkpoint_file {
+            Checkpoint {
+                last_replayed_slot: slot,
+            }
+            .store(checkpoint_file)?;
+        }
+    }
+
+    geyser_service.join().map_err(|err| {
+        LedgerToolError::Generic(format!("Failed to stop the Geyser plugins: {err:?}"))
+    })?;
+    println!(
+        "Replayed {num_slots} slots and {num_transactions} transactions from slot {starting_slot} \
+         to {ending_slot}"
+    );
+    Ok(())
+}
+
+struct GeyserReplayer {
+    transaction_notifier: Option<TransactionNotifierArc>,
+    block_metadata_notifier: Option<BlockMetadataNotifierArc>,
+    slot_status_notifier: Option<SlotStatusNotifier>,
+}
+
+impl GeyserReplayer {
+    /// Notify the plugins of everything stored for a rooted slot, returning
+    /// the number of transactions replayed, or `None` if the slot's block is
+    /// not available in the blockstore
+    fn replay_slot(&self, blockstore: &Blockstore, slot: Slot) -> Result<Option<usize>> {
+        let VersionedConfirmedBlockWithEntries { block, entries } =
+            match blockstore.get_rooted_block_with_entries(slot, false) {
+                Ok(block) => block,
+                Err(
+                    err @ (BlockstoreError::SlotUnavailable
+                    | BlockstoreError::SlotCleanedUp
+                    | BlockstoreError::DeadSlot),
+                ) => {
+                    warn!("Skipping slot {slot}: {err}");
+                    return Ok(None);
+                }
+                Err(err) => return Err(err.into()),
+            };
+        let num_transactions = block.transactions.len();
+
+        if let Some(transaction_notifier) = &self.transaction_notifier {
+            for (index, tx_with_meta) in block.transactions.iter().enumerate() {
+                let transaction = &tx_with_meta.transaction;
+                let is_vote = SanitizedVersionedTransaction::try_from(transaction.clone())
+                    .map(|transaction| is_simple_vote_transaction(&transaction))
+                    .unwrap_or(false);
+                transaction_notifier.notify_transaction(
+                    slot,
+                    index,
+                    &transaction.signatures[0],
+                    &transaction.message.hash(),
+                    is_vote,
+                    &tx_with_meta.meta,
+                    transaction,
+                );
+            }
+        }
+
+        if let Some(block_metadata_notifier) = &self.block_metadata_notifier {
+            let rewards = KeyedRewardsAndNumPartitions {
+                keyed_rewards: keyed_rewards(&block.rewards),
+                num_partitions: block.num_partitions,
+            };
+            block_metadata_notifier.notify_block_metadata(
+                block.parent_slot,
+                &block.previous_blockhash,
+                slot,
+                &block.blockhash,
+                &rewards,
+                block.block_time,
+                block.block_height,
+                num_transactions as u64,
+                entries.len() as u64,
+            );
+        }
+
+        if let Some(slot_status_notifier) = &self.slot_status_notifier {
+            let slot_status_notifier = slot_status_notifier.read().unwrap();
+            let parent = Some(block.parent_slot);
+            slot_status_notifier.notify_slot_processed(slot, parent);
+            slot_status_notifier.notify_slot_confirmed(slot, parent);
+            slot_status_notifier.notify_slot_rooted(slot, parent);
+        }
+
+        Ok(Some(num_transactions))
+    }
+}
+
+/// Convert the rewards stored in the blockstore back into the form the bank
+/// hands to the block metadata notifier
+fn keyed_rewards(rewards: &[Reward]) -> Vec<(Pubkey, RewardInfo)> {
+    rewards
+        .iter()
+        .filter_map(|reward| {
+            let Ok(pubkey) = Pubkey::from_str(&reward.pubkey) else {
+                warn!("Skipping reward with invalid pubkey {}", reward.pubkey);
+                return None;
+            };
+            let reward_type = reward.reward_type?;
+            Some((
+                pubkey,
+                RewardInfo {
+                    reward_type,
+                    lamports: reward.lamports,
+                    post_balance: reward.post_balance,
+                    commission: reward.commission,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_ledger::get_tmp_ledger_path_auto_delete, solana_reward_info::RewardType,
+    };
+
+    #[test]
+    fn test_checkpoint() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let checkpoint_file = ledger_path.path().join("geyser-replay.json");
+        assert_eq!(Checkpoint::load(&checkpoint_file).unwrap(), None);
+
+        for last_replayed_slot in [5, 6] {
+            Checkpoint { last_replayed_slot }
+                .store(&checkpoint_file)
+                .unwrap();
+            assert_eq!(
+                Checkpoint::load(&checkpoint_file).unwrap(),
+                Some(Checkpoint { last_replayed_slot })
+            );
+        }
+        assert!(!checkpoint_file.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_replay_slot_skips_unavailable_slot() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        // Rooted, but no shreds were ever received for the slot
+        blockstore.set_roots([1].iter()).unwrap();
+
+        let replayer = GeyserReplayer {
+            transaction_notifier: None,
+            block_metadata_notifier: None,
+            slot_status_notifier: None,
+        };
+        assert_eq!(replayer.replay_slot(&blockstore, 1).unwrap(), None);
+        assert!(matches!(
+            replayer.replay_slot(&blockstore, 2),
+            Err(LedgerToolError::Blockstore(BlockstoreError::SlotNotRooted))
+        ));
+    }
+
+    #[test]
+    fn test_keyed_rewards() {
+        let pubkey = Pubkey::new_unique();
+        let reward = Reward {
+            pubkey: pubkey.to_string(),
+            lamports: 42,
+            post_balance: 1_000,
+            reward_type: Some(RewardType::Voting),
+            commission: Some(10),
+        };
+        let rewards = vec![
+            reward.clone(),
+            Reward {
+                pubkey: "invalid".to_string(),
+                ..reward.clone()
+            },
+            Reward {
+                reward_type: None,
+                ..reward
+            },
+        ];
+        assert_eq!(
+            keyed_rewards(&rewards),
+            vec![(
+                pubkey,
+                RewardInfo {
+                    reward_type: RewardType::Voting,
+                    lamports: 42,
+                    post_balance: 1_000,
+                    commission: Some(10),
+                }
+            )]
+        );
+    }
+}
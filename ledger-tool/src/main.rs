@@ -2,15 +2,21 @@
 use {
     crate::{
         args::*,
+        banking_trace::*,
         bigtable::*,
         blockstore::*,
+        geyser_replay::*,
         ledger_path::*,
         ledger_utils::*,
         output::{
             AccountsOutputConfig, AccountsOutputMode, AccountsOutputStreamer, CliAccounts,
+            CliProgramCost, CliSignerCost, CliSlotCost, CliSlotCosts, CliWritableAccountCost,
             SlotBankHash,
         },
         program::*,
+        simulation_comparison::*,
+        snapshot_diff::*,
+        tiered_storage_convert::*,
     },
     agave_feature_set::{self as feature_set, FeatureSet},
     agave_reserved_account_keys::ReservedAccountKeys,
@@ -35,10 +41,14 @@ use {
     solana_cluster_type::ClusterType,
     solana_core::{
         banking_simulation::{BankingSimulator, BankingTraceEvents},
+        banking_stage::BankingStage,
         system_monitor_service::{SystemMonitorService, SystemMonitorStatsReportConfig},
         validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
     },
-    solana_cost_model::{cost_model::CostModel, cost_tracker::CostTracker},
+    solana_cost_model::{
+        cost_model::CostModel, cost_tracker::CostTracker,
+        cost_tracker_post_analysis::CostTrackerPostAnalysis,
+    },
     solana_feature_gate_interface::{self as feature, Feature},
     solana_inflation::Inflation,
     solana_instruction::TRANSACTION_LEVEL_STACK_HEIGHT,
@@ -88,6 +98,7 @@ use {
         fs::{read_dir, File},
         io::{self, Write},
         mem::swap,
+        num::NonZeroUsize,
         path::{Path, PathBuf},
         process::{exit, Command, Stdio},
         str::FromStr,
@@ -100,13 +111,19 @@ use {
 };
 
 mod args;
+mod banking_trace;
+mod banking_trace_scenario;
 mod bigtable;
 mod blockstore;
 mod error;
+mod geyser_replay;
 mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod simulation_comparison;
+mod snapshot_diff;
+mod tiered_storage_convert;
 
 fn render_dot(dot: String, output_file: &str, output_format: &str) -> io::Result<()> {
     let mut child = Command::new("dot")
@@ -443,7 +460,9 @@ fn compute_slot_cost(
     blockstore: &Blockstore,
     slot: Slot,
     allow_dead_slots: bool,
-) -> Result<(), String> {
+    top: usize,
+    near_limit_percentage: u64,
+) -> Result<CliSlotCost, String> {
     let (entries, _num_shreds, _is_full) = blockstore
         .get_slot_entries_with_shred_info(slot, 0, allow_dead_slots)
         .map_err(|err| format!("Slot: {slot}, Failed to load entries, err {err:?}"))?;
@@ -451,8 +470,10 @@ fn compute_slot_cost(
     let num_entries = entries.len();
     let mut num_transactions = 0;
     let mut num_programs = 0;
+    let mut num_rejected_transactions = 0;
 
-    let mut program_ids = HashMap::new();
+    let mut program_costs = HashMap::<Pubkey, CliProgramCost>::new();
+    let mut signer_costs = HashMap::<Pubkey, CliSignerCost>::new();
     let mut cost_tracker = CostTracker::default();
 
     let feature_set = FeatureSet::all_enabled();
@@ -482,25 +503,114 @@ fn compute_slot_cost(
                 let tx_cost = CostModel::calculate_cost(&transaction, &feature_set);
                 let result = cost_tracker.try_add(&tx_cost);
                 if result.is_err() {
-                    println!(
+                    num_rejected_transactions += 1;
+                    warn!(
                         "Slot: {slot}, CostModel rejected transaction {transaction:?}, reason \
                          {result:?}",
                     );
+                    // Like the block and writable account costs, the breakdown only covers
+                    // the transactions the cost tracker accepted
+                    return;
+                }
+                let cost = tx_cost.sum();
+
+                // Split the execution cost evenly across the transaction's instructions, the
+                // remainder going to the first one, so that the program costs of a block add
+                // up to its total execution cost
+                let message = transaction.message();
+                let num_instructions = message.instructions().len() as u64;
+                let programs_execution_cost = tx_cost.programs_execution_cost();
+                let mut invoked_programs = HashSet::new();
+                for (index, (program_id, _instruction)) in
+                    message.program_instructions_iter().enumerate()
+                {
+                    let program_cost = program_costs.entry(*program_id).or_default();
+                    program_cost.num_instructions += 1;
+                    program_cost.execution_cost += programs_execution_cost / num_instructions;
+                    if index == 0 {
+                        program_cost.execution_cost += programs_execution_cost % num_instructions;
+                    }
+                    if invoked_programs.insert(program_id) {
+                        program_cost.num_transactions += 1;
+                    }
                 }
-                for (program_id, _instruction) in transaction.message().program_instructions_iter()
+                for signer in message
+                    .account_keys()
+                    .iter()
+                    .take(message.header().num_required_signatures as usize)
                 {
-                    *program_ids.entry(*program_id).or_insert(0) += 1;
+                    let signer_cost = signer_costs.entry(*signer).or_default();
+                    signer_cost.num_transactions += 1;
+                    signer_cost.cost += cost;
                 }
             });
     }
 
-    println!(
-        "Slot: {slot}, Entries: {num_entries}, Transactions: {num_transactions}, Programs \
-         {num_programs}",
-    );
-    println!("  Programs: {program_ids:?}");
+    let block_cost_limit = cost_tracker.get_block_limit();
+    let account_cost_limit = cost_tracker.get_account_limit();
+    let near_limit = |limit: u64| limit.saturating_mul(near_limit_percentage) / 100;
+
+    let mut account_costs = cost_tracker
+        .get_cost_by_writable_accounts()
+        .iter()
+        .map(|(pubkey, cost)| (*pubkey, *cost))
+        .collect::<Vec<_>>();
+    account_costs.sort_unstable_by(|(pubkey_a, cost_a), (pubkey_b, cost_b)| {
+        cost_b.cmp(cost_a).then(pubkey_a.cmp(pubkey_b))
+    });
+    let writable_account_cost = |(pubkey, cost): &(Pubkey, u64)| CliWritableAccountCost {
+        pubkey: pubkey.to_string(),
+        cost: *cost,
+    };
 
-    Ok(())
+    let mut top_programs = program_costs
+        .into_iter()
+        .map(|(program_id, program_cost)| CliProgramCost {
+            program_id: program_id.to_string(),
+            ..program_cost
+        })
+        .collect::<Vec<_>>();
+    top_programs.sort_unstable_by(|a, b| {
+        b.execution_cost
+            .cmp(&a.execution_cost)
+            .then(a.program_id.cmp(&b.program_id))
+    });
+    top_programs.truncate(top);
+
+    let mut top_signers = signer_costs
+        .into_iter()
+        .map(|(signer, signer_cost)| CliSignerCost {
+            signer: signer.to_string(),
+            ..signer_cost
+        })
+        .collect::<Vec<_>>();
+    top_signers.sort_unstable_by(|a, b| b.cost.cmp(&a.cost).then(a.signer.cmp(&b.signer)));
+    top_signers.truncate(top);
+
+    Ok(CliSlotCost {
+        slot,
+        num_entries,
+        num_transactions,
+        num_instructions: num_programs,
+        num_rejected_transactions,
+        block_cost: cost_tracker.block_cost(),
+        block_cost_limit,
+        vote_cost: cost_tracker.vote_cost(),
+        account_cost_limit,
+        near_block_limit: cost_tracker.block_cost() >= near_limit(block_cost_limit),
+        accounts_near_limit: account_costs
+            .iter()
+            .take_while(|(_pubkey, cost)| *cost >= near_limit(account_cost_limit))
+            .map(writable_account_cost)
+            .collect(),
+        top_writable_accounts: account_costs
+            .iter()
+            .take(top)
+            .map(writable_account_cost)
+            .collect(),
+        top_programs,
+        top_signers,
+    })
 }
 
 /// Finds the accounts needed to replay slots `snapshot_slot` to `ending_slot`.
@@ -1530,6 +1640,33 @@ fn main() {
                         .default_value(BlockProductionMethod::default().into())
                         .help(BlockProductionMethod::cli_message()),
                 )
+                .arg(
+                    Arg::with_name("block_production_num_workers")
+                        .long("block-production-num-workers")
+                        .value_name("COUNT")
+                        .takes_value(true)
+                        .validator(|s| {
+                            is_within_range(s, 1..=BankingStage::max_num_workers().get())
+                        })
+                        .help(
+                            "Number of worker threads to use for block production [default: same \
+                             as agave-validator]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("compare")
+                        .long("compare")
+                        .value_name("METHOD[:NUM_WORKERS]")
+                        .takes_value(true)
+                        .multiple(true)
+                        .min_values(1)
+                        .validator(is_simulation_config)
+                        .help(
+                            "Simulate block production once per given configuration and compare \
+                             the produced blocks. Overrides --block-production-method and \
+                             --block-production-num-workers",
+                        ),
+                )
                 .arg(
                     Arg::with_name("transaction_struct")
                         .long("transaction-structure")
@@ -1681,8 +1818,33 @@ fn main() {
                              in ledger",
                         ),
                 )
-                .arg(&allow_dead_slots_arg),
+                .arg(&allow_dead_slots_arg)
+                .arg(
+                    Arg::with_name("top")
+                        .long("top")
+                        .value_name("NUM")
+                        .takes_value(true)
+                        .validator(is_parsable::<usize>)
+                        .default_value("10")
+                        .help("Number of top writable accounts, programs and signers to report"),
+                )
+                .arg(
+                    Arg::with_name("near_limit_percentage")
+                        .long("near-limit-percentage")
+                        .value_name("PERCENT")
+                        .takes_value(true)
+                        .validator(is_valid_percentage)
+                        .default_value("90")
+                        .help(
+                            "Flag the blocks and writable accounts whose cost reaches PERCENT of \
+                             the block or account cost limit",
+                        ),
+                ),
         )
+        .subcommand(banking_trace_subcommand())
+        .subcommand(geyser_replay_subcommand())
+        .subcommand(snapshot_diff_subcommand())
+        .subcommand(tiered_storage_convert_subcommand())
         .program_subcommand()
         .get_matches();
 
@@ -1698,9 +1860,21 @@ fn main() {
         .unwrap();
 
     match matches.subcommand() {
+        ("banking-trace", Some(arg_matches)) => {
+            banking_trace_process_command(&ledger_path, arg_matches)
+        }
         ("bigtable", Some(arg_matches)) => bigtable_process_command(&ledger_path, arg_matches),
         ("blockstore", Some(arg_matches)) => blockstore_process_command(&ledger_path, arg_matches),
+        ("geyser-replay", Some(arg_matches)) => {
+            geyser_replay_process_command(&ledger_path, arg_matches)
+        }
         ("program", Some(arg_matches)) => program(&ledger_path, arg_matches),
+        ("snapshot-diff", Some(arg_matches)) => {
+            snapshot_diff_process_command(&ledger_path, arg_matches)
+        }
+        ("convert-to-tiered-storage", Some(arg_matches)) => {
+            tiered_storage_convert_process_command(&ledger_path, arg_matches)
+        }
         // This match case provides legacy support for commands that were previously top level
         // subcommands of the binary, but have been moved under the blockstore subcommand.
         ("analyze-storage", Some(_))
@@ -2053,7 +2227,9 @@ fn main() {
                             .unwrap_or_else(|| {
                                 panic!("Archive format not recognized: {archive_format_str}")
                             });
-                        if let ArchiveFormat::TarZstd { config } = &mut archive_format {
+                        if let ArchiveFormat::TarZstd { config }
+                        | ArchiveFormat::TarZstdSeekable { config } = &mut archive_format
+                        {
                             config.compression_level = value_t_or_exit!(
                                 arg_matches,
                                 "snapshot_zstd_compression_level",
@@ -2535,22 +2711,29 @@ fn main() {
                     }
                 }
                 ("simulate-block-production", Some(arg_matches)) => {
-                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
-
                     let banking_trace_events = load_banking_trace_events_or_exit(&ledger_path);
-                    process_options.hash_overrides =
-                        Some(banking_trace_events.hash_overrides().clone());
-
                     let slot = value_t!(arg_matches, "first_simulated_slot", Slot).unwrap();
-                    let simulator = BankingSimulator::new(banking_trace_events, slot);
-                    let Some(parent_slot) = simulator.parent_slot() else {
-                        eprintln!(
-                            "Couldn't determine parent_slot of first_simulated_slot: {slot} due \
-                             to missing banking_trace_event data."
-                        );
-                        exit(1);
+                    let transaction_struct =
+                        value_t_or_exit!(arg_matches, "transaction_struct", TransactionStructure);
+
+                    let is_comparison = arg_matches.is_present("compare");
+                    let configs = if is_comparison {
+                        values_t_or_exit!(arg_matches, "compare", SimulationConfig)
+                    } else {
+                        vec![SimulationConfig {
+                            block_production_method: value_t_or_exit!(
+                                arg_matches,
+                                "block_production_method",
+                                BlockProductionMethod
+                            ),
+                            num_workers: value_t!(
+                                arg_matches,
+                                "block_production_num_workers",
+                                NonZeroUsize
+                            )
+                            .unwrap_or_else(|_| BankingStage::default_num_workers()),
+                        }]
                     };
-                    process_options.halt_at_slot = Some(parent_slot);
 
                     // PrimaryForMaintenance needed over Secondary to purge any
                     // existing simulated shreds from previous runs
@@ -2560,41 +2743,65 @@ fn main() {
                         AccessType::PrimaryForMaintenance,
                     ));
                     let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
-                    let LoadAndProcessLedgerOutput { bank_forks, .. } =
-                        load_and_process_ledger_or_exit(
-                            arg_matches,
-                            &genesis_config,
-                            blockstore.clone(),
-                            process_options,
-                            None, // transaction status sender
-                        );
 
-                    let block_production_method = value_t_or_exit!(
-                        arg_matches,
-                        "block_production_method",
-                        BlockProductionMethod
-                    );
-                    let transaction_struct =
-                        value_t_or_exit!(arg_matches, "transaction_struct", TransactionStructure);
+                    let mut runs = Vec::with_capacity(configs.len());
+                    for config in configs {
+                        let simulator = BankingSimulator::new(banking_trace_events.clone(), slot);
+                        let Some(parent_slot) = simulator.parent_slot() else {
+                            eprintln!(
+                                "Couldn't determine parent_slot of first_simulated_slot: {slot} \
+                                 due to missing banking_trace_event data."
+                            );
+                            exit(1);
+                        };
 
-                    info!(
-                        "Using: block-production-method: {block_production_method} \
-                         transaction-structure: {transaction_struct}"
-                    );
+                        // Every run starts over from the parent slot, as the previous run's
+                        // banks were frozen with its own simulated blocks
+                        let mut process_options = parse_process_options(&ledger_path, arg_matches);
+                        process_options.hash_overrides =
+                            Some(banking_trace_events.hash_overrides().clone());
+                        process_options.halt_at_slot = Some(parent_slot);
+                        let LoadAndProcessLedgerOutput { bank_forks, .. } =
+                            load_and_process_ledger_or_exit(
+                                arg_matches,
+                                &genesis_config,
+                                blockstore.clone(),
+                                process_options,
+                                None, // transaction status sender
+                            );
 
-                    match simulator.start(
-                        genesis_config,
-                        bank_forks,
-                        blockstore,
-                        block_production_method,
-                        transaction_struct,
-                    ) {
-                        Ok(()) => println!("Ok"),
-                        Err(error) => {
-                            eprintln!("{error:?}");
-                            exit(1);
-                        }
-                    };
+                        info!(
+                            "Using: block-production-method: {} num-workers: {} \
+                             transaction-structure: {transaction_struct}",
+                            config.block_production_method, config.num_workers,
+                        );
+
+                        match simulator.start(
+                            genesis_config.clone(),
+                            bank_forks,
+                            blockstore.clone(),
+                            config.block_production_method.clone(),
+                            transaction_struct.clone(),
+                            config.num_workers,
+                        ) {
+                            Ok(simulated_slots) => runs.push((config, simulated_slots)),
+                            Err(error) => {
+                                eprintln!("{error:?}");
+                                exit(1);
+                            }
+                        };
+                    }
+
+                    if is_comparison {
+                        let output_format =
+                            OutputFormat::from_matches(arg_matches, "output_format", false);
+                        println!(
+                            "{}",
+                            output_format.formatted_string(&compare_simulations(&runs))
+                        );
+                    } else {
+                        println!("Ok");
+                    }
                 }
                 ("accounts", Some(arg_matches)) => {
                     let process_options = parse_process_options(&ledger_path, arg_matches);
@@ -3163,10 +3370,35 @@ fn main() {
                         slots = values_t_or_exit!(arg_matches, "slots", Slot);
                     }
                     let allow_dead_slots = arg_matches.is_present("allow_dead_slots");
+                    let top = value_t_or_exit!(arg_matches, "top", usize);
+                    let near_limit_percentage =
+                        value_t_or_exit!(arg_matches, "near_limit_percentage", u64);
+                    let output_format =
+                        OutputFormat::from_matches(arg_matches, "output_format", false);
 
-                    for slot in slots {
-                        if let Err(err) = compute_slot_cost(&blockstore, slot, allow_dead_slots) {
-                            eprintln!("{err}");
+                    let slot_costs = slots.into_iter().filter_map(|slot| {
+                        compute_slot_cost(
+                            &blockstore,
+                            slot,
+                            allow_dead_slots,
+                            top,
+                            near_limit_percentage,
+                        )
+                        .map_err(|err| eprintln!("{err}"))
+                        .ok()
+                    });
+                    match output_format {
+                        OutputFormat::Json | OutputFormat::JsonCompact => {
+                            let slot_costs = CliSlotCosts {
+                                slots: slot_costs.collect(),
+                            };
+                            println!("{}", output_format.formatted_string(&slot_costs));
+                        }
+                        // Print each slot as soon as its cost is computed
+                        _ => {
+                            for slot_cost in slot_costs {
+                                print!("{}", output_format.formatted_string(&slot_cost));
+                            }
                         }
                     }
                 }
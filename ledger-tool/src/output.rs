@@ -962,3 +962,557 @@ impl fmt::Display for CliAccounts {
 }
 impl QuietDisplay for CliAccounts {}
 impl VerboseDisplay for CliAccounts {}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSnapshotDiff {
+    pub base: CliSnapshotSummary,
+    pub target: CliSnapshotSummary,
+    /// Difference of the banks' capitalization
+    pub capitalization_delta: i128,
+    /// Sum of the lamport differences of the compared accounts
+    pub lamports_delta: i128,
+    pub added: CliAccountsDelta,
+    pub removed: CliAccountsDelta,
+    pub modified: CliAccountsDelta,
+    pub owners: Vec<CliOwnerDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<CliAccountDiff>,
+}
+
+impl QuietDisplay for CliSnapshotDiff {}
+impl VerboseDisplay for CliSnapshotDiff {}
+
+impl fmt::Display for CliSnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Base:   {}", self.base)?;
+        writeln!(f, "Target: {}", self.target)?;
+        writeln!(f, "Capitalization delta: {:+}", self.capitalization_delta)?;
+        writeln!(f, "Account lamports delta: {:+}", self.lamports_delta)?;
+        writeln!(f, "Added accounts:    {}", self.added)?;
+        writeln!(f, "Removed accounts:  {}", self.removed)?;
+        writeln!(f, "Modified accounts: {}", self.modified)?;
+
+        if !self.owners.is_empty() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{:<44}  {:>9}  {:>9}  {:>9}  {:>20}  {:>14}",
+                "Owner", "Added", "Removed", "Modified", "Lamports delta", "Data len delta",
+            )?;
+            for owner in &self.owners {
+                writeln!(
+                    f,
+                    "{:<44}  {:>9}  {:>9}  {:>9}  {:>+20}  {:>+14}",
+                    owner.owner,
+                    owner.num_added,
+                    owner.num_removed,
+                    owner.num_modified,
+                    owner.lamports_delta,
+                    owner.data_len_delta,
+                )?;
+            }
+        }
+
+        if !self.accounts.is_empty() {
+            writeln!(f)?;
+            for account in &self.accounts {
+                writeln!(f, "{account}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSnapshotSummary {
+    pub slot: Slot,
+    pub bank_hash: String,
+    pub capitalization: u64,
+}
+
+impl Display for CliSnapshotSummary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "slot {}, bank hash {}, capitalization {}",
+            self.slot, self.bank_hash, self.capitalization,
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountsDelta {
+    pub num_accounts: usize,
+    pub lamports_delta: i128,
+    pub data_len_delta: i128,
+}
+
+impl Display for CliAccountsDelta {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}, lamports delta {:+}, data len delta {:+}",
+            self.num_accounts, self.lamports_delta, self.data_len_delta,
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliOwnerDiff {
+    pub owner: String,
+    pub num_added: usize,
+    pub num_removed: usize,
+    pub num_modified: usize,
+    pub lamports_delta: i128,
+    pub data_len_delta: i128,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CliAccountChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountDiff {
+    pub pubkey: String,
+    pub change: CliAccountChange,
+    /// The owner in the target snapshot, or in the base one for removed accounts
+    pub owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lamports_before: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lamports_after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_len_before: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_len_after: Option<usize>,
+}
+
+impl Display for CliAccountDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fn optional(value: Option<impl Display>) -> String {
+            value.map_or_else(|| "-".to_string(), |value| value.to_string())
+        }
+        write!(f, "{:?} {} owner: {}", self.change, self.pubkey, self.owner)?;
+        if let Some(previous_owner) = &self.previous_owner {
+            write!(f, " (was {previous_owner})")?;
+        }
+        write!(
+            f,
+            ", lamports: {} -> {}, data len: {} -> {}",
+            optional(self.lamports_before),
+            optional(self.lamports_after),
+            optional(self.data_len_before),
+            optional(self.data_len_after),
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBankingTraceSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_event_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_event_time: Option<String>,
+    pub duration_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_slot: Option<Slot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_slot: Option<Slot>,
+    pub num_slots: usize,
+    pub channels: Vec<CliBankingTraceChannel>,
+    pub num_transactions: usize,
+    pub num_vote_transactions: usize,
+    pub num_duplicates: usize,
+    /// Share of the transactions which were already sent earlier in the trace
+    pub duplicate_ratio: f64,
+    /// Compute unit prices of the non-vote transactions, in micro-lamports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_prices: Option<CliComputeUnitPriceDistribution>,
+    pub top_fee_payers: Vec<CliFeePayerCount>,
+}
+
+impl QuietDisplay for CliBankingTraceSummary {}
+impl VerboseDisplay for CliBankingTraceSummary {}
+
+impl fmt::Display for CliBankingTraceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let (Some(first_event_time), Some(last_event_time)) =
+            (&self.first_event_time, &self.last_event_time)
+        {
+            writeln!(
+                f,
+                "Events: {first_event_time} to {last_event_time} ({:.3}s)",
+                self.duration_secs,
+            )?;
+        } else {
+            writeln!(f, "Events: none")?;
+        }
+        if let (Some(first_slot), Some(last_slot)) = (self.first_slot, self.last_slot) {
+            writeln!(
+                f,
+                "Slots: {first_slot} to {last_slot} ({} frozen)",
+                self.num_slots
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<12}  {:>10}  {:>12}  {:>10}  {:>10}  {:>12}",
+            "Channel", "Batches", "Packets", "Discarded", "Invalid", "Packets/s",
+        )?;
+        for channel in &self.channels {
+            writeln!(
+                f,
+                "{:<12}  {:>10}  {:>12}  {:>10}  {:>10}  {:>12.1}",
+                channel.label,
+                channel.num_batches,
+                channel.num_packets,
+                channel.num_discarded,
+                channel.num_invalid,
+                channel.packets_per_second,
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Transactions: {} ({} votes), duplicates: {} ({:.2}%)",
+            self.num_transactions,
+            self.num_vote_transactions,
+            self.num_duplicates,
+            self.duplicate_ratio * 100.0,
+        )?;
+        if let Some(compute_unit_prices) = &self.compute_unit_prices {
+            writeln!(f, "Compute unit prices: {compute_unit_prices}")?;
+        }
+
+        if !self.top_fee_payers.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{:<44}  {:>12}", "Fee payer", "Transactions")?;
+            for fee_payer in &self.top_fee_payers {
+                writeln!(
+                    f,
+                    "{:<44}  {:>12}",
+                    fee_payer.fee_payer, fee_payer.num_transactions
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBankingTraceChannel {
+    pub label: String,
+    pub num_batches: usize,
+    pub num_packets: usize,
+    /// Packets marked as discarded by sigverify
+    pub num_discarded: usize,
+    /// Packets which couldn't be deserialized into a valid transaction
+    pub num_invalid: usize,
+    pub packets_per_second: f64,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliComputeUnitPriceDistribution {
+    pub num_transactions: usize,
+    pub num_with_priority_fee: usize,
+    pub min: u64,
+    pub p25: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl Display for CliComputeUnitPriceDistribution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} with a priority fee, min {}, p25 {}, median {}, p75 {}, p90 {}, p99 {}, max \
+             {}",
+            self.num_with_priority_fee,
+            self.num_transactions,
+            self.min,
+            self.p25,
+            self.median,
+            self.p75,
+            self.p90,
+            self.p99,
+            self.max,
+        )
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliFeePayerCount {
+    pub fee_payer: String,
+    pub num_transactions: usize,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSchedulerComparison {
+    /// Simulated configurations, as `METHOD:NUM_WORKERS`
+    pub configs: Vec<String>,
+    pub slots: Vec<CliSimulatedSlotComparison>,
+    /// Totals over all simulated slots, in the same order as `configs`
+    pub totals: Vec<CliSimulationTotals>,
+}
+
+impl QuietDisplay for CliSchedulerComparison {}
+impl VerboseDisplay for CliSchedulerComparison {}
+
+impl fmt::Display for CliSchedulerComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let config_width = self
+            .configs
+            .iter()
+            .map(|config| config.len())
+            .max()
+            .unwrap_or_default()
+            .max("Config".len());
+
+        for slot in &self.slots {
+            writeln!(f, "Slot {}:", slot.slot)?;
+            writeln!(
+                f,
+                "  {:<config_width$}  {:>8}  {:>14}  {:>14}  {:>12}  {:>12}  {:>9}",
+                "Config", "Txs", "Fees", "Priority fees", "Block CU", "Max acct CU", "Contended",
+            )?;
+            for (config, block) in self.configs.iter().zip(&slot.blocks) {
+                match block {
+                    Some(block) => writeln!(
+                        f,
+                        "  {:<config_width$}  {:>8}  {:>14}  {:>14}  {:>12}  {:>12}  {:>9}",
+                        config,
+                        block.transaction_count,
+                        block.fees,
+                        block.priority_fees,
+                        block.block_cost,
+                        block.costliest_account_cost,
+                        block.contended_account_count,
+                    )?,
+                    None => writeln!(f, "  {config:<config_width$}  not produced")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "Totals:")?;
+        writeln!(
+            f,
+            "  {:<config_width$}  {:>6}  {:>10}  {:>14}  {:>14}  {:>14}  {:>9}",
+            "Config", "Slots", "Txs", "Fees", "Priority fees", "Block CU", "Contended",
+        )?;
+        for (config, totals) in self.configs.iter().zip(&self.totals) {
+            writeln!(
+                f,
+                "  {:<config_width$}  {:>6}  {:>10}  {:>14}  {:>14}  {:>14}  {:>9}",
+                config,
+                totals.num_slots,
+                totals.transaction_count,
+                totals.fees,
+                totals.priority_fees,
+                totals.block_cost,
+                totals.contended_account_count,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulatedSlotComparison {
+    pub slot: Slot,
+    /// Block produced by each configuration, or `None` if the configuration didn't produce it
+    pub blocks: Vec<Option<CliSimulatedBlock>>,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulatedBlock {
+    pub transaction_count: u64,
+    /// Transaction fees, including priority fees
+    pub fees: u64,
+    pub priority_fees: u64,
+    pub block_cost: u64,
+    pub vote_cost: u64,
+    pub costliest_account: String,
+    pub costliest_account_cost: u64,
+    /// Writable accounts whose cost is close to the account cost limit
+    pub contended_account_count: usize,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulationTotals {
+    pub num_slots: usize,
+    pub transaction_count: u64,
+    pub fees: u64,
+    pub priority_fees: u64,
+    pub block_cost: u64,
+    pub contended_account_count: usize,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSlotCosts {
+    pub slots: Vec<CliSlotCost>,
+}
+
+impl QuietDisplay for CliSlotCosts {}
+impl VerboseDisplay for CliSlotCosts {}
+
+impl fmt::Display for CliSlotCosts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for slot in &self.slots {
+            write!(f, "{slot}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSlotCost {
+    pub slot: Slot,
+    pub num_entries: usize,
+    pub num_transactions: usize,
+    pub num_instructions: usize,
+    /// Transactions the cost tracker rejected because they would exceed a limit
+    pub num_rejected_transactions: usize,
+    pub block_cost: u64,
+    pub block_cost_limit: u64,
+    pub vote_cost: u64,
+    pub account_cost_limit: u64,
+    /// Whether the block cost is close to the block cost limit
+    pub near_block_limit: bool,
+    /// Writable accounts whose cost is close to the account cost limit
+    pub accounts_near_limit: Vec<CliWritableAccountCost>,
+    pub top_writable_accounts: Vec<CliWritableAccountCost>,
+    pub top_programs: Vec<CliProgramCost>,
+    pub top_signers: Vec<CliSignerCost>,
+}
+
+impl QuietDisplay for CliSlotCost {}
+impl VerboseDisplay for CliSlotCost {}
+
+impl fmt::Display for CliSlotCost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Slot: {}, Entries: {}, Transactions: {}, Programs {}",
+            self.slot, self.num_entries, self.num_transactions, self.num_instructions,
+        )?;
+        writeln!(
+            f,
+            "  Block cost: {} of {} CU{}, vote cost: {} CU",
+            self.block_cost,
+            self.block_cost_limit,
+            if self.near_block_limit {
+                " (near limit)"
+            } else {
+                ""
+            },
+            self.vote_cost,
+        )?;
+        if self.num_rejected_transactions > 0 {
+            writeln!(
+                f,
+                "  Rejected transactions: {}",
+                self.num_rejected_transactions
+            )?;
+        }
+        if !self.accounts_near_limit.is_empty() {
+            writeln!(
+                f,
+                "  Accounts near the account limit of {} CU:",
+                self.account_cost_limit
+            )?;
+            for account in &self.accounts_near_limit {
+                writeln!(f, "    {account}")?;
+            }
+        }
+        if !self.top_writable_accounts.is_empty() {
+            writeln!(f, "  Top writable accounts:")?;
+            for account in &self.top_writable_accounts {
+                writeln!(f, "    {account}")?;
+            }
+        }
+        if !self.top_programs.is_empty() {
+            writeln!(f, "  Top programs:")?;
+            for program in &self.top_programs {
+                writeln!(
+                    f,
+                    "    {:<44}  {:>8} instructions  {:>8} transactions  {:>12} CU",
+                    program.program_id,
+                    program.num_instructions,
+                    program.num_transactions,
+                    program.execution_cost,
+                )?;
+            }
+        }
+        if !self.top_signers.is_empty() {
+            writeln!(f, "  Top signers:")?;
+            for signer in &self.top_signers {
+                writeln!(
+                    f,
+                    "    {:<44}  {:>8} transactions  {:>12} CU",
+                    signer.signer, signer.num_transactions, signer.cost,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliWritableAccountCost {
+    pub pubkey: String,
+    pub cost: u64,
+}
+
+impl Display for CliWritableAccountCost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<44}  {:>12} CU", self.pubkey, self.cost)
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramCost {
+    pub program_id: String,
+    pub num_instructions: usize,
+    pub num_transactions: usize,
+    /// Share of the execution cost of the transactions invoking the program, each transaction's
+    /// execution cost being split evenly across its instructions
+    pub execution_cost: u64,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSignerCost {
+    pub signer: String,
+    pub num_transactions: usize,
+    pub cost: u64,
+}
@@ -0,0 +1,208 @@
+//! Comparison of the blocks produced by `simulate-block-production` under several scheduler
+//! configurations
+
+use {
+    crate::output::{
+        CliSchedulerComparison, CliSimulatedBlock, CliSimulatedSlotComparison, CliSimulationTotals,
+    },
+    solana_core::{
+        banking_simulation::SimulatedSlot, banking_stage::BankingStage,
+        validator::BlockProductionMethod,
+    },
+    std::{collections::BTreeSet, fmt, num::NonZeroUsize, str::FromStr},
+};
+
+/// A scheduler configuration to simulate, given as `METHOD[:NUM_WORKERS]` on the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationConfig {
+    pub block_production_method: BlockProductionMethod,
+    pub num_workers: NonZeroUsize,
+}
+
+impl FromStr for SimulationConfig {
+    type Err = String;
+
+    fn from_str(config: &str) -> Result<Self, Self::Err> {
+        let (method, num_workers) = match config.split_once(':') {
+            Some((method, num_workers)) => (method, Some(num_workers)),
+            None => (config, None),
+        };
+        let block_production_method = BlockProductionMethod::from_str(method)
+            .map_err(|_| format!("unknown block production method: {method}"))?;
+        let num_workers = match num_workers {
+            Some(num_workers) => num_workers
+                .parse::<NonZeroUsize>()
+                .map_err(|err| format!("invalid number of workers {num_workers}: {err}"))?,
+            None => BankingStage::default_num_workers(),
+        };
+        if num_workers > BankingStage::max_num_workers() {
+            return Err(format!(
+                "number of workers must be at most {}",
+                BankingStage::max_num_workers()
+            ));
+        }
+        Ok(Self {
+            block_production_method,
+            num_workers,
+        })
+    }
+}
+
+impl fmt::Display for SimulationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.block_production_method, self.num_workers)
+    }
+}
+
+pub fn is_simulation_config(config: String) -> Result<(), String> {
+    SimulationConfig::from_str(&config).map(|_| ())
+}
+
+/// Lines up the slots produced by each simulation run
+pub fn compare_simulations(
+    runs: &[(SimulationConfig, Vec<SimulatedSlot>)],
+) -> CliSchedulerComparison {
+    let slots = runs
+        .iter()
+        .flat_map(|(_config, simulated_slots)| simulated_slots.iter().map(|slot| slot.slot))
+        .collect::<BTreeSet<_>>();
+    let slots = slots
+        .into_iter()
+        .map(|slot| CliSimulatedSlotComparison {
+            slot,
+            blocks: runs
+                .iter()
+                .map(|(_config, simulated_slots)| {
+                    simulated_slots
+                        .iter()
+                        .find(|simulated_slot| simulated_slot.slot == slot)
+                        .map(simulated_block)
+                })
+                .collect(),
+        })
+        .collect();
+    let totals = runs
+        .iter()
+        .map(|(_config, simulated_slots)| {
+            simulated_slots.iter().fold(
+                CliSimulationTotals::default(),
+                |mut totals, simulated_slot| {
+                    totals.num_slots += 1;
+                    totals.transaction_count += simulated_slot.transaction_count;
+                    totals.fees += simulated_slot.fees;
+                    totals.priority_fees += simulated_slot.priority_fees;
+                    totals.block_cost += simulated_slot.block_cost;
+                    totals.contended_account_count += simulated_slot.contended_account_count;
+                    totals
+                },
+            )
+        })
+        .collect();
+
+    CliSchedulerComparison {
+        configs: runs.iter().map(|(config, _)| config.to_string()).collect(),
+        slots,
+        totals,
+    }
+}
+
+fn simulated_block(simulated_slot: &SimulatedSlot) -> CliSimulatedBlock {
+    CliSimulatedBlock {
+        transaction_count: simulated_slot.transaction_count,
+        fees: simulated_slot.fees,
+        priority_fees: simulated_slot.priority_fees,
+        block_cost: simulated_slot.block_cost,
+        vote_cost: simulated_slot.vote_cost,
+        costliest_account: simulated_slot.costliest_account.to_string(),
+        costliest_account_cost: simulated_slot.costliest_account_cost,
+        contended_account_count: simulated_slot.contended_account_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_pubkey::Pubkey};
+
+    fn simulated_slot(slot: u64, transaction_count: u64, block_cost: u64) -> SimulatedSlot {
+        SimulatedSlot {
+            slot,
+            transaction_count,
+            fees: transaction_count * 5000,
+            priority_fees: 0,
+            block_cost,
+            vote_cost: 0,
+            costliest_account: Pubkey::new_unique(),
+            costliest_account_cost: block_cost / 2,
+            contended_account_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_simulation_config() {
+        assert_eq!(
+            "central-scheduler:8".parse::<SimulationConfig>().unwrap(),
+            SimulationConfig {
+                block_production_method: BlockProductionMethod::CentralScheduler,
+                num_workers: NonZeroUsize::new(8).unwrap(),
+            }
+        );
+        assert_eq!(
+            "central-scheduler-greedy"
+                .parse::<SimulationConfig>()
+                .unwrap(),
+            SimulationConfig {
+                block_production_method: BlockProductionMethod::CentralSchedulerGreedy,
+                num_workers: BankingStage::default_num_workers(),
+            }
+        );
+        assert!("central-scheduler:0".parse::<SimulationConfig>().is_err());
+        assert!("central-scheduler:65".parse::<SimulationConfig>().is_err());
+        assert!("thread-local-multi-iterator"
+            .parse::<SimulationConfig>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_compare_simulations() {
+        let greedy = "central-scheduler-greedy:4"
+            .parse::<SimulationConfig>()
+            .unwrap();
+        let central = "central-scheduler:8".parse::<SimulationConfig>().unwrap();
+        let comparison = compare_simulations(&[
+            (
+                greedy,
+                vec![simulated_slot(10, 100, 1000), simulated_slot(11, 50, 500)],
+            ),
+            (central, vec![simulated_slot(11, 70, 700)]),
+        ]);
+
+        assert_eq!(
+            comparison.configs,
+            vec![
+                "central-scheduler-greedy:4".to_string(),
+                "central-scheduler:8".to_string()
+            ]
+        );
+        assert_eq!(comparison.slots.len(), 2);
+        assert_eq!(comparison.slots[0].slot, 10);
+        assert_eq!(
+            comparison.slots[0].blocks[0].as_ref().unwrap().block_cost,
+            1000
+        );
+        assert!(comparison.slots[0].blocks[1].is_none());
+        assert_eq!(
+            comparison.slots[1].blocks[1]
+                .as_ref()
+                .unwrap()
+                .transaction_count,
+            70
+        );
+
+        assert_eq!(comparison.totals[0].num_slots, 2);
+        assert_eq!(comparison.totals[0].transaction_count, 150);
+        assert_eq!(comparison.totals[0].fees, 750_000);
+        assert_eq!(comparison.totals[0].block_cost, 1500);
+        assert_eq!(comparison.totals[0].contended_account_count, 2);
+        assert_eq!(comparison.totals[1].num_slots, 1);
+    }
+}
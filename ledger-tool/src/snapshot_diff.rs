@@ -0,0 +1,488 @@
+//! The `snapshot-diff` subcommand
+
+use {
+    crate::{
+        args::{accounts_db_args, get_accounts_db_config, load_genesis_arg},
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+        ledger_utils::open_genesis_config_by,
+        output::{
+            CliAccountChange, CliAccountDiff, CliAccountsDelta, CliOwnerDiff, CliSnapshotDiff,
+            CliSnapshotSummary,
+        },
+        LEDGER_TOOL_DIRECTORY,
+    },
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    log::*,
+    solana_account::{accounts_equal, AccountSharedData, ReadableAccount},
+    solana_accounts_db::{
+        accounts_db::AccountsDbConfig, accounts_index::AccountsIndexConfig,
+        is_loadable::IsLoadable as _, utils::create_all_accounts_run_and_snapshot_dirs,
+    },
+    solana_cli_output::OutputFormat,
+    solana_genesis_config::GenesisConfig,
+    solana_pubkey::Pubkey,
+    solana_runtime::{
+        bank::Bank,
+        runtime_config::RuntimeConfig,
+        snapshot_archive_info::{
+            FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo, SnapshotArchiveInfoGetter,
+        },
+        snapshot_bank_utils::bank_from_snapshot_archives,
+        snapshot_utils::BANK_SNAPSHOTS_DIR,
+    },
+    std::{
+        collections::HashMap,
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+pub fn snapshot_diff_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("snapshot-diff")
+        .about("Compare the accounts of two snapshots")
+        .long_about(
+            "Compare the accounts of two snapshots. Both snapshots are loaded from their \
+             archives, each being a full snapshot archive optionally followed by an incremental \
+             snapshot archive. Accounts are reported as added, removed or modified in the target \
+             snapshot relative to the base snapshot, along with the lamport, data size and \
+             capitalization deltas, summarized per owner.",
+        )
+        .arg(load_genesis_arg())
+        .args(&accounts_db_args())
+        .arg(
+            Arg::with_name("base_snapshot")
+                .long("base-snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .required(true)
+                .help("The full snapshot archive of the base snapshot"),
+        )
+        .arg(
+            Arg::with_name("base_incremental_snapshot")
+                .long("base-incremental-snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .help("The incremental snapshot archive of the base snapshot"),
+        )
+        .arg(
+            Arg::with_name("target_snapshot")
+                .long("target-snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .required(true)
+                .help("The full snapshot archive of the target snapshot"),
+        )
+        .arg(
+            Arg::with_name("target_incremental_snapshot")
+                .long("target-incremental-snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .help("The incremental snapshot archive of the target snapshot"),
+        )
+        .arg(
+            Arg::with_name("include_sysvars")
+                .long("include-sysvars")
+                .takes_value(false)
+                .help("Include sysvars too"),
+        )
+        .arg(
+            Arg::with_name("no_account_changes")
+                .long("no-account-changes")
+                .takes_value(false)
+                .help(
+                    "Do not list every added, removed and modified account, only print the \
+                     summaries",
+                ),
+        )
+}
+
+pub fn snapshot_diff_process_command(ledger_path: &Path, arg_matches: &ArgMatches<'_>) {
+    do_snapshot_diff_process_command(ledger_path, arg_matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_snapshot_diff_process_command(
+    ledger_path: &Path,
+    arg_matches: &ArgMatches<'_>,
+) -> Result<()> {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+    let accounts_db_config = get_accounts_db_config(&ledger_path, arg_matches);
+    let options = DiffOptions {
+        include_sysvars: arg_matches.is_present("include_sysvars"),
+        list_accounts: !arg_matches.is_present("no_account_changes"),
+    };
+    let output_format = OutputFormat::from_matches(arg_matches, "output_format", false);
+
+    let work_dir = ledger_path
+        .join(LEDGER_TOOL_DIRECTORY)
+        .join("snapshot-diff");
+    let load_bank = |name: &str| {
+        let full_snapshot_arg = format!("{name}_snapshot");
+        let incremental_snapshot_arg = format!("{name}_incremental_snapshot");
+        let full_snapshot_archive = value_t_or_exit!(arg_matches, &full_snapshot_arg, PathBuf);
+        let incremental_snapshot_archive =
+            value_t!(arg_matches, &incremental_snapshot_arg, PathBuf).ok();
+        load_bank_from_snapshot_archives(
+            &genesis_config,
+            &accounts_db_config,
+            &work_dir.join(name),
+            full_snapshot_archive,
+            incremental_snapshot_archive,
+        )
+    };
+    let base_bank = load_bank("base")?;
+    let target_bank = load_bank("target")?;
+
+    let diff = diff_banks(&base_bank, &target_bank, options)?;
+    println!("{}", output_format.formatted_string(&diff));
+
+    drop((base_bank, target_bank));
+    if let Err(err) = fs::remove_dir_all(&work_dir) {
+        warn!("Failed to remove {}: {err}", work_dir.display());
+    }
+    Ok(())
+}
+
+/// Loads a bank from a full, and optionally an incremental, snapshot archive,
+/// unpacking the archives under `work_dir`
+pub(crate) fn load_bank_from_snapshot_archives(
+    genesis_config: &GenesisConfig,
+    accounts_db_config: &AccountsDbConfig,
+    work_dir: &Path,
+    full_snapshot_archive: PathBuf,
+    incremental_snapshot_archive: Option<PathBuf>,
+) -> Result<Bank> {
+    let full_snapshot_archive_info = FullSnapshotArchiveInfo::new_from_path(full_snapshot_archive)
+        .map_err(|err| LedgerToolError::BadArgument(err.to_string()))?;
+    let incremental_snapshot_archive_info = incremental_snapshot_archive
+        .map(IncrementalSnapshotArchiveInfo::new_from_path)
+        .transpose()
+        .map_err(|err| LedgerToolError::BadArgument(err.to_string()))?;
+    if let Some(incremental_snapshot_archive_info) = &incremental_snapshot_archive_info {
+        if incremental_snapshot_archive_info.base_slot() != full_snapshot_archive_info.slot() {
+            return Err(LedgerToolError::BadArgument(format!(
+                "incremental snapshot {} is not based on full snapshot {}",
+                incremental_snapshot_archive_info.path().display(),
+                full_snapshot_archive_info.path().display(),
+            )));
+        }
+    }
+
+    if work_dir.exists() {
+        fs::remove_dir_all(work_dir)?;
+    }
+    let bank_snapshots_dir = work_dir.join(BANK_SNAPSHOTS_DIR);
+    fs::create_dir_all(&bank_snapshots_dir)?;
+    let (account_paths, _account_snapshot_paths) =
+        create_all_accounts_run_and_snapshot_dirs(&[work_dir.join("accounts")])?;
+    // Both banks are loaded at the same time, so they each need their own index
+    let accounts_db_config = AccountsDbConfig {
+        index: Some(AccountsIndexConfig {
+            drives: Some(vec![work_dir.join("accounts_index")]),
+            ..accounts_db_config.index.clone().unwrap_or_default()
+        }),
+        ..accounts_db_config.clone()
+    };
+
+    bank_from_snapshot_archives(
+        &account_paths,
+        &bank_snapshots_dir,
+        &full_snapshot_archive_info,
+        incremental_snapshot_archive_info.as_ref(),
+        genesis_config,
+        &RuntimeConfig::default(),
+        None,
+        None,
+        true,
+        false,
+        false,
+        accounts_db_config,
+        None,
+        Arc::default(),
+    )
+    .map_err(|err| {
+        LedgerToolError::Generic(format!(
+            "Failed to load bank from snapshot archive {}: {err}",
+            full_snapshot_archive_info.path().display(),
+        ))
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DiffOptions {
+    include_sysvars: bool,
+    list_accounts: bool,
+}
+
+/// Compares the accounts of `target` to those of `base`
+fn diff_banks(base: &Bank, target: &Bank, options: DiffOptions) -> Result<CliSnapshotDiff> {
+    let is_included = |account: &AccountSharedData| {
+        account.is_loadable()
+            && (options.include_sysvars || !solana_sdk_ids::sysvar::check_id(account.owner()))
+    };
+    let mut diff = DiffBuilder {
+        options,
+        diff: CliSnapshotDiff {
+            base: snapshot_summary(base),
+            target: snapshot_summary(target),
+            capitalization_delta: i128::from(target.capitalization())
+                - i128::from(base.capitalization()),
+            ..CliSnapshotDiff::default()
+        },
+        owners: HashMap::default(),
+    };
+
+    target
+        .scan_all_accounts(
+            |account_tuple| {
+                if let Some((pubkey, account, _slot)) =
+                    account_tuple.filter(|(_, account, _)| is_included(account))
+                {
+                    match base.get_account(pubkey).filter(is_included) {
+                        None => diff.record(pubkey, None, Some(&account)),
+                        Some(base_account) if !accounts_equal(&base_account, &account) => {
+                            diff.record(pubkey, Some(&base_account), Some(&account))
+                        }
+                        Some(_) => {}
+                    }
+                }
+            },
+            false,
+        )
+        .map_err(|err| LedgerToolError::Generic(format!("Failed to scan target bank: {err}")))?;
+    base.scan_all_accounts(
+        |account_tuple| {
+            if let Some((pubkey, account, _slot)) =
+                account_tuple.filter(|(_, account, _)| is_included(account))
+            {
+                if target.get_account(pubkey).filter(is_included).is_none() {
+                    diff.record(pubkey, Some(&account), None);
+                }
+            }
+        },
+        false,
+    )
+    .map_err(|err| LedgerToolError::Generic(format!("Failed to scan base bank: {err}")))?;
+
+    Ok(diff.finish())
+}
+
+fn snapshot_summary(bank: &Bank) -> CliSnapshotSummary {
+    CliSnapshotSummary {
+        slot: bank.slot(),
+        bank_hash: bank.hash().to_string(),
+        capitalization: bank.capitalization(),
+    }
+}
+
+struct DiffBuilder {
+    options: DiffOptions,
+    diff: CliSnapshotDiff,
+    owners: HashMap<Pubkey, CliOwnerDiff>,
+}
+
+impl DiffBuilder {
+    /// Records an account that is only in the base snapshot, only in the
+    /// target snapshot, or differs between the two
+    fn record(
+        &mut self,
+        pubkey: &Pubkey,
+        before: Option<&AccountSharedData>,
+        after: Option<&AccountSharedData>,
+    ) {
+        let (change, owner) = match (before, after) {
+            (None, Some(after)) => (CliAccountChange::Added, after.owner()),
+            (Some(before), None) => (CliAccountChange::Removed, before.owner()),
+            (Some(_), Some(after)) => (CliAccountChange::Modified, after.owner()),
+            (None, None) => return,
+        };
+        let lamports =
+            |account: Option<&AccountSharedData>| account.map(|account| account.lamports());
+        let data_len =
+            |account: Option<&AccountSharedData>| account.map(|account| account.data().len());
+        let lamports_delta = i128::from(lamports(after).unwrap_or_default())
+            - i128::from(lamports(before).unwrap_or_default());
+        let data_len_delta = data_len(after).unwrap_or_default() as i128
+            - data_len(before).unwrap_or_default() as i128;
+
+        let delta = match change {
+            CliAccountChange::Added => &mut self.diff.added,
+            CliAccountChange::Removed => &mut self.diff.removed,
+            CliAccountChange::Modified => &mut self.diff.modified,
+        };
+        add_delta(delta, lamports_delta, data_len_delta);
+        self.diff.lamports_delta += lamports_delta;
+
+        let owner_diff = self.owners.entry(*owner).or_insert_with(|| CliOwnerDiff {
+            owner: owner.to_string(),
+            ..CliOwnerDiff::default()
+        });
+        match change {
+            CliAccountChange::Added => owner_diff.num_added += 1,
+            CliAccountChange::Removed => owner_diff.num_removed += 1,
+            CliAccountChange::Modified => owner_diff.num_modified += 1,
+        }
+        owner_diff.lamports_delta += lamports_delta;
+        owner_diff.data_len_delta += data_len_delta;
+
+        if self.options.list_accounts {
+            let previous_owner = before
+                .map(|before| before.owner())
+                .filter(|previous_owner| *previous_owner != owner);
+            self.diff.accounts.push(CliAccountDiff {
+                pubkey: pubkey.to_string(),
+                change,
+                owner: owner.to_string(),
+                previous_owner: previous_owner.map(|owner| owner.to_string()),
+                lamports_before: lamports(before),
+                lamports_after: lamports(after),
+                data_len_before: data_len(before),
+                data_len_after: data_len(after),
+            });
+        }
+    }
+
+    fn finish(self) -> CliSnapshotDiff {
+        let Self {
+            mut diff, owners, ..
+        } = self;
+        diff.owners = owners.into_values().collect();
+        // Owners with the most changed accounts first
+        diff.owners.sort_by(|a, b| {
+            let num_changed =
+                |owner: &CliOwnerDiff| owner.num_added + owner.num_removed + owner.num_modified;
+            num_changed(b)
+                .cmp(&num_changed(a))
+                .then_with(|| a.owner.cmp(&b.owner))
+        });
+        diff.accounts.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
+        diff
+    }
+}
+
+fn add_delta(delta: &mut CliAccountsDelta, lamports_delta: i128, data_len_delta: i128) {
+    delta.num_accounts += 1;
+    delta.lamports_delta += lamports_delta;
+    delta.data_len_delta += data_len_delta;
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_account::WritableAccount,
+        solana_runtime::genesis_utils::{create_genesis_config, GenesisConfigInfo},
+    };
+
+    #[test]
+    fn test_diff_banks() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(1_000_000);
+        let base = Arc::new(Bank::new_for_tests(&genesis_config));
+        let program = Pubkey::new_unique();
+        let unchanged = Pubkey::new_unique();
+        let modified = Pubkey::new_unique();
+        let removed = Pubkey::new_unique();
+        base.store_account(&unchanged, &AccountSharedData::new(10, 0, &program));
+        base.store_account(&modified, &AccountSharedData::new(20, 4, &program));
+        base.store_account(&removed, &AccountSharedData::new(30, 8, &program));
+
+        let target = Bank::new_from_parent(base.clone(), &Pubkey::default(), 1);
+        let added = Pubkey::new_unique();
+        target.store_account(&added, &AccountSharedData::new(40, 16, &program));
+        let mut modified_account = AccountSharedData::new(25, 6, &program);
+        modified_account.set_owner(Pubkey::new_unique());
+        target.store_account(&modified, &modified_account);
+        target.store_account(&removed, &AccountSharedData::default());
+
+        let diff = diff_banks(
+            &base,
+            &target,
+            DiffOptions {
+                include_sysvars: false,
+                list_accounts: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(diff.base.slot, 0);
+        assert_eq!(diff.target.slot, 1);
+        assert_eq!(
+            diff.added,
+            CliAccountsDelta {
+                num_accounts: 1,
+                lamports_delta: 40,
+                data_len_delta: 16,
+            }
+        );
+        assert_eq!(
+            diff.removed,
+            CliAccountsDelta {
+                num_accounts: 1,
+                lamports_delta: -30,
+                data_len_delta: -8,
+            }
+        );
+        assert_eq!(
+            diff.modified,
+            CliAccountsDelta {
+                num_accounts: 1,
+                lamports_delta: 5,
+                data_len_delta: 2,
+            }
+        );
+        assert_eq!(diff.lamports_delta, 15);
+
+        // The modified account moved to a new owner
+        assert_eq!(diff.owners.len(), 2);
+        assert_eq!(diff.owners[0].owner, program.to_string());
+        assert_eq!(
+            (
+                diff.owners[0].num_added,
+                diff.owners[0].num_removed,
+                diff.owners[0].num_modified
+            ),
+            (1, 1, 0)
+        );
+        assert_eq!(diff.owners[0].lamports_delta, 10);
+        assert_eq!(diff.owners[1].num_modified, 1);
+
+        // Accounts are listed by pubkey
+        let changes = diff
+            .accounts
+            .iter()
+            .map(|account| (account.pubkey.clone(), account.change))
+            .collect::<Vec<_>>();
+        let mut expected = vec![
+            (added.to_string(), CliAccountChange::Added),
+            (modified.to_string(), CliAccountChange::Modified),
+            (removed.to_string(), CliAccountChange::Removed),
+        ];
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(changes, expected);
+        let modified_diff = diff
+            .accounts
+            .iter()
+            .find(|account| account.pubkey == modified.to_string())
+            .unwrap();
+        assert_eq!(modified_diff.previous_owner, Some(program.to_string()));
+        assert_eq!(modified_diff.lamports_before, Some(20));
+        assert_eq!(modified_diff.lamports_after, Some(25));
+
+        // Without listing accounts only the summaries are reported
+        let diff = diff_banks(
+            &base,
+            &target,
+            DiffOptions {
+                include_sysvars: false,
+                list_accounts: false,
+            },
+        )
+        .unwrap();
+        assert!(diff.accounts.is_empty());
+        assert_eq!(diff.added.num_accounts, 1);
+    }
+}
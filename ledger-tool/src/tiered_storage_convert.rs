@@ -0,0 +1,235 @@
+//! The `convert-to-tiered-storage` subcommand
+
+use {
+    crate::{
+        args::{accounts_db_args, get_accounts_db_config, load_genesis_arg},
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+        ledger_utils::open_genesis_config_by,
+        snapshot_diff::load_bank_from_snapshot_archives,
+        LEDGER_TOOL_DIRECTORY,
+    },
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    log::*,
+    rayon::prelude::*,
+    solana_accounts_db::{
+        accounts_db::AccountStorageEntry,
+        accounts_file::AccountsFile,
+        tiered_storage::{cold::COLD_FORMAT, hot::HOT_FORMAT, TieredStorageFormat},
+    },
+    solana_clock::Slot,
+    solana_measure::measure_time,
+    solana_runtime::{
+        snapshot_archive_info::SnapshotArchiveInfoGetter,
+        snapshot_bank_utils::bank_to_full_snapshot_archive_with_storages,
+        snapshot_hash::SnapshotHash,
+        snapshot_utils::{
+            ArchiveFormat, DEFAULT_ARCHIVE_COMPRESSION, SUPPORTED_ARCHIVE_COMPRESSION,
+        },
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+const TIERED_STORAGE_FORMATS: &[&str] = &["hot", "cold"];
+
+pub fn tiered_storage_convert_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("convert-to-tiered-storage")
+        .about("Rewrite the account storages of a snapshot in the tiered storage format")
+        .long_about(
+            "Rewrite the account storages of a snapshot in the tiered storage format. The \
+             snapshot is loaded from a full snapshot archive, optionally followed by an \
+             incremental snapshot archive. Every account storage is rewritten as a tiered storage \
+             file, the accounts lt hash of the rewritten storages is verified against the bank's, \
+             and a new full snapshot archive holding the tiered storage files is written to the \
+             output directory.",
+        )
+        .arg(load_genesis_arg())
+        .args(&accounts_db_args())
+        .arg(
+            Arg::with_name("snapshot")
+                .long("snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .required(true)
+                .help("The full snapshot archive to convert"),
+        )
+        .arg(
+            Arg::with_name("incremental_snapshot")
+                .long("incremental-snapshot")
+                .value_name("ARCHIVE")
+                .takes_value(true)
+                .help("The incremental snapshot archive to apply on top of the full snapshot"),
+        )
+        .arg(
+            Arg::with_name("output_directory")
+                .long("output-directory")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Directory to write the converted full snapshot archive to"),
+        )
+        .arg(
+            Arg::with_name("tiered_storage_format")
+                .long("tiered-storage-format")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .possible_values(TIERED_STORAGE_FORMATS)
+                .default_value("hot")
+                .help("The tiered storage format to rewrite the account storages in"),
+        )
+        .arg(
+            Arg::with_name("snapshot_archive_format")
+                .long("snapshot-archive-format")
+                .possible_values(SUPPORTED_ARCHIVE_COMPRESSION)
+                .default_value(DEFAULT_ARCHIVE_COMPRESSION)
+                .value_name("ARCHIVE_TYPE")
+                .takes_value(true)
+                .help("Snapshot archive format to use."),
+        )
+}
+
+pub fn tiered_storage_convert_process_command(ledger_path: &Path, arg_matches: &ArgMatches<'_>) {
+    do_tiered_storage_convert_process_command(ledger_path, arg_matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_tiered_storage_convert_process_command(
+    ledger_path: &Path,
+    arg_matches: &ArgMatches<'_>,
+) -> Result<()> {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+    let accounts_db_config = get_accounts_db_config(&ledger_path, arg_matches);
+    let full_snapshot_archive = value_t_or_exit!(arg_matches, "snapshot", PathBuf);
+    let incremental_snapshot_archive = value_t!(arg_matches, "incremental_snapshot", PathBuf).ok();
+    let output_directory = value_t_or_exit!(arg_matches, "output_directory", PathBuf);
+    let tiered_storage_format =
+        match value_t_or_exit!(arg_matches, "tiered_storage_format", String).as_str() {
+            "hot" => &HOT_FORMAT,
+            "cold" => &COLD_FORMAT,
+            _ => unreachable!(),
+        };
+    let archive_format = {
+        let archive_format_str = value_t_or_exit!(arg_matches, "snapshot_archive_format", String);
+        ArchiveFormat::from_cli_arg(&archive_format_str).ok_or_else(|| {
+            LedgerToolError::BadArgument(format!(
+                "archive format not recognized: {archive_format_str}"
+            ))
+        })?
+    };
+
+    let work_dir = ledger_path
+        .join(LEDGER_TOOL_DIRECTORY)
+        .join("convert-to-tiered-storage");
+    if work_dir.exists() {
+        fs::remove_dir_all(&work_dir)?;
+    }
+    let bank = load_bank_from_snapshot_archives(
+        &genesis_config,
+        &accounts_db_config,
+        &work_dir.join("bank"),
+        full_snapshot_archive,
+        incremental_snapshot_archive,
+    )?;
+
+    let storages = bank.get_snapshot_storages(None);
+    let tiered_storages_dir = work_dir.join("tiered");
+    fs::create_dir_all(&tiered_storages_dir)?;
+    let (tiered_storages, convert_time) = measure_time!(
+        convert_storages(
+            &storages,
+            &tiered_storages_dir,
+            tiered_storage_format,
+            bank.slot(),
+        )?,
+        "convert storages"
+    );
+    info!(
+        "Converted {} storages for slot {}: {convert_time}",
+        tiered_storages.len(),
+        bank.slot(),
+    );
+
+    let (accounts_lt_hash, verify_time) = measure_time!(
+        bank.rc
+            .accounts
+            .accounts_db
+            .calculate_accounts_lt_hash_from_storages(&tiered_storages, bank.slot()),
+        "verify accounts lt hash"
+    );
+    info!("{verify_time}");
+    let expected_snapshot_hash = bank.get_snapshot_hash();
+    let snapshot_hash = SnapshotHash::new(accounts_lt_hash.0.checksum());
+    if snapshot_hash != expected_snapshot_hash {
+        return Err(LedgerToolError::Generic(format!(
+            "accounts lt hash mismatch after conversion: expected checksum {}, got {}",
+            expected_snapshot_hash.0, snapshot_hash.0,
+        )));
+    }
+
+    let original_size = total_storage_size(&storages);
+    let tiered_size = total_storage_size(&tiered_storages);
+    fs::create_dir_all(&output_directory)?;
+    let snapshot_archive_info = bank_to_full_snapshot_archive_with_storages(
+        &work_dir,
+        &bank,
+        tiered_storages,
+        None,
+        &output_directory,
+        &output_directory,
+        archive_format,
+    )
+    .map_err(|err| LedgerToolError::Generic(format!("Failed to create snapshot archive: {err}")))?;
+
+    println!(
+        "Converted {} storages at slot {} to tiered storage, {original_size} bytes -> \
+         {tiered_size} bytes",
+        storages.len(),
+        bank.slot(),
+    );
+    println!(
+        "Successfully created snapshot archive {}, accounts lt hash checksum verified: {}",
+        snapshot_archive_info.path().display(),
+        snapshot_hash.0,
+    );
+
+    drop((storages, bank));
+    if let Err(err) = fs::remove_dir_all(&work_dir) {
+        warn!("Failed to remove {}: {err}", work_dir.display());
+    }
+    Ok(())
+}
+
+/// Rewrites each of `storages` as a tiered storage file under `dir`
+fn convert_storages(
+    storages: &[Arc<AccountStorageEntry>],
+    dir: &Path,
+    format: &TieredStorageFormat,
+    snapshot_slot: Slot,
+) -> Result<Vec<Arc<AccountStorageEntry>>> {
+    storages
+        .par_iter()
+        .map(|storage| {
+            let path = dir.join(AccountsFile::file_name(storage.slot(), storage.id()));
+            storage
+                .rewrite_as_tiered_storage(path, format, Some(snapshot_slot))
+                .map(Arc::new)
+                .map_err(|err| {
+                    LedgerToolError::Generic(format!(
+                        "Failed to convert storage {}: {err}",
+                        storage.path().display(),
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn total_storage_size(storages: &[Arc<AccountStorageEntry>]) -> u64 {
+    storages.iter().map(|storage| storage.written_bytes()).sum()
+}
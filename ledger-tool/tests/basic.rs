@@ -1,9 +1,18 @@
 use {
     assert_cmd::prelude::*,
+    solana_entry::entry::next_entry,
+    solana_keypair::Keypair,
     solana_ledger::{
-        blockstore, blockstore::Blockstore, create_new_tmp_ledger_auto_delete,
-        genesis_utils::create_genesis_config, get_tmp_ledger_path_auto_delete,
+        blockstore,
+        blockstore::{entries_to_test_shreds, Blockstore},
+        create_new_tmp_ledger_auto_delete,
+        genesis_utils::create_genesis_config,
+        get_tmp_ledger_path_auto_delete,
     },
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_system_interface::{instruction as system_instruction, program as system_program},
+    solana_transaction::Transaction,
     std::{
         path::Path,
         process::{Command, Output},
@@ -87,3 +96,82 @@ fn ledger_tool_copy_test() {
         assert!(!src_slot_output.stdout.is_empty());
     }
 }
+
+#[test]
+fn compute_slot_cost_breakdown() {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transactions = (1..=3)
+        .map(|lamports| {
+            Transaction::new_signed_with_payer(
+                &[system_instruction::transfer(
+                    &payer.pubkey(),
+                    &recipient,
+                    lamports,
+                )],
+                Some(&payer.pubkey()),
+                &[&payer],
+                blockhash,
+            )
+        })
+        .collect();
+    let entries = vec![next_entry(&blockhash, 1, transactions)];
+    let shreds = entries_to_test_shreds(&entries, 1, 0, true, 0);
+    Blockstore::open(ledger_path.path())
+        .unwrap()
+        .insert_shreds(shreds, None, false)
+        .unwrap();
+
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path.path().to_str().unwrap(),
+        "compute-slot-cost",
+        "1",
+        "--output",
+        "json",
+    ]);
+    assert!(output.status.success());
+    let slot_costs: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let slot_cost = &slot_costs["slots"][0];
+    assert_eq!(slot_cost["slot"], 1);
+    assert_eq!(slot_cost["numTransactions"], 3);
+    assert_eq!(slot_cost["nearBlockLimit"], false);
+    assert!(slot_cost["accountsNearLimit"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    // The payer and the recipient are write-locked by every transaction
+    let top_writable_accounts = slot_cost["topWritableAccounts"].as_array().unwrap();
+    assert_eq!(top_writable_accounts.len(), 2);
+    assert_eq!(top_writable_accounts[0]["cost"], slot_cost["blockCost"]);
+    assert_eq!(
+        slot_cost["topPrograms"][0]["programId"],
+        system_program::id().to_string()
+    );
+    assert_eq!(slot_cost["topPrograms"][0]["numTransactions"], 3);
+    assert!(
+        slot_cost["topPrograms"][0]["executionCost"]
+            .as_u64()
+            .unwrap()
+            > 0
+    );
+    assert_eq!(
+        slot_cost["topSigners"][0]["signer"],
+        payer.pubkey().to_string()
+    );
+    assert_eq!(slot_cost["topSigners"][0]["cost"], slot_cost["blockCost"]);
+
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path.path().to_str().unwrap(),
+        "compute-slot-cost",
+        "1",
+    ]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Slot: 1, Entries: 1, Transactions: 3"));
+}
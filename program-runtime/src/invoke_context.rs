@@ -8,7 +8,7 @@ use {
         stable_log,
         sysvar_cache::SysvarCache,
     },
-    solana_account::{create_account_shared_data_for_test, AccountSharedData},
+    solana_account::{create_account_shared_data_for_test, AccountSharedData, ReadableAccount},
     solana_clock::Slot,
     solana_epoch_schedule::EpochSchedule,
     solana_hash::Hash,
@@ -26,7 +26,7 @@ use {
     },
     solana_svm_callback::InvokeContextCallback,
     solana_svm_feature_set::SVMFeatureSet,
-    solana_svm_log_collector::{ic_msg, LogCollector},
+    solana_svm_log_collector::{ic_msg, InstructionTrace, InstructionTraceAccount, LogCollector},
     solana_svm_measure::measure::Measure,
     solana_svm_timings::{ExecuteDetailsTimings, ExecuteTimings},
     solana_svm_transaction::{instruction::SVMInstruction, svm_message::SVMMessage},
@@ -182,6 +182,14 @@ pub struct SerializedAccountMetadata {
     pub vm_owner_addr: u64,
 }
 
+/// An instruction being recorded in the instruction trace of the log collector
+struct PendingInstructionTrace {
+    /// Index of the instruction's record in the instruction trace
+    index_in_trace: usize,
+    /// The instruction's accounts before it was processed
+    pre_accounts: Vec<AccountSharedData>,
+}
+
 /// Main pipeline from runtime to program execution.
 pub struct InvokeContext<'a> {
     /// Information about the currently executing transaction.
@@ -501,10 +509,12 @@ impl<'a> InvokeContext<'a> {
     ) -> Result<(), InstructionError> {
         *compute_units_consumed = 0;
         self.push()?;
-        self.process_executable_chain(compute_units_consumed, timings)
-            // MUST pop if and only if `push` succeeded, independent of `result`.
-            // Thus, the `.and()` instead of an `.and_then()`.
-            .and(self.pop())
+        let pending_instruction_trace = self.begin_instruction_trace();
+        let result = self.process_executable_chain(compute_units_consumed, timings);
+        self.end_instruction_trace(pending_instruction_trace, *compute_units_consumed);
+        // MUST pop if and only if `push` succeeded, independent of `result`.
+        // Thus, the `.and()` instead of an `.and_then()`.
+        result.and(self.pop())
     }
 
     /// Processes a precompile instruction
@@ -515,12 +525,103 @@ impl<'a> InvokeContext<'a> {
         message_instruction_datas_iter: impl Iterator<Item = &'ix_data [u8]>,
     ) -> Result<(), InstructionError> {
         self.push()?;
+        let pending_instruction_trace = self.begin_instruction_trace();
         let instruction_datas: Vec<_> = message_instruction_datas_iter.collect();
-        self.environment_config
+        let result = self
+            .environment_config
             .epoch_stake_callback
             .process_precompile(program_id, instruction_data, instruction_datas)
-            .map_err(InstructionError::from)
-            .and(self.pop())
+            .map_err(InstructionError::from);
+        self.end_instruction_trace(pending_instruction_trace, 0);
+        result.and(self.pop())
+    }
+
+    /// Starts recording the current instruction in the instruction trace of the log collector,
+    /// if it is enabled
+    fn begin_instruction_trace(&self) -> Option<PendingInstructionTrace> {
+        let mut log_collector = self.log_collector.as_ref()?.try_borrow_mut().ok()?;
+        let instruction_trace = log_collector.instruction_trace.as_mut()?;
+        let instruction_context = self
+            .transaction_context
+            .get_current_instruction_context()
+            .ok()?;
+        let mut accounts = Vec::<InstructionTraceAccount>::new();
+        let mut pre_accounts = Vec::new();
+        for instruction_account in instruction_context.instruction_accounts() {
+            let index_in_transaction = instruction_account.index_in_transaction;
+            if accounts
+                .iter()
+                .any(|account| account.index_in_transaction == index_in_transaction)
+            {
+                continue;
+            }
+            let account = self
+                .transaction_context
+                .accounts()
+                .try_borrow(index_in_transaction)
+                .ok()?;
+            accounts.push(InstructionTraceAccount {
+                index_in_transaction,
+                is_writable: instruction_account.is_writable(),
+                is_written: false,
+                pre_data_len: account.data().len(),
+                post_data_len: account.data().len(),
+            });
+            // Cloning only shares the account data, a write makes a copy of it
+            pre_accounts.push(AccountSharedData::clone(&account));
+        }
+        instruction_trace.push(InstructionTrace {
+            stack_height: self.get_stack_height(),
+            program_account_index: instruction_context
+                .get_index_of_program_account_in_transaction()
+                .ok()?,
+            compute_units_consumed: 0,
+            accounts,
+        });
+        Some(PendingInstructionTrace {
+            index_in_trace: instruction_trace.len().saturating_sub(1),
+            pre_accounts,
+        })
+    }
+
+    /// Completes the record of an instruction started by `begin_instruction_trace()`
+    fn end_instruction_trace(
+        &self,
+        pending_instruction_trace: Option<PendingInstructionTrace>,
+        compute_units_consumed: u64,
+    ) {
+        let Some(PendingInstructionTrace {
+            index_in_trace,
+            pre_accounts,
+        }) = pending_instruction_trace
+        else {
+            return;
+        };
+        let Some(Ok(mut log_collector)) = self
+            .log_collector
+            .as_ref()
+            .map(|log_collector| log_collector.try_borrow_mut())
+        else {
+            return;
+        };
+        let Some(instruction) = log_collector
+            .instruction_trace
+            .as_mut()
+            .and_then(|instruction_trace| instruction_trace.get_mut(index_in_trace))
+        else {
+            return;
+        };
+        instruction.compute_units_consumed = compute_units_consumed;
+        for (account, pre_account) in instruction.accounts.iter_mut().zip(pre_accounts) {
+            if let Ok(post_account) = self
+                .transaction_context
+                .accounts()
+                .try_borrow(account.index_in_transaction)
+            {
+                account.is_written = *post_account != pre_account;
+                account.post_data_len = post_account.data().len();
+            }
+        }
     }
 
     /// Calls the instruction's program entrypoint method
@@ -1349,6 +1450,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_instruction_instruction_trace() {
+        let program_key = Pubkey::new_unique();
+        let user_account = AccountSharedData::new(100, 123, &program_key);
+        let dummy_account = AccountSharedData::new(10, 0, &program_key);
+        let mut program_account = AccountSharedData::new(500, 500, &native_loader::id());
+        program_account.set_executable(true);
+        let transaction_accounts = vec![
+            (Pubkey::new_unique(), user_account),
+            (Pubkey::new_unique(), dummy_account),
+            (program_key, program_account),
+        ];
+        let instruction_accounts = vec![
+            InstructionAccount::new(0, false, true),
+            InstructionAccount::new(1, false, false),
+            InstructionAccount::new(0, false, true),
+        ];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let mut program_cache_for_tx_batch = ProgramCacheForTxBatch::default();
+        program_cache_for_tx_batch.replenish(
+            program_key,
+            Arc::new(ProgramCacheEntry::new_builtin(0, 0, MockBuiltin::vm)),
+        );
+        invoke_context.program_cache_for_tx_batch = &mut program_cache_for_tx_batch;
+        let log_collector = invoke_context.get_log_collector().unwrap();
+        log_collector.borrow_mut().enable_instruction_trace();
+
+        let instruction_data =
+            bincode::serialize(&MockInstruction::Resize { new_len: 200 }).unwrap();
+        invoke_context
+            .transaction_context
+            .configure_next_instruction_for_tests(2, instruction_accounts, &instruction_data)
+            .unwrap();
+        let mut compute_units_consumed = 0;
+        let result = invoke_context
+            .process_instruction(&mut compute_units_consumed, &mut ExecuteTimings::default());
+        assert!(result.is_ok());
+        assert_eq!(compute_units_consumed, MOCK_BUILTIN_COMPUTE_UNIT_COST);
+
+        assert_eq!(
+            log_collector.borrow().instruction_trace,
+            Some(vec![InstructionTrace {
+                stack_height: 1,
+                program_account_index: 2,
+                compute_units_consumed,
+                accounts: vec![
+                    InstructionTraceAccount {
+                        index_in_transaction: 0,
+                        is_writable: true,
+                        is_written: true,
+                        pre_data_len: 123,
+                        post_data_len: 200,
+                    },
+                    InstructionTraceAccount {
+                        index_in_transaction: 1,
+                        is_writable: false,
+                        is_written: false,
+                        pre_data_len: 0,
+                        post_data_len: 0,
+                    },
+                ],
+            }])
+        );
+    }
+
     #[test]
     fn test_prepare_instruction_maximum_accounts() {
         let mut transaction_accounts: Vec<TransactionAccount> =
@@ -15,6 +15,13 @@
 //! unsubscribe closure, an asynchronous function that can be called and
 //! `await`ed to unsubscribe.
 //!
+//! Unlike the blocking client, subscriptions are not resumed when the
+//! connection is lost; their streams end instead. To resume without missing
+//! notifications, subscribe on a new `PubsubClient` passing `fromSlot`, e.g.
+//! in the [`RpcTransactionLogsConfig`] of [`PubsubClient::logs_subscribe`] or
+//! the [`RpcSignatureSubscribeConfig`] of
+//! [`PubsubClient::signature_subscribe`].
+//!
 //! Note that `BoxStream` contains an immutable reference to the `PubsubClient`
 //! that created it. This makes `BoxStream` not `Send`, forcing it to stay in
 //! the same task as its `PubsubClient`. `PubsubClient` though is `Send` and
@@ -30,14 +37,16 @@
 //!
 //! This pattern is illustrated in the example below.
 //!
-//! By default the [`block_subscribe`] and [`vote_subscribe`] events are
-//! disabled on RPC nodes. They can be enabled by passing
-//! `--rpc-pubsub-enable-block-subscription` and
+//! By default the [`block_subscribe`], [`transaction_subscribe`] and
+//! [`vote_subscribe`] events are disabled on RPC nodes. They can be enabled by
+//! passing `--rpc-pubsub-enable-block-subscription`,
+//! `--rpc-pubsub-enable-transaction-subscription` and
 //! `--rpc-pubsub-enable-vote-subscription` to `agave-validator`. When these
 //! methods are disabled, the RPC server will return a "Method not found" error
 //! message.
 //!
 //! [`block_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.block_subscribe
+//! [`transaction_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.transaction_subscribe
 //! [`vote_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.vote_subscribe
 //!
 //! # Examples
@@ -182,12 +191,12 @@ use {
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
             RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcTransactionLogsFilter, RpcTransactionSubscribeConfig, RpcTransactionSubscribeFilter,
         },
         error_object::RpcErrorObject,
         response::{
             Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
-            RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
+            RpcSignatureResult, RpcTransactionUpdate, RpcVote, SlotInfo, SlotUpdate,
         },
     },
     solana_signature::Signature,
@@ -251,9 +260,10 @@ pub enum PubsubClientError {
 
 type UnsubscribeFn = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
 type SubscribeResponseMsg =
-    Result<(mpsc::UnboundedReceiver<Value>, UnsubscribeFn), PubsubClientError>;
+    Result<(mpsc::UnboundedReceiver<Value>, UnsubscribeFn, u64), PubsubClientError>;
 type SubscribeRequestMsg = (String, Value, oneshot::Sender<SubscribeResponseMsg>);
 type SubscribeResult<'a, T> = PubsubClientResult<(BoxStream<'a, T>, UnsubscribeFn)>;
+type SubscribeWithIdResult<'a, T> = PubsubClientResult<(BoxStream<'a, T>, u64, UnsubscribeFn)>;
 type RequestMsg = (
     String,
     Value,
@@ -266,7 +276,7 @@ type RequestMsg = (
 #[derive(Debug)]
 pub struct PubsubClient {
     subscribe_sender: mpsc::UnboundedSender<SubscribeRequestMsg>,
-    _request_sender: mpsc::UnboundedSender<RequestMsg>,
+    request_sender: mpsc::UnboundedSender<RequestMsg>,
     shutdown_sender: oneshot::Sender<()>,
     ws: JoinHandle<PubsubClientResult>,
 }
@@ -280,13 +290,12 @@ impl PubsubClient {
             .map_err(PubsubClientError::ConnectionError)?;
 
         let (subscribe_sender, subscribe_receiver) = mpsc::unbounded_channel();
-        let (_request_sender, request_receiver) = mpsc::unbounded_channel();
+        let (request_sender, request_receiver) = mpsc::unbounded_channel();
         let (shutdown_sender, shutdown_receiver) = oneshot::channel();
 
-        #[allow(clippy::used_underscore_binding)]
         Ok(Self {
             subscribe_sender,
-            _request_sender,
+            request_sender,
             shutdown_sender,
             ws: tokio::spawn(PubsubClient::run_ws(
                 ws,
@@ -303,6 +312,19 @@ impl PubsubClient {
     }
 
     async fn subscribe<'a, T>(&self, operation: &str, params: Value) -> SubscribeResult<'a, T>
+    where
+        T: DeserializeOwned + Send + 'a,
+    {
+        let (notifications, _subscription_id, unsubscribe) =
+            self.subscribe_with_id(operation, params).await?;
+        Ok((notifications, unsubscribe))
+    }
+
+    async fn subscribe_with_id<'a, T>(
+        &self,
+        operation: &str,
+        params: Value,
+    ) -> SubscribeWithIdResult<'a, T>
     where
         T: DeserializeOwned + Send + 'a,
     {
@@ -311,17 +333,32 @@ impl PubsubClient {
             .send((operation.to_string(), params, response_sender))
             .map_err(|err| PubsubClientError::ConnectionClosed(err.to_string()))?;
 
-        let (notifications, unsubscribe) = response_receiver
+        let (notifications, unsubscribe, subscription_id) = response_receiver
             .await
             .map_err(|err| PubsubClientError::ConnectionClosed(err.to_string()))??;
         Ok((
             UnboundedReceiverStream::new(notifications)
                 .filter_map(|value| ready(serde_json::from_value::<T>(value).ok()))
                 .boxed(),
+            subscription_id,
             unsubscribe,
         ))
     }
 
+    async fn request<T>(&self, method: &str, params: Value) -> PubsubClientResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.request_sender
+            .send((method.to_string(), params, response_sender))
+            .map_err(|err| PubsubClientError::ConnectionClosed(err.to_string()))?;
+        let result = response_receiver
+            .await
+            .map_err(|err| PubsubClientError::ConnectionClosed(err.to_string()))??;
+        serde_json::from_value(result).map_err(Into::into)
+    }
+
     /// Subscribe to account events.
     ///
     /// Receives messages of type [`UiAccount`] when an account's lamports or data changes.
@@ -340,6 +377,67 @@ impl PubsubClient {
         self.subscribe("account", params).await
     }
 
+    /// Subscribe to account events for a set of accounts.
+    ///
+    /// Receives messages of type [`RpcKeyedAccount`] when the lamports or data of
+    /// any of the accounts change. Accounts can be added to or removed from the
+    /// subscription with [`multi_account_add`] and [`multi_account_remove`],
+    /// using the returned subscription id.
+    ///
+    /// [`multi_account_add`]: PubsubClient::multi_account_add
+    /// [`multi_account_remove`]: PubsubClient::multi_account_remove
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `multiAccountSubscribe` RPC method.
+    pub async fn multi_account_subscribe(
+        &self,
+        pubkeys: &[Pubkey],
+        config: Option<RpcAccountInfoConfig>,
+    ) -> SubscribeWithIdResult<'_, RpcResponse<RpcKeyedAccount>> {
+        let pubkeys: Vec<_> = pubkeys.iter().map(Pubkey::to_string).collect();
+        self.subscribe_with_id("multiAccount", json!([pubkeys, config]))
+            .await
+    }
+
+    /// Add accounts to a subscription created by [`multi_account_subscribe`].
+    ///
+    /// Returns the number of accounts tracked by the subscription.
+    ///
+    /// [`multi_account_subscribe`]: PubsubClient::multi_account_subscribe
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `multiAccountAdd` RPC method.
+    pub async fn multi_account_add(
+        &self,
+        subscription_id: u64,
+        pubkeys: &[Pubkey],
+    ) -> PubsubClientResult<usize> {
+        let pubkeys: Vec<_> = pubkeys.iter().map(Pubkey::to_string).collect();
+        self.request("multiAccountAdd", json!([subscription_id, pubkeys]))
+            .await
+    }
+
+    /// Remove accounts from a subscription created by [`multi_account_subscribe`].
+    ///
+    /// Returns the number of accounts still tracked by the subscription.
+    ///
+    /// [`multi_account_subscribe`]: PubsubClient::multi_account_subscribe
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `multiAccountRemove` RPC method.
+    pub async fn multi_account_remove(
+        &self,
+        subscription_id: u64,
+        pubkeys: &[Pubkey],
+    ) -> PubsubClientResult<usize> {
+        let pubkeys: Vec<_> = pubkeys.iter().map(Pubkey::to_string).collect();
+        self.request("multiAccountRemove", json!([subscription_id, pubkeys]))
+            .await
+    }
+
     /// Subscribe to block events.
     ///
     /// Receives messages of type [`RpcBlockUpdate`] when a block is confirmed or finalized.
@@ -360,6 +458,25 @@ impl PubsubClient {
         self.subscribe("block", json!([filter, config])).await
     }
 
+    /// Subscribe to transaction events.
+    ///
+    /// Receives messages of type [`RpcTransactionUpdate`] when a transaction passing `filter` is
+    /// processed or confirmed, depending on the requested commitment.
+    ///
+    /// This method is disabled by default. It can be enabled by passing
+    /// `--rpc-pubsub-enable-transaction-subscription` to `agave-validator`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `transactionSubscribe` RPC method.
+    pub async fn transaction_subscribe(
+        &self,
+        filter: RpcTransactionSubscribeFilter,
+        config: Option<RpcTransactionSubscribeConfig>,
+    ) -> SubscribeResult<'_, RpcResponse<RpcTransactionUpdate>> {
+        self.subscribe("transaction", json!([filter, config])).await
+    }
+
     /// Subscribe to transaction log events.
     ///
     /// Receives messages of type [`RpcLogsResponse`] when a transaction is committed.
@@ -615,7 +732,7 @@ impl PubsubClient {
                                         }
                                     }.boxed());
 
-                                    if response_sender.send(Ok((notifications_receiver, unsubscribe))).is_err() {
+                                    if response_sender.send(Ok((notifications_receiver, unsubscribe, sid))).is_err() {
                                         break;
                                     }
                                     subscriptions.insert(sid, notifications_sender);
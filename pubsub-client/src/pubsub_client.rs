@@ -29,15 +29,30 @@
 //! reliably. For a non-blocking, cancelable API, use the asynchronous client
 //! in [`crate::nonblocking::pubsub_client`].
 //!
-//! By default the [`block_subscribe`] and [`vote_subscribe`] events are
-//! disabled on RPC nodes. They can be enabled by passing
-//! `--rpc-pubsub-enable-block-subscription` and
+//! The [`slot_subscribe`], [`root_subscribe`], [`logs_subscribe`] and
+//! [`signature_subscribe`] subscriptions reconnect automatically when their
+//! connection is lost. They resubscribe with `fromSlot` so that, if the RPC
+//! node keeps a replay buffer (`--rpc-pubsub-replay-buffer-slots`), the
+//! notifications missed while disconnected are received before live ones.
+//! If the node rejects `fromSlot` because its replay buffer is disabled, they
+//! resubscribe without it and the missed notifications are lost. The receiver
+//! is disconnected if the connection cannot be reestablished.
+//!
+//! By default the [`block_subscribe`], [`transaction_subscribe`] and
+//! [`vote_subscribe`] events are disabled on RPC nodes. They can be enabled by
+//! passing `--rpc-pubsub-enable-block-subscription`,
+//! `--rpc-pubsub-enable-transaction-subscription` and
 //! `--rpc-pubsub-enable-vote-subscription` to `agave-validator`. When these
 //! methods are disabled, the RPC server will return a "Method not found" error
 //! message.
 //!
 //! [`block_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.block_subscribe
+//! [`transaction_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.transaction_subscribe
 //! [`vote_subscribe`]: https://docs.rs/solana-rpc/latest/solana_rpc/rpc_pubsub/trait.RpcSolPubSub.html#tymethod.vote_subscribe
+//! [`slot_subscribe`]: PubsubClient::slot_subscribe
+//! [`root_subscribe`]: PubsubClient::root_subscribe
+//! [`logs_subscribe`]: PubsubClient::logs_subscribe
+//! [`signature_subscribe`]: PubsubClient::signature_subscribe
 //!
 //! # Examples
 //!
@@ -102,12 +117,13 @@ use {
     solana_rpc_client_types::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcProgramAccountsConfig, RpcRootSubscribeConfig, RpcSignatureSubscribeConfig,
+            RpcSlotSubscribeConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+            RpcTransactionSubscribeConfig, RpcTransactionSubscribeFilter,
         },
         response::{
             Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
-            RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
+            RpcSignatureResult, RpcTransactionUpdate, RpcVote, SlotInfo, SlotUpdate,
         },
     },
     solana_signature::Signature,
@@ -115,7 +131,7 @@ use {
         marker::PhantomData,
         net::TcpStream,
         sync::{
-            atomic::{AtomicBool, Ordering},
+            atomic::{AtomicBool, AtomicU64, Ordering},
             Arc, RwLock,
         },
         thread::{sleep, JoinHandle},
@@ -137,7 +153,8 @@ where
     message_type: PhantomData<T>,
     operation: &'static str,
     socket: Arc<RwLock<WebSocket<MaybeTlsStream<TcpStream>>>>,
-    subscription_id: u64,
+    // Replaced when the subscription is resumed on a new connection
+    subscription_id: Arc<AtomicU64>,
     t_cleanup: Option<JoinHandle<()>>,
     exit: Arc<AtomicBool>,
 }
@@ -147,6 +164,8 @@ where
     T: DeserializeOwned,
 {
     fn drop(&mut self) {
+        // Keep the receive loop from resuming the subscription once the socket is closed
+        self.exit.store(true, Ordering::Relaxed);
         self.send_unsubscribe()
             .unwrap_or_else(|_| warn!("unable to unsubscribe from websocket"));
         self.socket
@@ -205,7 +224,7 @@ where
             .unwrap()
             .send(Message::Text(
                 json!({
-                "jsonrpc":"2.0","id":1,"method":method,"params":[self.subscription_id]
+                "jsonrpc":"2.0","id":1,"method":method,"params":[self.subscription_id.load(Ordering::Relaxed)]
                 })
                 .to_string(),
             ))
@@ -280,6 +299,13 @@ pub type BlockSubscription = (
     Receiver<RpcResponse<RpcBlockUpdate>>,
 );
 
+pub type PubsubTransactionClientSubscription =
+    PubsubClientSubscription<RpcResponse<RpcTransactionUpdate>>;
+pub type TransactionSubscription = (
+    PubsubTransactionClientSubscription,
+    Receiver<RpcResponse<RpcTransactionUpdate>>,
+);
+
 pub type PubsubProgramClientSubscription = PubsubClientSubscription<RpcResponse<RpcKeyedAccount>>;
 pub type ProgramSubscription = (
     PubsubProgramClientSubscription,
@@ -292,6 +318,13 @@ pub type AccountSubscription = (
     Receiver<RpcResponse<UiAccount>>,
 );
 
+pub type PubsubMultiAccountClientSubscription =
+    PubsubClientSubscription<RpcResponse<RpcKeyedAccount>>;
+pub type MultiAccountSubscription = (
+    PubsubMultiAccountClientSubscription,
+    Receiver<RpcResponse<RpcKeyedAccount>>,
+);
+
 pub type PubsubVoteClientSubscription = PubsubClientSubscription<RpcVote>;
 pub type VoteSubscription = (PubsubVoteClientSubscription, Receiver<RpcVote>);
 
@@ -303,6 +336,13 @@ pub type RootSubscription = (PubsubRootClientSubscription, Receiver<Slot>);
 /// See the [module documentation][self].
 pub struct PubsubClient {}
 
+/// Number of consecutive failed attempts after which a subscription that lost
+/// its connection gives up and disconnects its receiver.
+const MAX_RECONNECT_ATTEMPTS: usize = 8;
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(8);
+const CURRENT_ROOT_TIMEOUT: Duration = Duration::from_secs(5);
+
 fn connect_with_retry(
     url: Url,
 ) -> Result<WebSocket<MaybeTlsStream<TcpStream>>, Box<tungstenite::Error>> {
@@ -379,7 +419,62 @@ impl PubsubClient {
             message_type: PhantomData,
             operation: "account",
             socket,
-            subscription_id,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
+            t_cleanup: Some(t_cleanup),
+            exit,
+        };
+
+        Ok((result, receiver))
+    }
+
+    /// Subscribe to account events for a set of accounts.
+    ///
+    /// Receives messages of type [`RpcKeyedAccount`] when the lamports or data of
+    /// any of the accounts change.
+    ///
+    /// The set of accounts is fixed for the lifetime of the subscription. To
+    /// add or remove accounts after subscribing, use the asynchronous client in
+    /// [`crate::nonblocking::pubsub_client`].
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `multiAccountSubscribe` RPC method.
+    pub fn multi_account_subscribe(
+        url: &str,
+        pubkeys: &[Pubkey],
+        config: Option<RpcAccountInfoConfig>,
+    ) -> Result<MultiAccountSubscription, PubsubClientError> {
+        let url = Url::parse(url)?;
+        let socket = connect_with_retry(url)?;
+        let (sender, receiver) = unbounded();
+
+        let socket = Arc::new(RwLock::new(socket));
+        let socket_clone = socket.clone();
+        let exit = Arc::new(AtomicBool::new(false));
+        let exit_clone = exit.clone();
+        let pubkeys: Vec<_> = pubkeys.iter().map(Pubkey::to_string).collect();
+        let body = json!({
+            "jsonrpc":"2.0",
+            "id":1,
+            "method":"multiAccountSubscribe",
+            "params":[
+                pubkeys,
+                config
+            ]
+        })
+        .to_string();
+        let subscription_id =
+            PubsubMultiAccountClientSubscription::send_subscribe(&socket_clone, body)?;
+
+        let t_cleanup = std::thread::spawn(move || {
+            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+        });
+
+        let result = PubsubClientSubscription {
+            message_type: PhantomData,
+            operation: "multiAccount",
+            socket,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
             t_cleanup: Some(t_cleanup),
             exit,
         };
@@ -430,7 +525,58 @@ impl PubsubClient {
             message_type: PhantomData,
             operation: "block",
             socket,
-            subscription_id,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
+            t_cleanup: Some(t_cleanup),
+            exit,
+        };
+
+        Ok((result, receiver))
+    }
+
+    /// Subscribe to transaction events.
+    ///
+    /// Receives messages of type [`RpcTransactionUpdate`] when a transaction passing `filter` is
+    /// processed or confirmed, depending on the requested commitment.
+    ///
+    /// This method is disabled by default. It can be enabled by passing
+    /// `--rpc-pubsub-enable-transaction-subscription` to `agave-validator`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `transactionSubscribe` RPC method.
+    pub fn transaction_subscribe(
+        url: &str,
+        filter: RpcTransactionSubscribeFilter,
+        config: Option<RpcTransactionSubscribeConfig>,
+    ) -> Result<TransactionSubscription, PubsubClientError> {
+        let url = Url::parse(url)?;
+        let socket = connect_with_retry(url)?;
+        let (sender, receiver) = unbounded();
+
+        let socket = Arc::new(RwLock::new(socket));
+        let socket_clone = socket.clone();
+        let exit = Arc::new(AtomicBool::new(false));
+        let exit_clone = exit.clone();
+        let body = json!({
+            "jsonrpc":"2.0",
+            "id":1,
+            "method":"transactionSubscribe",
+            "params":[filter, config]
+        })
+        .to_string();
+
+        let subscription_id =
+            PubsubTransactionClientSubscription::send_subscribe(&socket_clone, body)?;
+
+        let t_cleanup = std::thread::spawn(move || {
+            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+        });
+
+        let result = PubsubClientSubscription {
+            message_type: PhantomData,
+            operation: "transaction",
+            socket,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
             t_cleanup: Some(t_cleanup),
             exit,
         };
@@ -442,6 +588,9 @@ impl PubsubClient {
     ///
     /// Receives messages of type [`RpcLogsResponse`] when a transaction is committed.
     ///
+    /// If the connection is lost, the subscription is resumed on a new connection starting at
+    /// the slot of the last received message, so logs of that slot may be received twice.
+    ///
     /// # RPC Reference
     ///
     /// This method corresponds directly to the [`logsSubscribe`] RPC method.
@@ -453,25 +602,40 @@ impl PubsubClient {
         config: RpcTransactionLogsConfig,
     ) -> Result<LogsSubscription, PubsubClientError> {
         let url = Url::parse(url)?;
-        let socket = connect_with_retry(url)?;
+        let socket = connect_with_retry(url.clone())?;
         let (sender, receiver) = unbounded();
 
         let socket = Arc::new(RwLock::new(socket));
         let socket_clone = socket.clone();
         let exit = Arc::new(AtomicBool::new(false));
         let exit_clone = exit.clone();
-        let body = json!({
-            "jsonrpc":"2.0",
-            "id":1,
-            "method":"logsSubscribe",
-            "params":[filter, config]
-        })
-        .to_string();
+        let from_slot = config.from_slot;
+        let subscribe_body = move |from_slot| {
+            json!({
+                "jsonrpc":"2.0",
+                "id":1,
+                "method":"logsSubscribe",
+                "params":[filter, RpcTransactionLogsConfig { from_slot, ..config.clone() }]
+            })
+            .to_string()
+        };
 
-        let subscription_id = PubsubLogsClientSubscription::send_subscribe(&socket_clone, body)?;
+        let subscription_id = Arc::new(AtomicU64::new(
+            PubsubLogsClientSubscription::send_subscribe(&socket_clone, subscribe_body(from_slot))?,
+        ));
+        let subscription_id_clone = subscription_id.clone();
 
         let t_cleanup = std::thread::spawn(move || {
-            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+            Self::cleanup_with_reconnect(
+                exit_clone,
+                url,
+                &socket_clone,
+                &subscription_id_clone,
+                sender,
+                from_slot,
+                |response: &RpcResponse<RpcLogsResponse>| Some(response.context.slot),
+                subscribe_body,
+            )
         });
 
         let result = PubsubClientSubscription {
@@ -530,7 +694,7 @@ impl PubsubClient {
             message_type: PhantomData,
             operation: "program",
             socket,
-            subscription_id,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
             t_cleanup: Some(t_cleanup),
             exit,
         };
@@ -576,7 +740,7 @@ impl PubsubClient {
             message_type: PhantomData,
             operation: "vote",
             socket,
-            subscription_id,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
             t_cleanup: Some(t_cleanup),
             exit,
         };
@@ -591,6 +755,10 @@ impl PubsubClient {
     ///
     /// [root]: https://solana.com/docs/terminology#root
     ///
+    /// If the connection is lost, the subscription is resumed on a new connection starting
+    /// after the last received root, or after the root current at subscribe time if none
+    /// has been received yet.
+    ///
     /// # RPC Reference
     ///
     /// This method corresponds directly to the [`rootSubscribe`] RPC method.
@@ -598,23 +766,47 @@ impl PubsubClient {
     /// [`rootSubscribe`]: https://solana.com/docs/rpc/websocket/rootsubscribe
     pub fn root_subscribe(url: &str) -> Result<RootSubscription, PubsubClientError> {
         let url = Url::parse(url)?;
-        let socket = connect_with_retry(url)?;
+        let current_root = Self::get_current_root(&url)
+            .map_err(|err| warn!("unable to get the current root: {err:?}"))
+            .ok();
+        let socket = connect_with_retry(url.clone())?;
         let (sender, receiver) = unbounded();
 
         let socket = Arc::new(RwLock::new(socket));
         let socket_clone = socket.clone();
         let exit = Arc::new(AtomicBool::new(false));
         let exit_clone = exit.clone();
-        let body = json!({
-            "jsonrpc":"2.0",
-            "id":1,
-            "method":"rootSubscribe",
-        })
-        .to_string();
-        let subscription_id = PubsubRootClientSubscription::send_subscribe(&socket_clone, body)?;
+        let subscribe_body = |from_slot: Option<Slot>| {
+            let params: Vec<_> = from_slot
+                .map(|from_slot| RpcRootSubscribeConfig {
+                    from_slot: Some(from_slot),
+                })
+                .into_iter()
+                .collect();
+            json!({
+                "jsonrpc":"2.0",
+                "id":1,
+                "method":"rootSubscribe",
+                "params":params
+            })
+            .to_string()
+        };
+        let subscription_id = Arc::new(AtomicU64::new(
+            PubsubRootClientSubscription::send_subscribe(&socket_clone, subscribe_body(None))?,
+        ));
+        let subscription_id_clone = subscription_id.clone();
 
         let t_cleanup = std::thread::spawn(move || {
-            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+            Self::cleanup_with_reconnect(
+                exit_clone,
+                url,
+                &socket_clone,
+                &subscription_id_clone,
+                sender,
+                current_root.map(|root| root.saturating_add(1)),
+                |root: &Slot| Some(root.saturating_add(1)),
+                subscribe_body,
+            )
         });
 
         let result = PubsubClientSubscription {
@@ -637,6 +829,10 @@ impl PubsubClient {
     /// This is a subscription to a single notification. It is automatically
     /// cancelled by the server once the notification is sent.
     ///
+    /// If the connection is lost before the notification is received, the
+    /// subscription is resumed on a new connection, and the server notifies
+    /// immediately if the transaction was committed in the meantime.
+    ///
     /// # RPC Reference
     ///
     /// This method corresponds directly to the [`signatureSubscribe`] RPC method.
@@ -648,28 +844,53 @@ impl PubsubClient {
         config: Option<RpcSignatureSubscribeConfig>,
     ) -> Result<SignatureSubscription, PubsubClientError> {
         let url = Url::parse(url)?;
-        let socket = connect_with_retry(url)?;
+        let socket = connect_with_retry(url.clone())?;
         let (sender, receiver) = unbounded();
 
         let socket = Arc::new(RwLock::new(socket));
         let socket_clone = socket.clone();
         let exit = Arc::new(AtomicBool::new(false));
         let exit_clone = exit.clone();
-        let body = json!({
-            "jsonrpc":"2.0",
-            "id":1,
-            "method":"signatureSubscribe",
-            "params":[
-                signature.to_string(),
-                config
-            ]
-        })
-        .to_string();
-        let subscription_id =
-            PubsubSignatureClientSubscription::send_subscribe(&socket_clone, body)?;
+        let signature = signature.to_string();
+        let subscribe_body = move |from_slot: Option<Slot>| {
+            let config = match from_slot {
+                Some(from_slot) => Some(RpcSignatureSubscribeConfig {
+                    from_slot: Some(from_slot),
+                    ..config.clone().unwrap_or_default()
+                }),
+                None => config.clone(),
+            };
+            json!({
+                "jsonrpc":"2.0",
+                "id":1,
+                "method":"signatureSubscribe",
+                "params":[
+                    signature,
+                    config
+                ]
+            })
+            .to_string()
+        };
+        let subscription_id = Arc::new(AtomicU64::new(
+            PubsubSignatureClientSubscription::send_subscribe(&socket_clone, subscribe_body(None))?,
+        ));
+        let subscription_id_clone = subscription_id.clone();
 
         let t_cleanup = std::thread::spawn(move || {
-            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+            Self::cleanup_with_reconnect(
+                exit_clone,
+                url,
+                &socket_clone,
+                &subscription_id_clone,
+                sender,
+                // Any slot still in the server's status cache
+                Some(0),
+                |response: &RpcResponse<RpcSignatureResult>| match response.value {
+                    RpcSignatureResult::ProcessedSignature(_) => None,
+                    RpcSignatureResult::ReceivedSignature(_) => Some(0),
+                },
+                subscribe_body,
+            )
         });
 
         let result = PubsubClientSubscription {
@@ -688,6 +909,9 @@ impl PubsubClient {
     ///
     /// Receives messages of type [`SlotInfo`] when a slot is processed.
     ///
+    /// If the connection is lost, the subscription is resumed on a new connection starting
+    /// after the last received slot.
+    ///
     /// # RPC Reference
     ///
     /// This method corresponds directly to the [`slotSubscribe`] RPC method.
@@ -695,24 +919,44 @@ impl PubsubClient {
     /// [`slotSubscribe`]: https://solana.com/docs/rpc/websocket/slotsubscribe
     pub fn slot_subscribe(url: &str) -> Result<SlotsSubscription, PubsubClientError> {
         let url = Url::parse(url)?;
-        let socket = connect_with_retry(url)?;
+        let socket = connect_with_retry(url.clone())?;
         let (sender, receiver) = unbounded::<SlotInfo>();
 
         let socket = Arc::new(RwLock::new(socket));
         let socket_clone = socket.clone();
         let exit = Arc::new(AtomicBool::new(false));
         let exit_clone = exit.clone();
-        let body = json!({
-            "jsonrpc":"2.0",
-            "id":1,
-            "method":"slotSubscribe",
-            "params":[]
-        })
-        .to_string();
-        let subscription_id = PubsubSlotClientSubscription::send_subscribe(&socket_clone, body)?;
+        let subscribe_body = |from_slot: Option<Slot>| {
+            let params: Vec<_> = from_slot
+                .map(|from_slot| RpcSlotSubscribeConfig {
+                    from_slot: Some(from_slot),
+                })
+                .into_iter()
+                .collect();
+            json!({
+                "jsonrpc":"2.0",
+                "id":1,
+                "method":"slotSubscribe",
+                "params":params
+            })
+            .to_string()
+        };
+        let subscription_id = Arc::new(AtomicU64::new(
+            PubsubSlotClientSubscription::send_subscribe(&socket_clone, subscribe_body(None))?,
+        ));
+        let subscription_id_clone = subscription_id.clone();
 
         let t_cleanup = std::thread::spawn(move || {
-            Self::cleanup_with_sender(exit_clone, &socket_clone, sender)
+            Self::cleanup_with_reconnect(
+                exit_clone,
+                url,
+                &socket_clone,
+                &subscription_id_clone,
+                sender,
+                None,
+                |slot_info: &SlotInfo| Some(slot_info.slot.saturating_add(1)),
+                subscribe_body,
+            )
         });
 
         let result = PubsubClientSubscription {
@@ -769,12 +1013,42 @@ impl PubsubClient {
             message_type: PhantomData,
             operation: "slotsUpdates",
             socket,
-            subscription_id,
+            subscription_id: Arc::new(AtomicU64::new(subscription_id)),
             t_cleanup: Some(t_cleanup),
             exit,
         })
     }
 
+    /// Returns the root reported by the first slot notification on a separate connection.
+    fn get_current_root(url: &Url) -> Result<Slot, PubsubClientError> {
+        let socket = connect_with_retry(url.clone())?;
+        let set_read_timeout = match socket.get_ref() {
+            MaybeTlsStream::Plain(stream) => stream.set_read_timeout(Some(CURRENT_ROOT_TIMEOUT)),
+            MaybeTlsStream::Rustls(stream) => stream
+                .get_ref()
+                .set_read_timeout(Some(CURRENT_ROOT_TIMEOUT)),
+            _ => Ok(()),
+        };
+        set_read_timeout.map_err(|err| Box::new(tungstenite::Error::Io(err)))?;
+        let socket = Arc::new(RwLock::new(socket));
+        let body = json!({
+            "jsonrpc":"2.0",
+            "id":1,
+            "method":"slotSubscribe"
+        })
+        .to_string();
+        PubsubSlotClientSubscription::send_subscribe(&socket, body)?;
+        let result = loop {
+            match PubsubSlotClientSubscription::read_message(&socket) {
+                Ok(Some(slot_info)) => break Ok(slot_info.root),
+                Ok(None) => continue,
+                Err(err) => break Err(err),
+            }
+        };
+        let _ = socket.write().unwrap().close(None);
+        result
+    }
+
     fn cleanup_with_sender<T>(
         exit: Arc<AtomicBool>,
         socket: &Arc<RwLock<WebSocket<MaybeTlsStream<TcpStream>>>>,
@@ -818,6 +1092,126 @@ impl PubsubClient {
 
         info!("websocket - exited receive loop");
     }
+
+    /// Like [`Self::cleanup_with_sender`], but resumes the subscription on a new connection
+    /// when the current one is lost.
+    ///
+    /// `resume_slot` returns the `fromSlot` to resume at after a message is received, or `None`
+    /// once the subscription is complete. `subscribe_body` builds the subscribe request for a
+    /// given `fromSlot`; if the server rejects `fromSlot`, e.g. because its replay buffer is
+    /// disabled, the subscription is resumed from the current slot instead.
+    #[allow(clippy::too_many_arguments)]
+    fn cleanup_with_reconnect<T>(
+        exit: Arc<AtomicBool>,
+        url: Url,
+        socket: &Arc<RwLock<WebSocket<MaybeTlsStream<TcpStream>>>>,
+        subscription_id: &AtomicU64,
+        sender: Sender<T>,
+        mut from_slot: Option<Slot>,
+        resume_slot: impl Fn(&T) -> Option<Slot>,
+        subscribe_body: impl Fn(Option<Slot>) -> String,
+    ) where
+        T: DeserializeOwned,
+    {
+        let mut is_complete = false;
+        loop {
+            if exit.load(Ordering::Relaxed) {
+                break;
+            }
+
+            match PubsubClientSubscription::read_message(socket) {
+                Ok(Some(message)) => {
+                    match resume_slot(&message) {
+                        Some(slot) => from_slot = Some(slot),
+                        None => is_complete = true,
+                    }
+                    if let Err(err) = sender.send(message) {
+                        info!("receive error: {err:?}");
+                    }
+                }
+                Ok(None) => {
+                    // Nothing useful, means we received a ping message
+                }
+                Err(err) => {
+                    info!("receive error: {err:?}");
+                    if is_complete
+                        || !Self::reconnect::<T>(
+                            &exit,
+                            &url,
+                            socket,
+                            subscription_id,
+                            &subscribe_body(from_slot),
+                            from_slot.is_some().then(|| subscribe_body(None)).as_deref(),
+                        )
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+
+        info!("websocket - exited receive loop");
+    }
+
+    /// Replaces `socket` with a new connection to `url` and sends `body` to subscribe again,
+    /// backing off between attempts. If the server rejects `body`, `fallback_body` is sent on
+    /// the same connection instead. Returns false if no attempt succeeded.
+    fn reconnect<T>(
+        exit: &AtomicBool,
+        url: &Url,
+        socket: &Arc<RwLock<WebSocket<MaybeTlsStream<TcpStream>>>>,
+        subscription_id: &AtomicU64,
+        body: &str,
+        fallback_body: Option<&str>,
+    ) -> bool
+    where
+        T: DeserializeOwned,
+    {
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+        for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+            if exit.load(Ordering::Relaxed) {
+                return false;
+            }
+            let result = connect_with_retry(url.clone())
+                .map_err(PubsubClientError::from)
+                .and_then(|new_socket| {
+                    *socket.write().unwrap() = new_socket;
+                    match (
+                        PubsubClientSubscription::<T>::send_subscribe(socket, body.to_string()),
+                        fallback_body,
+                    ) {
+                        (
+                            Err(PubsubClientError::UnexpectedSubscriptionResponse(response)),
+                            Some(fallback_body),
+                        ) => {
+                            warn!(
+                                "websocket - resuming rejected ({response}), notifications missed \
+                                 while disconnected are lost"
+                            );
+                            PubsubClientSubscription::<T>::send_subscribe(
+                                socket,
+                                fallback_body.to_string(),
+                            )
+                        }
+                        (result, _) => result,
+                    }
+                });
+            match result {
+                Ok(id) => {
+                    info!("websocket - resubscribed after {attempt} attempt(s)");
+                    subscription_id.store(id, Ordering::Relaxed);
+                    return true;
+                }
+                Err(err) => {
+                    debug!("websocket - reconnect attempt {attempt} failed: {err:?}");
+                    sleep(backoff);
+                    backoff = backoff.saturating_mul(2).min(RECONNECT_MAX_BACKOFF);
+                }
+            }
+        }
+        warn!("websocket - giving up after {MAX_RECONNECT_ATTEMPTS} reconnect attempts");
+        false
+    }
 }
 
 #[cfg(test)]
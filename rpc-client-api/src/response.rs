@@ -7,14 +7,15 @@ pub use solana_rpc_client_types::response::{
     RpcBlockProductionRange, RpcBlockUpdate, RpcBlockUpdateError, RpcBlockhash,
     RpcBlockhashFeeCalculator, RpcConfirmedTransactionStatusWithSignature, RpcContactInfo,
     RpcFeeCalculator, RpcFeeRateGovernor, RpcIdentity, RpcInflationGovernor, RpcInflationRate,
-    RpcInflationReward, RpcKeyedAccount, RpcLeaderSchedule, RpcLogsResponse, RpcPerfSample,
-    RpcPrioritizationFee, RpcResponseContext, RpcSignatureConfirmation, RpcSignatureResult,
-    RpcSimulateTransactionResult, RpcSnapshotSlotInfo, RpcStorageTurn, RpcSupply,
-    RpcTokenAccountBalance, RpcVersionInfo, RpcVote, RpcVoteAccountInfo, RpcVoteAccountStatus,
-    SlotInfo, SlotTransactionStats, SlotUpdate, StakeActivationState, TransactionBinaryEncoding,
-    TransactionConfirmationStatus, TransactionError, TransactionParsedAccount, TransactionResult,
-    UiAccount, UiAccountData, UiAccountEncoding, UiAccountsList, UiCompiledInstruction,
-    UiConfirmedBlock, UiInnerInstructions, UiInstruction, UiLoadedAddresses, UiParsedInstruction,
+    RpcInflationReward, RpcKeyedAccount, RpcKeyedAccountsPage, RpcLeaderSchedule, RpcLogsResponse,
+    RpcPerfSample, RpcPrioritizationFee, RpcProgramAccounts, RpcResponseContext,
+    RpcSignatureConfirmation, RpcSignatureResult, RpcSimulateTransactionResult,
+    RpcSnapshotSlotInfo, RpcStorageTurn, RpcSupply, RpcTokenAccountBalance, RpcVersionInfo,
+    RpcVote, RpcVoteAccountInfo, RpcVoteAccountStatus, SlotInfo, SlotTransactionStats, SlotUpdate,
+    StakeActivationState, TransactionBinaryEncoding, TransactionConfirmationStatus,
+    TransactionError, TransactionParsedAccount, TransactionResult, UiAccount, UiAccountData,
+    UiAccountEncoding, UiAccountsList, UiCompiledInstruction, UiConfirmedBlock,
+    UiInnerInstructions, UiInstruction, UiLoadedAddresses, UiParsedInstruction,
     UiPartiallyDecodedInstruction, UiReturnDataEncoding, UiTokenAmount, UiTransactionError,
     UiTransactionReturnData, UiTransactionStatusMeta, UiTransactionTokenBalance, Value,
 };
@@ -0,0 +1,587 @@
+//! Build, sign, submit and confirm transactions that use a durable nonce.
+//!
+//! A durable nonce transaction never expires, so a transaction that has not
+//! been confirmed yet may still land as long as its nonce has not been
+//! advanced. [`DurableNonceSender`] therefore keeps resubmitting the same
+//! signed transaction until it is confirmed, and only rebuilds it against the
+//! new nonce value once the old one was consumed by some other transaction, at
+//! which point the previous transaction can no longer be processed.
+
+use {
+    crate::nonblocking::{data_from_account, get_account_with_commitment, Error},
+    async_trait::async_trait,
+    log::*,
+    solana_commitment_config::CommitmentConfig,
+    solana_hash::Hash,
+    solana_instruction::Instruction,
+    solana_message::Message,
+    solana_nonce::state::Data,
+    solana_pubkey::Pubkey,
+    solana_rpc_client::{
+        api::{client_error::Error as ClientError, config::RpcSendTransactionConfig},
+        nonblocking::rpc_client::RpcClient,
+    },
+    solana_signature::Signature,
+    solana_signer::{signers::Signers, SignerError},
+    solana_system_interface::instruction as system_instruction,
+    solana_transaction::Transaction,
+    solana_transaction_error::{TransactionError, TransactionResult},
+    std::{sync::Arc, time::Duration},
+    tokio::time::{sleep, Instant},
+};
+#[cfg(feature = "tpu-client-next")]
+use {
+    solana_rpc_client::api::client_error::ErrorKind as ClientErrorKind,
+    solana_tpu_client_next::transaction_batch::TransactionBatch, tokio::sync::mpsc,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DurableNonceSenderError {
+    #[error(transparent)]
+    Nonce(#[from] Error),
+    #[error("signing failed: {0}")]
+    Signer(#[from] SignerError),
+    #[error("transaction {signature} failed: {err}")]
+    TransactionFailed {
+        signature: Signature,
+        err: TransactionError,
+    },
+    #[error("transaction was not confirmed after {0} submissions")]
+    MaxSubmissionsExceeded(usize),
+}
+
+/// Submits signed transactions to the cluster.
+#[async_trait]
+pub trait TransactionSubmitter {
+    async fn submit(&self, transaction: &Transaction) -> Result<(), ClientError>;
+}
+
+#[async_trait]
+impl TransactionSubmitter for RpcClient {
+    async fn submit(&self, transaction: &Transaction) -> Result<(), ClientError> {
+        self.send_transaction_with_config(
+            transaction,
+            RpcSendTransactionConfig {
+                preflight_commitment: Some(self.commitment().commitment),
+                ..RpcSendTransactionConfig::default()
+            },
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+#[async_trait]
+impl<T: TransactionSubmitter + Send + Sync + ?Sized> TransactionSubmitter for Arc<T> {
+    async fn submit(&self, transaction: &Transaction) -> Result<(), ClientError> {
+        self.as_ref().submit(transaction).await
+    }
+}
+
+/// Hands transactions to a running `ConnectionWorkersScheduler` of
+/// `solana-tpu-client-next`.
+#[cfg(feature = "tpu-client-next")]
+#[async_trait]
+impl TransactionSubmitter for mpsc::Sender<TransactionBatch> {
+    async fn submit(&self, transaction: &Transaction) -> Result<(), ClientError> {
+        let wire_transaction = bincode::serialize(transaction)
+            .map_err(|err| ClientErrorKind::Custom(format!("{err}")))?;
+        self.send(TransactionBatch::new(vec![wire_transaction]))
+            .await
+            .map_err(|_| {
+                ClientErrorKind::Custom("transaction scheduler stopped".to_string()).into()
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DurableNonceSenderConfig {
+    /// Commitment used to read the nonce account and to confirm transactions.
+    pub commitment: CommitmentConfig,
+    /// Interval between two signature status checks.
+    pub poll_interval: Duration,
+    /// How long to wait for a confirmation before resubmitting.
+    pub resubmit_interval: Duration,
+    /// Maximum number of submissions, including resubmissions and rebuilds.
+    pub max_submissions: usize,
+}
+
+impl Default for DurableNonceSenderConfig {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            poll_interval: Duration::from_millis(500),
+            resubmit_interval: Duration::from_secs(10),
+            max_submissions: 30,
+        }
+    }
+}
+
+/// Build the message of a durable nonce transaction.
+///
+/// The `advance_nonce_account` instruction is prepended to `instructions` and
+/// the nonce value is used as the message's recent blockhash. The resulting
+/// message can be signed offline and later submitted by any means.
+pub fn build_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce_pubkey: &Pubkey,
+    nonce_authority: &Pubkey,
+    nonce_blockhash: &Hash,
+) -> Message {
+    let mut nonce_instructions = Vec::with_capacity(instructions.len() + 1);
+    nonce_instructions.push(system_instruction::advance_nonce_account(
+        nonce_pubkey,
+        nonce_authority,
+    ));
+    nonce_instructions.extend_from_slice(instructions);
+    Message::new_with_blockhash(&nonce_instructions, Some(payer), nonce_blockhash)
+}
+
+/// Sends durable nonce transactions and waits for their confirmation.
+pub struct DurableNonceSender<S> {
+    rpc_client: Arc<RpcClient>,
+    submitter: S,
+    config: DurableNonceSenderConfig,
+}
+
+impl DurableNonceSender<Arc<RpcClient>> {
+    /// Creates a sender that submits transactions through `rpc_client`.
+    pub fn new(rpc_client: Arc<RpcClient>, config: DurableNonceSenderConfig) -> Self {
+        Self::new_with_submitter(rpc_client.clone(), rpc_client, config)
+    }
+}
+
+impl<S: TransactionSubmitter> DurableNonceSender<S> {
+    /// Creates a sender that submits transactions through `submitter` and uses
+    /// `rpc_client` to read the nonce account and the transaction statuses.
+    pub fn new_with_submitter(
+        rpc_client: Arc<RpcClient>,
+        submitter: S,
+        config: DurableNonceSenderConfig,
+    ) -> Self {
+        Self {
+            rpc_client,
+            submitter,
+            config,
+        }
+    }
+
+    /// Build a durable nonce transaction from `instructions`, sign it and send
+    /// it until it is confirmed.
+    ///
+    /// If the nonce is advanced by another transaction before this one is
+    /// confirmed, the transaction is rebuilt and signed again against the new
+    /// nonce value. This is safe because the previous transaction can no
+    /// longer be processed once its nonce has been consumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DurableNonceSenderError::TransactionFailed`] if the
+    /// transaction failed, either in preflight or on chain. In the latter case
+    /// the nonce has been advanced by the failed transaction.
+    pub async fn send_and_confirm<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        nonce_pubkey: &Pubkey,
+        nonce_authority: &Pubkey,
+        signers: &T,
+    ) -> Result<Signature, DurableNonceSenderError> {
+        let mut submissions = 0;
+        loop {
+            let nonce_data = self.get_nonce_data(nonce_pubkey).await?;
+            if nonce_data.authority != *nonce_authority {
+                return Err(Error::InvalidAuthority {
+                    provided: *nonce_authority,
+                    expected: nonce_data.authority,
+                }
+                .into());
+            }
+            let nonce_blockhash = nonce_data.blockhash();
+            let message = build_message(
+                instructions,
+                payer,
+                nonce_pubkey,
+                nonce_authority,
+                &nonce_blockhash,
+            );
+            let mut transaction = Transaction::new_unsigned(message);
+            transaction.try_sign(signers, nonce_blockhash)?;
+            let signature = transaction.signatures[0];
+
+            let mut is_first_submission = true;
+            loop {
+                if submissions >= self.config.max_submissions {
+                    return Err(DurableNonceSenderError::MaxSubmissionsExceeded(submissions));
+                }
+                submissions += 1;
+                if let Err(err) = self.submitter.submit(&transaction).await {
+                    match err.get_transaction_error() {
+                        // Preflight failed, the nonce was not consumed
+                        Some(err) if is_first_submission => {
+                            return Err(DurableNonceSenderError::TransactionFailed {
+                                signature,
+                                err,
+                            });
+                        }
+                        _ => warn!("failed to submit transaction {signature}: {err}"),
+                    }
+                }
+                is_first_submission = false;
+
+                if let Some(result) = self.wait_for_confirmation(&signature).await? {
+                    return result.map(|()| signature).map_err(|err| {
+                        DurableNonceSenderError::TransactionFailed { signature, err }
+                    });
+                }
+
+                // Until its nonce is advanced, the transaction may still land
+                let current_nonce_data = self.get_nonce_data(nonce_pubkey).await?;
+                if current_nonce_data.blockhash() == nonce_blockhash {
+                    continue;
+                }
+                // The nonce may have been advanced by this very transaction
+                // since its status was last checked
+                if let Some(result) = self.get_signature_status(&signature, true).await? {
+                    return result.map(|()| signature).map_err(|err| {
+                        DurableNonceSenderError::TransactionFailed { signature, err }
+                    });
+                }
+                info!("nonce {nonce_pubkey} was consumed by another transaction, rebuilding");
+                break;
+            }
+        }
+    }
+
+    async fn get_nonce_data(&self, nonce_pubkey: &Pubkey) -> Result<Data, Error> {
+        let account =
+            get_account_with_commitment(&self.rpc_client, nonce_pubkey, self.config.commitment)
+                .await?;
+        data_from_account(&account)
+    }
+
+    /// Polls the status of `signature` until it reaches the configured
+    /// commitment, or returns `None` after `resubmit_interval`.
+    async fn wait_for_confirmation(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<TransactionResult<()>>, Error> {
+        let deadline = Instant::now() + self.config.resubmit_interval;
+        loop {
+            if let Some(result) = self.get_signature_status(signature, false).await? {
+                return Ok(Some(result));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            sleep(self.config.poll_interval).await;
+        }
+    }
+
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+        search_transaction_history: bool,
+    ) -> Result<Option<TransactionResult<()>>, Error> {
+        let signatures = [*signature];
+        let response = if search_transaction_history {
+            self.rpc_client
+                .get_signature_statuses_with_history(&signatures)
+                .await
+        } else {
+            self.rpc_client.get_signature_statuses(&signatures).await
+        }
+        .map_err(|e| Error::Client(format!("{e}")))?;
+        Ok(response
+            .value
+            .into_iter()
+            .next()
+            .flatten()
+            .filter(|status| status.satisfies_commitment(self.config.commitment))
+            .map(|status| status.status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        solana_account::Account,
+        solana_account_decoder::{encode_ui_account, UiAccountEncoding},
+        solana_fee_calculator::FeeCalculator,
+        solana_keypair::Keypair,
+        solana_nonce::{self as nonce, state::DurableNonce},
+        solana_rpc_client::mock_sender::MocksMap,
+        solana_rpc_client_api::{
+            request::RpcRequest,
+            response::{Response, RpcResponseContext},
+        },
+        solana_signer::Signer,
+    };
+
+    fn nonce_account_response(
+        nonce_pubkey: &Pubkey,
+        authority: Pubkey,
+        blockhash: Hash,
+    ) -> serde_json::Value {
+        let data = nonce::state::Data {
+            authority,
+            durable_nonce: DurableNonce::from_blockhash(&blockhash),
+            fee_calculator: FeeCalculator::new(5000),
+        };
+        let account = Account::new_data_with_space(
+            42,
+            &nonce::versions::Versions::new(nonce::state::State::Initialized(data)),
+            nonce::state::State::size(),
+            &solana_sdk_ids::system_program::id(),
+        )
+        .unwrap();
+        json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value: json!(Some(encode_ui_account(
+                nonce_pubkey,
+                &account,
+                UiAccountEncoding::Base64,
+                None,
+                None,
+            ))),
+        })
+    }
+
+    fn no_signature_status_response() -> serde_json::Value {
+        json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value: json!([null]),
+        })
+    }
+
+    fn test_config() -> DurableNonceSenderConfig {
+        DurableNonceSenderConfig {
+            poll_interval: Duration::ZERO,
+            resubmit_interval: Duration::ZERO,
+            ..DurableNonceSenderConfig::default()
+        }
+    }
+
+    fn sign(message: Message, payer: &Keypair) -> Signature {
+        let blockhash = message.recent_blockhash;
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.sign(&[payer], blockhash);
+        transaction.signatures[0]
+    }
+
+    #[test]
+    fn test_build_message_prepends_advance_nonce() {
+        let payer = Pubkey::new_unique();
+        let nonce_pubkey = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        let message = build_message(
+            &[transfer.clone()],
+            &payer,
+            &nonce_pubkey,
+            &authority,
+            &blockhash,
+        );
+        assert_eq!(message.recent_blockhash, blockhash);
+        assert_eq!(
+            message,
+            Message::new_with_blockhash(
+                &[
+                    system_instruction::advance_nonce_account(&nonce_pubkey, &authority),
+                    transfer,
+                ],
+                Some(&payer),
+                &blockhash,
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm() {
+        let payer = Keypair::new();
+        let nonce_pubkey = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, payer.pubkey(), blockhash),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+        let sender = DurableNonceSender::new(rpc_client, test_config());
+
+        let signature = sender
+            .send_and_confirm(
+                &[],
+                &payer.pubkey(),
+                &nonce_pubkey,
+                &payer.pubkey(),
+                &[&payer],
+            )
+            .await
+            .unwrap();
+        let message = build_message(
+            &[],
+            &payer.pubkey(),
+            &nonce_pubkey,
+            &payer.pubkey(),
+            DurableNonce::from_blockhash(&blockhash).as_hash(),
+        );
+        assert_eq!(signature, sign(message, &payer));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_failed() {
+        let payer = Keypair::new();
+        let nonce_pubkey = Pubkey::new_unique();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, payer.pubkey(), Hash::new_unique()),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map(
+            "instruction_error",
+            mocks,
+        ));
+        let sender = DurableNonceSender::new(rpc_client, test_config());
+
+        assert!(matches!(
+            sender
+                .send_and_confirm(
+                    &[],
+                    &payer.pubkey(),
+                    &nonce_pubkey,
+                    &payer.pubkey(),
+                    &[&payer]
+                )
+                .await,
+            Err(DurableNonceSenderError::TransactionFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_invalid_authority() {
+        let payer = Keypair::new();
+        let nonce_pubkey = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, authority, Hash::new_unique()),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+        let sender = DurableNonceSender::new(rpc_client, test_config());
+
+        assert!(matches!(
+            sender
+                .send_and_confirm(
+                    &[],
+                    &payer.pubkey(),
+                    &nonce_pubkey,
+                    &payer.pubkey(),
+                    &[&payer]
+                )
+                .await,
+            Err(DurableNonceSenderError::Nonce(
+                Error::InvalidAuthority { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_rebuilds_after_nonce_consumed() {
+        let payer = Keypair::new();
+        let nonce_pubkey = Pubkey::new_unique();
+        let old_blockhash = Hash::new_unique();
+        let new_blockhash = Hash::new_unique();
+        let mut mocks = MocksMap::default();
+        // The first transaction is not confirmed, and the nonce gets consumed
+        // by another transaction in the meantime
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, payer.pubkey(), old_blockhash),
+        );
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, payer.pubkey(), new_blockhash),
+        );
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            nonce_account_response(&nonce_pubkey, payer.pubkey(), new_blockhash),
+        );
+        mocks.insert(
+            RpcRequest::GetSignatureStatuses,
+            no_signature_status_response(),
+        );
+        mocks.insert(
+            RpcRequest::GetSignatureStatuses,
+            no_signature_status_response(),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+        let sender = DurableNonceSender::new(rpc_client, test_config());
+
+        let signature = sender
+            .send_and_confirm(
+                &[],
+                &payer.pubkey(),
+                &nonce_pubkey,
+                &payer.pubkey(),
+                &[&payer],
+            )
+            .await
+            .unwrap();
+        let message = build_message(
+            &[],
+            &payer.pubkey(),
+            &nonce_pubkey,
+            &payer.pubkey(),
+            DurableNonce::from_blockhash(&new_blockhash).as_hash(),
+        );
+        assert_eq!(signature, sign(message, &payer));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_max_submissions() {
+        let payer = Keypair::new();
+        let nonce_pubkey = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let mut mocks = MocksMap::default();
+        for _ in 0..3 {
+            mocks.insert(
+                RpcRequest::GetAccountInfo,
+                nonce_account_response(&nonce_pubkey, payer.pubkey(), blockhash),
+            );
+        }
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("sig_not_found", mocks));
+        let sender = DurableNonceSender::new(
+            rpc_client,
+            DurableNonceSenderConfig {
+                max_submissions: 2,
+                ..test_config()
+            },
+        );
+
+        assert!(matches!(
+            sender
+                .send_and_confirm(
+                    &[],
+                    &payer.pubkey(),
+                    &nonce_pubkey,
+                    &payer.pubkey(),
+                    &[&payer]
+                )
+                .await,
+            Err(DurableNonceSenderError::MaxSubmissionsExceeded(2))
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Durable transaction nonce helpers.
 
 pub mod blockhash_query;
+pub mod durable_nonce_sender;
 
 use {
     solana_account::{state_traits::StateMut, Account, ReadableAccount},
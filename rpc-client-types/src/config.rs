@@ -1,9 +1,10 @@
 use {
     crate::filter::RpcFilterType,
-    solana_clock::{Epoch, Slot},
+    solana_clock::{Epoch, Slot, UnixTimestamp},
+    std::collections::HashMap,
 };
 pub use {
-    solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig},
+    solana_account_decoder_client_types::{UiAccountData, UiAccountEncoding, UiDataSliceConfig},
     solana_commitment_config::{CommitmentConfig, CommitmentLevel},
     solana_transaction_status_client_types::{TransactionDetails, UiTransactionEncoding},
 };
@@ -46,6 +47,67 @@ pub struct RpcSimulateTransactionConfig {
     pub min_context_slot: Option<Slot>,
     #[serde(default)]
     pub inner_instructions: bool,
+    /// Account states to simulate against in place of the bank's, keyed by address
+    pub account_overrides: Option<HashMap<String, RpcAccountOverride>>,
+    pub sysvar_overrides: Option<RpcSysvarOverrides>,
+    /// Return the compute units and account accesses of every instruction
+    #[serde(default)]
+    pub trace: bool,
+    /// Return the cost model's estimate of the transaction and the capacity left in the working
+    /// bank's block
+    #[serde(default)]
+    pub cost: bool,
+}
+
+/// Replacement state for a single account during simulation
+///
+/// Fields which are not set keep the value of the account in the bank, or of a default account if
+/// it does not exist.
+///
+/// Programs and the accounts owned by a program loader can not be overridden, and an override can
+/// not turn an account into one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountOverride {
+    pub lamports: Option<u64>,
+    pub owner: Option<String>,
+    pub data: Option<UiAccountData>,
+    pub executable: Option<bool>,
+}
+
+/// Sysvar values to simulate against in place of the bank's
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSysvarOverrides {
+    pub clock: Option<RpcClockOverride>,
+}
+
+/// Replacement `Clock` sysvar fields; fields which are not set keep the bank's value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcClockOverride {
+    pub slot: Option<Slot>,
+    pub unix_timestamp: Option<UnixTimestamp>,
+}
+
+/// Configuration for `simulateBundle`
+///
+/// The options apply to every transaction of the bundle. The requested `accounts` are returned
+/// as they are after each transaction.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleConfig {
+    #[serde(default)]
+    pub sig_verify: bool,
+    #[serde(default)]
+    pub replace_recent_blockhash: bool,
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub encoding: Option<UiTransactionEncoding>,
+    pub accounts: Option<RpcSimulateTransactionAccountsConfig>,
+    pub min_context_slot: Option<Slot>,
+    #[serde(default)]
+    pub inner_instructions: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -166,6 +228,11 @@ pub struct RpcProgramAccountsConfig {
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
     pub sort_results: Option<bool>,
+    /// Return a page of at most this many accounts, sorted by pubkey, along with a cursor to
+    /// the next page
+    pub page_size: Option<usize>,
+    /// Cursor returned with the previous page
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -181,6 +248,8 @@ pub enum RpcTransactionLogsFilter {
 pub struct RpcTransactionLogsConfig {
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
+    /// Replay the logs of buffered slots starting at this slot before live notifications
+    pub from_slot: Option<Slot>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -196,6 +265,22 @@ pub struct RpcSignatureSubscribeConfig {
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
     pub enable_received_notification: Option<bool>,
+    /// Notify immediately if the signature was already processed at or after this slot
+    pub from_slot: Option<Slot>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotSubscribeConfig {
+    /// Replay the buffered slots starting at this slot before live notifications
+    pub from_slot: Option<Slot>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRootSubscribeConfig {
+    /// Replay the buffered roots starting at this slot before live notifications
+    pub from_slot: Option<Slot>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -216,6 +301,36 @@ pub struct RpcBlockSubscribeConfig {
     pub max_supported_transaction_version: Option<u8>,
 }
 
+/// Selects the transactions delivered by `transactionSubscribe`. Addresses are base-58 encoded
+/// and matched against every account a transaction loads, including those loaded from address
+/// lookup tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionSubscribeFilter {
+    /// Only deliver transactions that load at least one of these accounts, if not empty
+    #[serde(default)]
+    pub account_include: Vec<String>,
+    /// Never deliver transactions that load any of these accounts
+    #[serde(default)]
+    pub account_exclude: Vec<String>,
+    /// Only deliver transactions that load all of these accounts
+    #[serde(default)]
+    pub account_required: Vec<String>,
+    /// Deliver vote transactions, defaults to false
+    pub vote: Option<bool>,
+    /// Deliver failed transactions, defaults to true
+    pub failed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionSubscribeConfig {
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub encoding: Option<UiTransactionEncoding>,
+    pub max_supported_transaction_version: Option<u8>,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSignaturesForAddressConfig {
@@ -8,6 +8,7 @@ use {
 const MAX_DATA_SIZE: usize = 128;
 const MAX_DATA_BASE58_SIZE: usize = 175;
 const MAX_DATA_BASE64_SIZE: usize = 172;
+pub const MAX_OR_FILTERS: usize = 4;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +16,11 @@ pub enum RpcFilterType {
     DataSize(u64),
     Memcmp(Memcmp),
     TokenAccountState,
+    ValueCmp(ValueCmp),
+    DataSizeRange(RangeFilter),
+    LamportsRange(RangeFilter),
+    /// Matches accounts passing any of the filters. Groups cannot be nested.
+    Or(Vec<RpcFilterType>),
 }
 
 impl RpcFilterType {
@@ -55,6 +61,32 @@ impl RpcFilterType {
                 }
             }
             RpcFilterType::TokenAccountState => Ok(()),
+            RpcFilterType::ValueCmp(compare) => {
+                if compare.value > compare.value_type.max_value() {
+                    Err(RpcFilterError::ValueOutOfRange)
+                } else {
+                    Ok(())
+                }
+            }
+            RpcFilterType::DataSizeRange(range) | RpcFilterType::LamportsRange(range) => {
+                if range.is_empty() {
+                    Err(RpcFilterError::InvalidRange)
+                } else {
+                    Ok(())
+                }
+            }
+            RpcFilterType::Or(filters) => {
+                if filters.is_empty() || filters.len() > MAX_OR_FILTERS {
+                    return Err(RpcFilterError::InvalidOrFilterLength);
+                }
+                for filter in filters {
+                    if matches!(filter, RpcFilterType::Or(_)) {
+                        return Err(RpcFilterError::NestedOrFilter);
+                    }
+                    filter.verify()?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -67,6 +99,14 @@ pub enum RpcFilterError {
     Base58DecodeError(#[from] bs58::decode::Error),
     #[error("base64 decode error")]
     Base64DecodeError(#[from] base64::DecodeError),
+    #[error("compared value does not fit in the compared type")]
+    ValueOutOfRange,
+    #[error("range minimum is greater than its maximum")]
+    InvalidRange,
+    #[error("or filter should contain between 1 and {max} filters", max = MAX_OR_FILTERS)]
+    InvalidOrFilterLength,
+    #[error("or filters cannot be nested")]
+    NestedOrFilter,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
@@ -208,6 +248,129 @@ impl Memcmp {
     }
 }
 
+/// Width of the little-endian unsigned integer read by a `ValueCmp` filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueCmpType {
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl ValueCmpType {
+    pub fn size(&self) -> usize {
+        match self {
+            ValueCmpType::U8 => 1,
+            ValueCmpType::U16 => 2,
+            ValueCmpType::U32 => 4,
+            ValueCmpType::U64 => 8,
+        }
+    }
+
+    fn max_value(&self) -> u64 {
+        match self {
+            ValueCmpType::U8 => u8::MAX.into(),
+            ValueCmpType::U16 => u16::MAX.into(),
+            ValueCmpType::U32 => u32::MAX.into(),
+            ValueCmpType::U64 => u64::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompareOperator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOperator {
+    pub fn compare(&self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            CompareOperator::Eq => lhs == rhs,
+            CompareOperator::Ne => lhs != rhs,
+            CompareOperator::Gt => lhs > rhs,
+            CompareOperator::Ge => lhs >= rhs,
+            CompareOperator::Lt => lhs < rhs,
+            CompareOperator::Le => lhs <= rhs,
+        }
+    }
+}
+
+/// Compares a little-endian unsigned integer in the account data to a value
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueCmp {
+    /// Data offset of the integer
+    pub offset: usize,
+    /// Width of the integer
+    #[serde(rename = "type")]
+    pub value_type: ValueCmpType,
+    /// Comparison applied with the integer on the left-hand side
+    pub operator: CompareOperator,
+    pub value: u64,
+}
+
+impl ValueCmp {
+    pub fn new(
+        offset: usize,
+        value_type: ValueCmpType,
+        operator: CompareOperator,
+        value: u64,
+    ) -> Self {
+        Self {
+            offset,
+            value_type,
+            operator,
+            value,
+        }
+    }
+
+    /// Accounts too short to hold the integer never match
+    pub fn value_matches(&self, data: &[u8]) -> bool {
+        let size = self.value_type.size();
+        let Some(bytes) = self
+            .offset
+            .checked_add(size)
+            .and_then(|end| data.get(self.offset..end))
+        else {
+            return false;
+        };
+        let mut le_bytes = [0; 8];
+        le_bytes[..size].copy_from_slice(bytes);
+        self.operator
+            .compare(u64::from_le_bytes(le_bytes), self.value)
+    }
+}
+
+/// An inclusive range, unbounded on the sides left out
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RangeFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<u64>,
+}
+
+impl RangeFilter {
+    pub fn new(min: Option<u64>, max: Option<u64>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!((self.min, self.max), (Some(min), Some(max)) if min > max)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -309,6 +472,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_verify_comparison_filters() {
+        let value_cmp = |value_type, value| {
+            RpcFilterType::ValueCmp(ValueCmp::new(0, value_type, CompareOperator::Ge, value))
+        };
+        assert_eq!(value_cmp(ValueCmpType::U8, 255).verify(), Ok(()));
+        assert_eq!(
+            value_cmp(ValueCmpType::U8, 256).verify(),
+            Err(RpcFilterError::ValueOutOfRange)
+        );
+        assert_eq!(value_cmp(ValueCmpType::U64, u64::MAX).verify(), Ok(()));
+
+        assert_eq!(
+            RpcFilterType::DataSizeRange(RangeFilter::new(Some(1), Some(1))).verify(),
+            Ok(())
+        );
+        assert_eq!(
+            RpcFilterType::LamportsRange(RangeFilter::new(None, Some(1))).verify(),
+            Ok(())
+        );
+        assert_eq!(
+            RpcFilterType::LamportsRange(RangeFilter::new(Some(2), Some(1))).verify(),
+            Err(RpcFilterError::InvalidRange)
+        );
+
+        let data_size = RpcFilterType::DataSize(1);
+        assert_eq!(RpcFilterType::Or(vec![data_size.clone()]).verify(), Ok(()));
+        assert_eq!(
+            RpcFilterType::Or(vec![]).verify(),
+            Err(RpcFilterError::InvalidOrFilterLength)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![data_size.clone(); MAX_OR_FILTERS + 1]).verify(),
+            Err(RpcFilterError::InvalidOrFilterLength)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![RpcFilterType::Or(vec![data_size])]).verify(),
+            Err(RpcFilterError::NestedOrFilter)
+        );
+        // Members of the group are verified too
+        assert_eq!(
+            RpcFilterType::Or(vec![value_cmp(ValueCmpType::U16, 1 << 16)]).verify(),
+            Err(RpcFilterError::ValueOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_value_matches() {
+        let data = [1, 0, 2, 0, 0, 0];
+        let value_cmp = |offset, value_type, operator, value| {
+            ValueCmp::new(offset, value_type, operator, value).value_matches(&data)
+        };
+
+        assert!(value_cmp(0, ValueCmpType::U8, CompareOperator::Eq, 1));
+        assert!(value_cmp(0, ValueCmpType::U16, CompareOperator::Eq, 1));
+        assert!(value_cmp(
+            0,
+            ValueCmpType::U32,
+            CompareOperator::Eq,
+            0x0002_0001
+        ));
+        assert!(value_cmp(2, ValueCmpType::U32, CompareOperator::Ge, 2));
+        assert!(!value_cmp(2, ValueCmpType::U32, CompareOperator::Gt, 2));
+        assert!(value_cmp(2, ValueCmpType::U8, CompareOperator::Lt, 3));
+        assert!(value_cmp(2, ValueCmpType::U8, CompareOperator::Le, 2));
+        assert!(value_cmp(1, ValueCmpType::U8, CompareOperator::Ne, 1));
+
+        // Integers overrunning the data never match
+        assert!(!value_cmp(0, ValueCmpType::U64, CompareOperator::Ge, 0));
+        assert!(!value_cmp(6, ValueCmpType::U8, CompareOperator::Ge, 0));
+        assert!(!value_cmp(
+            usize::MAX,
+            ValueCmpType::U8,
+            CompareOperator::Ge,
+            0
+        ));
+    }
+
+    #[test]
+    fn test_range_contains() {
+        assert!(RangeFilter::default().contains(0));
+        assert!(RangeFilter::default().contains(u64::MAX));
+
+        let range = RangeFilter::new(Some(2), Some(4));
+        assert!(!range.contains(1));
+        assert!(range.contains(2));
+        assert!(range.contains(4));
+        assert!(!range.contains(5));
+
+        assert!(RangeFilter::new(Some(2), None).contains(u64::MAX));
+        assert!(!RangeFilter::new(None, Some(4)).contains(5));
+    }
+
+    #[test]
+    fn test_comparison_filters_serde() {
+        let filters: Vec<RpcFilterType> = serde_json::from_value(json!([
+            {"valueCmp": {"offset": 8, "type": "u64", "operator": "ge", "value": 100}},
+            {"dataSizeRange": {"min": 100}},
+            {"or": [
+                {"lamportsRange": {"min": 1, "max": 10}},
+                {"dataSize": 0},
+            ]},
+        ]))
+        .unwrap();
+        assert_eq!(
+            filters,
+            vec![
+                RpcFilterType::ValueCmp(ValueCmp::new(
+                    8,
+                    ValueCmpType::U64,
+                    CompareOperator::Ge,
+                    100
+                )),
+                RpcFilterType::DataSizeRange(RangeFilter::new(Some(100), None)),
+                RpcFilterType::Or(vec![
+                    RpcFilterType::LamportsRange(RangeFilter::new(Some(1), Some(10))),
+                    RpcFilterType::DataSize(0),
+                ]),
+            ]
+        );
+        assert_eq!(json!(filters[1]), json!({"dataSizeRange": {"min": 100}}));
+    }
+
     const BASE58_STR: &str = "Bpf4ERpEvSFmCSTNh1PzTWTkALrKXvMXEdthxHuwCQcf";
     const BASE64_STR: &str = "oMoycDvJzrjQpCfukbO4VW/FLGLfnbqBEc9KUEVgj2g=";
     const BYTES: [u8; 4] = [0, 1, 2, 3];
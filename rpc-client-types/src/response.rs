@@ -197,6 +197,22 @@ pub struct RpcKeyedAccount {
     pub account: UiAccount,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcKeyedAccountsPage {
+    pub accounts: Vec<RpcKeyedAccount>,
+    /// Opaque cursor to the next page, `None` on the last page
+    pub cursor: Option<String>,
+}
+
+/// `getProgramAccounts` results, which are paginated when a page size or cursor is requested
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RpcProgramAccounts {
+    Page(Response<RpcKeyedAccountsPage>),
+    Accounts(OptionalContext<Vec<RpcKeyedAccount>>),
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SlotInfo {
     pub slot: Slot,
@@ -445,6 +461,117 @@ pub struct RpcSimulateTransactionResult {
     pub pre_token_balances: Option<Vec<UiTransactionTokenBalance>>,
     pub post_token_balances: Option<Vec<UiTransactionTokenBalance>>,
     pub loaded_addresses: Option<UiLoadedAddresses>,
+    /// Only returned if requested with `trace`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<RpcInstructionTrace>>,
+    /// Only returned if requested with `cost`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<RpcTransactionCost>,
+}
+
+/// Compute units and account accesses of a single top level or CPI instruction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInstructionTrace {
+    /// Invocation stack height, 1 for top level instructions
+    pub stack_height: u32,
+    pub program_id: String,
+    /// Compute units consumed, including those of the instructions it invoked
+    pub compute_units_consumed: u64,
+    pub accounts: Vec<RpcInstructionTraceAccount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInstructionTraceAccount {
+    pub pubkey: String,
+    /// Whether the instruction was allowed to write to the account
+    pub writable: bool,
+    /// Whether the account changed while processing the instruction
+    pub written: bool,
+    pub pre_data_len: u64,
+    pub post_data_len: u64,
+}
+
+/// Cost model estimate of a transaction, as charged against the block cost limits when it is
+/// included in a block, along with the room left under those limits in the working bank
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionCost {
+    pub signature_cost: u64,
+    pub write_lock_cost: u64,
+    pub data_bytes_cost: u64,
+    pub loaded_accounts_data_size_cost: u64,
+    /// Compute units requested by the transaction, or the default for its instructions
+    pub programs_execution_cost: u64,
+    /// Sum of all the costs above
+    pub total_cost: u64,
+    pub allocated_accounts_data_size: u64,
+    pub is_simple_vote: bool,
+    /// Slot of the working bank the limits and remaining capacity are taken from
+    pub slot: Slot,
+    pub block_cost_limit: u64,
+    pub remaining_block_cost: u64,
+    pub vote_cost_limit: u64,
+    pub remaining_vote_cost: u64,
+    pub account_cost_limit: u64,
+    /// Capacity left for each account the transaction write locks
+    pub writable_accounts: Vec<RpcWritableAccountCost>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWritableAccountCost {
+    pub pubkey: String,
+    /// Cost of the transactions already in the block which write lock the account
+    pub cost: u64,
+    pub remaining_cost: u64,
+}
+
+/// Outcome of a single transaction submitted with `sendTransactionBatch`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum RpcSendTransactionBatchResult {
+    /// The transaction passed preflight and was handed to the send-transaction-service
+    Accepted { signature: String },
+    /// The transaction appeared earlier in the batch, is already being retried, or has
+    /// already been processed
+    Duplicate { signature: String },
+    /// The transaction could not be decoded or failed preflight
+    Rejected {
+        /// Absent if the transaction could not be decoded
+        signature: Option<String>,
+        error: RpcSendTransactionBatchError,
+    },
+}
+
+/// The JSON RPC error `sendTransaction` would have returned for the transaction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSendTransactionBatchError {
+    pub code: i64,
+    pub message: String,
+    /// The simulation result for preflight failures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+/// State of a transaction in the send-transaction-service retry pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionRetryStatus {
+    /// Number of times the transaction has been sent, including the first broadcast
+    pub send_attempts: usize,
+    /// `None` if the transaction is retried until its blockhash expires
+    pub max_retries: Option<usize>,
+    /// Slot of the working bank when the transaction was last sent
+    pub last_sent_slot: Option<Slot>,
+    /// TPU addresses the transaction was last sent to, as `ip:port` strings
+    pub last_sent_to: Vec<String>,
+    /// The transaction is dropped from the retry pool once the block height exceeds this
+    pub last_valid_block_height: u64,
+    /// Nonce account of a durable-nonce transaction as a base-58 string
+    pub durable_nonce_account: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -535,6 +662,16 @@ pub struct RpcBlockUpdate {
     pub err: Option<RpcBlockUpdateError>,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionUpdate {
+    pub signature: String, // Signature as base-58 string
+    /// Position of the transaction within its slot
+    pub index: usize,
+    pub is_vote: bool,
+    pub transaction: EncodedTransactionWithStatusMeta,
+}
+
 impl From<ConfirmedTransactionStatusWithSignature> for RpcConfirmedTransactionStatusWithSignature {
     fn from(value: ConfirmedTransactionStatusWithSignature) -> Self {
         let ConfirmedTransactionStatusWithSignature {
@@ -0,0 +1,668 @@
+//! Nonblocking [`RpcSender`] that spreads requests over several RPC endpoints.
+//!
+//! [`FailoverSender`] sends each request to the best endpoint it knows of, and
+//! to the next one if the endpoint cannot be reached or reports that it is
+//! unhealthy or behind. Endpoints are periodically health checked in the
+//! background with `getHealth` and `getSlot`, so that nodes lagging behind the
+//! others are avoided before any request fails. A request carrying a
+//! `minContextSlot` is preferably sent to endpoints known to have reached that
+//! slot.
+//!
+//! Health checks are spawned on the runtime sending the request, and requests
+//! are routed without health state until the first one completes. The blocking
+//! [`RpcClient`] runs its requests on a single-threaded runtime that is only
+//! driven while a request is in progress, so there a health check only makes
+//! progress during later requests. Call [`FailoverSender::check_health`]
+//! before handing the sender to the client to route the first requests by
+//! health.
+//!
+//! [`RpcClient`]: crate::rpc_client::RpcClient
+//!
+//! Each endpoint has a circuit breaker: after a number of consecutive failures
+//! the endpoint is only tried once all others have failed, until a cooldown
+//! expires.
+//!
+//! Read requests can optionally be hedged: if the first endpoint has not
+//! responded after a delay, the request is also sent to the next endpoint, and
+//! whichever response arrives first is used.
+//!
+//! ```no_run
+//! use solana_commitment_config::CommitmentConfig;
+//! use solana_rpc_client::{
+//!     failover_sender::FailoverSender,
+//!     rpc_client::{RpcClient, RpcClientConfig},
+//! };
+//!
+//! let sender = FailoverSender::new([
+//!     "http://rpc-1.example.com:8899",
+//!     "http://rpc-2.example.com:8899",
+//! ]);
+//! let client = RpcClient::new_sender(
+//!     sender,
+//!     RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+//! );
+//! let slot = client.get_slot()?;
+//! # Ok::<(), solana_rpc_client_api::client_error::Error>(())
+//! ```
+
+use {
+    crate::{http_sender::HttpSender, rpc_sender::*},
+    async_trait::async_trait,
+    futures::future::join_all,
+    log::*,
+    serde_json::{json, Value},
+    solana_clock::Slot,
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client_api::{
+        client_error::{Error as ClientError, ErrorKind as ClientErrorKind, Result},
+        custom_error,
+        request::{RpcError, RpcRequest},
+    },
+    std::{
+        sync::{Arc, Mutex, RwLock},
+        time::{Duration, Instant},
+    },
+    tokio::time::{sleep, timeout},
+};
+
+/// Configuration of a [`FailoverSender`].
+#[derive(Debug, Clone)]
+pub struct FailoverSenderConfig {
+    /// How often the endpoints are health checked
+    pub health_check_interval: Duration,
+    /// How long a health check waits for an endpoint to respond
+    pub health_check_timeout: Duration,
+    /// Endpoints more than this many slots behind the most advanced endpoint
+    /// are only used when the others fail
+    pub max_slot_lag: u64,
+    /// Number of consecutive failures that open the circuit breaker of an
+    /// endpoint
+    pub circuit_breaker_threshold: usize,
+    /// How long an endpoint with an open circuit breaker is avoided
+    pub circuit_breaker_cooldown: Duration,
+    /// If set, read requests that have not completed after this delay are also
+    /// sent to the next endpoint
+    pub hedge_delay: Option<Duration>,
+}
+
+impl Default for FailoverSenderConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval: Duration::from_secs(10),
+            health_check_timeout: Duration::from_secs(2),
+            max_slot_lag: 50,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown: Duration::from_secs(30),
+            hedge_delay: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    is_unhealthy: bool,
+    slot: Option<Slot>,
+    consecutive_failures: usize,
+    circuit_open_until: Option<Instant>,
+}
+
+struct Endpoint {
+    sender: Box<dyn RpcSender + Send + Sync + 'static>,
+    state: RwLock<EndpointState>,
+}
+
+impl Endpoint {
+    fn record_result(&self, result: &Result<Value>, config: &FailoverSenderConfig) {
+        let mut state = self.state.write().unwrap();
+        match result {
+            Err(err) if is_endpoint_failure(err) => {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                if state.consecutive_failures >= config.circuit_breaker_threshold {
+                    debug!("opening the circuit breaker of {}", self.sender.url());
+                    state.circuit_open_until =
+                        Some(Instant::now() + config.circuit_breaker_cooldown);
+                }
+            }
+            _ => {
+                state.consecutive_failures = 0;
+                state.circuit_open_until = None;
+            }
+        }
+    }
+}
+
+/// Nonblocking [`RpcSender`] that fails over between several RPC endpoints.
+///
+/// See the [module documentation][self].
+pub struct FailoverSender {
+    endpoints: Arc<Vec<Endpoint>>,
+    config: FailoverSenderConfig,
+    next_health_check: Mutex<Instant>,
+}
+
+impl FailoverSender {
+    /// Create a sender over HTTP endpoints, in order of preference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn new<U: ToString>(urls: impl IntoIterator<Item = U>) -> Self {
+        Self::new_with_config(urls, FailoverSenderConfig::default())
+    }
+
+    /// Create a sender over HTTP endpoints, in order of preference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn new_with_config<U: ToString>(
+        urls: impl IntoIterator<Item = U>,
+        config: FailoverSenderConfig,
+    ) -> Self {
+        Self::new_with_senders(
+            urls.into_iter()
+                .map(|url| Box::new(HttpSender::new(url)) as Box<_>)
+                .collect(),
+            config,
+        )
+    }
+
+    /// Create a sender over arbitrary senders, in order of preference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `senders` is empty.
+    pub fn new_with_senders(
+        senders: Vec<Box<dyn RpcSender + Send + Sync + 'static>>,
+        config: FailoverSenderConfig,
+    ) -> Self {
+        assert!(!senders.is_empty(), "FailoverSender requires an endpoint");
+        Self {
+            endpoints: Arc::new(
+                senders
+                    .into_iter()
+                    .map(|sender| Endpoint {
+                        sender,
+                        state: RwLock::default(),
+                    })
+                    .collect(),
+            ),
+            config,
+            next_health_check: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Query the health and slot of every endpoint.
+    ///
+    /// This is done periodically in the background as requests are sent, and
+    /// only needs to be called to refresh the state of the endpoints sooner,
+    /// e.g. before the first request.
+    pub async fn check_health(&self) {
+        check_endpoints_health(&self.endpoints, self.config.health_check_timeout).await;
+    }
+
+    /// Starts a health check in the background if one is due, without waiting
+    /// for it to complete.
+    ///
+    /// The check is spawned on the current runtime, see the [module
+    /// documentation][self] for what that means for the blocking client.
+    fn check_health_if_due(&self) {
+        let is_due = {
+            let mut next_health_check = self.next_health_check.lock().unwrap();
+            let now = Instant::now();
+            if now >= *next_health_check {
+                *next_health_check = now + self.config.health_check_interval;
+                true
+            } else {
+                false
+            }
+        };
+        if is_due {
+            let endpoints = self.endpoints.clone();
+            let health_check_timeout = self.config.health_check_timeout;
+            tokio::spawn(async move {
+                check_endpoints_health(&endpoints, health_check_timeout).await;
+            });
+        }
+    }
+
+    /// Returns all endpoints, the ones that should be tried first first.
+    fn ranked_endpoints(&self, min_context_slot: Option<Slot>) -> Vec<&Endpoint> {
+        let now = Instant::now();
+        let highest_slot = self
+            .endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.state.read().unwrap().slot)
+            .max();
+        let mut endpoints: Vec<_> = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| {
+                let state = endpoint.state.read().unwrap();
+                let is_circuit_open = state
+                    .circuit_open_until
+                    .is_some_and(|open_until| open_until > now);
+                let is_lagging = state.slot.is_some_and(|slot| {
+                    highest_slot.is_some_and(|highest_slot| {
+                        highest_slot.saturating_sub(slot) > self.config.max_slot_lag
+                    }) || min_context_slot.is_some_and(|min_context_slot| slot < min_context_slot)
+                });
+                (
+                    (is_circuit_open, state.is_unhealthy || is_lagging, index),
+                    endpoint,
+                )
+            })
+            .collect();
+        endpoints.sort_by_key(|(rank, _)| *rank);
+        endpoints
+            .into_iter()
+            .map(|(_, endpoint)| endpoint)
+            .collect()
+    }
+
+    async fn send_to(
+        &self,
+        endpoint: &Endpoint,
+        request: RpcRequest,
+        params: Value,
+    ) -> Result<Value> {
+        let result = endpoint.sender.send(request, params).await;
+        endpoint.record_result(&result, &self.config);
+        result
+    }
+
+    /// Sends `request` to `primary`, and also to `secondary` if `primary` has
+    /// not responded after `delay`. Returns the first successful result and
+    /// the number of endpoints used.
+    async fn send_hedged(
+        &self,
+        primary: &Endpoint,
+        secondary: &Endpoint,
+        request: RpcRequest,
+        params: &Value,
+        delay: Duration,
+    ) -> (Result<Value>, usize) {
+        let primary_send = self.send_to(primary, request, params.clone());
+        tokio::pin!(primary_send);
+        tokio::select! {
+            result = &mut primary_send => return (result, 1),
+            _ = sleep(delay) => (),
+        }
+
+        debug!("hedging {request} to {}", secondary.sender.url());
+        let secondary_send = self.send_to(secondary, request, params.clone());
+        tokio::pin!(secondary_send);
+        let result = tokio::select! {
+            result = &mut primary_send => match result {
+                Err(err) if is_endpoint_failure(&err) => secondary_send.await,
+                result => result,
+            },
+            result = &mut secondary_send => match result {
+                Err(err) if is_endpoint_failure(&err) => primary_send.await,
+                result => result,
+            },
+        };
+        (result, 2)
+    }
+}
+
+async fn check_endpoints_health(endpoints: &[Endpoint], health_check_timeout: Duration) {
+    let checks = endpoints.iter().map(|endpoint| async move {
+        let health = timeout(
+            health_check_timeout,
+            endpoint.sender.send(RpcRequest::GetHealth, Value::Null),
+        )
+        .await;
+        let slot = timeout(
+            health_check_timeout,
+            endpoint
+                .sender
+                .send(RpcRequest::GetSlot, json!([CommitmentConfig::processed()])),
+        )
+        .await;
+        let is_healthy = matches!(health, Ok(Ok(_)));
+        let slot = match slot {
+            Ok(Ok(slot)) => slot.as_u64(),
+            _ => None,
+        };
+        (is_healthy, slot)
+    });
+    let results = join_all(checks).await;
+    for (endpoint, (is_healthy, slot)) in endpoints.iter().zip(results) {
+        let mut state = endpoint.state.write().unwrap();
+        state.is_unhealthy = !is_healthy;
+        state.slot = slot.or(state.slot);
+    }
+}
+
+/// Whether the request may succeed on another endpoint.
+fn is_endpoint_failure(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => {
+            true
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => matches!(
+            *code,
+            custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | custom_error::JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+                | custom_error::JSON_RPC_SERVER_ERROR_RATE_LIMITED
+        ),
+        _ => false,
+    }
+}
+
+/// Requests without side effects, which are safe to send to several endpoints.
+fn is_hedgeable(request: &RpcRequest) -> bool {
+    !matches!(
+        request,
+        RpcRequest::Custom { .. }
+            | RpcRequest::DeregisterNode
+            | RpcRequest::RegisterNode
+            | RpcRequest::RequestAirdrop
+            | RpcRequest::SendTransaction
+            | RpcRequest::SendTransactionBatch
+            | RpcRequest::SignVote
+    )
+}
+
+fn min_context_slot(params: &Value) -> Option<Slot> {
+    params
+        .as_array()?
+        .iter()
+        .find_map(|param| param.get("minContextSlot")?.as_u64())
+}
+
+#[async_trait]
+impl RpcSender for FailoverSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.check_health_if_due();
+
+        let endpoints = self.ranked_endpoints(min_context_slot(&params));
+        let hedge_delay = self.config.hedge_delay.filter(|_| is_hedgeable(&request));
+        let mut last_err = None;
+        let mut index = 0;
+        while let Some(endpoint) = endpoints.get(index) {
+            let (result, num_used) = match (hedge_delay, endpoints.get(index + 1)) {
+                (Some(delay), Some(secondary)) => {
+                    self.send_hedged(endpoint, secondary, request, &params, delay)
+                        .await
+                }
+                _ => (self.send_to(endpoint, request, params.clone()).await, 1),
+            };
+            match result {
+                Err(err) if is_endpoint_failure(&err) => {
+                    debug!("{request} failed on {}: {err}", endpoint.sender.url());
+                    last_err = Some(err);
+                }
+                result => return result,
+            }
+            index += num_used;
+        }
+        Err(last_err.expect("at least one endpoint"))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.sender.get_transport_stats())
+            .fold(RpcTransportStats::default(), |total, stats| {
+                RpcTransportStats {
+                    request_count: total.request_count + stats.request_count,
+                    elapsed_time: total.elapsed_time + stats.elapsed_time,
+                    rate_limited_time: total.rate_limited_time + stats.rate_limited_time,
+                }
+            })
+    }
+
+    /// The URL of the endpoint the next request would be sent to first.
+    fn url(&self) -> String {
+        self.ranked_endpoints(None)[0].sender.url()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::rpc_client::{RpcClient, RpcClientConfig},
+        std::{
+            io,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+        },
+    };
+
+    struct TestSender {
+        url: &'static str,
+        slot: Slot,
+        is_down: bool,
+        delay: Duration,
+        health_check_delay: Duration,
+        request_count: Arc<AtomicUsize>,
+    }
+
+    impl TestSender {
+        fn new(url: &'static str, slot: Slot) -> Self {
+            Self {
+                url,
+                slot,
+                is_down: false,
+                delay: Duration::ZERO,
+                health_check_delay: Duration::ZERO,
+                request_count: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RpcSender for TestSender {
+        async fn send(&self, request: RpcRequest, _params: Value) -> Result<Value> {
+            if !matches!(request, RpcRequest::GetHealth | RpcRequest::GetSlot) {
+                self.request_count.fetch_add(1, Ordering::Relaxed);
+            }
+            if self.is_down {
+                return Err(io::Error::from(io::ErrorKind::ConnectionRefused).into());
+            }
+            match request {
+                RpcRequest::GetHealth | RpcRequest::GetSlot => sleep(self.health_check_delay).await,
+                _ => sleep(self.delay).await,
+            }
+            match request {
+                RpcRequest::GetHealth => Ok(json!("ok")),
+                RpcRequest::GetSlot => Ok(json!(self.slot)),
+                _ => Ok(json!(self.url)),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            self.url.to_string()
+        }
+    }
+
+    fn failover_sender(senders: Vec<TestSender>, config: FailoverSenderConfig) -> FailoverSender {
+        FailoverSender::new_with_senders(
+            senders
+                .into_iter()
+                .map(|sender| Box::new(sender) as Box<_>)
+                .collect(),
+            config,
+        )
+    }
+
+    fn skip_health_checks(sender: &FailoverSender) {
+        *sender.next_health_check.lock().unwrap() = Instant::now() + Duration::from_secs(60);
+    }
+
+    #[tokio::test]
+    async fn test_failover_and_circuit_breaker() {
+        let down = TestSender {
+            is_down: true,
+            ..TestSender::new("down", 100)
+        };
+        let down_requests = down.request_count.clone();
+        let sender = failover_sender(
+            vec![down, TestSender::new("up", 100)],
+            FailoverSenderConfig {
+                circuit_breaker_threshold: 2,
+                ..FailoverSenderConfig::default()
+            },
+        );
+        skip_health_checks(&sender);
+
+        for _ in 0..3 {
+            let result = sender.send(RpcRequest::GetVersion, Value::Null).await;
+            assert_eq!(result.unwrap(), json!("up"));
+        }
+        // The circuit breaker opened after the second failure
+        assert_eq!(down_requests.load(Ordering::Relaxed), 2);
+        assert_eq!(sender.url(), "up");
+
+        // A request that fails everywhere returns the last error
+        let sender = failover_sender(
+            vec![TestSender {
+                is_down: true,
+                ..TestSender::new("down", 100)
+            }],
+            FailoverSenderConfig::default(),
+        );
+        let err = sender
+            .send(RpcRequest::GetVersion, Value::Null)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind(), ClientErrorKind::Io(_)));
+    }
+
+    #[tokio::test]
+    async fn test_route_around_lagging_endpoints() {
+        let lagging = TestSender::new("lagging", 100);
+        let lagging_requests = lagging.request_count.clone();
+        let sender = failover_sender(
+            vec![lagging, TestSender::new("ahead", 200)],
+            FailoverSenderConfig::default(),
+        );
+        sender.check_health().await;
+        skip_health_checks(&sender);
+
+        let result = sender.send(RpcRequest::GetVersion, Value::Null).await;
+        assert_eq!(result.unwrap(), json!("ahead"));
+        assert_eq!(lagging_requests.load(Ordering::Relaxed), 0);
+
+        // Within the allowed lag, but not at the requested context slot
+        let sender = failover_sender(
+            vec![
+                TestSender::new("behind", 180),
+                TestSender::new("ahead", 200),
+            ],
+            FailoverSenderConfig::default(),
+        );
+        sender.check_health().await;
+        skip_health_checks(&sender);
+        let result = sender
+            .send(RpcRequest::GetVersion, Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(result, json!("behind"));
+        let result = sender
+            .send(
+                RpcRequest::GetBalance,
+                json!(["11111111111111111111111111111111", {"minContextSlot": 190}]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, json!("ahead"));
+    }
+
+    #[tokio::test]
+    async fn test_hedged_requests() {
+        let slow = TestSender {
+            delay: Duration::from_millis(500),
+            health_check_delay: Duration::from_millis(500),
+            ..TestSender::new("slow", 100)
+        };
+        let sender = failover_sender(
+            vec![slow, TestSender::new("fast", 100)],
+            FailoverSenderConfig {
+                hedge_delay: Some(Duration::from_millis(50)),
+                ..FailoverSenderConfig::default()
+            },
+        );
+        skip_health_checks(&sender);
+
+        let result = sender.send(RpcRequest::GetVersion, Value::Null).await;
+        assert_eq!(result.unwrap(), json!("fast"));
+
+        // Transactions are only sent to one endpoint
+        let result = sender.send(RpcRequest::SendTransaction, Value::Null).await;
+        assert_eq!(result.unwrap(), json!("slow"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_check_does_not_block_requests() {
+        let health_check_delay = Duration::from_millis(500);
+        let slow_health = TestSender {
+            health_check_delay,
+            ..TestSender::new("slow", 100)
+        };
+        let sender = failover_sender(
+            vec![slow_health],
+            FailoverSenderConfig {
+                health_check_timeout: Duration::from_secs(5),
+                ..FailoverSenderConfig::default()
+            },
+        );
+
+        // The request does not wait for the health check it triggers
+        let start = tokio::time::Instant::now();
+        let result = sender.send(RpcRequest::GetVersion, Value::Null).await;
+        assert_eq!(result.unwrap(), json!("slow"));
+        assert!(start.elapsed() < health_check_delay);
+        assert!(sender.endpoints[0].state.read().unwrap().slot.is_none());
+
+        // The health check completes in the background
+        sleep(health_check_delay * 3).await;
+        assert_eq!(sender.endpoints[0].state.read().unwrap().slot, Some(100));
+    }
+
+    #[test]
+    fn test_health_check_with_blocking_client() {
+        let health_check_delay = Duration::from_millis(50);
+        let lagging = TestSender {
+            health_check_delay,
+            ..TestSender::new("lagging", 100)
+        };
+        let ahead = TestSender {
+            health_check_delay,
+            ..TestSender::new("ahead", 200)
+        };
+        let client = RpcClient::new_sender(
+            failover_sender(vec![lagging, ahead], FailoverSenderConfig::default()),
+            RpcClientConfig::default(),
+        );
+        let send = || {
+            client
+                .send::<String>(RpcRequest::GetVersion, Value::Null)
+                .unwrap()
+        };
+
+        // Routed without health state, and the health check it triggers does
+        // not progress while the client is idle
+        assert_eq!(send(), "lagging");
+        std::thread::sleep(health_check_delay * 4);
+        assert_eq!(send(), "lagging");
+
+        // Until a later call drives the client's runtime
+        client.runtime().block_on(sleep(health_check_delay * 10));
+        assert_eq!(send(), "ahead");
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 
+pub mod failover_sender;
 pub mod http_sender;
 pub mod mock_sender;
 pub mod nonblocking;
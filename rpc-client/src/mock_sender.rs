@@ -20,8 +20,9 @@ use {
             Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
             RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcIdentity,
             RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
+            RpcKeyedAccountsPage, RpcPerfSample, RpcPrioritizationFee, RpcResponseContext,
+            RpcSendTransactionBatchResult, RpcSimulateTransactionResult, RpcSnapshotSlotInfo,
+            RpcSupply, RpcTransactionRetryStatus, RpcVersionInfo, RpcVoteAccountInfo,
             RpcVoteAccountStatus,
         },
     },
@@ -345,6 +346,38 @@ impl RpcSender for MockSender {
                 };
                 Value::String(signature)
             }
+            "sendTransactionBatch" => {
+                let results: Vec<_> = params[0]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|tx_str| {
+                        let data = BASE64_STANDARD.decode(tx_str.as_str().unwrap()).unwrap();
+                        let tx: Transaction = bincode::deserialize(&data).unwrap();
+                        RpcSendTransactionBatchResult::Accepted {
+                            signature: tx.signatures[0].to_string(),
+                        }
+                    })
+                    .collect();
+                serde_json::to_value(results)?
+            }
+            "getTransactionRetryStatus" => {
+                let num_signatures = params[0].as_array().map_or(0, Vec::len);
+                serde_json::to_value(Response {
+                    context: RpcResponseContext { slot: 1, api_version: None },
+                    value: vec![
+                        Some(RpcTransactionRetryStatus {
+                            send_attempts: 1,
+                            max_retries: None,
+                            last_sent_slot: Some(1),
+                            last_sent_to: vec!["127.0.0.1:8003".to_string()],
+                            last_valid_block_height: 301,
+                            durable_nonce_account: None,
+                        });
+                        num_signatures
+                    ],
+                })?
+            }
             "simulateTransaction" => serde_json::to_value(Response {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: RpcSimulateTransactionResult {
@@ -362,8 +395,37 @@ impl RpcSender for MockSender {
                     pre_token_balances: None,
                     post_token_balances: None,
                     loaded_addresses: None,
+                    trace: None,
+                    cost: None,
                 }
             })?,
+            "simulateBundle" => {
+                let num_transactions = params[0].as_array().map_or(0, Vec::len);
+                serde_json::to_value(Response {
+                    context: RpcResponseContext { slot: 1, api_version: None },
+                    value: vec![
+                        RpcSimulateTransactionResult {
+                            err: None,
+                            logs: None,
+                            accounts: None,
+                            units_consumed: None,
+                            loaded_accounts_data_size: None,
+                            return_data: None,
+                            inner_instructions: None,
+                            replacement_blockhash: None,
+                            fee: None,
+                            pre_balances: None,
+                            post_balances: None,
+                            pre_token_balances: None,
+                            post_token_balances: None,
+                            loaded_addresses: None,
+                            trace: None,
+                            cost: None,
+                        };
+                        num_transactions
+                    ],
+                })?
+            }
             "getMinimumBalanceForRentExemption" => json![20],
             "getVersion" => {
                 let version = Version::default();
@@ -479,12 +541,21 @@ impl RpcSender for MockSender {
             })?,
             "getProgramAccounts" => {
                 let pubkey = Pubkey::from_str(PUBKEY).unwrap();
-                serde_json::to_value(vec![
+                let accounts = vec![
                     RpcKeyedAccount {
                         pubkey: PUBKEY.to_string(),
                         account: mock_encoded_account(&pubkey)
                     }
-                ])?
+                ];
+                let config = &params[1];
+                if !config["pageSize"].is_null() || !config["cursor"].is_null() {
+                    serde_json::to_value(Response {
+                        context: RpcResponseContext { slot: 1, api_version: None },
+                        value: RpcKeyedAccountsPage { accounts, cursor: None },
+                    })?
+                } else {
+                    serde_json::to_value(accounts)?
+                }
             },
             _ => Value::Null,
         };
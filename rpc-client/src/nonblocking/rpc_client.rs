@@ -21,7 +21,7 @@ use {
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::serialize,
-    futures::join,
+    futures::{join, stream, Stream},
     log::*,
     serde_json::{json, Value},
     solana_account::Account,
@@ -40,7 +40,10 @@ use {
             Error as ClientError, ErrorKind as ClientErrorKind, Result as ClientResult,
         },
         config::{RpcAccountInfoConfig, *},
-        request::{RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter},
+        request::{
+            RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter,
+            MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE,
+        },
         response::*,
     },
     solana_signature::Signature,
@@ -1019,6 +1022,98 @@ impl RpcClient {
         }
     }
 
+    /// Submits a batch of signed transactions to the network.
+    ///
+    /// Each transaction goes through the same checks as in
+    /// [`send_transaction`], with the preflight simulations run in parallel on
+    /// the node. One [`RpcSendTransactionBatchResult`] is returned per
+    /// transaction, in order: a transaction that fails preflight is reported
+    /// in its entry instead of failing the whole request, and a transaction
+    /// that is repeated within the batch, already being retried or already
+    /// processed is reported as a duplicate.
+    ///
+    /// [`send_transaction`]: RpcClient::send_transaction
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `sendTransactionBatch` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     response::RpcSendTransactionBatchResult,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice pays Bob and Carol
+    /// #     let alice = Keypair::new();
+    /// #     let bob = Keypair::new();
+    /// #     let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+    /// let pay_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&alice, &carol.pubkey(), 50, latest_blockhash);
+    /// let results = rpc_client.send_transaction_batch(&[pay_bob, pay_carol]).await?;
+    /// for result in results {
+    ///     if let RpcSendTransactionBatchResult::Rejected { error, .. } = result {
+    ///         println!("transaction rejected: {}", error.message);
+    ///     }
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn send_transaction_batch(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> ClientResult<Vec<RpcSendTransactionBatchResult>> {
+        self.send_transaction_batch_with_config(
+            transactions,
+            RpcSendTransactionConfig {
+                preflight_commitment: Some(self.commitment().commitment),
+                ..RpcSendTransactionConfig::default()
+            },
+        )
+        .await
+    }
+
+    /// Submits a batch of signed transactions to the network.
+    ///
+    /// See [`send_transaction_batch`] for details. The configuration applies
+    /// to every transaction in the batch.
+    ///
+    /// [`send_transaction_batch`]: RpcClient::send_transaction_batch
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `sendTransactionBatch` RPC method.
+    pub async fn send_transaction_batch_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSendTransactionConfig,
+    ) -> ClientResult<Vec<RpcSendTransactionBatchResult>> {
+        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Base64);
+        let config = RpcSendTransactionConfig {
+            encoding: Some(encoding),
+            preflight_commitment: Some(config.preflight_commitment.unwrap_or_default()),
+            ..config
+        };
+        let serialized_encoded = transactions
+            .iter()
+            .map(|transaction| serialize_and_encode(transaction, encoding))
+            .collect::<ClientResult<Vec<_>>>()?;
+        self.send(
+            RpcRequest::SendTransactionBatch,
+            json!([serialized_encoded, config]),
+        )
+        .await
+    }
+
     /// Check the confirmation status of a transaction.
     ///
     /// Returns `true` if the given transaction succeeded and has been committed
@@ -1400,6 +1495,97 @@ impl RpcClient {
         .await
     }
 
+    /// Simulates sending an ordered list of transactions.
+    ///
+    /// The transactions are executed one after the other against the same
+    /// bank, so each transaction sees the account changes made by the
+    /// transactions before it. Nothing is committed. One
+    /// [`RpcSimulateTransactionResult`] is returned per transaction.
+    ///
+    /// By default, signatures are not verified during simulation. To verify
+    /// signatures, call the [`simulate_bundle_with_config`] method, with the
+    /// [`sig_verify`] field of [`RpcSimulateBundleConfig`] set to `true`.
+    ///
+    /// [`simulate_bundle_with_config`]: RpcClient::simulate_bundle_with_config
+    /// [`sig_verify`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::sig_verify
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice funds Bob, who then pays Carol
+    /// #     let alice = Keypair::new();
+    /// #     let bob = Keypair::new();
+    /// #     let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+    /// let fund_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&bob, &carol.pubkey(), 50, latest_blockhash);
+    /// let results = rpc_client.simulate_bundle(&[fund_bob, pay_carol]).await?;
+    /// assert!(results.value.iter().all(|result| result.err.is_none()));
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn simulate_bundle(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> RpcResult<Vec<RpcSimulateTransactionResult>> {
+        self.simulate_bundle_with_config(
+            transactions,
+            RpcSimulateBundleConfig {
+                commitment: Some(self.commitment()),
+                ..RpcSimulateBundleConfig::default()
+            },
+        )
+        .await
+    }
+
+    /// Simulates sending an ordered list of transactions.
+    ///
+    /// See [`simulate_bundle`] for details. The requested [`accounts`] are
+    /// reported after each transaction, in the [`accounts`][accounts2] field
+    /// of its [`RpcSimulateTransactionResult`].
+    ///
+    /// [`simulate_bundle`]: RpcClient::simulate_bundle
+    /// [`accounts`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::accounts
+    /// [accounts2]: solana_rpc_client_api::response::RpcSimulateTransactionResult::accounts
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub async fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<Vec<RpcSimulateTransactionResult>> {
+        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Base64);
+        let commitment = config.commitment.unwrap_or_default();
+        let config = RpcSimulateBundleConfig {
+            encoding: Some(encoding),
+            commitment: Some(commitment),
+            ..config
+        };
+        let serialized_encoded = transactions
+            .iter()
+            .map(|transaction| serialize_and_encode(transaction, encoding))
+            .collect::<ClientResult<Vec<_>>>()?;
+        self.send(
+            RpcRequest::SimulateBundle,
+            json!([serialized_encoded, config]),
+        )
+        .await
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -1642,6 +1828,55 @@ impl RpcClient {
         .await
     }
 
+    /// Gets the state of a list of transactions in the node's retry pool.
+    ///
+    /// The returned vector of [`RpcTransactionRetryStatus`] has the same
+    /// length as the input slice. Transactions submitted to this node with
+    /// [`send_transaction`] or [`send_transaction_batch`] are rebroadcast by
+    /// the node until they are processed, their blockhash expires or their
+    /// maximum number of retries is reached. The entry of a transaction that
+    /// is not, or no longer, being retried by the node is `None`.
+    ///
+    /// [`send_transaction`]: RpcClient::send_transaction
+    /// [`send_transaction_batch`]: RpcClient::send_transaction_batch
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getTransactionRetryStatus`
+    /// RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_system_transaction as system_transaction;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// #     let bob = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+    /// let tx = system_transaction::transfer(&alice, &bob.pubkey(), 50, latest_blockhash);
+    /// let signature = rpc_client.send_transaction(&tx).await?;
+    /// let statuses = rpc_client.get_transaction_retry_status(&[signature]).await?.value;
+    /// if let Some(status) = &statuses[0] {
+    ///     println!("sent {} times", status.send_attempts);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_transaction_retry_status(
+        &self,
+        signatures: &[Signature],
+    ) -> RpcResult<Vec<Option<RpcTransactionRetryStatus>>> {
+        let signatures: Vec<_> = signatures.iter().map(|s| s.to_string()).collect();
+        self.send(RpcRequest::GetTransactionRetryStatus, json!([signatures]))
+            .await
+    }
+
     /// Check if a transaction has been processed with the given [commitment level][cl].
     ///
     /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
@@ -4055,6 +4290,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     page_size: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -4085,6 +4322,72 @@ impl RpcClient {
         parse_keyed_accounts(accounts, RpcRequest::GetProgramAccounts)
     }
 
+    /// Returns a page of the accounts owned by the provided program pubkey, sorted by pubkey,
+    /// along with a cursor to the next page.
+    ///
+    /// Pass the returned cursor back in `config.cursor` to fetch the next page; no cursor is
+    /// returned with the last page. The server's maximum page size is used unless
+    /// `config.page_size` is set. Pages are served from the bank of the first page while the
+    /// server still has it, and from a later bank after that, so together they are not
+    /// necessarily a single snapshot of the program's accounts.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getProgramAccounts`] RPC method.
+    ///
+    /// [`getProgramAccounts`]: https://solana.com/docs/rpc/http/getprogramaccounts
+    pub async fn get_program_accounts_page(
+        &self,
+        pubkey: &Pubkey,
+        mut config: RpcProgramAccountsConfig,
+    ) -> ClientResult<(Vec<(Pubkey, Account)>, Option<String>)> {
+        let commitment = config
+            .account_config
+            .commitment
+            .unwrap_or_else(|| self.commitment());
+        config.account_config.commitment = Some(commitment);
+        if config.page_size.is_none() && config.cursor.is_none() {
+            config.page_size = Some(MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE);
+        }
+
+        let RpcKeyedAccountsPage { accounts, cursor } = self
+            .send::<Response<RpcKeyedAccountsPage>>(
+                RpcRequest::GetProgramAccounts,
+                json!([pubkey.to_string(), config]),
+            )
+            .await?
+            .value;
+        let accounts = parse_keyed_accounts(accounts, RpcRequest::GetProgramAccounts)?;
+        Ok((accounts, cursor))
+    }
+
+    /// Returns a stream of the pages of accounts owned by the provided program pubkey, fetching
+    /// each page with [`get_program_accounts_page`] as the stream is polled.
+    ///
+    /// The stream ends after the last page or the first error.
+    ///
+    /// [`get_program_accounts_page`]: RpcClient::get_program_accounts_page
+    pub fn get_program_accounts_pages(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> impl Stream<Item = ClientResult<Vec<(Pubkey, Account)>>> + '_ {
+        let pubkey = *pubkey;
+        stream::try_unfold(Some(config), move |config| async move {
+            let Some(config) = config else {
+                return Ok(None);
+            };
+            let (accounts, cursor) = self
+                .get_program_accounts_page(&pubkey, config.clone())
+                .await?;
+            let next_config = cursor.map(|cursor| RpcProgramAccountsConfig {
+                cursor: Some(cursor),
+                ..config
+            });
+            Ok::<_, ClientError>(Some((accounts, next_config)))
+        })
+    }
+
     /// Returns the stake minimum delegation, in lamports.
     ///
     /// # RPC Reference
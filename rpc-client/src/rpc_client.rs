@@ -964,6 +964,76 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).send_transaction_with_config(transaction, config))
     }
 
+    /// Submits a batch of signed transactions to the network.
+    ///
+    /// Each transaction goes through the same checks as in
+    /// [`send_transaction`], with the preflight simulations run in parallel on
+    /// the node. One [`RpcSendTransactionBatchResult`] is returned per
+    /// transaction, in order: a transaction that fails preflight is reported
+    /// in its entry instead of failing the whole request, and a transaction
+    /// that is repeated within the batch, already being retried or already
+    /// processed is reported as a duplicate.
+    ///
+    /// [`send_transaction`]: RpcClient::send_transaction
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `sendTransactionBatch` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     response::RpcSendTransactionBatchResult,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice pays Bob and Carol
+    /// # let alice = Keypair::new();
+    /// # let bob = Keypair::new();
+    /// # let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash()?;
+    /// let pay_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&alice, &carol.pubkey(), 50, latest_blockhash);
+    /// let results = rpc_client.send_transaction_batch(&[pay_bob, pay_carol])?;
+    /// for result in results {
+    ///     if let RpcSendTransactionBatchResult::Rejected { error, .. } = result {
+    ///         println!("transaction rejected: {}", error.message);
+    ///     }
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn send_transaction_batch(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> ClientResult<Vec<RpcSendTransactionBatchResult>> {
+        self.invoke((self.rpc_client.as_ref()).send_transaction_batch(transactions))
+    }
+
+    /// Submits a batch of signed transactions to the network.
+    ///
+    /// See [`send_transaction_batch`] for details. The configuration applies
+    /// to every transaction in the batch.
+    ///
+    /// [`send_transaction_batch`]: RpcClient::send_transaction_batch
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `sendTransactionBatch` RPC method.
+    pub fn send_transaction_batch_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSendTransactionConfig,
+    ) -> ClientResult<Vec<RpcSendTransactionBatchResult>> {
+        self.invoke(
+            (self.rpc_client.as_ref()).send_transaction_batch_with_config(transactions, config),
+        )
+    }
+
     pub fn send<T>(&self, request: RpcRequest, params: Value) -> ClientResult<T>
     where
         T: serde::de::DeserializeOwned,
@@ -1235,6 +1305,72 @@ impl RpcClient {
         )
     }
 
+    /// Simulates sending an ordered list of transactions.
+    ///
+    /// The transactions are executed one after the other against the same
+    /// bank, so each transaction sees the account changes made by the
+    /// transactions before it. Nothing is committed. One
+    /// [`RpcSimulateTransactionResult`] is returned per transaction.
+    ///
+    /// By default, signatures are not verified during simulation. To verify
+    /// signatures, call the [`simulate_bundle_with_config`] method, with the
+    /// [`sig_verify`] field of [`RpcSimulateBundleConfig`] set to `true`.
+    ///
+    /// [`simulate_bundle_with_config`]: RpcClient::simulate_bundle_with_config
+    /// [`sig_verify`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::sig_verify
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice funds Bob, who then pays Carol
+    /// # let alice = Keypair::new();
+    /// # let bob = Keypair::new();
+    /// # let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash()?;
+    /// let fund_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&bob, &carol.pubkey(), 50, latest_blockhash);
+    /// let results = rpc_client.simulate_bundle(&[fund_bob, pay_carol])?;
+    /// assert!(results.value.iter().all(|result| result.err.is_none()));
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn simulate_bundle(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> RpcResult<Vec<RpcSimulateTransactionResult>> {
+        self.invoke((self.rpc_client.as_ref()).simulate_bundle(transactions))
+    }
+
+    /// Simulates sending an ordered list of transactions.
+    ///
+    /// See [`simulate_bundle`] for details. The requested [`accounts`] are
+    /// reported after each transaction, in the [`accounts`][accounts2] field
+    /// of its [`RpcSimulateTransactionResult`].
+    ///
+    /// [`simulate_bundle`]: RpcClient::simulate_bundle
+    /// [`accounts`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::accounts
+    /// [accounts2]: solana_rpc_client_api::response::RpcSimulateTransactionResult::accounts
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<Vec<RpcSimulateTransactionResult>> {
+        self.invoke((self.rpc_client.as_ref()).simulate_bundle_with_config(transactions, config))
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -1454,6 +1590,50 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_signature_statuses_with_history(signatures))
     }
 
+    /// Gets the state of a list of transactions in the node's retry pool.
+    ///
+    /// The returned vector of [`RpcTransactionRetryStatus`] has the same
+    /// length as the input slice. Transactions submitted to this node with
+    /// [`send_transaction`] or [`send_transaction_batch`] are rebroadcast by
+    /// the node until they are processed, their blockhash expires or their
+    /// maximum number of retries is reached. The entry of a transaction that
+    /// is not, or no longer, being retried by the node is `None`.
+    ///
+    /// [`send_transaction`]: RpcClient::send_transaction
+    /// [`send_transaction_batch`]: RpcClient::send_transaction_batch
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getTransactionRetryStatus`
+    /// RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_system_transaction as system_transaction;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// # let bob = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash()?;
+    /// let tx = system_transaction::transfer(&alice, &bob.pubkey(), 50, latest_blockhash);
+    /// let signature = rpc_client.send_transaction(&tx)?;
+    /// let statuses = rpc_client.get_transaction_retry_status(&[signature])?.value;
+    /// if let Some(status) = &statuses[0] {
+    ///     println!("sent {} times", status.send_attempts);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_transaction_retry_status(
+        &self,
+        signatures: &[Signature],
+    ) -> RpcResult<Vec<Option<RpcTransactionRetryStatus>>> {
+        self.invoke((self.rpc_client.as_ref()).get_transaction_retry_status(signatures))
+    }
+
     /// Check if a transaction has been processed with the given [commitment level][cl].
     ///
     /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
@@ -3421,6 +3601,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     page_size: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3436,6 +3618,74 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_program_accounts_with_config(pubkey, config))
     }
 
+    /// Returns a page of the accounts owned by the provided program pubkey, sorted by pubkey,
+    /// along with a cursor to the next page.
+    ///
+    /// Pass the returned cursor back in `config.cursor` to fetch the next page; no cursor is
+    /// returned with the last page. The server's maximum page size is used unless
+    /// `config.page_size` is set.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the [`getProgramAccounts`] RPC method.
+    ///
+    /// [`getProgramAccounts`]: https://solana.com/docs/rpc/http/getprogramaccounts
+    pub fn get_program_accounts_page(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<(Vec<(Pubkey, Account)>, Option<String>)> {
+        self.invoke((self.rpc_client.as_ref()).get_program_accounts_page(pubkey, config))
+    }
+
+    /// Returns an iterator over the pages of accounts owned by the provided program pubkey,
+    /// fetching each page with [`get_program_accounts_page`] as the iterator advances.
+    ///
+    /// The iterator ends after the last page or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcProgramAccountsConfig};
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_pubkey::Pubkey;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let program_id = Pubkey::new_unique();
+    /// let config = RpcProgramAccountsConfig {
+    ///     page_size: Some(1_000),
+    ///     ..RpcProgramAccountsConfig::default()
+    /// };
+    /// for page in rpc_client.get_program_accounts_pages(&program_id, config) {
+    ///     for (pubkey, account) in page? {
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// [`get_program_accounts_page`]: RpcClient::get_program_accounts_page
+    pub fn get_program_accounts_pages(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> impl Iterator<Item = ClientResult<Vec<(Pubkey, Account)>>> + '_ {
+        let pubkey = *pubkey;
+        let mut config = Some(config);
+        std::iter::from_fn(move || {
+            let current_config = config.take()?;
+            Some(
+                self.get_program_accounts_page(&pubkey, current_config.clone())
+                    .map(|(accounts, cursor)| {
+                        config = cursor.map(|cursor| RpcProgramAccountsConfig {
+                            cursor: Some(cursor),
+                            ..current_config
+                        });
+                        accounts
+                    }),
+            )
+        })
+    }
+
     /// Returns the stake minimum delegation, in lamports.
     ///
     /// # RPC Reference
@@ -4080,6 +4330,8 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4115,6 +4367,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4200,6 +4454,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4219,6 +4475,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4238,6 +4496,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4257,6 +4517,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4269,6 +4531,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_program_accounts_pages() {
+        let program_id = Pubkey::new_unique();
+        let (accounts, keyed_accounts): (Vec<_>, Vec<_>) = (0..3)
+            .map(|lamports| {
+                let pubkey = Pubkey::new_unique();
+                let account = Account::new(lamports, 0, &program_id);
+                let keyed_account = RpcKeyedAccount {
+                    pubkey: pubkey.to_string(),
+                    account: encode_ui_account(
+                        &pubkey,
+                        &account,
+                        UiAccountEncoding::Base64,
+                        None,
+                        None,
+                    ),
+                };
+                ((pubkey, account), keyed_account)
+            })
+            .unzip();
+        let page = |accounts: &[RpcKeyedAccount], cursor: Option<&str>| {
+            serde_json::to_value(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None,
+                },
+                value: RpcKeyedAccountsPage {
+                    accounts: accounts.to_vec(),
+                    cursor: cursor.map(str::to_string),
+                },
+            })
+            .unwrap()
+        };
+        let mocks: MocksMap = [
+            (
+                RpcRequest::GetProgramAccounts,
+                page(&keyed_accounts[..2], Some("cursor")),
+            ),
+            (
+                RpcRequest::GetProgramAccounts,
+                page(&keyed_accounts[2..], None),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let rpc_client = RpcClient::new_mock_with_mocks_map("mock_client".to_string(), mocks);
+
+        let pages = rpc_client
+            .get_program_accounts_pages(
+                &program_id,
+                RpcProgramAccountsConfig {
+                    page_size: Some(2),
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )
+            .collect::<ClientResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(pages, vec![accounts[..2].to_vec(), accounts[2..].to_vec()]);
+    }
+
     #[test_case(LegacyMessage {
         header: MessageHeader {
             num_required_signatures: 1,
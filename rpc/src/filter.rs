@@ -9,5 +9,62 @@ pub fn filter_allows(filter: &RpcFilterType, account: &AccountSharedData) -> boo
         RpcFilterType::DataSize(size) => account.data().len() as u64 == *size,
         RpcFilterType::Memcmp(compare) => compare.bytes_match(account.data()),
         RpcFilterType::TokenAccountState => Account::valid_account_data(account.data()),
+        RpcFilterType::ValueCmp(compare) => compare.value_matches(account.data()),
+        RpcFilterType::DataSizeRange(range) => range.contains(account.data().len() as u64),
+        RpcFilterType::LamportsRange(range) => range.contains(account.lamports()),
+        RpcFilterType::Or(filters) => filters
+            .iter()
+            .any(|filter_type| filter_allows(filter_type, account)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_pubkey::Pubkey,
+        solana_rpc_client_api::filter::{
+            CompareOperator, Memcmp, RangeFilter, ValueCmp, ValueCmpType,
+        },
+    };
+
+    #[test]
+    fn test_filter_allows() {
+        let account = AccountSharedData::create(42, vec![1, 0, 2, 0], Pubkey::default(), false, 0);
+
+        assert!(filter_allows(
+            &RpcFilterType::ValueCmp(ValueCmp::new(2, ValueCmpType::U16, CompareOperator::Ge, 2)),
+            &account
+        ));
+        assert!(filter_allows(
+            &RpcFilterType::DataSizeRange(RangeFilter::new(Some(4), None)),
+            &account
+        ));
+        assert!(!filter_allows(
+            &RpcFilterType::DataSizeRange(RangeFilter::new(None, Some(3))),
+            &account
+        ));
+        assert!(filter_allows(
+            &RpcFilterType::LamportsRange(RangeFilter::new(Some(40), Some(50))),
+            &account
+        ));
+        assert!(!filter_allows(
+            &RpcFilterType::LamportsRange(RangeFilter::new(Some(43), None)),
+            &account
+        ));
+
+        // An or group needs a single matching filter
+        let memcmp = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![1]));
+        assert!(filter_allows(
+            &RpcFilterType::Or(vec![RpcFilterType::DataSize(0), memcmp]),
+            &account
+        ));
+        assert!(!filter_allows(
+            &RpcFilterType::Or(vec![
+                RpcFilterType::DataSize(0),
+                RpcFilterType::TokenAccountState,
+            ]),
+            &account
+        ));
     }
 }
@@ -8,8 +8,11 @@ pub mod rpc;
 mod rpc_cache;
 pub mod rpc_completed_slots_service;
 pub mod rpc_health;
+mod rpc_http_server;
 pub mod rpc_pubsub;
 pub mod rpc_pubsub_service;
+pub mod rpc_rate_limiter;
+mod rpc_replay_buffer;
 pub mod rpc_service;
 pub mod rpc_subscription_tracker;
 pub mod rpc_subscriptions;
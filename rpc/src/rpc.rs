@@ -6,7 +6,9 @@ use {
         filter::filter_allows, max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        rpc_rate_limiter::RpcRateLimitConfig,
     },
+    agave_feature_set::FeatureSet,
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::{config::Options, serialize},
     crossbeam_channel::{unbounded, Receiver, Sender},
@@ -16,7 +18,8 @@ use {
         BoxFuture, Error, Metadata, Result,
     },
     jsonrpc_derive::rpc,
-    solana_account::{AccountSharedData, ReadableAccount},
+    rayon::{prelude::*, ThreadPool, ThreadPoolBuilder},
+    solana_account::{AccountSharedData, ReadableAccount, WritableAccount, PROGRAM_OWNERS},
     solana_account_decoder::{
         encode_ui_account,
         parse_account_data::SplTokenAdditionalDataV2,
@@ -30,8 +33,11 @@ use {
         },
     },
     solana_client::connection_cache::Protocol,
-    solana_clock::{Slot, UnixTimestamp, MAX_PROCESSING_AGE},
+    solana_clock::{Clock, Slot, UnixTimestamp, MAX_PROCESSING_AGE},
     solana_commitment_config::{CommitmentConfig, CommitmentLevel},
+    solana_cost_model::{
+        cost_model::CostModel, cost_tracker_post_analysis::CostTrackerPostAnalysis,
+    },
     solana_entry::entry::Entry,
     solana_epoch_info::EpochInfo,
     solana_epoch_rewards_hasher::EpochRewardsHasher,
@@ -44,6 +50,7 @@ use {
         blockstore::{Blockstore, BlockstoreError, SignatureInfosForAddress},
         blockstore_meta::{PerfSample, PerfSampleV1, PerfSampleV2},
         leader_schedule_cache::LeaderScheduleCache,
+        transaction_balances::svm_token_info_to_token_balance,
     },
     solana_message::{AddressLoader, SanitizedMessage},
     solana_metrics::inc_new_counter_info,
@@ -57,14 +64,15 @@ use {
         request::{
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
-            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-            MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
+            MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
+            MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
+            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, MAX_SEND_TRANSACTION_BATCH_SIZE,
+            MAX_SIMULATE_BUNDLE_TRANSACTIONS, NUM_LARGEST_ACCOUNTS,
         },
         response::{Response as RpcResponse, *},
     },
     solana_runtime::{
-        bank::{Bank, TransactionSimulationResult},
+        bank::{Bank, TransactionSimulationConfig, TransactionSimulationResult},
         bank_forks::BankForks,
         commitment::{BlockCommitmentArray, BlockCommitmentCache},
         non_circulating_supply::{calculate_non_circulating_supply, NonCirculatingSupply},
@@ -73,11 +81,14 @@ use {
         snapshot_utils,
     },
     solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
-    solana_send_transaction_service::send_transaction_service::TransactionInfo,
+    solana_send_transaction_service::send_transaction_service::{
+        RetryStatusReader, TransactionInfo,
+    },
     solana_signature::Signature,
     solana_signer::Signer,
     solana_stake_program,
     solana_storage_bigtable::Error as StorageError,
+    solana_svm::account_overrides::AccountOverrides,
     solana_transaction::{
         sanitized::{MessageHash, SanitizedTransaction, MAX_TX_ACCOUNT_LOCKS},
         versioned::VersionedTransaction,
@@ -85,11 +96,11 @@ use {
     solana_transaction_context::transaction_accounts::TransactionAccount,
     solana_transaction_error::TransactionError,
     solana_transaction_status::{
-        map_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
+        map_inner_instructions, parse_ui_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
         ConfirmedTransactionStatusWithSignature, ConfirmedTransactionWithStatusMeta,
         EncodedConfirmedTransactionWithStatusMeta, Reward, RewardType, Rewards,
         TransactionBinaryEncoding, TransactionConfirmationStatus, TransactionStatus,
-        UiConfirmedBlock, UiTransactionEncoding,
+        UiConfirmedBlock, UiLoadedAddresses, UiTransactionEncoding,
     },
     solana_validator_exit::Exit,
     solana_vote_program::vote_state::MAX_LOCKOUT_HISTORY,
@@ -109,11 +120,12 @@ use {
         cmp::{max, min, Reverse},
         collections::{BinaryHeap, HashMap, HashSet},
         convert::TryFrom,
-        net::SocketAddr,
+        fmt,
+        net::{IpAddr, SocketAddr},
         str::FromStr,
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
-            Arc, RwLock,
+            Arc, OnceLock, RwLock,
         },
         time::Duration,
     },
@@ -141,6 +153,9 @@ type RpcCustomResult<T> = std::result::Result<T, RpcCustomError>;
 
 pub const MAX_REQUEST_BODY_SIZE: usize = 50 * (1 << 10); // 50kB
 pub const PERFORMANCE_SAMPLES_LIMIT: usize = 720;
+/// Number of threads running the preflight checks of `sendTransactionBatch`, shared by all
+/// requests
+const SEND_TRANSACTION_BATCH_PREFLIGHT_THREADS: usize = 4;
 
 fn new_response<T>(bank: &Bank, value: T) -> RpcResponse<T> {
     RpcResponse {
@@ -175,6 +190,8 @@ pub struct JsonRpcConfig {
     pub full_api: bool,
     pub rpc_scan_and_fix_roots: bool,
     pub max_request_body_size: Option<usize>,
+    /// Rate limits applied to each client, disabled if `None`
+    pub rate_limit: Option<RpcRateLimitConfig>,
     /// Disable the health check, used for tests and TestValidator
     pub disable_health_check: bool,
 }
@@ -196,6 +213,7 @@ impl Default for JsonRpcConfig {
             full_api: Default::default(),
             rpc_scan_and_fix_roots: Default::default(),
             max_request_body_size: Option::default(),
+            rate_limit: Option::default(),
             disable_health_check: Default::default(),
         }
     }
@@ -255,9 +273,22 @@ pub struct JsonRpcRequestProcessor {
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
     runtime: Arc<Runtime>,
+    client_ip: Option<IpAddr>,
+    retry_status: Option<RetryStatusReader>,
+    preflight_thread_pool: Arc<ThreadPool>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
 
+fn new_preflight_thread_pool() -> Arc<ThreadPool> {
+    Arc::new(
+        ThreadPoolBuilder::new()
+            .num_threads(SEND_TRANSACTION_BATCH_PREFLIGHT_THREADS)
+            .thread_name(|i| format!("solRpcPreflt{i:02}"))
+            .build()
+            .unwrap(),
+    )
+}
+
 impl JsonRpcRequestProcessor {
     pub fn clone_without_bigtable(&self) -> JsonRpcRequestProcessor {
         Self {
@@ -265,6 +296,27 @@ impl JsonRpcRequestProcessor {
             ..self.clone()
         }
     }
+
+    pub(crate) fn with_client_ip(self, client_ip: Option<IpAddr>) -> JsonRpcRequestProcessor {
+        Self { client_ip, ..self }
+    }
+
+    /// The address of the client this request is served for, if known
+    pub(crate) fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    /// Exposes the retry pool of the send-transaction-service consuming this processor's
+    /// transactions to `getTransactionRetryStatus`
+    pub(crate) fn with_retry_status(
+        self,
+        retry_status: RetryStatusReader,
+    ) -> JsonRpcRequestProcessor {
+        Self {
+            retry_status: Some(retry_status),
+            ..self
+        }
+    }
 }
 
 impl JsonRpcRequestProcessor {
@@ -440,6 +492,9 @@ impl JsonRpcRequestProcessor {
                 max_complete_transaction_status_slot,
                 prioritization_fee_cache,
                 runtime,
+                client_ip: None,
+                retry_status: None,
+                preflight_thread_pool: new_preflight_thread_pool(),
             },
             transaction_receiver,
         )
@@ -481,7 +536,7 @@ impl JsonRpcRequestProcessor {
         let runtime = service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj);
         let client = Client::create_client(Some(runtime.handle().clone()), my_tpu_address, None, 1);
 
-        SendTransactionService::new_with_client(
+        let retry_status = SendTransactionService::new_with_client(
             &bank_forks,
             transaction_receiver,
             client,
@@ -491,7 +546,8 @@ impl JsonRpcRequestProcessor {
                 ..SendTransactionServiceConfig::default()
             },
             exit.clone(),
-        );
+        )
+        .retry_status_reader();
 
         let leader_schedule_cache = Arc::new(LeaderScheduleCache::new_from_bank(&bank));
         let slot = bank.slot();
@@ -525,6 +581,9 @@ impl JsonRpcRequestProcessor {
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
             runtime,
+            client_ip: None,
+            retry_status: Some(retry_status),
+            preflight_thread_pool: new_preflight_thread_pool(),
         }
     }
 
@@ -617,54 +676,156 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
-        let keyed_accounts = {
-            if let Some(owner) = get_spl_token_owner_filter(&program_id, &filters) {
-                self.get_filtered_spl_token_accounts_by_owner(
-                    Arc::clone(&bank),
-                    program_id,
-                    owner,
-                    filters,
-                    sort_results,
-                )
-                .await?
-            } else if let Some(mint) = get_spl_token_mint_filter(&program_id, &filters) {
-                self.get_filtered_spl_token_accounts_by_mint(
-                    Arc::clone(&bank),
-                    program_id,
-                    mint,
-                    filters,
-                    sort_results,
-                )
-                .await?
-            } else {
-                self.get_filtered_program_accounts(
-                    Arc::clone(&bank),
-                    program_id,
-                    filters,
-                    sort_results,
-                )
-                .await?
+        let keyed_accounts = self
+            .load_program_accounts(&bank, program_id, filters, sort_results)
+            .await?;
+        let accounts = encode_program_accounts(
+            &bank,
+            &program_id,
+            keyed_accounts,
+            encoding,
+            data_slice_config,
+        )?;
+        Ok(match with_context {
+            true => OptionalContext::Context(new_response(&bank, accounts)),
+            false => OptionalContext::NoContext(accounts),
+        })
+    }
+
+    /// Like `get_program_accounts`, but returns at most `page_size` accounts, sorted by pubkey
+    /// and following the `cursor` of the previous page if any.
+    ///
+    /// The first page is served from the bank of the requested commitment, and every following
+    /// page from that same bank while it is available. Once it is not, e.g. after the next root,
+    /// the walk resumes from the first key after the cursor in the bank of the requested
+    /// commitment, which must be at least at the cursor's slot. The pages are then not a single
+    /// snapshot of the program's accounts: accounts changed in between are returned as of the
+    /// later bank, and accounts created in between at keys before the cursor are missed.
+    pub async fn get_program_accounts_page(
+        &self,
+        program_id: Pubkey,
+        config: Option<RpcAccountInfoConfig>,
+        mut filters: Vec<RpcFilterType>,
+        page_size: usize,
+        cursor: Option<ProgramAccountsCursor>,
+    ) -> Result<RpcResponse<RpcKeyedAccountsPage>> {
+        let RpcAccountInfoConfig {
+            encoding,
+            data_slice: data_slice_config,
+            commitment,
+            min_context_slot,
+        } = config.unwrap_or_default();
+        let cursor_bank =
+            cursor.and_then(|cursor| self.bank_forks.read().unwrap().get(cursor.slot));
+        let bank = match cursor_bank {
+            Some(bank) => {
+                if min_context_slot.is_some_and(|min_context_slot| bank.slot() < min_context_slot) {
+                    return Err(RpcCustomError::MinContextSlotNotReached {
+                        context_slot: bank.slot(),
+                    }
+                    .into());
+                }
+                bank
             }
+            // Resume from a bank that has not gone back past the previous page
+            None => self.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot: min_context_slot.max(cursor.map(|cursor| cursor.slot)),
+            })?,
         };
-        let accounts = if is_known_spl_token_id(&program_id)
-            && encoding == UiAccountEncoding::JsonParsed
-        {
-            get_parsed_token_accounts(Arc::clone(&bank), keyed_accounts.into_iter()).collect()
-        } else {
+        let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
+        optimize_filters(&mut filters);
+        let start_after = cursor.map(|cursor| cursor.pubkey);
+        // One account more than fits on the page tells whether there is a next page
+        let limit = page_size.saturating_add(1);
+        let mut keyed_accounts = if self.is_secondary_index_scan(&program_id, &filters) {
+            // Secondary indexes hold the matching pubkeys unordered, but they only hold the
+            // matching ones, so sorting them all is bounded by the result size
+            let mut keyed_accounts = self
+                .load_program_accounts(&bank, program_id, filters, false)
+                .await?;
+            keyed_accounts.sort_unstable_by_key(|(pubkey, _account)| *pubkey);
+            if let Some(start_after) = start_after {
+                let start =
+                    keyed_accounts.partition_point(|(pubkey, _account)| *pubkey <= start_after);
+                keyed_accounts.drain(..start);
+            }
+            keyed_accounts.truncate(limit);
             keyed_accounts
-                .into_iter()
-                .map(|(pubkey, account)| {
-                    Ok(RpcKeyedAccount {
-                        pubkey: pubkey.to_string(),
-                        account: encode_account(&account, &pubkey, encoding, data_slice_config)?,
+        } else {
+            let bank = Arc::clone(&bank);
+            self.runtime
+                .spawn_blocking(move || {
+                    bank.get_filtered_program_accounts_in_order(
+                        &program_id,
+                        |account: &AccountSharedData| {
+                            filters
+                                .iter()
+                                .all(|filter_type| filter_allows(filter_type, account))
+                        },
+                        start_after,
+                        limit,
+                    )
+                    .map_err(|e| RpcCustomError::ScanError {
+                        message: e.to_string(),
                     })
                 })
-                .collect::<Result<Vec<_>>>()?
+                .await
+                .expect("Failed to spawn blocking task")?
         };
-        Ok(match with_context {
-            true => OptionalContext::Context(new_response(&bank, accounts)),
-            false => OptionalContext::NoContext(accounts),
-        })
+        let has_next_page = keyed_accounts.len() > page_size;
+        keyed_accounts.truncate(page_size);
+        let cursor = has_next_page.then(|| {
+            ProgramAccountsCursor {
+                slot: bank.slot(),
+                pubkey: keyed_accounts[page_size - 1].0,
+            }
+            .to_string()
+        });
+        let accounts = encode_program_accounts(
+            &bank,
+            &program_id,
+            keyed_accounts,
+            encoding,
+            data_slice_config,
+        )?;
+        Ok(new_response(
+            &bank,
+            RpcKeyedAccountsPage { accounts, cursor },
+        ))
+    }
+
+    async fn load_program_accounts(
+        &self,
+        bank: &Arc<Bank>,
+        program_id: Pubkey,
+        filters: Vec<RpcFilterType>,
+        sort_results: bool,
+    ) -> Result<Vec<(Pubkey, AccountSharedData)>> {
+        let keyed_accounts = if let Some(owner) = get_spl_token_owner_filter(&program_id, &filters)
+        {
+            self.get_filtered_spl_token_accounts_by_owner(
+                Arc::clone(bank),
+                program_id,
+                owner,
+                filters,
+                sort_results,
+            )
+            .await?
+        } else if let Some(mint) = get_spl_token_mint_filter(&program_id, &filters) {
+            self.get_filtered_spl_token_accounts_by_mint(
+                Arc::clone(bank),
+                program_id,
+                mint,
+                filters,
+                sort_results,
+            )
+            .await?
+        } else {
+            self.get_filtered_program_accounts(Arc::clone(bank), program_id, filters, sort_results)
+                .await?
+        };
+        Ok(keyed_accounts)
     }
 
     fn filter_map_rewards<'a, F>(
@@ -1639,6 +1800,34 @@ impl JsonRpcRequestProcessor {
             .map(|(_, status)| status))
     }
 
+    pub fn get_transaction_retry_status(
+        &self,
+        signatures: Vec<Signature>,
+    ) -> RpcResponse<Vec<Option<RpcTransactionRetryStatus>>> {
+        let bank = self.bank(Some(CommitmentConfig::processed()));
+        let statuses = signatures
+            .iter()
+            .map(|signature| {
+                let status = self.retry_status.as_ref()?.get(signature)?;
+                Some(RpcTransactionRetryStatus {
+                    send_attempts: status.send_attempts,
+                    max_retries: status.max_retries,
+                    last_sent_slot: status.last_sent_slot,
+                    last_sent_to: status
+                        .last_sent_to
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    last_valid_block_height: status.last_valid_block_height,
+                    durable_nonce_account: status
+                        .durable_nonce_account
+                        .map(|nonce_account| nonce_account.to_string()),
+                })
+            })
+            .collect();
+        new_response(&bank, statuses)
+    }
+
     pub async fn get_signature_statuses(
         &self,
         signatures: Vec<Signature>,
@@ -2205,7 +2394,19 @@ impl JsonRpcRequestProcessor {
         sort_results: bool,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         optimize_filters(&mut filters);
-        if self
+        if let Some(index_key) = self.memcmp_index_key(&program_id, &filters) {
+            self.get_filtered_indexed_accounts(
+                &bank,
+                &index_key,
+                &program_id,
+                filters,
+                sort_results,
+            )
+            .await
+            .map_err(|e| RpcCustomError::ScanError {
+                message: e.to_string(),
+            })
+        } else if self
             .config
             .account_indexes
             .contains(&AccountIndex::ProgramId)
@@ -2253,6 +2454,36 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    /// Whether `load_program_accounts` serves `program_id` and `filters` from a secondary index
+    /// rather than by scanning all accounts
+    fn is_secondary_index_scan(&self, program_id: &Pubkey, filters: &[RpcFilterType]) -> bool {
+        let account_indexes = &self.config.account_indexes;
+        (get_spl_token_owner_filter(program_id, filters).is_some()
+            && account_indexes.contains(&AccountIndex::SplTokenOwner))
+            || (get_spl_token_mint_filter(program_id, filters).is_some()
+                && account_indexes.contains(&AccountIndex::SplTokenMint))
+            || self.memcmp_index_key(program_id, filters).is_some()
+            || account_indexes.contains(&AccountIndex::ProgramId)
+    }
+
+    /// Find an enabled memcmp index on `program_id` able to serve one of the
+    /// memcmp `filters`, which must already have been optimized to raw bytes
+    fn memcmp_index_key(&self, program_id: &Pubkey, filters: &[RpcFilterType]) -> Option<IndexKey> {
+        self.config
+            .account_indexes
+            .memcmp_indexes()
+            .filter(|memcmp_index| memcmp_index.program_id == *program_id)
+            .find_map(|memcmp_index| {
+                filters.iter().find_map(|filter_type| match filter_type {
+                    RpcFilterType::Memcmp(compare) => memcmp_index
+                        .key_from_memcmp(compare.offset(), compare.raw_bytes_as_ref()?)
+                        .filter(|index_key| self.config.account_indexes.include_key(index_key))
+                        .map(|index_key| IndexKey::Memcmp(*memcmp_index, index_key)),
+                    _ => None,
+                })
+            })
+    }
+
     /// Get an iterator of spl-token accounts by owner address
     async fn get_filtered_spl_token_accounts_by_owner(
         &self,
@@ -2400,15 +2631,79 @@ impl JsonRpcRequestProcessor {
     }
 }
 
+fn encode_program_accounts(
+    bank: &Arc<Bank>,
+    program_id: &Pubkey,
+    keyed_accounts: Vec<(Pubkey, AccountSharedData)>,
+    encoding: UiAccountEncoding,
+    data_slice_config: Option<UiDataSliceConfig>,
+) -> Result<Vec<RpcKeyedAccount>> {
+    if is_known_spl_token_id(program_id) && encoding == UiAccountEncoding::JsonParsed {
+        Ok(get_parsed_token_accounts(Arc::clone(bank), keyed_accounts.into_iter()).collect())
+    } else {
+        keyed_accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                Ok(RpcKeyedAccount {
+                    pubkey: pubkey.to_string(),
+                    account: encode_account(&account, &pubkey, encoding, data_slice_config)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Position of a `getProgramAccounts` page: the slot it was served from and the last pubkey
+/// returned. Handed to clients as an opaque base58 string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramAccountsCursor {
+    slot: Slot,
+    pubkey: Pubkey,
+}
+
+impl ProgramAccountsCursor {
+    const SERIALIZED_SIZE: usize = std::mem::size_of::<Slot>() + PUBKEY_BYTES;
+}
+
+impl fmt::Display for ProgramAccountsCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = [0; Self::SERIALIZED_SIZE];
+        bytes[..8].copy_from_slice(&self.slot.to_le_bytes());
+        bytes[8..].copy_from_slice(self.pubkey.as_ref());
+        write!(f, "{}", bs58::encode(bytes).into_string())
+    }
+}
+
+impl FromStr for ProgramAccountsCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid_cursor = || Error::invalid_params(format!("Invalid param: cursor {s}"));
+        let bytes = bs58::decode(s).into_vec().map_err(|_| invalid_cursor())?;
+        if bytes.len() != Self::SERIALIZED_SIZE {
+            return Err(invalid_cursor());
+        }
+        let (slot, pubkey) = bytes.split_at(8);
+        Ok(Self {
+            slot: Slot::from_le_bytes(slot.try_into().unwrap()),
+            pubkey: Pubkey::try_from(pubkey).unwrap(),
+        })
+    }
+}
+
 pub(crate) fn optimize_filters(filters: &mut [RpcFilterType]) {
-    filters.iter_mut().for_each(|filter_type| {
-        if let RpcFilterType::Memcmp(compare) = filter_type {
-            if let Err(err) = compare.convert_to_raw_bytes() {
-                // All filters should have been previously verified
-                warn!("Invalid filter: bytes could not be decoded, {err}");
+    filters
+        .iter_mut()
+        .for_each(|filter_type| match filter_type {
+            RpcFilterType::Memcmp(compare) => {
+                if let Err(err) = compare.convert_to_raw_bytes() {
+                    // All filters should have been previously verified
+                    warn!("Invalid filter: bytes could not be decoded, {err}");
+                }
             }
-        }
-    })
+            RpcFilterType::Or(filters) => optimize_filters(filters),
+            _ => {}
+        })
 }
 
 fn verify_transaction(transaction: &SanitizedTransaction) -> Result<()> {
@@ -2432,6 +2727,18 @@ pub(crate) fn verify_filters(filters: &[RpcFilterType]) -> Result<()> {
     Ok(())
 }
 
+fn verify_page_size(page_size: Option<usize>) -> Result<usize> {
+    match page_size {
+        None => Ok(MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE),
+        Some(page_size) if (1..=MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE).contains(&page_size) => {
+            Ok(page_size)
+        }
+        Some(_) => Err(Error::invalid_params(format!(
+            "Invalid param: pageSize must be between 1 and {MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE}"
+        ))),
+    }
+}
+
 fn verify_filter(input: &RpcFilterType) -> Result<()> {
     input
         .verify()
@@ -2584,6 +2891,11 @@ fn get_spl_token_owner_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
                 }
             }
             RpcFilterType::TokenAccountState => token_account_state_filter = true,
+            // Only narrow the results further, and are applied after the index scan
+            RpcFilterType::ValueCmp(_)
+            | RpcFilterType::DataSizeRange(_)
+            | RpcFilterType::LamportsRange(_)
+            | RpcFilterType::Or(_) => {}
         }
     }
     if data_size_filter == Some(account_packed_len as u64)
@@ -2634,6 +2946,11 @@ fn get_spl_token_mint_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
                 }
             }
             RpcFilterType::TokenAccountState => token_account_state_filter = true,
+            // Only narrow the results further, and are applied after the index scan
+            RpcFilterType::ValueCmp(_)
+            | RpcFilterType::DataSizeRange(_)
+            | RpcFilterType::LamportsRange(_)
+            | RpcFilterType::Or(_) => {}
         }
     }
     if data_size_filter == Some(account_packed_len as u64)
@@ -2681,16 +2998,132 @@ fn get_token_program_id_and_mint(
 }
 
 fn _send_transaction(
-    meta: JsonRpcRequestProcessor,
-    message_hash: Hash,
-    signature: Signature,
-    blockhash: Hash,
+    meta: &JsonRpcRequestProcessor,
+    transaction_info: TransactionInfo,
+) -> Result<String> {
+    let signature = transaction_info.signature;
+    meta.transaction_sender
+        .send(transaction_info)
+        .unwrap_or_else(|err| warn!("Failed to enqueue transaction: {err}"));
+
+    Ok(signature.to_string())
+}
+
+/// Fails if the node is too far behind for preflight checks to be meaningful
+fn check_preflight_health(health: &RpcHealth, skip_preflight_health_check: bool) -> Result<()> {
+    if skip_preflight_health_check {
+        return Ok(());
+    }
+    match health.check() {
+        RpcHealthStatus::Ok => Ok(()),
+        RpcHealthStatus::Unknown => {
+            inc_new_counter_info!("rpc-send-tx_health-unknown", 1);
+            Err(RpcCustomError::NodeUnhealthy {
+                num_slots_behind: None,
+            }
+            .into())
+        }
+        RpcHealthStatus::Behind { num_slots } => {
+            inc_new_counter_info!("rpc-send-tx_health-behind", 1);
+            Err(RpcCustomError::NodeUnhealthy {
+                num_slots_behind: Some(num_slots),
+            }
+            .into())
+        }
+    }
+}
+
+/// Sanitizes a transaction submitted for sending and, unless `skip_preflight` is set,
+/// verifies its signatures, checks the node health with `check_health` and simulates it
+/// against `preflight_bank`
+fn prepare_transaction_for_send(
+    preflight_bank: &Bank,
+    unsanitized_tx: VersionedTransaction,
     wire_transaction: Vec<u8>,
-    last_valid_block_height: u64,
-    durable_nonce_info: Option<(Pubkey, Hash)>,
+    skip_preflight: bool,
     max_retries: Option<usize>,
-) -> Result<String> {
-    let transaction_info = TransactionInfo::new(
+    check_health: impl FnOnce() -> Result<()>,
+) -> Result<TransactionInfo> {
+    let transaction = sanitize_transaction(
+        unsanitized_tx,
+        preflight_bank,
+        preflight_bank.get_reserved_account_keys(),
+    )?;
+    let blockhash = *transaction.message().recent_blockhash();
+    let message_hash = *transaction.message_hash();
+    let signature = *transaction.signature();
+
+    let mut last_valid_block_height = preflight_bank
+        .get_blockhash_last_valid_block_height(&blockhash)
+        .unwrap_or(0);
+
+    let durable_nonce_info = transaction
+        .get_durable_nonce()
+        .map(|&pubkey| (pubkey, blockhash));
+    if durable_nonce_info.is_some() || (skip_preflight && last_valid_block_height == 0) {
+        // While it uses a defined constant, this last_valid_block_height value is chosen arbitrarily.
+        // It provides a fallback timeout for durable-nonce transaction retries in case of
+        // malicious packing of the retry queue. Durable-nonce transactions are otherwise
+        // retried until the nonce is advanced.
+        last_valid_block_height = preflight_bank.block_height() + MAX_PROCESSING_AGE as u64;
+    }
+
+    if !skip_preflight {
+        verify_transaction(&transaction)?;
+
+        check_health()?;
+
+        if let TransactionSimulationResult {
+            result: Err(err),
+            logs,
+            post_simulation_accounts: _,
+            rollback_accounts: _,
+            units_consumed,
+            loaded_accounts_data_size,
+            return_data,
+            inner_instructions: _, // Always `None` due to `enable_cpi_recording = false`
+            fee,
+            pre_balances: _,
+            post_balances: _,
+            pre_token_balances: _,
+            post_token_balances: _,
+            instruction_trace: _,
+        } = preflight_bank.simulate_transaction(&transaction, false)
+        {
+            match err {
+                TransactionError::BlockhashNotFound => {
+                    inc_new_counter_info!("rpc-send-tx_err-blockhash-not-found", 1);
+                }
+                _ => {
+                    inc_new_counter_info!("rpc-send-tx_err-other", 1);
+                }
+            }
+            return Err(RpcCustomError::SendTransactionPreflightFailure {
+                message: format!("Transaction simulation failed: {err}"),
+                result: RpcSimulateTransactionResult {
+                    err: Some(err.into()),
+                    logs: Some(logs),
+                    accounts: None,
+                    units_consumed: Some(units_consumed),
+                    loaded_accounts_data_size: Some(loaded_accounts_data_size),
+                    return_data: return_data.map(|return_data| return_data.into()),
+                    inner_instructions: None,
+                    replacement_blockhash: None,
+                    fee,
+                    pre_balances: None,
+                    post_balances: None,
+                    pre_token_balances: None,
+                    post_token_balances: None,
+                    loaded_addresses: None,
+                    trace: None,
+                    cost: None,
+                },
+            }
+            .into());
+        }
+    }
+
+    Ok(TransactionInfo::new(
         message_hash,
         signature,
         blockhash,
@@ -2699,12 +3132,21 @@ fn _send_transaction(
         durable_nonce_info,
         max_retries,
         None,
-    );
-    meta.transaction_sender
-        .send(transaction_info)
-        .unwrap_or_else(|err| warn!("Failed to enqueue transaction: {err}"));
+    ))
+}
 
-    Ok(signature.to_string())
+fn new_send_transaction_batch_error(
+    signature: Option<Signature>,
+    error: Error,
+) -> RpcSendTransactionBatchResult {
+    RpcSendTransactionBatchResult::Rejected {
+        signature: signature.map(|signature| signature.to_string()),
+        error: RpcSendTransactionBatchError {
+            code: error.code.code(),
+            message: error.message,
+            data: error.data,
+        },
+    }
 }
 
 // Minimal RPC interface that known validators are expected to provide
@@ -3301,7 +3743,7 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>>;
+        ) -> BoxFuture<Result<RpcProgramAccounts>>;
 
         #[rpc(meta, name = "getLargestAccounts")]
         fn get_largest_accounts(
@@ -3357,23 +3799,43 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>> {
+        ) -> BoxFuture<Result<RpcProgramAccounts>> {
             debug!("get_program_accounts rpc request received: {program_id_str:?}");
             async move {
                 let program_id = verify_pubkey(&program_id_str)?;
-                let (config, filters, with_context, sort_results) = if let Some(config) = config {
-                    (
-                        Some(config.account_config),
-                        config.filters.unwrap_or_default(),
-                        config.with_context.unwrap_or_default(),
-                        config.sort_results.unwrap_or(true),
+                let RpcProgramAccountsConfig {
+                    filters,
+                    account_config,
+                    with_context,
+                    sort_results,
+                    page_size,
+                    cursor,
+                } = config.unwrap_or_default();
+                let filters = filters.unwrap_or_default();
+                verify_filters(&filters)?;
+                if page_size.is_some() || cursor.is_some() {
+                    let page_size = verify_page_size(page_size)?;
+                    let cursor = cursor.as_deref().map(str::parse).transpose()?;
+                    meta.get_program_accounts_page(
+                        program_id,
+                        Some(account_config),
+                        filters,
+                        page_size,
+                        cursor,
                     )
+                    .await
+                    .map(RpcProgramAccounts::Page)
                 } else {
-                    (None, vec![], false, true)
-                };
-                verify_filters(&filters)?;
-                meta.get_program_accounts(program_id, config, filters, with_context, sort_results)
+                    meta.get_program_accounts(
+                        program_id,
+                        Some(account_config),
+                        filters,
+                        with_context.unwrap_or_default(),
+                        sort_results.unwrap_or(true),
+                    )
                     .await
+                    .map(RpcProgramAccounts::Accounts)
+                }
             }
             .boxed()
         }
@@ -3452,7 +3914,6 @@ pub mod rpc_full {
     use {
         super::*,
         solana_message::{SanitizedVersionedMessage, VersionedMessage},
-        solana_transaction_status::{parse_ui_inner_instructions, UiLoadedAddresses},
     };
     #[rpc]
     pub trait Full {
@@ -3507,6 +3968,21 @@ pub mod rpc_full {
             config: Option<RpcSendTransactionConfig>,
         ) -> Result<String>;
 
+        #[rpc(meta, name = "sendTransactionBatch")]
+        fn send_transaction_batch(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSendTransactionConfig>,
+        ) -> Result<Vec<RpcSendTransactionBatchResult>>;
+
+        #[rpc(meta, name = "getTransactionRetryStatus")]
+        fn get_transaction_retry_status(
+            &self,
+            meta: Self::Metadata,
+            signature_strs: Vec<String>,
+        ) -> Result<RpcResponse<Vec<Option<RpcTransactionRetryStatus>>>>;
+
         #[rpc(meta, name = "simulateTransaction")]
         fn simulate_transaction(
             &self,
@@ -3515,6 +3991,14 @@ pub mod rpc_full {
             config: Option<RpcSimulateTransactionConfig>,
         ) -> Result<RpcResponse<RpcSimulateTransactionResult>>;
 
+        #[rpc(meta, name = "simulateBundle")]
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<Vec<RpcSimulateTransactionResult>>>;
+
         #[rpc(meta, name = "minimumLedgerSlot")]
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
@@ -3790,14 +4274,17 @@ pub mod rpc_full {
             };
 
             _send_transaction(
-                meta,
-                message_hash,
-                signature,
-                blockhash,
-                wire_transaction,
-                last_valid_block_height,
-                None,
-                None,
+                &meta,
+                TransactionInfo::new(
+                    message_hash,
+                    signature,
+                    blockhash,
+                    wire_transaction,
+                    last_valid_block_height,
+                    None,
+                    None,
+                    None,
+                ),
             )
         }
 
@@ -3834,109 +4321,157 @@ pub mod rpc_full {
                 min_context_slot,
             })?;
 
-            let transaction = sanitize_transaction(
-                unsanitized_tx,
+            let transaction_info = prepare_transaction_for_send(
                 preflight_bank,
-                preflight_bank.get_reserved_account_keys(),
+                unsanitized_tx,
+                wire_transaction,
+                skip_preflight,
+                max_retries,
+                || check_preflight_health(&meta.health, meta.config.skip_preflight_health_check),
             )?;
-            let blockhash = *transaction.message().recent_blockhash();
-            let message_hash = *transaction.message_hash();
-            let signature = *transaction.signature();
-
-            let mut last_valid_block_height = preflight_bank
-                .get_blockhash_last_valid_block_height(&blockhash)
-                .unwrap_or(0);
+            _send_transaction(&meta, transaction_info)
+        }
 
-            let durable_nonce_info = transaction
-                .get_durable_nonce()
-                .map(|&pubkey| (pubkey, blockhash));
-            if durable_nonce_info.is_some() || (skip_preflight && last_valid_block_height == 0) {
-                // While it uses a defined constant, this last_valid_block_height value is chosen arbitrarily.
-                // It provides a fallback timeout for durable-nonce transaction retries in case of
-                // malicious packing of the retry queue. Durable-nonce transactions are otherwise
-                // retried until the nonce is advanced.
-                last_valid_block_height = preflight_bank.block_height() + MAX_PROCESSING_AGE as u64;
-            }
+        fn send_transaction_batch(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSendTransactionConfig>,
+        ) -> Result<Vec<RpcSendTransactionBatchResult>> {
+            debug!(
+                "send_transaction_batch rpc request received: {:?}",
+                data.len()
+            );
+            if data.is_empty() {
+                return Err(Error::invalid_params("No transactions provided"));
+            }
+            if data.len() > MAX_SEND_TRANSACTION_BATCH_SIZE {
+                return Err(Error::invalid_params(format!(
+                    "Too many transactions provided; max {MAX_SEND_TRANSACTION_BATCH_SIZE}"
+                )));
+            }
+            let RpcSendTransactionConfig {
+                skip_preflight,
+                preflight_commitment,
+                encoding,
+                max_retries,
+                min_context_slot,
+            } = config.unwrap_or_default();
+            let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
+            let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "unsupported encoding: {tx_encoding}. Supported encodings: base58, base64"
+                ))
+            })?;
 
-            if !skip_preflight {
-                verify_transaction(&transaction)?;
+            let preflight_commitment = if skip_preflight {
+                Some(CommitmentConfig::processed())
+            } else {
+                preflight_commitment.map(|commitment| CommitmentConfig { commitment })
+            };
+            let preflight_bank = &*meta.get_bank_with_config(RpcContextConfig {
+                commitment: preflight_commitment,
+                min_context_slot,
+            })?;
 
-                if !meta.config.skip_preflight_health_check {
-                    match meta.health.check() {
-                        RpcHealthStatus::Ok => (),
-                        RpcHealthStatus::Unknown => {
-                            inc_new_counter_info!("rpc-send-tx_health-unknown", 1);
-                            return Err(RpcCustomError::NodeUnhealthy {
-                                num_slots_behind: None,
-                            }
-                            .into());
-                        }
-                        RpcHealthStatus::Behind { num_slots } => {
-                            inc_new_counter_info!("rpc-send-tx_health-behind", 1);
-                            return Err(RpcCustomError::NodeUnhealthy {
-                                num_slots_behind: Some(num_slots),
-                            }
-                            .into());
+            // Duplicates are filtered out before preflight, which would otherwise report
+            // transactions that were already processed as failed
+            let mut batch_signatures = HashSet::new();
+            let mut results = Vec::with_capacity(data.len());
+            let mut transactions = vec![];
+            for data in data {
+                let (wire_transaction, unsanitized_tx) =
+                    match decode_and_deserialize::<VersionedTransaction>(data, binary_encoding) {
+                        Ok(decoded) => decoded,
+                        Err(err) => {
+                            results.push(Some(new_send_transaction_batch_error(None, err)));
+                            continue;
                         }
+                    };
+                let signature = unsanitized_tx.signatures.first().copied();
+                if let Some(signature) = signature {
+                    let is_duplicate = !batch_signatures.insert(signature)
+                        || meta
+                            .retry_status
+                            .as_ref()
+                            .is_some_and(|retry_status| retry_status.contains(&signature))
+                        || preflight_bank.has_signature(&signature);
+                    if is_duplicate {
+                        results.push(Some(RpcSendTransactionBatchResult::Duplicate {
+                            signature: signature.to_string(),
+                        }));
+                        continue;
                     }
                 }
+                results.push(None);
+                transactions.push((
+                    results.len() - 1,
+                    signature,
+                    wire_transaction,
+                    unsanitized_tx,
+                ));
+            }
 
-                if let TransactionSimulationResult {
-                    result: Err(err),
-                    logs,
-                    post_simulation_accounts: _,
-                    units_consumed,
-                    loaded_accounts_data_size,
-                    return_data,
-                    inner_instructions: _, // Always `None` due to `enable_cpi_recording = false`
-                    fee,
-                    pre_balances: _,
-                    post_balances: _,
-                    pre_token_balances: _,
-                    post_token_balances: _,
-                } = preflight_bank.simulate_transaction(&transaction, false)
-                {
-                    match err {
-                        TransactionError::BlockhashNotFound => {
-                            inc_new_counter_info!("rpc-send-tx_err-blockhash-not-found", 1);
-                        }
-                        _ => {
-                            inc_new_counter_info!("rpc-send-tx_err-other", 1);
-                        }
-                    }
-                    return Err(RpcCustomError::SendTransactionPreflightFailure {
-                        message: format!("Transaction simulation failed: {err}"),
-                        result: RpcSimulateTransactionResult {
-                            err: Some(err.into()),
-                            logs: Some(logs),
-                            accounts: None,
-                            units_consumed: Some(units_consumed),
-                            loaded_accounts_data_size: Some(loaded_accounts_data_size),
-                            return_data: return_data.map(|return_data| return_data.into()),
-                            inner_instructions: None,
-                            replacement_blockhash: None,
-                            fee,
-                            pre_balances: None,
-                            post_balances: None,
-                            pre_token_balances: None,
-                            post_token_balances: None,
-                            loaded_addresses: None,
-                        },
-                    }
-                    .into());
-                }
+            // The node health is checked at most once for the whole batch
+            let health = OnceLock::new();
+            let check_health = || {
+                health
+                    .get_or_init(|| {
+                        check_preflight_health(
+                            &meta.health,
+                            meta.config.skip_preflight_health_check,
+                        )
+                    })
+                    .clone()
+            };
+            // Preflight runs on a dedicated pool so that batches cannot tie up the global one
+            let prepared = meta.preflight_thread_pool.install(|| {
+                transactions
+                    .into_par_iter()
+                    .map(|(index, signature, wire_transaction, unsanitized_tx)| {
+                        let transaction_info = prepare_transaction_for_send(
+                            preflight_bank,
+                            unsanitized_tx,
+                            wire_transaction,
+                            skip_preflight,
+                            max_retries,
+                            check_health,
+                        );
+                        (index, signature, transaction_info)
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            for (index, signature, transaction_info) in prepared {
+                results[index] = Some(match transaction_info {
+                    Ok(transaction_info) => RpcSendTransactionBatchResult::Accepted {
+                        signature: _send_transaction(&meta, transaction_info)?,
+                    },
+                    Err(err) => new_send_transaction_batch_error(signature, err),
+                });
             }
+            Ok(results.into_iter().flatten().collect())
+        }
 
-            _send_transaction(
-                meta,
-                message_hash,
-                signature,
-                blockhash,
-                wire_transaction,
-                last_valid_block_height,
-                durable_nonce_info,
-                max_retries,
-            )
+        fn get_transaction_retry_status(
+            &self,
+            meta: Self::Metadata,
+            signature_strs: Vec<String>,
+        ) -> Result<RpcResponse<Vec<Option<RpcTransactionRetryStatus>>>> {
+            debug!(
+                "get_transaction_retry_status rpc request received: {:?}",
+                signature_strs.len()
+            );
+            if signature_strs.len() > MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS {
+                return Err(Error::invalid_params(format!(
+                    "Too many inputs provided; max {MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS}"
+                )));
+            }
+            let signatures = signature_strs
+                .iter()
+                .map(|signature_str| verify_signature(signature_str))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(meta.get_transaction_retry_status(signatures))
         }
 
         fn simulate_transaction(
@@ -3954,6 +4489,10 @@ pub mod rpc_full {
                 accounts: config_accounts,
                 min_context_slot,
                 inner_instructions: enable_cpi_recording,
+                account_overrides: config_account_overrides,
+                sysvar_overrides,
+                trace,
+                cost,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -3975,17 +4514,10 @@ pub mod rpc_full {
                         "sigVerify may not be used with replaceRecentBlockhash",
                     ));
                 }
-                let recent_blockhash = bank.last_blockhash();
-                unsanitized_tx
-                    .message
-                    .set_recent_blockhash(recent_blockhash);
-                let last_valid_block_height = bank
-                    .get_blockhash_last_valid_block_height(&recent_blockhash)
-                    .expect("bank blockhash queue should contain blockhash");
-                blockhash.replace(RpcBlockhash {
-                    blockhash: recent_blockhash.to_string(),
-                    last_valid_block_height,
-                });
+                blockhash.replace(replace_recent_blockhash_for_simulation(
+                    bank,
+                    &mut unsanitized_tx,
+                ));
             }
 
             let transaction =
@@ -3994,34 +4526,33 @@ pub mod rpc_full {
                 verify_transaction(&transaction)?;
             }
 
-            let TransactionSimulationResult {
-                result,
-                logs,
-                post_simulation_accounts,
-                units_consumed,
-                loaded_accounts_data_size,
-                return_data,
-                inner_instructions,
-                fee,
-                pre_balances,
-                post_balances,
-                pre_token_balances,
-                post_token_balances,
-            } = bank.simulate_transaction(&transaction, enable_cpi_recording);
-
-            let account_keys = transaction.message().account_keys();
-            let number_of_accounts = account_keys.len();
+            let account_overrides =
+                get_simulation_account_overrides(bank, config_account_overrides, sysvar_overrides)?;
+            // Requested accounts the transaction does not load are reported in their overridden
+            // state, so keep those before the overrides are handed to the bank
+            let requested_account_overrides = config_accounts
+                .iter()
+                .flat_map(|config_accounts| &config_accounts.addresses)
+                .filter_map(|address| {
+                    let pubkey = Pubkey::from_str(address).ok()?;
+                    let account = account_overrides.get(&pubkey)?.clone();
+                    Some((pubkey, account))
+                })
+                .collect::<HashMap<_, _>>();
+            let mut simulation = bank.simulate_transaction_with_config(
+                &transaction,
+                TransactionSimulationConfig {
+                    enable_cpi_recording,
+                    enable_instruction_trace_recording: trace,
+                    account_overrides,
+                },
+            );
+            let post_simulation_accounts = std::mem::take(&mut simulation.post_simulation_accounts);
 
-            let accounts = if let Some(config_accounts) = config_accounts {
-                let accounts_encoding = config_accounts
-                    .encoding
-                    .unwrap_or(UiAccountEncoding::Base64);
+            let number_of_accounts = transaction.message().account_keys().len();
 
-                if accounts_encoding == UiAccountEncoding::Binary
-                    || accounts_encoding == UiAccountEncoding::Base58
-                {
-                    return Err(Error::invalid_params("base58 encoding not supported"));
-                }
+            let accounts = if let Some(config_accounts) = config_accounts {
+                let accounts_encoding = verify_simulation_accounts_encoding(&config_accounts)?;
 
                 if config_accounts.addresses.len() > number_of_accounts {
                     return Err(Error::invalid_params(format!(
@@ -4029,66 +4560,157 @@ pub mod rpc_full {
                     )));
                 }
 
-                if result.is_err() {
+                if simulation.result.is_err() {
                     Some(vec![None; config_accounts.addresses.len()])
                 } else {
-                    let mut post_simulation_accounts_map = HashMap::new();
-                    for (pubkey, data) in post_simulation_accounts {
-                        post_simulation_accounts_map.insert(pubkey, data);
-                    }
-
-                    Some(
-                        config_accounts
-                            .addresses
-                            .iter()
-                            .map(|address_str| {
-                                let pubkey = verify_pubkey(address_str)?;
-                                get_encoded_account(
-                                    bank,
-                                    &pubkey,
-                                    accounts_encoding,
-                                    None,
-                                    Some(&post_simulation_accounts_map),
-                                )
-                            })
-                            .collect::<Result<Vec<_>>>()?,
-                    )
+                    let mut post_simulation_accounts_map = requested_account_overrides;
+                    post_simulation_accounts_map.extend(post_simulation_accounts);
+
+                    Some(get_encoded_simulation_accounts(
+                        bank,
+                        &config_accounts.addresses,
+                        accounts_encoding,
+                        &post_simulation_accounts_map,
+                    )?)
                 }
             } else {
                 None
             };
 
-            let inner_instructions = inner_instructions.map(|info| {
-                map_inner_instructions(info)
-                    .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
-                    .collect()
+            let cost = cost.then(|| {
+                let working_bank = meta.bank_forks.read().unwrap().working_bank();
+                new_rpc_transaction_cost(&transaction, &bank.feature_set, &working_bank)
             });
 
             Ok(new_response(
                 bank,
                 RpcSimulateTransactionResult {
-                    err: result.err().map(Into::into),
-                    logs: Some(logs),
-                    accounts,
-                    units_consumed: Some(units_consumed),
-                    loaded_accounts_data_size: Some(loaded_accounts_data_size),
-                    return_data: return_data.map(|return_data| return_data.into()),
-                    inner_instructions,
-                    replacement_blockhash: blockhash,
-                    fee,
-                    pre_balances,
-                    post_balances,
-                    pre_token_balances: pre_token_balances.map(|balances| {
-                        balances.into_iter().map(|balance| solana_ledger::transaction_balances::svm_token_info_to_token_balance(balance).into()).collect()
-                    }),
-                    post_token_balances: post_token_balances.map(|balances| {
-                        balances.into_iter().map(|balance| solana_ledger::transaction_balances::svm_token_info_to_token_balance(balance).into()).collect()
-                    }),
-                    loaded_addresses: Some(UiLoadedAddresses::from(&transaction.get_loaded_addresses())),
+                    cost,
+                    ..new_rpc_simulate_transaction_result(
+                        &transaction,
+                        simulation,
+                        accounts,
+                        blockhash,
+                    )
                 },
             ))
         }
 
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<Vec<RpcSimulateTransactionResult>>> {
+            debug!("simulate_bundle rpc request received");
+            let RpcSimulateBundleConfig {
+                sig_verify,
+                replace_recent_blockhash,
+                commitment,
+                encoding,
+                accounts: config_accounts,
+                min_context_slot,
+                inner_instructions: enable_cpi_recording,
+            } = config.unwrap_or_default();
+            if data.is_empty() {
+                return Err(Error::invalid_params("No transactions provided"));
+            }
+            if data.len() > MAX_SIMULATE_BUNDLE_TRANSACTIONS {
+                return Err(Error::invalid_params(format!(
+                    "Too many transactions provided; max {MAX_SIMULATE_BUNDLE_TRANSACTIONS}"
+                )));
+            }
+            if replace_recent_blockhash && sig_verify {
+                return Err(Error::invalid_params(
+                    "sigVerify may not be used with replaceRecentBlockhash",
+                ));
+            }
+            let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
+            let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "unsupported encoding: {tx_encoding}. Supported encodings: base58, base64"
+                ))
+            })?;
+            let config_accounts = config_accounts
+                .map(|config_accounts| {
+                    let accounts_encoding = verify_simulation_accounts_encoding(&config_accounts)?;
+                    if config_accounts.addresses.len() > MAX_MULTIPLE_ACCOUNTS {
+                        return Err(Error::invalid_params(format!(
+                            "Too many accounts provided; max {MAX_MULTIPLE_ACCOUNTS}"
+                        )));
+                    }
+                    Ok((config_accounts.addresses, accounts_encoding))
+                })
+                .transpose()?;
+
+            let bank = &*meta.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot,
+            })?;
+            let mut blockhash: Option<RpcBlockhash> = None;
+            let transactions = data
+                .into_iter()
+                .map(|data| {
+                    let (_, mut unsanitized_tx) =
+                        decode_and_deserialize::<VersionedTransaction>(data, binary_encoding)?;
+                    if replace_recent_blockhash {
+                        blockhash.replace(replace_recent_blockhash_for_simulation(
+                            bank,
+                            &mut unsanitized_tx,
+                        ));
+                    }
+                    let transaction = sanitize_transaction(
+                        unsanitized_tx,
+                        bank,
+                        bank.get_reserved_account_keys(),
+                    )?;
+                    if sig_verify {
+                        verify_transaction(&transaction)?;
+                    }
+                    Ok(transaction)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let simulations = bank.simulate_transactions(&transactions, enable_cpi_recording);
+
+            // The accounts written by each transaction, as seen by the next ones
+            let mut post_simulation_accounts_map = HashMap::new();
+            let results = transactions
+                .iter()
+                .zip(simulations)
+                .map(|(transaction, mut simulation)| {
+                    let post_simulation_accounts =
+                        std::mem::take(&mut simulation.post_simulation_accounts);
+                    let accounts = match &config_accounts {
+                        Some((addresses, _)) if simulation.result.is_err() => {
+                            // A failed transaction still charges its fee and advances its nonce
+                            post_simulation_accounts_map
+                                .extend(std::mem::take(&mut simulation.rollback_accounts));
+                            Some(vec![None; addresses.len()])
+                        }
+                        Some((addresses, accounts_encoding)) => {
+                            post_simulation_accounts_map.extend(post_simulation_accounts);
+                            Some(get_encoded_simulation_accounts(
+                                bank,
+                                addresses,
+                                *accounts_encoding,
+                                &post_simulation_accounts_map,
+                            )?)
+                        }
+                        None => None,
+                    };
+                    Ok(new_rpc_simulate_transaction_result(
+                        transaction,
+                        simulation,
+                        accounts,
+                        blockhash.clone(),
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(new_response(bank, results))
+        }
+
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot> {
             debug!("minimum_ledger_slot rpc request received");
             meta.minimum_ledger_slot()
@@ -4402,6 +5024,278 @@ fn sanitize_transaction(
     .map_err(|err| Error::invalid_params(format!("invalid transaction: {err}")))
 }
 
+/// Replaces the recent blockhash of a transaction to simulate with the bank's latest blockhash
+fn replace_recent_blockhash_for_simulation(
+    bank: &Bank,
+    transaction: &mut VersionedTransaction,
+) -> RpcBlockhash {
+    let recent_blockhash = bank.last_blockhash();
+    transaction.message.set_recent_blockhash(recent_blockhash);
+    let last_valid_block_height = bank
+        .get_blockhash_last_valid_block_height(&recent_blockhash)
+        .expect("bank blockhash queue should contain blockhash");
+    RpcBlockhash {
+        blockhash: recent_blockhash.to_string(),
+        last_valid_block_height,
+    }
+}
+
+fn verify_simulation_accounts_encoding(
+    config_accounts: &RpcSimulateTransactionAccountsConfig,
+) -> Result<UiAccountEncoding> {
+    let accounts_encoding = config_accounts
+        .encoding
+        .unwrap_or(UiAccountEncoding::Base64);
+
+    if accounts_encoding == UiAccountEncoding::Binary
+        || accounts_encoding == UiAccountEncoding::Base58
+    {
+        return Err(Error::invalid_params("base58 encoding not supported"));
+    }
+    Ok(accounts_encoding)
+}
+
+/// Encodes the requested accounts as they are after a simulation, falling back to the bank for
+/// accounts the simulation did not load
+fn get_encoded_simulation_accounts(
+    bank: &Bank,
+    addresses: &[String],
+    accounts_encoding: UiAccountEncoding,
+    post_simulation_accounts: &HashMap<Pubkey, AccountSharedData>,
+) -> Result<Vec<Option<UiAccount>>> {
+    addresses
+        .iter()
+        .map(|address_str| {
+            let pubkey = verify_pubkey(address_str)?;
+            get_encoded_account(
+                bank,
+                &pubkey,
+                accounts_encoding,
+                None,
+                Some(post_simulation_accounts),
+            )
+        })
+        .collect()
+}
+
+/// Builds the accounts which replace the bank's during a simulation from the `accountOverrides`
+/// and `sysvarOverrides` of its config
+fn get_simulation_account_overrides(
+    bank: &Bank,
+    config_account_overrides: Option<HashMap<String, RpcAccountOverride>>,
+    sysvar_overrides: Option<RpcSysvarOverrides>,
+) -> Result<AccountOverrides> {
+    let mut account_overrides = AccountOverrides::default();
+    let config_account_overrides = config_account_overrides.unwrap_or_default();
+    if config_account_overrides.len() > MAX_MULTIPLE_ACCOUNTS {
+        return Err(Error::invalid_params(format!(
+            "Too many account overrides provided; max {MAX_MULTIPLE_ACCOUNTS}"
+        )));
+    }
+    for (address, account_override) in config_account_overrides {
+        let pubkey = verify_pubkey(&address)?;
+        let RpcAccountOverride {
+            lamports,
+            owner,
+            data,
+            executable,
+        } = account_override;
+        // The program cache is keyed by address and does not see overrides, so programs and the
+        // accounts holding their data can not be replaced, nor can new ones be created
+        let program_account_error = || {
+            Error::invalid_params(format!(
+                "Invalid account override {address}; program accounts can not be overridden"
+            ))
+        };
+        let mut account = bank.get_account(&pubkey).unwrap_or_default();
+        if is_program_account(&account) {
+            return Err(program_account_error());
+        }
+        if let Some(lamports) = lamports {
+            account.set_lamports(lamports);
+        }
+        if let Some(owner) = owner {
+            account.set_owner(verify_pubkey(&owner)?);
+        }
+        if let Some(data) = data {
+            let data = data.decode().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "Invalid data for account override {address}; only binary encodings are \
+                     supported"
+                ))
+            })?;
+            account.set_data_from_slice(&data);
+        }
+        if let Some(executable) = executable {
+            account.set_executable(executable);
+        }
+        if is_program_account(&account) {
+            return Err(program_account_error());
+        }
+        account_overrides.set_account(&pubkey, Some(account));
+    }
+
+    if let Some(clock_override) =
+        sysvar_overrides.and_then(|sysvar_overrides| sysvar_overrides.clock)
+    {
+        let clock_id = solana_sysvar::clock::id();
+        let mut account = account_overrides
+            .get(&clock_id)
+            .cloned()
+            .or_else(|| bank.get_account(&clock_id))
+            .ok_or_else(|| Error::invalid_params("Clock sysvar account not found"))?;
+        let mut clock: Clock = bincode::deserialize(account.data())
+            .map_err(|_| Error::invalid_params("Invalid Clock sysvar account data"))?;
+        if let Some(slot) = clock_override.slot {
+            clock.slot = slot;
+        }
+        if let Some(unix_timestamp) = clock_override.unix_timestamp {
+            clock.unix_timestamp = unix_timestamp;
+        }
+        account.set_data_from_slice(&serialize(&clock).unwrap());
+        account_overrides.set_account(&clock_id, Some(account));
+    }
+    Ok(account_overrides)
+}
+
+fn is_program_account(account: &AccountSharedData) -> bool {
+    account.executable() || PROGRAM_OWNERS.contains(account.owner())
+}
+
+fn new_rpc_simulate_transaction_result(
+    transaction: &RuntimeTransaction<SanitizedTransaction>,
+    simulation: TransactionSimulationResult,
+    accounts: Option<Vec<Option<UiAccount>>>,
+    replacement_blockhash: Option<RpcBlockhash>,
+) -> RpcSimulateTransactionResult {
+    let TransactionSimulationResult {
+        result,
+        logs,
+        post_simulation_accounts: _,
+        rollback_accounts: _,
+        units_consumed,
+        loaded_accounts_data_size,
+        return_data,
+        inner_instructions,
+        fee,
+        pre_balances,
+        post_balances,
+        pre_token_balances,
+        post_token_balances,
+        instruction_trace,
+    } = simulation;
+
+    let account_keys = transaction.message().account_keys();
+    let inner_instructions = inner_instructions.map(|info| {
+        map_inner_instructions(info)
+            .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
+            .collect()
+    });
+    let key_string = |index: u16| {
+        account_keys
+            .get(usize::from(index))
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    };
+    let trace = instruction_trace.map(|instruction_trace| {
+        instruction_trace
+            .into_iter()
+            .map(|instruction| RpcInstructionTrace {
+                stack_height: instruction.stack_height as u32,
+                program_id: key_string(instruction.program_account_index),
+                compute_units_consumed: instruction.compute_units_consumed,
+                accounts: instruction
+                    .accounts
+                    .into_iter()
+                    .map(|account| RpcInstructionTraceAccount {
+                        pubkey: key_string(account.index_in_transaction),
+                        writable: account.is_writable,
+                        written: account.is_written,
+                        pre_data_len: account.pre_data_len as u64,
+                        post_data_len: account.post_data_len as u64,
+                    })
+                    .collect(),
+            })
+            .collect()
+    });
+
+    RpcSimulateTransactionResult {
+        err: result.err().map(Into::into),
+        logs: Some(logs),
+        accounts,
+        units_consumed: Some(units_consumed),
+        loaded_accounts_data_size: Some(loaded_accounts_data_size),
+        return_data: return_data.map(|return_data| return_data.into()),
+        inner_instructions,
+        replacement_blockhash,
+        fee,
+        pre_balances,
+        post_balances,
+        pre_token_balances: pre_token_balances.map(|balances| {
+            balances
+                .into_iter()
+                .map(|balance| svm_token_info_to_token_balance(balance).into())
+                .collect()
+        }),
+        post_token_balances: post_token_balances.map(|balances| {
+            balances
+                .into_iter()
+                .map(|balance| svm_token_info_to_token_balance(balance).into())
+                .collect()
+        }),
+        loaded_addresses: Some(UiLoadedAddresses::from(&transaction.get_loaded_addresses())),
+        trace,
+        cost: None,
+    }
+}
+
+fn new_rpc_transaction_cost(
+    transaction: &RuntimeTransaction<SanitizedTransaction>,
+    feature_set: &FeatureSet,
+    working_bank: &Bank,
+) -> RpcTransactionCost {
+    let transaction_cost = CostModel::calculate_cost(transaction, feature_set);
+    let cost_tracker = working_bank.read_cost_tracker().unwrap();
+    let account_cost_limit = cost_tracker.get_account_limit();
+    let cost_by_writable_accounts = cost_tracker.get_cost_by_writable_accounts();
+    let writable_accounts = transaction_cost
+        .writable_accounts()
+        .map(|pubkey| {
+            let cost = cost_by_writable_accounts
+                .get(pubkey)
+                .copied()
+                .unwrap_or_default();
+            RpcWritableAccountCost {
+                pubkey: pubkey.to_string(),
+                cost,
+                remaining_cost: account_cost_limit.saturating_sub(cost),
+            }
+        })
+        .collect();
+
+    RpcTransactionCost {
+        signature_cost: transaction_cost.signature_cost(),
+        write_lock_cost: transaction_cost.write_lock_cost(),
+        data_bytes_cost: u64::from(transaction_cost.data_bytes_cost()),
+        loaded_accounts_data_size_cost: transaction_cost.loaded_accounts_data_size_cost(),
+        programs_execution_cost: transaction_cost.programs_execution_cost(),
+        total_cost: transaction_cost.sum(),
+        allocated_accounts_data_size: transaction_cost.allocated_accounts_data_size(),
+        is_simple_vote: transaction_cost.is_simple_vote(),
+        slot: working_bank.slot(),
+        block_cost_limit: cost_tracker.get_block_limit(),
+        remaining_block_cost: cost_tracker
+            .get_block_limit()
+            .saturating_sub(cost_tracker.block_cost()),
+        vote_cost_limit: cost_tracker.get_vote_limit(),
+        remaining_vote_cost: cost_tracker
+            .get_vote_limit()
+            .saturating_sub(cost_tracker.vote_cost()),
+        account_cost_limit,
+        writable_accounts,
+    }
+}
+
 pub fn create_validator_exit(exit: Arc<AtomicBool>) -> Arc<RwLock<Exit>> {
     let mut validator_exit = Exit::default();
     validator_exit.register_exit(Box::new(move || exit.store(true, Ordering::Relaxed)));
@@ -4510,7 +5404,11 @@ pub mod tests {
         jsonrpc_core_client::transports::local,
         serde::de::DeserializeOwned,
         solana_account::{Account, WritableAccount},
-        solana_accounts_db::accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
+        solana_account_decoder::parse_account_data::ParsedAccount,
+        solana_accounts_db::{
+            accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
+            accounts_index::MemcmpIndex,
+        },
         solana_address_lookup_table_interface::{
             self as address_lookup_table,
             state::{AddressLookupTable, LookupTableMeta},
@@ -4541,6 +5439,7 @@ pub mod tests {
         solana_rpc_client_api::{
             custom_error::{
                 JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -5967,32 +6866,294 @@ pub mod tests {
     }
 
     #[test]
-    fn test_rpc_simulate_transaction() {
+    fn test_rpc_get_program_accounts_page() {
         let rpc = RpcHandler::start();
         let bank = rpc.working_bank();
-        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
-        let recent_blockhash = bank.confirmed_last_blockhash();
-        let RpcHandler {
-            ref meta, ref io, ..
-        } = rpc;
 
-        let bob_pubkey = solana_pubkey::new_rand();
-        let mut tx = system_transaction::transfer(
-            &rpc.mint_keypair,
-            &bob_pubkey,
-            rent_exempt_amount,
-            recent_blockhash,
+        let program_id = Pubkey::new_unique();
+        let mut program_account_keys = (0..5)
+            .map(|lamports| {
+                let pubkey = Pubkey::new_unique();
+                bank.store_account(&pubkey, &AccountSharedData::new(lamports, 0, &program_id));
+                pubkey
+            })
+            .collect::<Vec<_>>();
+        program_account_keys.sort();
+
+        // Walk the pages, which together hold every account in pubkey order
+        let mut cursor = None;
+        let mut pages = vec![];
+        loop {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"pageSize": 2, "cursor": cursor},
+                ])),
+            );
+            let result: RpcResponse<RpcKeyedAccountsPage> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert_eq!(result.context.slot, bank.slot());
+            pages.push(
+                result
+                    .value
+                    .accounts
+                    .into_iter()
+                    .map(|keyed_account| Pubkey::from_str(&keyed_account.pubkey).unwrap())
+                    .collect::<Vec<_>>(),
+            );
+            cursor = result.value.cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(
+            pages,
+            program_account_keys
+                .chunks(2)
+                .map(|chunk| chunk.to_vec())
+                .collect::<Vec<_>>()
         );
-        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
-        tx.signatures[0] = Signature::default();
-        let tx_badsig_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
-        tx.message.recent_blockhash = Hash::default();
-        let tx_invalid_recent_blockhash = bs58::encode(serialize(&tx).unwrap()).into_string();
 
-        // Simulation bank must be frozen
-        bank.freeze();
+        // Filters apply before paging
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"pageSize": 10, "filters": [{"lamportsRange": {"min": 3}}]},
+            ])),
+        );
+        let result: RpcResponse<RpcKeyedAccountsPage> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.accounts.len(), 2);
+        assert_eq!(result.value.cursor, None);
 
-        let loaded_accounts_data_size = expected_loaded_accounts_data_size(&bank, &tx);
+        // Without the bank of the cursor, pages are not served from an older bank
+        let cursor = ProgramAccountsCursor {
+            slot: bank.slot() + 1,
+            pubkey: program_account_keys[0],
+        };
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([program_id.to_string(), {"cursor": cursor.to_string()}])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED);
+
+        let cursor = ProgramAccountsCursor {
+            slot: bank.slot(),
+            pubkey: program_account_keys[0],
+        };
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"cursor": cursor.to_string(), "minContextSlot": bank.slot() + 1},
+            ])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED);
+
+        for invalid_config in [
+            json!({"pageSize": 0}),
+            json!({"pageSize": MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE + 1}),
+            json!({"cursor": "not a cursor"}),
+            json!({"cursor": program_id.to_string()}),
+        ] {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([program_id.to_string(), invalid_config])),
+            );
+            let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(code, ErrorCode::InvalidParams.code());
+        }
+    }
+
+    #[test]
+    fn test_rpc_get_program_accounts_page_after_root() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let program_id = Pubkey::new_unique();
+        let program_account_keys = (0..4)
+            .map(|lamports| {
+                let pubkey = Pubkey::new_unique();
+                bank.store_account(&pubkey, &AccountSharedData::new(lamports, 0, &program_id));
+                pubkey
+            })
+            .collect::<Vec<_>>();
+        let get_page = |cursor: Option<String>| {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"pageSize": 2, "cursor": cursor},
+                ])),
+            );
+            let result: RpcResponse<RpcKeyedAccountsPage> =
+                parse_success_result(rpc.handle_request_sync(request));
+            let keys = result
+                .value
+                .accounts
+                .into_iter()
+                .map(|keyed_account| Pubkey::from_str(&keyed_account.pubkey).unwrap())
+                .collect::<Vec<_>>();
+            (result.context.slot, keys, result.value.cursor)
+        };
+
+        let (slot, keys, cursor) = get_page(None);
+        assert_eq!(slot, bank.slot());
+        assert_eq!(keys, program_account_keys[..2]);
+
+        // Root a new slot, which prunes the bank of the cursor
+        let new_bank = Bank::new_from_parent(bank.clone(), &Pubkey::default(), bank.slot() + 1);
+        let new_account_key = Pubkey::new_unique();
+        new_bank.store_account(&new_account_key, &AccountSharedData::new(1, 0, &program_id));
+        rpc.bank_forks.write().unwrap().insert(new_bank);
+        rpc.bank_forks
+            .write()
+            .unwrap()
+            .set_root(bank.slot() + 1, None, Some(0))
+            .unwrap();
+        assert!(rpc.bank_forks.read().unwrap().get(bank.slot()).is_none());
+
+        // The walk resumes after the cursor in the new root
+        let (slot, keys, cursor) = get_page(cursor);
+        assert_eq!(slot, bank.slot() + 1);
+        assert_eq!(keys, program_account_keys[2..]);
+        let (slot, keys, cursor) = get_page(cursor);
+        assert_eq!(slot, bank.slot() + 1);
+        assert_eq!(keys, [new_account_key]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_program_accounts_cursor() {
+        let cursor = ProgramAccountsCursor {
+            slot: 42,
+            pubkey: Pubkey::new_unique(),
+        };
+        assert_eq!(
+            cursor.to_string().parse::<ProgramAccountsCursor>(),
+            Ok(cursor)
+        );
+    }
+
+    #[test]
+    fn test_rpc_get_program_accounts_memcmp_index() {
+        // Index nonce accounts by their authority
+        let memcmp_index = MemcmpIndex::new(system_program::id(), 8, PUBKEY_BYTES).unwrap();
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            account_indexes: AccountSecondaryIndexes {
+                keys: None,
+                indexes: HashSet::from([AccountIndex::Memcmp(memcmp_index)]),
+            },
+            ..JsonRpcConfig::default()
+        });
+        let bank = rpc.working_bank();
+
+        let nonce_authorities = (0..2)
+            .map(|_| {
+                let pubkey = Pubkey::new_unique();
+                let authority = Pubkey::new_unique();
+                let account = AccountSharedData::new_data(
+                    42,
+                    &nonce::versions::Versions::new(nonce::state::State::new_initialized(
+                        &authority,
+                        DurableNonce::default(),
+                        1000,
+                    )),
+                    &system_program::id(),
+                )
+                .unwrap();
+                bank.store_account(&pubkey, &account);
+                (pubkey, authority)
+            })
+            .collect::<Vec<_>>();
+
+        let accounts_index = &bank.accounts().accounts_db.accounts_index;
+        for (_pubkey, authority) in &nonce_authorities {
+            assert_eq!(
+                accounts_index.get_index_key_size(&AccountIndex::Memcmp(memcmp_index), authority),
+                Some(1)
+            );
+        }
+
+        // A filter on the indexed bytes is served from the index, and the
+        // remaining filters are still applied
+        for (filters, expected_len) in [
+            (
+                json!([{"memcmp": {"offset": 8, "bytes": nonce_authorities[0].1.to_string()}}]),
+                1,
+            ),
+            (
+                json!([
+                    {"memcmp": {"offset": 8, "bytes": nonce_authorities[1].1.to_string()}},
+                    {"dataSize": nonce::state::State::size()},
+                ]),
+                1,
+            ),
+            (
+                json!([
+                    {"memcmp": {"offset": 8, "bytes": nonce_authorities[1].1.to_string()}},
+                    {"dataSize": 1},
+                ]),
+                0,
+            ),
+            (
+                json!([{"memcmp": {"offset": 8, "bytes": Pubkey::new_unique().to_string()}}]),
+                0,
+            ),
+        ] {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([system_program::id().to_string(), {"filters": filters}])),
+            );
+            let result: Vec<RpcKeyedAccount> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert_eq!(result.len(), expected_len);
+        }
+
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{
+                    "memcmp": {"offset": 8, "bytes": nonce_authorities[0].1.to_string()},
+                }]},
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result[0].pubkey, nonce_authorities[0].0.to_string());
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        let bob_pubkey = solana_pubkey::new_rand();
+        let mut tx = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+        tx.signatures[0] = Signature::default();
+        let tx_badsig_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+        tx.message.recent_blockhash = Hash::default();
+        let tx_invalid_recent_blockhash = bs58::encode(serialize(&tx).unwrap()).into_string();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let loaded_accounts_data_size = expected_loaded_accounts_data_size(&bank, &tx);
 
         // Good signature with sigVerify=true
         let req = format!(
@@ -6655,6 +7816,467 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_simulate_bundle() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        // alice can only pay bob once she has been funded by the first transaction
+        let alice = Keypair::new();
+        let bob_pubkey = Pubkey::new_unique();
+        let fund_alice = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &alice.pubkey(),
+            2 * rent_exempt_amount + 5000,
+            recent_blockhash,
+        );
+        let pay_bob =
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let fund_alice_encoded = bs58::encode(serialize(&fund_alice).unwrap()).into_string();
+        let pay_bob_encoded = bs58::encode(serialize(&pay_bob).unwrap()).into_string();
+
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateBundle",
+                 "params":[
+                   ["{fund_alice_encoded}", "{pay_bob_encoded}"],
+                   {{
+                     "sigVerify": true,
+                     "accounts": {{
+                       "addresses": ["{}", "{bob_pubkey}"]
+                     }}
+                   }}
+                 ]
+            }}"#,
+            alice.pubkey(),
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let results = result["result"]["value"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["err"], Value::Null);
+        assert_eq!(results[1]["err"], Value::Null);
+        assert_eq!(results[0]["fee"], 5000);
+        assert_eq!(results[1]["fee"], 5000);
+
+        // the requested accounts are returned as they are after each transaction
+        let lamports = |index: usize| -> Vec<Option<u64>> {
+            results[index]["accounts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|account| account["lamports"].as_u64())
+                .collect()
+        };
+        assert_eq!(lamports(0), [Some(2 * rent_exempt_amount + 5000), None]);
+        assert_eq!(
+            lamports(1),
+            [Some(rent_exempt_amount), Some(rent_exempt_amount)]
+        );
+
+        // nothing was committed to the bank
+        assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+        assert_eq!(bank.get_balance(&bob_pubkey), 0);
+
+        // alice is still charged the fee of a failed transfer, which later results reflect even
+        // when the transactions after it do not load her account
+        let overpay_bob = system_transaction::transfer(
+            &alice,
+            &bob_pubkey,
+            3 * rent_exempt_amount,
+            recent_blockhash,
+        );
+        let fund_bob = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateBundle",
+                 "params":[
+                   ["{fund_alice_encoded}", "{}", "{}"],
+                   {{
+                     "accounts": {{
+                       "addresses": ["{}", "{bob_pubkey}"]
+                     }}
+                   }}
+                 ]
+            }}"#,
+            bs58::encode(serialize(&overpay_bob).unwrap()).into_string(),
+            bs58::encode(serialize(&fund_bob).unwrap()).into_string(),
+            alice.pubkey(),
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let results = result["result"]["value"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_ne!(results[1]["err"], Value::Null);
+        assert_eq!(results[2]["err"], Value::Null);
+        let lamports = |index: usize| -> Vec<Option<u64>> {
+            results[index]["accounts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|account| account["lamports"].as_u64())
+                .collect()
+        };
+        assert_eq!(
+            lamports(2),
+            [Some(2 * rent_exempt_amount), Some(rent_exempt_amount)]
+        );
+
+        // Too many transactions
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"simulateBundle","params":[[{}]]}}"#,
+            vec![format!("\"{pay_bob_encoded}\""); MAX_SIMULATE_BUNDLE_TRANSACTIONS + 1].join(","),
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let expected = json!({
+            "jsonrpc":"2.0",
+            "error": {
+                "code": error::ErrorCode::InvalidParams.code(),
+                "message": format!(
+                    "Too many transactions provided; max {MAX_SIMULATE_BUNDLE_TRANSACTIONS}"
+                ),
+            },
+            "id":1
+        });
+        let expected: Response =
+            serde_json::from_value(expected).expect("expected response deserialization");
+        let result: Response = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_with_overrides() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        // alice only has funds in the overridden state
+        let alice = Keypair::new();
+        let bob_pubkey = Pubkey::new_unique();
+        let pay_bob =
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let pay_bob_encoded = bs58::encode(serialize(&pay_bob).unwrap()).into_string();
+        // carol is not loaded by the transaction but is still reported as overridden
+        let carol_pubkey = Pubkey::new_unique();
+
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":[
+                   "{pay_bob_encoded}",
+                   {{
+                     "sigVerify": true,
+                     "accounts": {{
+                       "addresses": ["{}", "{bob_pubkey}", "{carol_pubkey}"]
+                     }},
+                     "accountOverrides": {{
+                       "{}": {{
+                         "lamports": {},
+                         "owner": "{}"
+                       }},
+                       "{carol_pubkey}": {{
+                         "lamports": 42
+                       }}
+                     }}
+                   }}
+                 ]
+            }}"#,
+            alice.pubkey(),
+            alice.pubkey(),
+            2 * rent_exempt_amount + 5000,
+            system_program::id(),
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], Value::Null);
+        let lamports: Vec<_> = value["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|account| account["lamports"].as_u64())
+            .collect();
+        assert_eq!(
+            lamports,
+            [Some(rent_exempt_amount), Some(rent_exempt_amount), Some(42)]
+        );
+
+        // nothing was committed to the bank
+        assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+        assert_eq!(bank.get_balance(&bob_pubkey), 0);
+        assert_eq!(bank.get_balance(&carol_pubkey), 0);
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_with_trace() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta,
+            ref io,
+            ref mint_keypair,
+            ..
+        } = rpc;
+
+        let bob_pubkey = Pubkey::new_unique();
+        let tx = system_transaction::transfer(
+            mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":["{tx_serialized_encoded}", {{"trace": true}}]
+            }}"#,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], Value::Null);
+        let written_account = |pubkey: Pubkey| {
+            json!({
+                "pubkey": pubkey.to_string(),
+                "writable": true,
+                "written": true,
+                "preDataLen": 0,
+                "postDataLen": 0,
+            })
+        };
+        assert_eq!(
+            value["trace"],
+            json!([{
+                "stackHeight": 1,
+                "programId": system_program::id().to_string(),
+                "computeUnitsConsumed": value["unitsConsumed"],
+                "accounts": [
+                    written_account(mint_keypair.pubkey()),
+                    written_account(bob_pubkey),
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_with_cost() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta,
+            ref io,
+            ref mint_keypair,
+            ..
+        } = rpc;
+
+        // Fill part of the working bank's block with a transaction writing to the mint
+        let included_tx =
+            RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+                mint_keypair,
+                &Pubkey::new_unique(),
+                rent_exempt_amount,
+                recent_blockhash,
+            ));
+        let included_cost = CostModel::calculate_cost(&included_tx, &bank.feature_set);
+        bank.write_cost_tracker()
+            .unwrap()
+            .try_add(&included_cost)
+            .unwrap();
+
+        let bob_pubkey = Pubkey::new_unique();
+        let tx = system_transaction::transfer(
+            mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+        let tx_cost = CostModel::calculate_cost(
+            &RuntimeTransaction::from_transaction_for_tests(tx),
+            &bank.feature_set,
+        )
+        .sum();
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":["{tx_serialized_encoded}", {{"cost": true}}]
+            }}"#,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], Value::Null);
+        assert!(value.get("trace").is_none());
+
+        let cost: RpcTransactionCost = serde_json::from_value(value["cost"].clone()).unwrap();
+        assert_eq!(cost.total_cost, tx_cost);
+        assert_eq!(
+            cost.total_cost,
+            cost.signature_cost
+                + cost.write_lock_cost
+                + cost.data_bytes_cost
+                + cost.loaded_accounts_data_size_cost
+                + cost.programs_execution_cost
+        );
+        assert_eq!(cost.signature_cost, included_cost.signature_cost());
+        assert_eq!(cost.write_lock_cost, included_cost.write_lock_cost());
+        assert!(!cost.is_simple_vote);
+        assert_eq!(cost.slot, bank.slot());
+        assert_eq!(
+            cost.remaining_block_cost,
+            cost.block_cost_limit - included_cost.sum()
+        );
+        assert_eq!(cost.remaining_vote_cost, cost.vote_cost_limit);
+        assert_eq!(
+            cost.writable_accounts,
+            vec![
+                RpcWritableAccountCost {
+                    pubkey: mint_keypair.pubkey().to_string(),
+                    cost: included_cost.sum(),
+                    remaining_cost: cost.account_cost_limit - included_cost.sum(),
+                },
+                RpcWritableAccountCost {
+                    pubkey: bob_pubkey.to_string(),
+                    cost: 0,
+                    remaining_cost: cost.account_cost_limit,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_simulation_account_overrides() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let account_overrides = get_simulation_account_overrides(
+            &bank,
+            Some(HashMap::from([(
+                address.to_string(),
+                RpcAccountOverride {
+                    owner: Some(owner.to_string()),
+                    data: Some(UiAccountData::Binary(
+                        BASE64_STANDARD.encode([1, 2, 3]),
+                        UiAccountEncoding::Base64,
+                    )),
+                    executable: Some(false),
+                    ..RpcAccountOverride::default()
+                },
+            )])),
+            Some(RpcSysvarOverrides {
+                clock: Some(RpcClockOverride {
+                    slot: Some(bank.slot() + 100),
+                    unix_timestamp: Some(1_234_567_890),
+                }),
+            }),
+        )
+        .unwrap();
+
+        let account = account_overrides.get(&address).unwrap();
+        assert_eq!(account.lamports(), 0);
+        assert_eq!(account.owner(), &owner);
+        assert_eq!(account.data(), &[1, 2, 3]);
+        assert!(!account.executable());
+
+        let clock_account = account_overrides.get(&solana_sysvar::clock::id()).unwrap();
+        let clock: Clock = bincode::deserialize(clock_account.data()).unwrap();
+        assert_eq!(clock.slot, bank.slot() + 100);
+        assert_eq!(clock.unix_timestamp, 1_234_567_890);
+        assert_eq!(clock.epoch, bank.clock().epoch);
+
+        // parsed account data can not be used for overrides
+        let err = get_simulation_account_overrides(
+            &bank,
+            Some(HashMap::from([(
+                address.to_string(),
+                RpcAccountOverride {
+                    data: Some(UiAccountData::Json(ParsedAccount {
+                        program: "test".to_string(),
+                        parsed: Value::Null,
+                        space: 0,
+                    })),
+                    ..RpcAccountOverride::default()
+                },
+            )])),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, error::ErrorCode::InvalidParams);
+
+        // programs and program data can neither be replaced nor created
+        for (address, account_override) in [
+            (
+                address,
+                RpcAccountOverride {
+                    executable: Some(true),
+                    ..RpcAccountOverride::default()
+                },
+            ),
+            (
+                address,
+                RpcAccountOverride {
+                    owner: Some(bpf_loader_upgradeable::id().to_string()),
+                    ..RpcAccountOverride::default()
+                },
+            ),
+            (
+                system_program::id(),
+                RpcAccountOverride {
+                    lamports: Some(42),
+                    ..RpcAccountOverride::default()
+                },
+            ),
+        ] {
+            let err = get_simulation_account_overrides(
+                &bank,
+                Some(HashMap::from([(address.to_string(), account_override)])),
+                None,
+            )
+            .unwrap_err();
+            assert_eq!(err.code, error::ErrorCode::InvalidParams);
+        }
+    }
+
     #[test]
     #[should_panic(expected = "simulation bank must be frozen")]
     fn test_rpc_simulate_transaction_panic_on_unfrozen_bank() {
@@ -6987,6 +8609,137 @@ pub mod tests {
         rpc_send_transaction_preflight::<TpuClientNextClient>();
     }
 
+    fn rpc_send_transaction_batch<Client: ClientWithCreator>() {
+        let genesis = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let meta =
+            JsonRpcRequestProcessor::new_from_bank::<Client>(bank, SocketAddrSpace::Unspecified);
+        meta.health
+            .stub_set_health_status(Some(RpcHealthStatus::Ok));
+        let bank = meta.bank_forks.read().unwrap().root_bank();
+        // Freeze the bank to prevent a panic in `run_transaction_simulation()`
+        bank.freeze();
+
+        let mut io = MetaIoHandler::default();
+        io.extend_with(rpc_full::FullImpl.to_delegate());
+
+        let transaction = system_transaction::transfer(
+            &genesis.mint_keypair,
+            &genesis.mint_keypair.pubkey(),
+            42,
+            bank.last_blockhash(),
+        );
+        let bad_blockhash_transaction = system_transaction::transfer(
+            &genesis.mint_keypair,
+            &genesis.mint_keypair.pubkey(),
+            42,
+            Hash::default(),
+        );
+        let encode =
+            |transaction: &Transaction| bs58::encode(serialize(transaction).unwrap()).into_string();
+        let req = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransactionBatch",
+            "params": [[
+                encode(&transaction),
+                encode(&transaction),
+                encode(&bad_blockhash_transaction),
+                "37u9WtQpcm6ULa3Vmu7ySnANv",
+            ]],
+        });
+        let res = io.handle_request_sync(&req.to_string(), meta.clone());
+        let json: Value = serde_json::from_str(&res.unwrap()).unwrap();
+        let results: Vec<RpcSendTransactionBatchResult> =
+            serde_json::from_value(json["result"].clone()).unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0],
+            RpcSendTransactionBatchResult::Accepted {
+                signature: transaction.signatures[0].to_string(),
+            }
+        );
+        assert_eq!(
+            results[1],
+            RpcSendTransactionBatchResult::Duplicate {
+                signature: transaction.signatures[0].to_string(),
+            }
+        );
+        let RpcSendTransactionBatchResult::Rejected { signature, error } = &results[2] else {
+            panic!("unexpected result: {:?}", results[2]);
+        };
+        assert_eq!(
+            signature,
+            &Some(bad_blockhash_transaction.signatures[0].to_string())
+        );
+        assert_eq!(error.code, -32002);
+        assert_eq!(
+            error.message,
+            "Transaction simulation failed: Blockhash not found"
+        );
+        assert!(error.data.is_some());
+        let RpcSendTransactionBatchResult::Rejected { signature, error } = &results[3] else {
+            panic!("unexpected result: {:?}", results[3]);
+        };
+        assert_eq!(signature, &None);
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+
+        // The accepted transaction shows up in the retry pool once the send-transaction-service
+        // has broadcast it
+        let req = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTransactionRetryStatus",
+            "params": [[
+                transaction.signatures[0].to_string(),
+                bad_blockhash_transaction.signatures[0].to_string(),
+            ]],
+        });
+        let mut statuses = vec![];
+        for _ in 0..100 {
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            let json: Value = serde_json::from_str(&res.unwrap()).unwrap();
+            statuses = serde_json::from_value::<Vec<Option<RpcTransactionRetryStatus>>>(
+                json["result"]["value"].clone(),
+            )
+            .unwrap();
+            if statuses[0].is_some() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let status = statuses[0].as_ref().expect("transaction in retry pool");
+        assert!(status.send_attempts >= 1);
+        assert_eq!(status.max_retries, None);
+        assert_eq!(status.last_sent_slot, Some(bank.slot()));
+        assert_eq!(status.durable_nonce_account, None);
+        assert_eq!(statuses[1], None);
+
+        // Resubmitting a transaction that is being retried is reported as a duplicate
+        let req = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransactionBatch",
+            "params": [[encode(&transaction)]],
+        });
+        let res = io.handle_request_sync(&req.to_string(), meta);
+        let json: Value = serde_json::from_str(&res.unwrap()).unwrap();
+        assert_eq!(
+            json["result"],
+            json!([{"status": "duplicate", "signature": transaction.signatures[0].to_string()}])
+        );
+    }
+
+    #[test]
+    fn test_rpc_send_transaction_batch_with_connection_cache() {
+        rpc_send_transaction_batch::<ConnectionCacheClient<NullTpuInfo>>();
+    }
+
+    #[test]
+    fn test_rpc_send_transaction_batch_with_tpu_client_next() {
+        rpc_send_transaction_batch::<TpuClientNextClient>();
+    }
+
     #[test]
     fn test_rpc_verify_filter() {
         let filter = RpcFilterType::Memcmp(Memcmp::new(
@@ -0,0 +1,97 @@
+//! The `rpc_http_server` module implements the HTTP transport of the JSON RPC service.
+//!
+//! It serves requests the way `jsonrpc_http_server::ServerBuilder` does when configured by the
+//! JSON RPC service: CORS for any origin with a max age of a day, the request middleware, a
+//! maximum request body size and keep alive. Unlike `ServerBuilder`, which does not expose the
+//! peer address of a connection, it attaches that address to every request so that rate limits
+//! can be keyed on it, see [`peer_addr`].
+
+use {
+    crate::{rpc::JsonRpcRequestProcessor, rpc_rate_limiter::RpcRateLimitMiddleware},
+    jsonrpc_http_server::{
+        cors,
+        hyper::{
+            self,
+            server::conn::AddrStream,
+            service::{make_service_fn, service_fn, Service},
+        },
+        AccessControlAllowOrigin, RequestMiddleware, RestApi, Rpc, ServerHandler,
+    },
+    std::{convert::Infallible, future::Future, net::SocketAddr, pin::Pin, sync::Arc},
+    tokio::runtime::Runtime as TokioRuntime,
+    tokio_util::sync::CancellationToken,
+};
+
+const CORS_MAX_AGE_SECS: u32 = 86400;
+
+pub(crate) type JsonRpc = Rpc<JsonRpcRequestProcessor, RpcRateLimitMiddleware>;
+
+/// Returns the address of the peer that sent `request`
+pub(crate) fn peer_addr(request: &hyper::Request<hyper::Body>) -> Option<SocketAddr> {
+    request.extensions().get::<SocketAddr>().copied()
+}
+
+pub(crate) struct HttpServer {
+    close_handle: CancellationToken,
+    server: Pin<Box<dyn Future<Output = hyper::Result<()>>>>,
+}
+
+impl HttpServer {
+    /// Binds `rpc_addr`, serving requests on `runtime` once [`Self::wait`] is called.
+    pub(crate) fn bind(
+        runtime: &TokioRuntime,
+        rpc_addr: &SocketAddr,
+        rpc: JsonRpc,
+        request_middleware: Arc<dyn RequestMiddleware>,
+        max_request_body_size: usize,
+    ) -> hyper::Result<Self> {
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let peer_addr = conn.remote_addr();
+            let mut handler = ServerHandler::new(
+                rpc.downgrade(),
+                Some(vec![AccessControlAllowOrigin::Any]),
+                Some(CORS_MAX_AGE_SECS),
+                cors::AccessControlAllowHeaders::Any,
+                None,
+                request_middleware.clone(),
+                RestApi::Disabled,
+                None,
+                max_request_body_size,
+                true,
+            );
+            let service = service_fn(move |mut request: hyper::Request<hyper::Body>| {
+                request.extensions_mut().insert(peer_addr);
+                handler.call(request)
+            });
+            async move { Ok::<_, Infallible>(service) }
+        });
+
+        let builder = {
+            let _guard = runtime.enter();
+            hyper::Server::try_bind(rpc_addr)?
+        };
+        let close_handle = CancellationToken::new();
+        let server = builder
+            .http1_keepalive(true)
+            .tcp_nodelay(true)
+            // Recover from accept errors (e.g. too many open files) instead of shutting down the
+            // server
+            .tcp_sleep_on_accept_errors(true)
+            .serve(make_service)
+            .with_graceful_shutdown(close_handle.clone().cancelled_owned());
+        Ok(Self {
+            close_handle,
+            server: Box::pin(server),
+        })
+    }
+
+    /// Returns a handle that stops the server once cancelled.
+    pub(crate) fn close_handle(&self) -> CancellationToken {
+        self.close_handle.clone()
+    }
+
+    /// Serves requests until the server is closed.
+    pub(crate) fn wait(self, runtime: &TokioRuntime) -> hyper::Result<()> {
+        runtime.block_on(self.server)
+    }
+}
@@ -4,12 +4,16 @@ use crate::{rpc_pubsub_service, rpc_subscriptions::RpcSubscriptions};
 use {
     crate::{
         rpc::{check_is_at_least_confirmed, optimize_filters, verify_filters},
-        rpc_pubsub_service::PubSubConfig,
+        rpc_pubsub_service::{
+            ClientConnection, PubSubConfig, MAX_MULTI_ACCOUNT_SUBSCRIPTION_ACCOUNTS,
+            MAX_TRANSACTION_SUBSCRIPTION_FILTER_ACCOUNTS,
+        },
         rpc_subscription_tracker::{
             AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
+            LogsSubscriptionKind, LogsSubscriptionParams, MultiAccountSet,
+            MultiAccountSubscriptionParams, ProgramSubscriptionParams, ReplayId,
             SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionParams,
-            SubscriptionToken,
+            SubscriptionToken, TransactionSubscriptionParams,
         },
     },
     dashmap::DashMap,
@@ -22,12 +26,14 @@ use {
     solana_rpc_client_api::{
         config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcProgramAccountsConfig, RpcRootSubscribeConfig, RpcSignatureSubscribeConfig,
+            RpcSlotSubscribeConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+            RpcTransactionSubscribeConfig, RpcTransactionSubscribeFilter,
         },
         response::{
             Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
-            RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
+            RpcSignatureResult, RpcTransactionUpdate, RpcVersionInfo, RpcVote, SlotInfo,
+            SlotUpdate,
         },
     },
     solana_signature::Signature,
@@ -78,6 +84,41 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification every time the data of any account in a set is changed
+    // Accepts pubkey parameters as base-58 encoded strings
+    #[pubsub(
+        subscription = "multiAccountNotification",
+        subscribe,
+        name = "multiAccountSubscribe"
+    )]
+    fn multi_account_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcKeyedAccount>>,
+        pubkey_strs: Vec<String>,
+        config: Option<RpcAccountInfoConfig>,
+    );
+
+    // Unsubscribe from multi-account notification subscription.
+    #[pubsub(
+        subscription = "multiAccountNotification",
+        unsubscribe,
+        name = "multiAccountUnsubscribe"
+    )]
+    fn multi_account_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
+    // Add accounts to a multi-account notification subscription
+    #[rpc(name = "multiAccountAdd")]
+    fn multi_account_add(&self, id: SubscriptionId, pubkey_strs: Vec<String>) -> Result<usize>;
+
+    // Remove accounts from a multi-account notification subscription
+    #[rpc(name = "multiAccountRemove")]
+    fn multi_account_remove(&self, id: SubscriptionId, pubkey_strs: Vec<String>) -> Result<usize>;
+
     // Get notification every time account data owned by a particular program is changed
     // Accepts pubkey parameter as base-58 encoded string
     #[pubsub(
@@ -156,7 +197,12 @@ pub trait RpcSolPubSub {
 
     // Get notification when slot is encountered
     #[pubsub(subscription = "slotNotification", subscribe, name = "slotSubscribe")]
-    fn slot_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<SlotInfo>);
+    fn slot_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<SlotInfo>,
+        config: Option<RpcSlotSubscribeConfig>,
+    );
 
     // Unsubscribe from slot notification subscription.
     #[pubsub(
@@ -216,6 +262,32 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification every time a transaction passing the filter is processed or confirmed
+    #[pubsub(
+        subscription = "transactionNotification",
+        subscribe,
+        name = "transactionSubscribe"
+    )]
+    fn transaction_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcTransactionUpdate>>,
+        filter: RpcTransactionSubscribeFilter,
+        config: Option<RpcTransactionSubscribeConfig>,
+    );
+
+    // Unsubscribe from transaction notification subscription.
+    #[pubsub(
+        subscription = "transactionNotification",
+        unsubscribe,
+        name = "transactionUnsubscribe"
+    )]
+    fn transaction_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
     // Get notification when vote is encountered
     #[pubsub(subscription = "voteNotification", subscribe, name = "voteSubscribe")]
     fn vote_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<RpcVote>);
@@ -234,7 +306,12 @@ pub trait RpcSolPubSub {
 
     // Get notification when a new root is set
     #[pubsub(subscription = "rootNotification", subscribe, name = "rootSubscribe")]
-    fn root_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<Slot>);
+    fn root_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<Slot>,
+        config: Option<RpcRootSubscribeConfig>,
+    );
 
     // Unsubscribe from slot notification subscription.
     #[pubsub(
@@ -270,6 +347,33 @@ mod internal {
         #[rpc(name = "accountUnsubscribe")]
         fn account_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification every time the data of any account in a set is changed
+        // Accepts pubkey parameters as base-58 encoded strings
+        #[rpc(name = "multiAccountSubscribe")]
+        fn multi_account_subscribe(
+            &self,
+            pubkey_strs: Vec<String>,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from multi-account notification subscription.
+        #[rpc(name = "multiAccountUnsubscribe")]
+        fn multi_account_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
+        // Add accounts to a multi-account notification subscription, returns the number of
+        // accounts now tracked by the subscription
+        #[rpc(name = "multiAccountAdd")]
+        fn multi_account_add(&self, id: SubscriptionId, pubkey_strs: Vec<String>) -> Result<usize>;
+
+        // Remove accounts from a multi-account notification subscription, returns the number of
+        // accounts still tracked by the subscription
+        #[rpc(name = "multiAccountRemove")]
+        fn multi_account_remove(
+            &self,
+            id: SubscriptionId,
+            pubkey_strs: Vec<String>,
+        ) -> Result<usize>;
+
         // Get notification every time account data owned by a particular program is changed
         // Accepts pubkey parameter as base-58 encoded string
         #[rpc(name = "programSubscribe")]
@@ -310,7 +414,7 @@ mod internal {
 
         // Get notification when slot is encountered
         #[rpc(name = "slotSubscribe")]
-        fn slot_subscribe(&self) -> Result<SubscriptionId>;
+        fn slot_subscribe(&self, config: Option<RpcSlotSubscribeConfig>) -> Result<SubscriptionId>;
 
         // Unsubscribe from slot notification subscription.
         #[rpc(name = "slotUnsubscribe")]
@@ -336,6 +440,18 @@ mod internal {
         #[rpc(name = "blockUnsubscribe")]
         fn block_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification every time a transaction passing the filter is processed or confirmed
+        #[rpc(name = "transactionSubscribe")]
+        fn transaction_subscribe(
+            &self,
+            filter: RpcTransactionSubscribeFilter,
+            config: Option<RpcTransactionSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from transaction notification subscription.
+        #[rpc(name = "transactionUnsubscribe")]
+        fn transaction_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get notification when vote is encountered
         #[rpc(name = "voteSubscribe")]
         fn vote_subscribe(&self) -> Result<SubscriptionId>;
@@ -346,7 +462,7 @@ mod internal {
 
         // Get notification when a new root is set
         #[rpc(name = "rootSubscribe")]
-        fn root_subscribe(&self) -> Result<SubscriptionId>;
+        fn root_subscribe(&self, config: Option<RpcRootSubscribeConfig>) -> Result<SubscriptionId>;
 
         // Unsubscribe from slot notification subscription.
         #[rpc(name = "rootUnsubscribe")]
@@ -362,6 +478,8 @@ pub struct RpcSolPubSubImpl {
     config: PubSubConfig,
     subscription_control: SubscriptionControl,
     current_subscriptions: Arc<DashMap<SubscriptionId, SubscriptionToken>>,
+    pending_replays: Arc<DashMap<SubscriptionId, ReplayId>>,
+    client_connection: Option<ClientConnection>,
 }
 
 impl RpcSolPubSubImpl {
@@ -369,15 +487,31 @@ impl RpcSolPubSubImpl {
         config: PubSubConfig,
         subscription_control: SubscriptionControl,
         current_subscriptions: Arc<DashMap<SubscriptionId, SubscriptionToken>>,
+        pending_replays: Arc<DashMap<SubscriptionId, ReplayId>>,
+        client_connection: Option<ClientConnection>,
     ) -> Self {
         Self {
             config,
             subscription_control,
             current_subscriptions,
+            pending_replays,
+            client_connection,
         }
     }
 
     fn subscribe(&self, params: SubscriptionParams) -> Result<SubscriptionId> {
+        if self
+            .client_connection
+            .as_ref()
+            .is_some_and(ClientConnection::is_subscription_limit_reached)
+        {
+            inc_new_counter_info!("rpc-subscription-refused-client-limit-reached", 1);
+            return Err(Error {
+                code: ErrorCode::InvalidRequest,
+                message: "Subscription refused. Client subscription limit reached".into(),
+                data: None,
+            });
+        }
         let token = self
             .subscription_control
             .subscribe(params)
@@ -392,8 +526,36 @@ impl RpcSolPubSubImpl {
         Ok(id)
     }
 
+    /// Subscribes and, if `from_slot` is set, has the buffered notifications starting at that
+    /// slot sent to this connection before any live notification of the subscription.
+    fn subscribe_from(
+        &self,
+        params: SubscriptionParams,
+        from_slot: Option<Slot>,
+    ) -> Result<SubscriptionId> {
+        // Signatures are looked up in the status cache rather than in the replay buffer
+        let needs_replay_buffer =
+            from_slot.is_some() && !matches!(params, SubscriptionParams::Signature(_));
+        if needs_replay_buffer && !self.subscription_control.is_replay_enabled() {
+            return Err(Error {
+                code: ErrorCode::InvalidParams,
+                message: "Invalid Request: fromSlot is not supported, the replay buffer is \
+                          disabled on this node"
+                    .into(),
+                data: None,
+            });
+        }
+        let id = self.subscribe(params.clone())?;
+        if let Some(from_slot) = from_slot {
+            let replay_id = self.subscription_control.replay(&params, id, from_slot);
+            self.pending_replays.insert(id, replay_id);
+        }
+        Ok(id)
+    }
+
     fn unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
         if self.current_subscriptions.remove(&id).is_some() {
+            self.pending_replays.remove(&id);
             Ok(true)
         } else {
             Err(Error {
@@ -404,10 +566,26 @@ impl RpcSolPubSubImpl {
         }
     }
 
+    fn multi_account_params(&self, id: SubscriptionId) -> Result<MultiAccountSubscriptionParams> {
+        match self
+            .current_subscriptions
+            .get(&id)
+            .as_ref()
+            .map(|token| token.params())
+        {
+            Some(SubscriptionParams::MultiAccount(params)) => Ok(params.clone()),
+            _ => Err(Error {
+                code: ErrorCode::InvalidParams,
+                message: "Invalid subscription id.".into(),
+                data: None,
+            }),
+        }
+    }
+
     #[cfg(test)]
     pub fn block_until_processed(&self, rpc_subscriptions: &Arc<RpcSubscriptions>) {
         let (rpc, mut receiver) = rpc_pubsub_service::test_connection(rpc_subscriptions);
-        rpc.slot_subscribe().unwrap();
+        rpc.slot_subscribe(None).unwrap();
         rpc_subscriptions.notify_slot(1, 0, 0);
         receiver.recv();
     }
@@ -421,6 +599,38 @@ fn param<T: FromStr>(param_str: &str, thing: &str) -> Result<T> {
     })
 }
 
+fn multi_account_pubkeys(pubkey_strs: &[String]) -> Result<Vec<Pubkey>> {
+    if pubkey_strs.len() > MAX_MULTI_ACCOUNT_SUBSCRIPTION_ACCOUNTS {
+        return Err(too_many_multi_accounts_error());
+    }
+    pubkey_strs
+        .iter()
+        .map(|pubkey_str| param::<Pubkey>(pubkey_str, "pubkey"))
+        .collect()
+}
+
+/// Parses the addresses of a transaction subscription filter into a sorted, deduplicated list.
+fn filter_pubkeys(pubkey_strs: &[String], thing: &str) -> Result<Vec<Pubkey>> {
+    let mut pubkeys = pubkey_strs
+        .iter()
+        .map(|pubkey_str| param::<Pubkey>(pubkey_str, thing))
+        .collect::<Result<Vec<_>>>()?;
+    pubkeys.sort_unstable();
+    pubkeys.dedup();
+    Ok(pubkeys)
+}
+
+fn too_many_multi_accounts_error() -> Error {
+    Error {
+        code: ErrorCode::InvalidParams,
+        message: format!(
+            "Invalid Request: Too many accounts, maximum is \
+             {MAX_MULTI_ACCOUNT_SUBSCRIPTION_ACCOUNTS}"
+        ),
+        data: None,
+    }
+}
+
 impl RpcSolPubSubInternal for RpcSolPubSubImpl {
     fn account_subscribe(
         &self,
@@ -446,6 +656,48 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn multi_account_subscribe(
+        &self,
+        pubkey_strs: Vec<String>,
+        config: Option<RpcAccountInfoConfig>,
+    ) -> Result<SubscriptionId> {
+        let RpcAccountInfoConfig {
+            encoding,
+            data_slice,
+            commitment,
+            min_context_slot: _, // ignored
+        } = config.unwrap_or_default();
+        let pubkeys = multi_account_pubkeys(&pubkey_strs)?;
+        let params = MultiAccountSubscriptionParams {
+            accounts: Arc::new(MultiAccountSet::new(pubkeys)),
+            commitment: commitment.unwrap_or_default(),
+            data_slice,
+            encoding: encoding.unwrap_or(UiAccountEncoding::Binary),
+        };
+        self.subscribe(SubscriptionParams::MultiAccount(params))
+    }
+
+    fn multi_account_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
+    fn multi_account_add(&self, id: SubscriptionId, pubkey_strs: Vec<String>) -> Result<usize> {
+        let pubkeys = multi_account_pubkeys(&pubkey_strs)?;
+        let params = self.multi_account_params(id)?;
+        let num_accounts = params
+            .accounts
+            .add(pubkeys, MAX_MULTI_ACCOUNT_SUBSCRIPTION_ACCOUNTS)
+            .map_err(|_| too_many_multi_accounts_error())?;
+        self.subscription_control
+            .notify_multi_accounts_added(&params);
+        Ok(num_accounts)
+    }
+
+    fn multi_account_remove(&self, id: SubscriptionId, pubkey_strs: Vec<String>) -> Result<usize> {
+        let pubkeys = multi_account_pubkeys(&pubkey_strs)?;
+        Ok(self.multi_account_params(id)?.accounts.remove(&pubkeys))
+    }
+
     fn program_subscribe(
         &self,
         pubkey_str: String,
@@ -499,9 +751,15 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
                     LogsSubscriptionKind::Single(param::<Pubkey>(&keys[0], "mentions")?)
                 }
             },
-            commitment: config.and_then(|c| c.commitment).unwrap_or_default(),
+            commitment: config
+                .as_ref()
+                .and_then(|c| c.commitment)
+                .unwrap_or_default(),
         };
-        self.subscribe(SubscriptionParams::Logs(params))
+        self.subscribe_from(
+            SubscriptionParams::Logs(params),
+            config.and_then(|c| c.from_slot),
+        )
     }
 
     fn logs_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
@@ -519,15 +777,18 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
             commitment: config.commitment.unwrap_or_default(),
             enable_received_notification: config.enable_received_notification.unwrap_or_default(),
         };
-        self.subscribe(SubscriptionParams::Signature(params))
+        self.subscribe_from(SubscriptionParams::Signature(params), config.from_slot)
     }
 
     fn signature_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
         self.unsubscribe(id)
     }
 
-    fn slot_subscribe(&self) -> Result<SubscriptionId> {
-        self.subscribe(SubscriptionParams::Slot)
+    fn slot_subscribe(&self, config: Option<RpcSlotSubscribeConfig>) -> Result<SubscriptionId> {
+        self.subscribe_from(
+            SubscriptionParams::Slot,
+            config.and_then(|config| config.from_slot),
+        )
     }
 
     fn slot_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
@@ -579,6 +840,50 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn transaction_subscribe(
+        &self,
+        filter: RpcTransactionSubscribeFilter,
+        config: Option<RpcTransactionSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        if !self.config.enable_transaction_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        let config = config.unwrap_or_default();
+        let commitment = config.commitment.unwrap_or_default();
+        if commitment.is_finalized() {
+            return Err(Error::invalid_params(
+                "Method does not support commitment above `confirmed`",
+            ));
+        }
+        let num_accounts = filter.account_include.len()
+            + filter.account_exclude.len()
+            + filter.account_required.len();
+        if num_accounts > MAX_TRANSACTION_SUBSCRIPTION_FILTER_ACCOUNTS {
+            return Err(Error::invalid_params(format!(
+                "Invalid Request: filter accounts limit of \
+                 {MAX_TRANSACTION_SUBSCRIPTION_FILTER_ACCOUNTS} reached"
+            )));
+        }
+        let params = TransactionSubscriptionParams {
+            account_include: filter_pubkeys(&filter.account_include, "account_include")?,
+            account_exclude: filter_pubkeys(&filter.account_exclude, "account_exclude")?,
+            account_required: filter_pubkeys(&filter.account_required, "account_required")?,
+            vote: filter.vote.unwrap_or_default(),
+            failed: filter.failed.unwrap_or(true),
+            encoding: config.encoding.unwrap_or(UiTransactionEncoding::Base64),
+            max_supported_transaction_version: config.max_supported_transaction_version,
+            commitment,
+        };
+        self.subscribe(SubscriptionParams::Transaction(params))
+    }
+
+    fn transaction_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        if !self.config.enable_transaction_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        self.unsubscribe(id)
+    }
+
     fn vote_subscribe(&self) -> Result<SubscriptionId> {
         if !self.config.enable_vote_subscription {
             return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
@@ -593,8 +898,11 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
-    fn root_subscribe(&self) -> Result<SubscriptionId> {
-        self.subscribe(SubscriptionParams::Root)
+    fn root_subscribe(&self, config: Option<RpcRootSubscribeConfig>) -> Result<SubscriptionId> {
+        self.subscribe_from(
+            SubscriptionParams::Root,
+            config.and_then(|config| config.from_slot),
+        )
     }
 
     fn root_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
@@ -622,7 +930,9 @@ mod tests {
         jsonrpc_core::{IoHandler, Response},
         serial_test::serial,
         solana_account::ReadableAccount,
-        solana_account_decoder::{parse_account_data::parse_account_data_v3, UiAccountEncoding},
+        solana_account_decoder::{
+            parse_account_data::parse_account_data_v3, UiAccountEncoding, UiDataSliceConfig,
+        },
         solana_clock::Slot,
         solana_commitment_config::CommitmentConfig,
         solana_hash::Hash,
@@ -752,6 +1062,7 @@ mod tests {
             Some(RpcSignatureSubscribeConfig {
                 commitment: Some(CommitmentConfig::finalized()),
                 enable_received_notification: Some(true),
+                from_slot: None,
             }),
         )
         .unwrap();
@@ -786,6 +1097,7 @@ mod tests {
             Some(RpcSignatureSubscribeConfig {
                 commitment: Some(CommitmentConfig::confirmed()),
                 enable_received_notification: Some(true),
+                from_slot: None,
             }),
         )
         .unwrap();
@@ -1076,6 +1388,268 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_account_subscribe_with_data_slice() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair: alice,
+            ..
+        } = create_genesis_config(10_000);
+
+        let nonce_account = Keypair::new();
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                1, 1,
+            ))),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        rpc.account_subscribe(
+            nonce_account.pubkey().to_string(),
+            Some(RpcAccountInfoConfig {
+                commitment: Some(CommitmentConfig::processed()),
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 4,
+                    length: 8,
+                }),
+                min_context_slot: None,
+            }),
+        )
+        .unwrap();
+        rpc.block_until_processed(&rpc_subscriptions);
+
+        let ixs = system_instruction::create_nonce_account(
+            &alice.pubkey(),
+            &nonce_account.pubkey(),
+            &alice.pubkey(),
+            100,
+        );
+        let message = Message::new(&ixs, Some(&alice.pubkey()));
+        let tx = Transaction::new(&[&alice, &nonce_account], message, blockhash);
+        process_transaction_and_notify(&bank_forks, &tx, &rpc_subscriptions, 1).unwrap();
+
+        let account = bank_forks
+            .read()
+            .unwrap()
+            .get(1)
+            .unwrap()
+            .get_account(&nonce_account.pubkey())
+            .unwrap();
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "accountNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 1 },
+                   "value": {
+                       "owner": system_program::id().to_string(),
+                       "lamports": 100,
+                       "data": [BASE64_STANDARD.encode(&account.data()[4..12]), "base64"],
+                       "executable": false,
+                       "rentEpoch": u64::MAX,
+                       "space": account.data().len(),
+                   },
+               },
+               "subscription": 0,
+           }
+        });
+
+        let response = receiver.recv();
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_multi_account_subscribe() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair: alice,
+            ..
+        } = create_genesis_config(10_000);
+
+        let nonce_account = Keypair::new();
+        let bob = Keypair::new();
+        let carol = Keypair::new();
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                1, 1,
+            ))),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        let sub_id = rpc
+            .multi_account_subscribe(
+                vec![carol.pubkey().to_string()],
+                Some(RpcAccountInfoConfig {
+                    commitment: Some(CommitmentConfig::processed()),
+                    encoding: Some(UiAccountEncoding::Base64),
+                    data_slice: Some(UiDataSliceConfig {
+                        offset: 0,
+                        length: 4,
+                    }),
+                    min_context_slot: None,
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            rpc.multi_account_add(
+                sub_id,
+                vec![nonce_account.pubkey().to_string(), bob.pubkey().to_string()],
+            )
+            .unwrap(),
+            3
+        );
+        assert_eq!(
+            rpc.multi_account_remove(sub_id, vec![carol.pubkey().to_string()])
+                .unwrap(),
+            2
+        );
+        assert!(rpc
+            .multi_account_add(sub_id, vec!["invalid".to_string()])
+            .is_err());
+        assert!(rpc
+            .multi_account_add(SubscriptionId::from(42), vec![bob.pubkey().to_string()])
+            .is_err());
+        rpc.block_until_processed(&rpc_subscriptions);
+
+        let ixs = system_instruction::create_nonce_account(
+            &alice.pubkey(),
+            &nonce_account.pubkey(),
+            &alice.pubkey(),
+            100,
+        );
+        let message = Message::new(&ixs, Some(&alice.pubkey()));
+        let tx = Transaction::new(&[&alice, &nonce_account], message, blockhash);
+        process_transaction_and_notify(&bank_forks, &tx, &rpc_subscriptions, 1).unwrap();
+
+        let account = bank_forks
+            .read()
+            .unwrap()
+            .get(1)
+            .unwrap()
+            .get_account(&nonce_account.pubkey())
+            .unwrap();
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "multiAccountNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 1 },
+                   "value": {
+                       "pubkey": nonce_account.pubkey().to_string(),
+                       "account": {
+                           "owner": system_program::id().to_string(),
+                           "lamports": 100,
+                           "data": [BASE64_STANDARD.encode(&account.data()[..4]), "base64"],
+                           "executable": false,
+                           "rentEpoch": u64::MAX,
+                           "space": account.data().len(),
+                       },
+                   },
+               },
+               "subscription": 0,
+           }
+        });
+
+        let response = receiver.recv();
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+
+        assert!(rpc.multi_account_unsubscribe(sub_id).unwrap());
+        assert!(rpc
+            .multi_account_remove(sub_id, vec![bob.pubkey().to_string()])
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_transaction_subscribe_invalid_params() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+        let (rpc, _receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        let config = RpcTransactionSubscribeConfig {
+            commitment: Some(CommitmentConfig::finalized()),
+            ..RpcTransactionSubscribeConfig::default()
+        };
+        let err = rpc
+            .transaction_subscribe(RpcTransactionSubscribeFilter::default(), Some(config))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+
+        let filter = RpcTransactionSubscribeFilter {
+            account_include: vec!["not a pubkey".to_string()],
+            ..RpcTransactionSubscribeFilter::default()
+        };
+        let err = rpc.transaction_subscribe(filter, None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+
+        let filter = RpcTransactionSubscribeFilter {
+            account_include: vec![
+                Pubkey::new_unique().to_string();
+                MAX_TRANSACTION_SUBSCRIPTION_FILTER_ACCOUNTS + 1
+            ],
+            ..RpcTransactionSubscribeFilter::default()
+        };
+        let err = rpc.transaction_subscribe(filter, None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+
+        // Filters listing the same accounts in a different order share a subscription
+        let (key1, key2) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let filter = |keys: [Pubkey; 2]| RpcTransactionSubscribeFilter {
+            account_include: keys.iter().map(ToString::to_string).collect(),
+            ..RpcTransactionSubscribeFilter::default()
+        };
+        let id1 = rpc
+            .transaction_subscribe(filter([key1, key2]), None)
+            .unwrap();
+        let (rpc2, _receiver2) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+        let id2 = rpc2
+            .transaction_subscribe(filter([key2, key1]), None)
+            .unwrap();
+        assert_eq!(id1, id2);
+    }
+
     #[test]
     #[serial]
     fn test_account_unsubscribe() {
@@ -1265,7 +1839,7 @@ mod tests {
             bank_forks,
         ));
         let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
-        rpc.slot_subscribe().unwrap();
+        rpc.slot_subscribe(None).unwrap();
 
         rpc_subscriptions.notify_slot(0, 0, 0);
 
@@ -1296,7 +1870,7 @@ mod tests {
             bank_forks,
         ));
         let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
-        let sub_id = rpc.slot_subscribe().unwrap();
+        let sub_id = rpc.slot_subscribe(None).unwrap();
 
         rpc_subscriptions.notify_slot(0, 0, 0);
         let response = receiver.recv();
@@ -1316,6 +1890,41 @@ mod tests {
         assert!(rpc.slot_unsubscribe(sub_id).is_ok());
     }
 
+    #[test]
+    #[serial]
+    fn test_subscribe_from_slot_without_replay_buffer() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        // The replay buffer is disabled by default
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::default_with_bank_forks(
+            max_complete_transaction_status_slot,
+            bank_forks,
+        ));
+        let (rpc, _receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        let err = rpc
+            .slot_subscribe(Some(RpcSlotSubscribeConfig { from_slot: Some(0) }))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        let err = rpc
+            .root_subscribe(Some(RpcRootSubscribeConfig { from_slot: Some(0) }))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+
+        // Signatures are resumed from the status cache instead
+        rpc.signature_subscribe(
+            Signature::default().to_string(),
+            Some(RpcSignatureSubscribeConfig {
+                from_slot: Some(0),
+                ..RpcSignatureSubscribeConfig::default()
+            }),
+        )
+        .unwrap();
+        rpc.slot_subscribe(None).unwrap();
+    }
+
     #[test]
     #[serial]
     fn test_vote_subscribe() {
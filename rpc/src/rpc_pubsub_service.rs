@@ -4,9 +4,9 @@ use {
     crate::{
         rpc_pubsub::{RpcSolPubSubImpl, RpcSolPubSubInternal},
         rpc_subscription_tracker::{
-            SubscriptionControl, SubscriptionId, SubscriptionParams, SubscriptionToken,
+            ReplayId, SubscriptionControl, SubscriptionId, SubscriptionParams, SubscriptionToken,
         },
-        rpc_subscriptions::{RpcNotification, RpcSubscriptions},
+        rpc_subscriptions::{ReplayMarker, RpcNotification, RpcSubscriptions},
     },
     dashmap::{mapref::entry::Entry, DashMap},
     jsonrpc_core::IoHandler,
@@ -16,12 +16,12 @@ use {
     solana_time_utils::AtomicInterval,
     std::{
         io,
-        net::SocketAddr,
+        net::{IpAddr, SocketAddr},
         num::NonZeroUsize,
         str,
         sync::{
             atomic::{AtomicU64, AtomicUsize, Ordering},
-            Arc,
+            Arc, Weak,
         },
         thread::{self, Builder, JoinHandle},
     },
@@ -32,18 +32,27 @@ use {
 };
 
 pub const MAX_ACTIVE_SUBSCRIPTIONS: usize = 1_000_000;
+pub const MAX_MULTI_ACCOUNT_SUBSCRIPTION_ACCOUNTS: usize = 10_000;
+pub const MAX_TRANSACTION_SUBSCRIPTION_FILTER_ACCOUNTS: usize = 256;
 pub const DEFAULT_QUEUE_CAPACITY_ITEMS: usize = 10_000_000;
 pub const DEFAULT_TEST_QUEUE_CAPACITY_ITEMS: usize = 100;
 pub const DEFAULT_QUEUE_CAPACITY_BYTES: usize = 256 * 1024 * 1024;
+pub const DEFAULT_TEST_REPLAY_BUFFER_SLOTS: usize = 32;
 pub const DEFAULT_WORKER_THREADS: usize = 1;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PubSubConfig {
     pub enable_block_subscription: bool,
     pub enable_vote_subscription: bool,
+    pub enable_transaction_subscription: bool,
     pub max_active_subscriptions: usize,
+    /// Maximum number of active subscriptions across all connections from one IP address
+    pub max_active_subscriptions_per_ip: Option<usize>,
     pub queue_capacity_items: usize,
     pub queue_capacity_bytes: usize,
+    /// Number of recent slots whose notifications are kept for clients subscribing with
+    /// `fromSlot`; 0 disables the replay buffer
+    pub replay_buffer_slots: usize,
     pub worker_threads: usize,
     pub notification_threads: Option<NonZeroUsize>,
 }
@@ -53,9 +62,12 @@ impl Default for PubSubConfig {
         Self {
             enable_block_subscription: false,
             enable_vote_subscription: false,
+            enable_transaction_subscription: false,
             max_active_subscriptions: MAX_ACTIVE_SUBSCRIPTIONS,
+            max_active_subscriptions_per_ip: None,
             queue_capacity_items: DEFAULT_QUEUE_CAPACITY_ITEMS,
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
+            replay_buffer_slots: 0,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(get_thread_count()),
         }
@@ -67,9 +79,12 @@ impl PubSubConfig {
         Self {
             enable_block_subscription: false,
             enable_vote_subscription: false,
+            enable_transaction_subscription: false,
             max_active_subscriptions: MAX_ACTIVE_SUBSCRIPTIONS,
+            max_active_subscriptions_per_ip: None,
             queue_capacity_items: DEFAULT_TEST_QUEUE_CAPACITY_ITEMS,
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
+            replay_buffer_slots: DEFAULT_TEST_REPLAY_BUFFER_SLOTS,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(2),
         }
@@ -128,6 +143,7 @@ const METRICS_REPORT_INTERVAL_MS: u64 = 10_000;
 #[derive(Default)]
 struct SentNotificationStats {
     num_account: AtomicUsize,
+    num_multi_account: AtomicUsize,
     num_logs: AtomicUsize,
     num_program: AtomicUsize,
     num_signature: AtomicUsize,
@@ -136,6 +152,7 @@ struct SentNotificationStats {
     num_root: AtomicUsize,
     num_vote: AtomicUsize,
     num_block: AtomicUsize,
+    num_transaction: AtomicUsize,
     total_creation_to_queue_time_us: AtomicU64,
     last_report: AtomicInterval,
 }
@@ -150,6 +167,11 @@ impl SentNotificationStats {
                     self.num_account.swap(0, Ordering::Relaxed) as i64,
                     i64
                 ),
+                (
+                    "num_multi_account",
+                    self.num_multi_account.swap(0, Ordering::Relaxed) as i64,
+                    i64
+                ),
                 (
                     "num_logs",
                     self.num_logs.swap(0, Ordering::Relaxed) as i64,
@@ -190,6 +212,11 @@ impl SentNotificationStats {
                     self.num_block.swap(0, Ordering::Relaxed) as i64,
                     i64
                 ),
+                (
+                    "num_transaction",
+                    self.num_transaction.swap(0, Ordering::Relaxed) as i64,
+                    i64
+                ),
                 (
                     "total_creation_to_queue_time_us",
                     self.total_creation_to_queue_time_us
@@ -203,6 +230,7 @@ impl SentNotificationStats {
 
 struct BroadcastHandler {
     current_subscriptions: Arc<DashMap<SubscriptionId, SubscriptionToken>>,
+    pending_replays: Arc<DashMap<SubscriptionId, ReplayId>>,
     sent_stats: Arc<SentNotificationStats>,
 }
 
@@ -215,6 +243,9 @@ fn increment_sent_notification_stats(
         SubscriptionParams::Account(_) => {
             stats.num_account.fetch_add(1, Ordering::Relaxed);
         }
+        SubscriptionParams::MultiAccount(_) => {
+            stats.num_multi_account.fetch_add(1, Ordering::Relaxed);
+        }
         SubscriptionParams::Logs(_) => {
             stats.num_logs.fetch_add(1, Ordering::Relaxed);
         }
@@ -239,6 +270,9 @@ fn increment_sent_notification_stats(
         SubscriptionParams::Block(_) => {
             stats.num_block.fetch_add(1, Ordering::Relaxed);
         }
+        SubscriptionParams::Transaction(_) => {
+            stats.num_transaction.fetch_add(1, Ordering::Relaxed);
+        }
     }
     stats.total_creation_to_queue_time_us.fetch_add(
         notification.created_at.elapsed().as_micros() as u64,
@@ -249,15 +283,37 @@ fn increment_sent_notification_stats(
 }
 
 impl BroadcastHandler {
-    fn new(current_subscriptions: Arc<DashMap<SubscriptionId, SubscriptionToken>>) -> Self {
+    fn new(
+        current_subscriptions: Arc<DashMap<SubscriptionId, SubscriptionToken>>,
+        pending_replays: Arc<DashMap<SubscriptionId, ReplayId>>,
+    ) -> Self {
         let sent_stats = Arc::new(SentNotificationStats::default());
         Self {
             current_subscriptions,
+            pending_replays,
             sent_stats,
         }
     }
 
     fn handle(&self, notification: RpcNotification) -> Result<Option<Arc<String>>, Error> {
+        let pending_replay = self
+            .pending_replays
+            .get(&notification.subscription_id)
+            .map(|replay_id| *replay_id);
+        match notification.replay {
+            // Live notifications sent before the replay completes are part of the replay
+            None if pending_replay.is_some() => return Ok(None),
+            None => (),
+            Some(ReplayMarker::Item(replay_id)) if pending_replay == Some(replay_id) => (),
+            Some(ReplayMarker::Item(_)) => return Ok(None),
+            Some(ReplayMarker::Done(replay_id)) => {
+                self.pending_replays
+                    .remove_if(&notification.subscription_id, |_, pending| {
+                        *pending == replay_id
+                    });
+                return Ok(None);
+            }
+        }
         if let Entry::Occupied(entry) = self
             .current_subscriptions
             .entry(notification.subscription_id)
@@ -282,6 +338,69 @@ impl BroadcastHandler {
     }
 }
 
+type ConnectionSubscriptions = DashMap<SubscriptionId, SubscriptionToken>;
+
+/// The subscriptions of every open connection, grouped by client IP address
+#[derive(Clone, Default)]
+struct ClientConnections(Arc<DashMap<IpAddr, Vec<Weak<ConnectionSubscriptions>>>>);
+
+impl ClientConnections {
+    fn register(
+        &self,
+        ip: IpAddr,
+        subscriptions: &Arc<ConnectionSubscriptions>,
+        max_subscriptions: usize,
+    ) -> ClientConnection {
+        let subscriptions = Arc::downgrade(subscriptions);
+        self.0.entry(ip).or_default().push(subscriptions.clone());
+        ClientConnection {
+            ip,
+            subscriptions,
+            connections: self.clone(),
+            max_subscriptions,
+        }
+    }
+}
+
+/// A connection counted towards the subscription limit of its client IP address
+pub struct ClientConnection {
+    ip: IpAddr,
+    subscriptions: Weak<ConnectionSubscriptions>,
+    connections: ClientConnections,
+    max_subscriptions: usize,
+}
+
+impl ClientConnection {
+    pub(crate) fn is_subscription_limit_reached(&self) -> bool {
+        let num_subscriptions = self
+            .connections
+            .0
+            .get(&self.ip)
+            .map(|connections| {
+                connections
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .map(|subscriptions| subscriptions.len())
+                    .sum()
+            })
+            .unwrap_or_default();
+        num_subscriptions >= self.max_subscriptions
+    }
+}
+
+impl Drop for ClientConnection {
+    fn drop(&mut self) {
+        if let Entry::Occupied(mut entry) = self.connections.0.entry(self.ip) {
+            entry
+                .get_mut()
+                .retain(|subscriptions| !subscriptions.ptr_eq(&self.subscriptions));
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 pub struct TestBroadcastReceiver {
     handler: BroadcastHandler,
@@ -330,18 +449,22 @@ pub fn test_connection(
     subscriptions: &Arc<RpcSubscriptions>,
 ) -> (RpcSolPubSubImpl, TestBroadcastReceiver) {
     let current_subscriptions = Arc::new(DashMap::new());
+    let pending_replays = Arc::new(DashMap::new());
 
     let rpc_impl = RpcSolPubSubImpl::new(
         PubSubConfig {
             enable_block_subscription: true,
             enable_vote_subscription: true,
+            enable_transaction_subscription: true,
             queue_capacity_items: 100,
             ..PubSubConfig::default()
         },
         subscriptions.control().clone(),
         Arc::clone(&current_subscriptions),
+        Arc::clone(&pending_replays),
+        None,
     );
-    let broadcast_handler = BroadcastHandler::new(current_subscriptions);
+    let broadcast_handler = BroadcastHandler::new(current_subscriptions, pending_replays);
     let receiver = TestBroadcastReceiver {
         inner: subscriptions.control().broadcast_receiver(),
         handler: broadcast_handler,
@@ -363,6 +486,8 @@ enum Error {
 
 async fn handle_connection(
     socket: TcpStream,
+    client_ip: IpAddr,
+    client_connections: ClientConnections,
     subscription_control: SubscriptionControl,
     config: PubSubConfig,
     mut tripwire: Tripwire,
@@ -382,15 +507,23 @@ async fn handle_connection(
     let mut broadcast_receiver = subscription_control.broadcast_receiver();
     let mut data = Vec::new();
     let current_subscriptions = Arc::new(DashMap::new());
+    let pending_replays = Arc::new(DashMap::new());
+    let client_connection = config
+        .max_active_subscriptions_per_ip
+        .map(|max_subscriptions| {
+            client_connections.register(client_ip, &current_subscriptions, max_subscriptions)
+        });
 
     let mut json_rpc_handler = IoHandler::new();
     let rpc_impl = RpcSolPubSubImpl::new(
         config,
         subscription_control,
         Arc::clone(&current_subscriptions),
+        Arc::clone(&pending_replays),
+        client_connection,
     );
     json_rpc_handler.extend_with(rpc_impl.to_delegate());
-    let broadcast_handler = BroadcastHandler::new(current_subscriptions);
+    let broadcast_handler = BroadcastHandler::new(current_subscriptions, pending_replays);
     loop {
         // Extra block for dropping `receive_future`.
         {
@@ -461,18 +594,25 @@ async fn listen(
         }
     };
     let counter = TokenCounter::new("rpc_pubsub_connections");
+    let client_connections = ClientConnections::default();
     loop {
         select! {
             result = listener.accept() => match result {
                 Ok((socket, addr)) => {
                     debug!("new client ({addr:?})");
+                    let client_connections = client_connections.clone();
                     let subscription_control = subscription_control.clone();
                     let config = config.clone();
                     let tripwire = tripwire.clone();
                     let counter_token = counter.create_token();
                     tokio::spawn(async move {
                         let handle = handle_connection(
-                            socket, subscription_control, config, tripwire
+                            socket,
+                            addr.ip(),
+                            client_connections,
+                            subscription_control,
+                            config,
+                            tripwire,
                         );
                         match handle.await {
                             Ok(()) => debug!("connection closed ({addr:?})"),
@@ -500,7 +640,7 @@ mod tests {
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
         },
         std::{
-            net::{IpAddr, Ipv4Addr},
+            net::Ipv4Addr,
             sync::{
                 atomic::{AtomicBool, AtomicU64},
                 RwLock,
@@ -530,4 +670,53 @@ mod tests {
         let thread = pubsub_service.thread_hdl.thread();
         assert_eq!(thread.name().unwrap(), "solRpcPubSub");
     }
+
+    #[test]
+    fn test_max_active_subscriptions_per_ip() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            bank_forks,
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            optimistically_confirmed_bank,
+        ));
+        let client_connections = ClientConnections::default();
+        let connect = |ip| {
+            let current_subscriptions = Arc::new(DashMap::new());
+            let client_connection = client_connections.register(ip, &current_subscriptions, 2);
+            RpcSolPubSubImpl::new(
+                PubSubConfig::default_for_tests(),
+                subscriptions.control().clone(),
+                current_subscriptions,
+                Arc::new(DashMap::new()),
+                Some(client_connection),
+            )
+        };
+        let client_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+        let rpc_a = connect(client_ip);
+        let rpc_b = connect(client_ip);
+        rpc_a.slot_subscribe(None).unwrap();
+        rpc_b.root_subscribe(None).unwrap();
+        // The limit is shared by all connections from the same address
+        assert!(rpc_a.slots_updates_subscribe().is_err());
+        assert!(rpc_b.slots_updates_subscribe().is_err());
+        let rpc_c = connect(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+        rpc_c.slots_updates_subscribe().unwrap();
+
+        // Closing a connection frees its subscriptions
+        drop(rpc_b);
+        rpc_a.slots_updates_subscribe().unwrap();
+        assert!(rpc_a.root_subscribe(None).is_err());
+        assert_eq!(client_connections.0.get(&client_ip).unwrap().len(), 1);
+        drop(rpc_a);
+        assert!(!client_connections.0.contains_key(&client_ip));
+    }
 }
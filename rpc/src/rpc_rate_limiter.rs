@@ -0,0 +1,453 @@
+//! The `rpc_rate_limiter` module implements per-client and per-method token bucket rate limits
+//! for the JSON RPC service.
+//!
+//! Every client is granted a bucket of credits that refills at a constant rate. Each call is
+//! charged the cost of its method, so heavy methods drain the bucket faster than light ones.
+//! Individual methods can additionally be capped to a number of calls per second across all
+//! clients. Calls that exceed either limit are rejected with a `RateLimited` error carrying the
+//! number of milliseconds after which the call would be accepted.
+//!
+//! Clients are identified by the address of the TCP connection a request arrived on. Behind a
+//! load balancer, the address it forwards in a header is used instead, provided the connection
+//! comes from one of the configured trusted proxies.
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    dashmap::DashMap,
+    jsonrpc_core::{
+        futures::future::{self, Either, Ready},
+        Call, Middleware, Output,
+    },
+    jsonrpc_http_server::hyper::HeaderMap,
+    solana_rpc_client_api::custom_error::RpcCustomError,
+    std::{
+        collections::{HashMap, HashSet},
+        future::Future,
+        net::IpAddr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+/// Methods charged more than one credit per call by default
+pub const DEFAULT_RPC_RATE_LIMIT_METHOD_COSTS: &[(&str, u64)] = &[
+    ("getBlock", 10),
+    ("getProgramAccounts", 20),
+    ("getSignaturesForAddress", 10),
+];
+pub const DEFAULT_RPC_RATE_LIMIT_CLIENT_IP_HEADER: &str = "X-Forwarded-For";
+/// Buckets of idle clients are dropped once this many clients are tracked
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcRateLimitConfig {
+    /// Credits granted to each client per second
+    pub credits_per_second: u64,
+    /// Maximum number of credits a client can accumulate while idle
+    pub burst_credits: u64,
+    /// Credits charged per call; methods not listed are charged one credit
+    pub method_costs: HashMap<String, u64>,
+    /// Maximum number of calls per second to a method across all clients
+    pub method_quotas: HashMap<String, u64>,
+    /// HTTP header in which trusted proxies forward the client IP address
+    pub client_ip_header: String,
+    /// Addresses of the proxies whose `client_ip_header` is honored. The header is ignored on
+    /// connections from any other address, since clients can set it to anything.
+    pub trusted_proxies: HashSet<IpAddr>,
+}
+
+impl Default for RpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            credits_per_second: 100,
+            burst_credits: 200,
+            method_costs: DEFAULT_RPC_RATE_LIMIT_METHOD_COSTS
+                .iter()
+                .map(|(method, cost)| (method.to_string(), *cost))
+                .collect(),
+            method_quotas: HashMap::default(),
+            client_ip_header: DEFAULT_RPC_RATE_LIMIT_CLIENT_IP_HEADER.to_string(),
+            trusted_proxies: HashSet::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    credits: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u64, now: Instant) -> Self {
+        Self {
+            credits: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, credits_per_second: u64, capacity: u64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.credits =
+            (self.credits + elapsed.as_secs_f64() * credits_per_second as f64).min(capacity as f64);
+        self.last_refill = now;
+    }
+
+    /// Returns how long to wait until `cost` credits are available, if they are not already
+    fn wait_time(&self, cost: u64, credits_per_second: u64) -> Option<Duration> {
+        let missing = cost as f64 - self.credits;
+        (missing > 0.0).then(|| {
+            Duration::try_from_secs_f64(missing / credits_per_second as f64)
+                .unwrap_or(Duration::MAX)
+        })
+    }
+
+    fn is_full(&self, capacity: u64) -> bool {
+        self.credits >= capacity as f64
+    }
+}
+
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    config: RpcRateLimitConfig,
+    clients: DashMap<Option<IpAddr>, TokenBucket>,
+    /// Number of tracked clients at which idle buckets are next dropped
+    sweep_threshold: AtomicUsize,
+    methods: HashMap<String, Mutex<TokenBucket>>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: RpcRateLimitConfig) -> Self {
+        let now = Instant::now();
+        let methods = config
+            .method_quotas
+            .iter()
+            .map(|(method, quota)| (method.clone(), Mutex::new(TokenBucket::new(*quota, now))))
+            .collect();
+        Self {
+            config,
+            clients: DashMap::new(),
+            sweep_threshold: AtomicUsize::new(MAX_TRACKED_CLIENTS),
+            methods,
+        }
+    }
+
+    /// Returns the address of the client that sent a request with `headers` over a connection
+    /// from `peer`
+    ///
+    /// Each proxy appends the address it received the request from to the forwarding header, so
+    /// the hops are walked from the right. The first one that is not a trusted proxy is the
+    /// client; everything to its left was written by the client itself.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let trusted_proxies = &self.config.trusted_proxies;
+        let mut client = peer;
+        if !trusted_proxies.contains(&peer) {
+            return client;
+        }
+        let hops = headers
+            .get_all(self.config.client_ip_header.as_str())
+            .iter()
+            .flat_map(|value| value.to_str().unwrap_or_default().split(','));
+        for hop in hops.rev() {
+            // Nothing left of a malformed hop can be trusted, so the request is attributed to
+            // the last hop that was
+            let Ok(hop) = hop.trim().parse() else {
+                break;
+            };
+            client = hop;
+            if !trusted_proxies.contains(&hop) {
+                break;
+            }
+        }
+        client
+    }
+
+    fn method_cost(&self, method: &str) -> u64 {
+        self.config
+            .method_costs
+            .get(method)
+            .copied()
+            .unwrap_or(1)
+            // A call costing more than the burst would never be accepted
+            .min(self.config.burst_credits)
+    }
+
+    /// Charges a call to `method` by `client`, or returns how long the client should wait
+    /// before retrying it
+    pub fn check(&self, client: Option<IpAddr>, method: &str) -> Result<(), Duration> {
+        self.check_at(client, method, Instant::now())
+    }
+
+    fn check_at(&self, client: Option<IpAddr>, method: &str, now: Instant) -> Result<(), Duration> {
+        let mut method_bucket = match self.methods.get(method) {
+            Some(method_bucket) => {
+                let quota = self.config.method_quotas[method];
+                let mut method_bucket = method_bucket.lock().unwrap();
+                method_bucket.refill(quota, quota, now);
+                if let Some(wait_time) = method_bucket.wait_time(1, quota) {
+                    return Err(wait_time);
+                }
+                Some(method_bucket)
+            }
+            None => None,
+        };
+
+        let RpcRateLimitConfig {
+            credits_per_second,
+            burst_credits,
+            ..
+        } = self.config;
+        if self.clients.len() >= self.sweep_threshold.load(Ordering::Relaxed)
+            && !self.clients.contains_key(&client)
+        {
+            // A full bucket behaves the same as a new one, so forgetting it is free
+            self.clients.retain(|_, bucket| {
+                bucket.refill(credits_per_second, burst_credits, now);
+                !bucket.is_full(burst_credits)
+            });
+            // Sweep again only once as many clients were added as are still tracked, so that
+            // every sweep is paid for by the insertions before it
+            self.sweep_threshold.store(
+                MAX_TRACKED_CLIENTS.max(self.clients.len().saturating_mul(2)),
+                Ordering::Relaxed,
+            );
+        }
+        let cost = self.method_cost(method);
+        let mut client_bucket = self
+            .clients
+            .entry(client)
+            .or_insert_with(|| TokenBucket::new(burst_credits, now));
+        client_bucket.refill(credits_per_second, burst_credits, now);
+        if let Some(wait_time) = client_bucket.wait_time(cost, credits_per_second) {
+            return Err(wait_time);
+        }
+
+        client_bucket.credits -= cost as f64;
+        if let Some(method_bucket) = method_bucket.as_mut() {
+            method_bucket.credits -= 1.0;
+        }
+        Ok(())
+    }
+}
+
+/// Rejects calls that exceed the configured rate limits before they are dispatched
+#[derive(Debug, Default)]
+pub struct RpcRateLimitMiddleware {
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+}
+
+impl RpcRateLimitMiddleware {
+    pub fn new(rate_limiter: Option<Arc<RpcRateLimiter>>) -> Self {
+        Self { rate_limiter }
+    }
+}
+
+impl Middleware<JsonRpcRequestProcessor> for RpcRateLimitMiddleware {
+    type Future = jsonrpc_core::middleware::NoopFuture;
+    type CallFuture = Ready<Option<Output>>;
+
+    fn on_call<F, X>(
+        &self,
+        call: Call,
+        meta: JsonRpcRequestProcessor,
+        next: F,
+    ) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, JsonRpcRequestProcessor) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return Either::Right(next(call, meta));
+        };
+        let (method, id, jsonrpc) = match &call {
+            Call::MethodCall(method_call) => (
+                method_call.method.clone(),
+                Some(method_call.id.clone()),
+                method_call.jsonrpc,
+            ),
+            Call::Notification(notification) => {
+                (notification.method.clone(), None, notification.jsonrpc)
+            }
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        match rate_limiter.check(meta.client_ip(), &method) {
+            Ok(()) => Either::Right(next(call, meta)),
+            Err(wait_time) => {
+                inc_new_counter_info!("rpc-rate-limited", 1);
+                let error = RpcCustomError::RateLimited {
+                    // Round up so that retrying after the hint always succeeds
+                    retry_after_ms: wait_time.as_micros().div_ceil(1000) as u64,
+                };
+                // Notifications have no response, so they are dropped silently
+                Either::Left(future::ready(
+                    id.map(|id| Output::from(Err(error.into()), id, jsonrpc)),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jsonrpc_http_server::hyper::header::HeaderValue,
+        std::net::{Ipv4Addr, Ipv6Addr},
+    };
+
+    fn new_rate_limiter(credits_per_second: u64, burst_credits: u64) -> RpcRateLimiter {
+        RpcRateLimiter::new(RpcRateLimitConfig {
+            credits_per_second,
+            burst_credits,
+            ..RpcRateLimitConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_client_burst_and_refill() {
+        let rate_limiter = new_rate_limiter(10, 5);
+        let client = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert_eq!(rate_limiter.check_at(client, "getSlot", now), Ok(()));
+        }
+        assert_eq!(
+            rate_limiter.check_at(client, "getSlot", now),
+            Err(Duration::from_millis(100))
+        );
+
+        // Other clients have their own bucket
+        let other_client = Some(IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(rate_limiter.check_at(other_client, "getSlot", now), Ok(()));
+        assert_eq!(rate_limiter.check_at(None, "getSlot", now), Ok(()));
+
+        let now = now + Duration::from_millis(100);
+        assert_eq!(rate_limiter.check_at(client, "getSlot", now), Ok(()));
+        assert!(rate_limiter.check_at(client, "getSlot", now).is_err());
+    }
+
+    #[test]
+    fn test_method_costs() {
+        let rate_limiter = new_rate_limiter(10, 41);
+        let client = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let now = Instant::now();
+
+        assert_eq!(
+            rate_limiter.check_at(client, "getProgramAccounts", now),
+            Ok(())
+        );
+        assert_eq!(rate_limiter.check_at(client, "getBlock", now), Ok(()));
+        assert_eq!(rate_limiter.check_at(client, "getBlock", now), Ok(()));
+        assert_eq!(
+            rate_limiter.check_at(client, "getSignaturesForAddress", now),
+            Err(Duration::from_millis(900))
+        );
+        // Cheap calls still fit in what is left
+        assert_eq!(rate_limiter.check_at(client, "getSlot", now), Ok(()));
+
+        // Calls costing more than the burst are charged the burst
+        let rate_limiter = new_rate_limiter(10, 5);
+        assert_eq!(
+            rate_limiter.check_at(client, "getProgramAccounts", now),
+            Ok(())
+        );
+        assert_eq!(
+            rate_limiter.check_at(client, "getProgramAccounts", now),
+            Err(Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn test_method_quotas() {
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            method_quotas: HashMap::from([("getBlock".to_string(), 2)]),
+            ..RpcRateLimitConfig::default()
+        });
+        let now = Instant::now();
+        let client = |i| Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+
+        assert_eq!(rate_limiter.check_at(client(1), "getBlock", now), Ok(()));
+        assert_eq!(rate_limiter.check_at(client(2), "getBlock", now), Ok(()));
+        assert_eq!(
+            rate_limiter.check_at(client(3), "getBlock", now),
+            Err(Duration::from_millis(500))
+        );
+        // Other methods are not affected by the quota
+        assert_eq!(rate_limiter.check_at(client(3), "getSlot", now), Ok(()));
+
+        let now = now + Duration::from_millis(500);
+        assert_eq!(rate_limiter.check_at(client(3), "getBlock", now), Ok(()));
+        assert!(rate_limiter.check_at(client(1), "getBlock", now).is_err());
+    }
+
+    #[test]
+    fn test_sweep_idle_clients() {
+        let rate_limiter = new_rate_limiter(10, 5);
+        rate_limiter.sweep_threshold.store(4, Ordering::Relaxed);
+        let client = |i| Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+        let now = Instant::now();
+
+        for i in 0..4 {
+            assert_eq!(rate_limiter.check_at(client(i), "getSlot", now), Ok(()));
+        }
+        // None of the buckets refilled yet, so none can be dropped
+        assert_eq!(rate_limiter.check_at(client(4), "getSlot", now), Ok(()));
+        assert_eq!(rate_limiter.clients.len(), 5);
+        // The next sweep waits until as many clients were added as are tracked
+        assert_eq!(
+            rate_limiter.sweep_threshold.load(Ordering::Relaxed),
+            MAX_TRACKED_CLIENTS
+        );
+
+        rate_limiter.sweep_threshold.store(5, Ordering::Relaxed);
+        let now = now + Duration::from_millis(100);
+        assert_eq!(rate_limiter.check_at(client(5), "getSlot", now), Ok(()));
+        assert_eq!(rate_limiter.clients.len(), 1);
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let proxy = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            trusted_proxies: HashSet::from([proxy, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]),
+            ..RpcRateLimitConfig::default()
+        });
+        let peer = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1));
+        let client = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+        let headers = |value| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                DEFAULT_RPC_RATE_LIMIT_CLIENT_IP_HEADER,
+                HeaderValue::from_static(value),
+            );
+            headers
+        };
+
+        // The header is ignored unless the connection comes from a trusted proxy
+        assert_eq!(rate_limiter.client_ip(peer, &HeaderMap::new()), peer);
+        assert_eq!(rate_limiter.client_ip(peer, &headers("203.0.113.7")), peer);
+        assert_eq!(rate_limiter.client_ip(proxy, &HeaderMap::new()), proxy);
+
+        // Addresses the client prepended are skipped, as are the trusted proxies
+        assert_eq!(
+            rate_limiter.client_ip(proxy, &headers("192.0.2.1, 203.0.113.7")),
+            client
+        );
+        assert_eq!(
+            rate_limiter.client_ip(proxy, &headers("192.0.2.1, 203.0.113.7, 10.0.0.2")),
+            client
+        );
+        assert_eq!(
+            rate_limiter.client_ip(proxy, &headers("10.0.0.2, 10.0.0.1")),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))
+        );
+        assert_eq!(
+            rate_limiter.client_ip(proxy, &headers("203.0.113.7, not an address")),
+            proxy
+        );
+    }
+}
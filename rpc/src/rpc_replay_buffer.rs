@@ -0,0 +1,192 @@
+//! The `rpc_replay_buffer` module keeps the notifications of the most recent slots, so that a
+//! pubsub client reconnecting with `fromSlot` can receive the notifications it missed.
+
+use {
+    solana_clock::Slot,
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client_api::response::SlotInfo,
+    solana_runtime::bank::{Bank, TransactionLogCollector},
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, RwLock},
+    },
+};
+
+#[derive(Default)]
+struct ReplaySlot {
+    slot_info: Option<SlotInfo>,
+    is_root: bool,
+    // The bank's log collector is not modified once the bank is frozen, so it is shared rather
+    // than copied
+    logs: Option<Arc<RwLock<TransactionLogCollector>>>,
+    logs_processed: bool,
+    logs_confirmed: bool,
+    logs_finalized: bool,
+}
+
+/// Node-wide notifications of the last `max_slots` slots, keyed by slot.
+pub(crate) struct ReplayBuffer {
+    max_slots: usize,
+    slots: BTreeMap<Slot, ReplaySlot>,
+}
+
+impl ReplayBuffer {
+    pub(crate) fn new(max_slots: usize) -> Self {
+        Self {
+            max_slots,
+            slots: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.max_slots > 0
+    }
+
+    fn entry(&mut self, slot: Slot) -> Option<&mut ReplaySlot> {
+        if !self.is_enabled() {
+            return None;
+        }
+        if self.slots.len() >= self.max_slots && !self.slots.contains_key(&slot) {
+            // Evict the oldest slot, unless `slot` is even older
+            let (oldest, _) = self.slots.first_key_value()?;
+            if slot < *oldest {
+                return None;
+            }
+            self.slots.pop_first();
+        }
+        Some(self.slots.entry(slot).or_default())
+    }
+
+    pub(crate) fn push_slot(&mut self, slot_info: SlotInfo) {
+        if let Some(entry) = self.entry(slot_info.slot) {
+            entry.slot_info = Some(slot_info);
+        }
+    }
+
+    pub(crate) fn push_root(&mut self, root: Slot) {
+        if let Some(entry) = self.entry(root) {
+            entry.is_root = true;
+        }
+    }
+
+    /// Records that the logs of `bank` were notified to subscribers at `commitment`.
+    pub(crate) fn push_logs(&mut self, bank: &Bank, commitment: CommitmentConfig) {
+        if let Some(entry) = self.entry(bank.slot()) {
+            entry
+                .logs
+                .get_or_insert_with(|| Arc::clone(&bank.transaction_log_collector));
+            if commitment.is_finalized() {
+                entry.logs_finalized = true;
+            } else if commitment.is_confirmed() {
+                entry.logs_confirmed = true;
+            } else {
+                entry.logs_processed = true;
+            }
+        }
+    }
+
+    pub(crate) fn slots(&self, from_slot: Slot) -> impl Iterator<Item = &SlotInfo> {
+        self.slots
+            .range(from_slot..)
+            .filter_map(|(_, entry)| entry.slot_info.as_ref())
+    }
+
+    pub(crate) fn roots(&self, from_slot: Slot) -> impl Iterator<Item = Slot> + '_ {
+        self.slots
+            .range(from_slot..)
+            .filter(|(_, entry)| entry.is_root)
+            .map(|(slot, _)| *slot)
+    }
+
+    /// Returns the logs of the slots at or after `from_slot` that were notified at `commitment`.
+    pub(crate) fn logs(
+        &self,
+        from_slot: Slot,
+        commitment: CommitmentConfig,
+    ) -> impl Iterator<Item = (Slot, &RwLock<TransactionLogCollector>)> {
+        self.slots
+            .range(from_slot..)
+            .filter(move |(_, entry)| {
+                if commitment.is_finalized() {
+                    entry.logs_finalized
+                } else if commitment.is_confirmed() {
+                    entry.logs_confirmed
+                } else {
+                    entry.logs_processed
+                }
+            })
+            .filter_map(|(slot, entry)| Some((*slot, entry.logs.as_deref()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::genesis_utils::{create_genesis_config, GenesisConfigInfo},
+    };
+
+    fn slot_info(slot: Slot) -> SlotInfo {
+        SlotInfo {
+            slot,
+            parent: slot.saturating_sub(1),
+            root: 0,
+        }
+    }
+
+    #[test]
+    fn test_replay_buffer_evicts_oldest_slots() {
+        let mut buffer = ReplayBuffer::new(3);
+        for slot in 1..=4 {
+            buffer.push_slot(slot_info(slot));
+        }
+        buffer.push_root(2);
+        buffer.push_root(4);
+        assert_eq!(
+            buffer.slots(0).map(|info| info.slot).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            buffer.slots(3).map(|info| info.slot).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(buffer.roots(0).collect::<Vec<_>>(), vec![2, 4]);
+
+        // Slots older than the whole buffer are dropped
+        buffer.push_slot(slot_info(1));
+        assert_eq!(
+            buffer.slots(0).map(|info| info.slot).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_replay_buffer_disabled() {
+        let mut buffer = ReplayBuffer::new(0);
+        buffer.push_slot(slot_info(1));
+        buffer.push_root(1);
+        assert_eq!(buffer.slots(0).count(), 0);
+        assert_eq!(buffer.roots(0).count(), 0);
+    }
+
+    #[test]
+    fn test_replay_buffer_logs_by_commitment() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let mut buffer = ReplayBuffer::new(8);
+
+        buffer.push_logs(&bank, CommitmentConfig::processed());
+        assert_eq!(buffer.logs(0, CommitmentConfig::processed()).count(), 1);
+        assert_eq!(buffer.logs(0, CommitmentConfig::confirmed()).count(), 0);
+        assert_eq!(buffer.logs(0, CommitmentConfig::finalized()).count(), 0);
+
+        buffer.push_logs(&bank, CommitmentConfig::finalized());
+        assert_eq!(buffer.logs(0, CommitmentConfig::finalized()).count(), 1);
+        assert_eq!(
+            buffer
+                .logs(bank.slot() + 1, CommitmentConfig::finalized())
+                .count(),
+            0
+        );
+    }
+}
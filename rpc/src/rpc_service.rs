@@ -8,13 +8,12 @@ use {
         rpc::{rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *},
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        rpc_http_server::{self, HttpServer, JsonRpc},
+        rpc_rate_limiter::{RpcRateLimitMiddleware, RpcRateLimiter},
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
-    jsonrpc_http_server::{
-        hyper, AccessControlAllowOrigin, CloseHandle, DomainsValidation, RequestMiddleware,
-        RequestMiddlewareAction, ServerBuilder,
-    },
+    jsonrpc_http_server::{hyper, RequestMiddleware, RequestMiddlewareAction},
     regex::Regex,
     solana_cli_output::display::build_balance_message,
     solana_client::{
@@ -65,6 +64,7 @@ use {
     tokio_util::{
         bytes::Bytes,
         codec::{BytesCodec, FramedRead},
+        sync::CancellationToken,
     },
 };
 
@@ -120,7 +120,7 @@ pub struct JsonRpcService {
     #[cfg(test)]
     pub request_processor: JsonRpcRequestProcessor, // Used only by test_rpc_new()...
 
-    close_handle: Option<CloseHandle>,
+    close_handle: Option<CancellationToken>,
 
     client_updater: Arc<dyn NotifyKeyUpdate + Send + Sync>,
 }
@@ -769,6 +769,10 @@ impl JsonRpcService {
             };
 
         let full_api = config.full_api;
+        let rate_limiter = config
+            .rate_limit
+            .clone()
+            .map(|rate_limit_config| Arc::new(RpcRateLimiter::new(rate_limit_config)));
         let max_request_body_size = config
             .max_request_body_size
             .unwrap_or(MAX_REQUEST_BODY_SIZE);
@@ -792,13 +796,15 @@ impl JsonRpcService {
             Arc::clone(&runtime),
         );
 
-        let _send_transaction_service = Arc::new(SendTransactionService::new_with_client(
+        let send_transaction_service = Arc::new(SendTransactionService::new_with_client(
             &bank_forks,
             receiver,
             client.clone(),
             send_transaction_service_config,
             exit,
         ));
+        let request_processor =
+            request_processor.with_retry_status(send_transaction_service.retry_status_reader());
 
         #[cfg(test)]
         let test_request_processor = request_processor.clone();
@@ -811,7 +817,9 @@ impl JsonRpcService {
             .spawn(move || {
                 renice_this_thread(rpc_niceness_adj).unwrap();
 
-                let mut io = MetaIoHandler::default();
+                let mut io = MetaIoHandler::with_middleware(RpcRateLimitMiddleware::new(
+                    rate_limiter.clone(),
+                ));
 
                 io.extend_with(rpc_minimal::MinimalImpl.to_delegate());
                 if full_api {
@@ -827,40 +835,50 @@ impl JsonRpcService {
                     bank_forks.clone(),
                     health.clone(),
                 );
-                let server = ServerBuilder::with_meta_extractor(
-                    io,
-                    move |req: &hyper::Request<hyper::Body>| {
+                let rpc = JsonRpc {
+                    handler: Arc::new(io),
+                    extractor: Arc::new(move |req: &hyper::Request<hyper::Body>| {
                         let xbigtable = req.headers().get("x-bigtable");
-                        if xbigtable.is_some_and(|v| v == "disabled") {
+                        let request_processor = if xbigtable.is_some_and(|v| v == "disabled") {
                             request_processor.clone_without_bigtable()
                         } else {
                             request_processor.clone()
-                        }
-                    },
-                )
-                .event_loop_executor(runtime.handle().clone())
-                .threads(1)
-                .cors(DomainsValidation::AllowOnly(vec![
-                    AccessControlAllowOrigin::Any,
-                ]))
-                .cors_max_age(86400)
-                .request_middleware(request_middleware)
-                .max_request_body_size(max_request_body_size)
-                .start_http(&rpc_addr);
-
-                if let Err(e) = server {
-                    warn!(
-                        "JSON RPC service unavailable error: {e:?}. Also, check that port {} is \
-                         not already in use by another application",
-                        rpc_addr.port()
-                    );
-                    close_handle_sender.send(Err(e.to_string())).unwrap();
-                    return;
-                }
+                        };
+                        let peer_addr = rpc_http_server::peer_addr(req);
+                        let client_ip = match (&rate_limiter, peer_addr) {
+                            (Some(rate_limiter), Some(peer_addr)) => {
+                                Some(rate_limiter.client_ip(peer_addr.ip(), req.headers()))
+                            }
+                            _ => None,
+                        };
+                        request_processor.with_client_ip(client_ip)
+                    }),
+                };
+
+                let server = HttpServer::bind(
+                    &runtime,
+                    &rpc_addr,
+                    rpc,
+                    Arc::new(request_middleware),
+                    max_request_body_size,
+                );
+                let server = match server {
+                    Ok(server) => server,
+                    Err(e) => {
+                        warn!(
+                            "JSON RPC service unavailable error: {e:?}. Also, check that port {} \
+                             is not already in use by another application",
+                            rpc_addr.port()
+                        );
+                        close_handle_sender.send(Err(e.to_string())).unwrap();
+                        return;
+                    }
+                };
 
-                let server = server.unwrap();
                 close_handle_sender.send(Ok(server.close_handle())).unwrap();
-                server.wait();
+                if let Err(err) = server.wait(&runtime) {
+                    error!("Error running JSON RPC service: {err:?}");
+                }
                 exit_bigtable_ledger_upload_service.store(true, Ordering::Relaxed);
             })
             .unwrap();
@@ -871,7 +889,7 @@ impl JsonRpcService {
             .write()
             .unwrap()
             .register_exit(Box::new(move || {
-                close_handle_.close();
+                close_handle_.cancel();
             }));
         Ok(Self {
             thread_hdl,
@@ -884,7 +902,7 @@ impl JsonRpcService {
 
     pub fn exit(&mut self) {
         if let Some(c) = self.close_handle.take() {
-            c.close()
+            c.cancel()
         }
     }
 
@@ -903,18 +921,14 @@ pub fn service_runtime(
     rpc_blocking_threads: usize,
     rpc_niceness_adj: i8,
 ) -> Arc<TokioRuntime> {
-    // The jsonrpc_http_server crate supports two execution models:
-    //
-    // - By default, it spawns a number of threads - configured with .threads(N) - and runs a
-    //   single-threaded futures executor in each thread.
-    // - Alternatively when configured with .event_loop_executor(executor) and .threads(1),
-    //   it executes all the tasks on the given executor, not spawning any extra internal threads.
+    // The HTTP server and all the tasks it spawns run on this runtime, not spawning any extra
+    // internal threads.
     //
-    // We use the latter configuration, using a multi threaded tokio runtime as the executor. We
-    // do this so we can configure the number of worker threads, the number of blocking threads
-    // and then use tokio::task::spawn_blocking() to avoid blocking the worker threads on CPU
-    // bound operations like getMultipleAccounts. This results in reduced latency, since fast
-    // rpc calls (the majority) are not blocked by slow CPU bound ones.
+    // We use a multi threaded tokio runtime as the executor. We do this so we can configure the
+    // number of worker threads, the number of blocking threads and then use
+    // tokio::task::spawn_blocking() to avoid blocking the worker threads on CPU bound operations
+    // like getMultipleAccounts. This results in reduced latency, since fast rpc calls (the
+    // majority) are not blocked by slow CPU bound ones.
     //
     // NB: `rpc_blocking_threads` shouldn't be set too high (defaults to num_cpus / 2). Too many
     // (busy) blocking threads could compete with CPU time with other validator threads and
@@ -949,8 +963,8 @@ mod tests {
         solana_runtime::bank::Bank,
         solana_signer::Signer,
         std::{
-            io::Write,
-            net::{IpAddr, Ipv4Addr},
+            io::{Read, Write},
+            net::{IpAddr, Ipv4Addr, TcpStream},
         },
         tokio::runtime::Runtime,
     };
@@ -1021,6 +1035,142 @@ mod tests {
         rpc_service.join().unwrap();
     }
 
+    fn start_rpc_service(
+        config: JsonRpcConfig,
+        ledger_path: &Path,
+    ) -> (JsonRpcService, SocketAddr) {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let exit = Arc::new(AtomicBool::new(false));
+        let validator_exit = create_validator_exit(exit.clone());
+        let bank = Bank::new_for_tests(&genesis_config);
+        let cluster_info = Arc::new(new_test_cluster_info());
+        let ip_addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let port_range = solana_net_utils::sockets::localhost_port_range_for_tests();
+        let rpc_addr = SocketAddr::new(
+            ip_addr,
+            solana_net_utils::find_available_port_in_range(ip_addr, port_range).unwrap(),
+        );
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let blockstore = Arc::new(Blockstore::open(ledger_path).unwrap());
+        let block_commitment_cache = Arc::new(RwLock::new(BlockCommitmentCache::default()));
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let connection_cache = Arc::new(ConnectionCache::new("connection_cache_test"));
+        let rpc_service = JsonRpcService::new(
+            rpc_addr,
+            config,
+            None,
+            bank_forks,
+            block_commitment_cache,
+            blockstore,
+            cluster_info,
+            None,
+            Hash::default(),
+            ledger_path,
+            validator_exit,
+            exit,
+            Arc::new(AtomicBool::new(false)),
+            optimistically_confirmed_bank,
+            send_transaction_service::Config {
+                retry_rate_ms: 1000,
+                leader_forward_count: 1,
+                ..send_transaction_service::Config::default()
+            },
+            Arc::new(MaxSlots::default()),
+            Arc::new(LeaderScheduleCache::default()),
+            connection_cache,
+            Arc::new(AtomicU64::default()),
+            Arc::new(PrioritizationFeeCache::default()),
+        )
+        .expect("assume successful JsonRpcService start");
+        (rpc_service, rpc_addr)
+    }
+
+    /// Sends a raw HTTP request and returns the response with lowercase headers
+    fn send_http_request(rpc_addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(rpc_addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = vec![];
+        // The server may reset a connection it rejected after responding
+        let _ = stream.read_to_end(&mut response);
+        let response = String::from_utf8(response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        format!("{}\r\n\r\n{body}", head.to_lowercase())
+    }
+
+    fn post_request(body: &str) -> String {
+        format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nOrigin: http://example.com\r\nContent-Type: \
+             application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn test_http_server() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let (mut rpc_service, rpc_addr) = start_rpc_service(
+            JsonRpcConfig {
+                max_request_body_size: Some(128),
+                ..JsonRpcConfig::default()
+            },
+            ledger_path.path(),
+        );
+
+        // CORS preflight requests are allowed from any origin
+        let response = send_http_request(
+            rpc_addr,
+            "OPTIONS / HTTP/1.1\r\nHost: localhost\r\nOrigin: http://example.com\r\n\
+             Access-Control-Request-Method: POST\r\nAccess-Control-Request-Headers: \
+             content-type\r\nConnection: close\r\n\r\n",
+        );
+        assert!(response.starts_with("http/1.1 200"), "{response}");
+        assert!(
+            response.contains("access-control-allow-origin:"),
+            "{response}"
+        );
+        assert!(
+            response.contains("access-control-max-age: 86400"),
+            "{response}"
+        );
+
+        // JSON RPC requests
+        let response = send_http_request(
+            rpc_addr,
+            &post_request(r#"{"jsonrpc":"2.0","id":1,"method":"getVersion"}"#),
+        );
+        assert!(response.starts_with("http/1.1 200"), "{response}");
+        assert!(
+            response.contains("access-control-allow-origin:"),
+            "{response}"
+        );
+        assert!(response.contains(r#""result":{"#), "{response}");
+
+        // The request middleware serves the health path
+        let response = send_http_request(
+            rpc_addr,
+            "GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        );
+        assert!(response.starts_with("http/1.1 200"), "{response}");
+        let (_head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(["ok", "behind", "unknown"].contains(&body), "{response}");
+
+        // Request bodies are limited in size
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [vec!["11111111111111111111111111111111"; 4]],
+        });
+        let response = send_http_request(rpc_addr, &post_request(&body.to_string()));
+        assert!(response.starts_with("http/1.1 413"), "{response}");
+
+        // Closing stops the server
+        rpc_service.exit();
+        rpc_service.join().unwrap();
+        assert!(TcpStream::connect(rpc_addr).is_err());
+    }
+
     fn create_bank_forks() -> Arc<RwLock<BankForks>> {
         let GenesisConfigInfo {
             mut genesis_config, ..
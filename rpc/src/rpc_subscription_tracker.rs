@@ -1,9 +1,12 @@
 use {
-    crate::rpc_subscriptions::{NotificationEntry, RpcNotification, TimestampedNotificationEntry},
+    crate::rpc_subscriptions::{
+        NotificationEntry, ReplayRequest, RpcNotification, TimestampedNotificationEntry,
+    },
     dashmap::{mapref::entry::Entry as DashEntry, DashMap},
     solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
     solana_clock::Slot,
     solana_commitment_config::CommitmentConfig,
+    solana_message::AccountKeys,
     solana_metrics::{CounterToken, TokenCounter},
     solana_pubkey::Pubkey,
     solana_rpc_client_api::filter::RpcFilterType,
@@ -16,9 +19,10 @@ use {
     std::{
         collections::hash_map::{Entry, HashMap},
         fmt,
+        hash::{Hash, Hasher},
         sync::{
-            atomic::{AtomicU64, Ordering},
-            Arc, RwLock, Weak,
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            Arc, RwLock, RwLockWriteGuard, Weak,
         },
     },
     thiserror::Error,
@@ -40,16 +44,22 @@ impl From<SubscriptionId> for u64 {
     }
 }
 
+/// Identifies one request to replay buffered notifications to a single connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReplayId(u64);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SubscriptionParams {
     Account(AccountSubscriptionParams),
     Block(BlockSubscriptionParams),
     Logs(LogsSubscriptionParams),
+    MultiAccount(MultiAccountSubscriptionParams),
     Program(ProgramSubscriptionParams),
     Signature(SignatureSubscriptionParams),
     Slot,
     SlotsUpdates,
     Root,
+    Transaction(TransactionSubscriptionParams),
     Vote,
 }
 
@@ -58,12 +68,14 @@ impl SubscriptionParams {
         match self {
             SubscriptionParams::Account(_) => "accountNotification",
             SubscriptionParams::Logs(_) => "logsNotification",
+            SubscriptionParams::MultiAccount(_) => "multiAccountNotification",
             SubscriptionParams::Program(_) => "programNotification",
             SubscriptionParams::Signature(_) => "signatureNotification",
             SubscriptionParams::Slot => "slotNotification",
             SubscriptionParams::SlotsUpdates => "slotsUpdatesNotification",
             SubscriptionParams::Block(_) => "blockNotification",
             SubscriptionParams::Root => "rootNotification",
+            SubscriptionParams::Transaction(_) => "transactionNotification",
             SubscriptionParams::Vote => "voteNotification",
         }
     }
@@ -72,9 +84,11 @@ impl SubscriptionParams {
         match self {
             SubscriptionParams::Account(params) => Some(params.commitment),
             SubscriptionParams::Logs(params) => Some(params.commitment),
+            SubscriptionParams::MultiAccount(params) => Some(params.commitment),
             SubscriptionParams::Program(params) => Some(params.commitment),
             SubscriptionParams::Signature(params) => Some(params.commitment),
             SubscriptionParams::Block(params) => Some(params.commitment),
+            SubscriptionParams::Transaction(params) => Some(params.commitment),
             SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
             | SubscriptionParams::Root
@@ -87,11 +101,13 @@ impl SubscriptionParams {
             SubscriptionParams::Account(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
+            SubscriptionParams::MultiAccount(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
             SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
+            | SubscriptionParams::Transaction(_)
             | SubscriptionParams::Vote => return false,
         };
         !commitment.is_confirmed()
@@ -102,16 +118,22 @@ impl SubscriptionParams {
             SubscriptionParams::Account(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
+            SubscriptionParams::MultiAccount(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
             SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
+            | SubscriptionParams::Transaction(_)
             | SubscriptionParams::Vote => return false,
         };
         commitment.is_confirmed()
     }
 
+    fn is_transaction_watcher(&self) -> bool {
+        matches!(self, SubscriptionParams::Transaction(_))
+    }
+
     fn is_node_progress_watcher(&self) -> bool {
         matches!(
             self,
@@ -160,6 +182,98 @@ pub enum LogsSubscriptionKind {
     Single(Pubkey),
 }
 
+/// Parameters of a `multiAccountSubscribe` subscription.
+///
+/// Unlike other subscriptions, the tracked accounts can be changed after subscribing, so two
+/// multi-account subscriptions are only considered equal if they share the same account set.
+#[derive(Debug, Clone)]
+pub struct MultiAccountSubscriptionParams {
+    pub accounts: Arc<MultiAccountSet>,
+    pub encoding: UiAccountEncoding,
+    pub data_slice: Option<UiDataSliceConfig>,
+    pub commitment: CommitmentConfig,
+}
+
+impl PartialEq for MultiAccountSubscriptionParams {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.accounts, &other.accounts)
+    }
+}
+
+impl Eq for MultiAccountSubscriptionParams {}
+
+impl Hash for MultiAccountSubscriptionParams {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.accounts).hash(state);
+    }
+}
+
+/// The accounts tracked by a multi-account subscription, each mapped to the slot it was last
+/// notified at. `None` means the account was just added and its current state has not been
+/// recorded by the notification thread yet.
+#[derive(Default)]
+pub struct MultiAccountSet(RwLock<HashMap<Pubkey, Option<Slot>>>);
+
+impl fmt::Debug for MultiAccountSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiAccountSet")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl MultiAccountSet {
+    pub fn new(pubkeys: impl IntoIterator<Item = Pubkey>) -> Self {
+        Self(RwLock::new(
+            pubkeys.into_iter().map(|pubkey| (pubkey, None)).collect(),
+        ))
+    }
+
+    /// Adds `pubkeys` to the set unless the set would grow beyond `max_len`, returns the new
+    /// number of tracked accounts.
+    pub fn add(
+        &self,
+        pubkeys: impl IntoIterator<Item = Pubkey>,
+        max_len: usize,
+    ) -> Result<usize, Error> {
+        let mut accounts = self.0.write().unwrap();
+        let new_pubkeys: Vec<_> = pubkeys
+            .into_iter()
+            .filter(|pubkey| !accounts.contains_key(pubkey))
+            .collect();
+        if accounts.len().saturating_add(new_pubkeys.len()) > max_len {
+            return Err(Error::TooManyAccounts(max_len));
+        }
+        accounts.extend(new_pubkeys.into_iter().map(|pubkey| (pubkey, None)));
+        Ok(accounts.len())
+    }
+
+    /// Removes `pubkeys` from the set, returns the new number of tracked accounts.
+    pub fn remove<'a>(&self, pubkeys: impl IntoIterator<Item = &'a Pubkey>) -> usize {
+        let mut accounts = self.0.write().unwrap();
+        for pubkey in pubkeys {
+            accounts.remove(pubkey);
+        }
+        accounts.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.read().unwrap().is_empty()
+    }
+
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.0.read().unwrap().contains_key(pubkey)
+    }
+
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, HashMap<Pubkey, Option<Slot>>> {
+        self.0.write().unwrap()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProgramSubscriptionParams {
     pub pubkey: Pubkey,
@@ -177,6 +291,44 @@ pub struct SignatureSubscriptionParams {
     pub enable_received_notification: bool,
 }
 
+/// Parameters of a `transactionSubscribe` subscription. The account lists are kept sorted and
+/// deduplicated so that equivalent filters share a subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TransactionSubscriptionParams {
+    pub account_include: Vec<Pubkey>,
+    pub account_exclude: Vec<Pubkey>,
+    pub account_required: Vec<Pubkey>,
+    pub vote: bool,
+    pub failed: bool,
+    pub encoding: UiTransactionEncoding,
+    pub max_supported_transaction_version: Option<u8>,
+    pub commitment: CommitmentConfig,
+}
+
+impl TransactionSubscriptionParams {
+    /// Returns true if a transaction loading `account_keys` passes the subscription filters.
+    pub fn matches(&self, is_vote: bool, failed: bool, account_keys: &AccountKeys) -> bool {
+        if (is_vote && !self.vote) || (failed && !self.failed) {
+            return false;
+        }
+        let mut included = self.account_include.is_empty();
+        let mut num_required = 0;
+        for key in account_keys.iter() {
+            if self.account_exclude.binary_search(key).is_ok() {
+                return false;
+            }
+            if !included && self.account_include.binary_search(key).is_ok() {
+                included = true;
+            }
+            if self.account_required.binary_search(key).is_ok() {
+                num_required += 1;
+            }
+        }
+        // A transaction cannot load the same account twice, so counting the matches is enough
+        included && num_required == self.account_required.len()
+    }
+}
+
 #[derive(Clone)]
 pub struct SubscriptionControl(Arc<SubscriptionControlInner>);
 pub struct WeakSubscriptionTokenRef(Weak<SubscriptionTokenInner>, SubscriptionId);
@@ -188,11 +340,17 @@ struct SubscriptionControlInner {
     sender: crossbeam_channel::Sender<TimestampedNotificationEntry>,
     broadcast_sender: broadcast::Sender<RpcNotification>,
     counter: TokenCounter,
+    num_transaction_subscriptions: AtomicUsize,
+    next_replay_id: AtomicU64,
+    is_replay_enabled: bool,
 }
 
 impl SubscriptionControl {
+    /// `is_replay_enabled` tells whether the notification thread keeps a replay buffer, without
+    /// which subscriptions can not be resumed with `fromSlot`.
     pub fn new(
         max_active_subscriptions: usize,
+        is_replay_enabled: bool,
         sender: crossbeam_channel::Sender<TimestampedNotificationEntry>,
         broadcast_sender: broadcast::Sender<RpcNotification>,
     ) -> Self {
@@ -203,9 +361,16 @@ impl SubscriptionControl {
             sender,
             broadcast_sender,
             counter: TokenCounter::new("rpc_pubsub_total_subscriptions"),
+            num_transaction_subscriptions: AtomicUsize::new(0),
+            next_replay_id: AtomicU64::new(0),
+            is_replay_enabled,
         }))
     }
 
+    pub fn is_replay_enabled(&self) -> bool {
+        self.0.is_replay_enabled
+    }
+
     pub fn broadcast_receiver(&self) -> broadcast::Receiver<RpcNotification> {
         self.0.broadcast_sender.subscribe()
     }
@@ -249,6 +414,11 @@ impl SubscriptionControl {
                 }
                 let id = SubscriptionId::from(self.0.next_id.fetch_add(1, Ordering::AcqRel));
                 let (token, weak_ref) = create_token_and_weak_ref(id, entry.key().clone());
+                if entry.key().is_transaction_watcher() {
+                    self.0
+                        .num_transaction_subscriptions
+                        .fetch_add(1, Ordering::Relaxed);
+                }
                 let _ = self
                     .0
                     .sender
@@ -263,10 +433,46 @@ impl SubscriptionControl {
         }
     }
 
+    /// Asks the notification thread to record the current state of the accounts just added to a
+    /// multi-account subscription, so that only their later changes are notified.
+    pub fn notify_multi_accounts_added(&self, params: &MultiAccountSubscriptionParams) {
+        let _ = self
+            .0
+            .sender
+            .send(NotificationEntry::MultiAccountsAdded(params.clone()).into());
+    }
+
+    /// Asks the notification thread to send the buffered notifications of subscription `id`
+    /// starting at `from_slot`. Only the connection holding the returned id receives them.
+    pub fn replay(
+        &self,
+        params: &SubscriptionParams,
+        id: SubscriptionId,
+        from_slot: Slot,
+    ) -> ReplayId {
+        let replay_id = ReplayId(self.0.next_replay_id.fetch_add(1, Ordering::AcqRel));
+        let _ = self.0.sender.send(
+            NotificationEntry::Replay(ReplayRequest {
+                params: params.clone(),
+                id,
+                replay_id,
+                from_slot,
+            })
+            .into(),
+        );
+        replay_id
+    }
+
     pub fn total(&self) -> usize {
         self.0.subscriptions.len()
     }
 
+    /// Returns true if there is at least one `transactionSubscribe` subscription, so that
+    /// producers can skip building transaction notifications nobody listens to.
+    pub fn has_transaction_subscriptions(&self) -> bool {
+        self.0.num_transaction_subscriptions.load(Ordering::Relaxed) > 0
+    }
+
     #[cfg(test)]
     pub fn assert_subscribed(&self, params: &SubscriptionParams) {
         assert!(self.0.subscriptions.contains_key(params));
@@ -346,12 +552,18 @@ impl SubscriptionInfo {
 pub enum Error {
     #[error("node subscription limit reached")]
     TooManySubscriptions,
+    #[error("subscription account limit of {0} reached")]
+    TooManyAccounts(usize),
 }
 
 struct LogsSubscriptionsIndex {
     all_count: usize,
     all_with_votes_count: usize,
     single_count: HashMap<Pubkey, usize>,
+    // Logs of dropped subscriptions keep being collected until the root passes the given slot,
+    // so that a client reconnecting with `fromSlot` can have them replayed.
+    retained: HashMap<LogsSubscriptionKind, Slot>,
+    retention_slots: Slot,
 
     bank_forks: Arc<RwLock<BankForks>>,
 }
@@ -369,6 +581,13 @@ impl LogsSubscriptionsIndex {
     }
 
     fn remove(&mut self, params: &LogsSubscriptionParams) {
+        if self.retention_slots > 0 {
+            let root = self.bank_forks.read().unwrap().root();
+            self.retained.insert(
+                params.kind.clone(),
+                root.saturating_add(self.retention_slots),
+            );
+        }
         match params.kind {
             LogsSubscriptionKind::All => self.all_count -= 1,
             LogsSubscriptionKind::AllWithVotes => self.all_with_votes_count -= 1,
@@ -385,14 +604,35 @@ impl LogsSubscriptionsIndex {
         self.update_config();
     }
 
+    fn set_root(&mut self, root: Slot) {
+        let num_retained = self.retained.len();
+        self.retained.retain(|_, retain_until| *retain_until > root);
+        if self.retained.len() != num_retained {
+            self.update_config();
+        }
+    }
+
     fn update_config(&self) {
-        let mentioned_addresses = self.single_count.keys().copied().collect();
-        let config = if self.all_with_votes_count > 0 {
+        let mentioned_addresses = self
+            .single_count
+            .keys()
+            .chain(self.retained.keys().filter_map(|kind| match kind {
+                LogsSubscriptionKind::Single(pubkey) => Some(pubkey),
+                LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes => None,
+            }))
+            .copied()
+            .collect();
+        let all_with_votes = self.all_with_votes_count > 0
+            || self
+                .retained
+                .contains_key(&LogsSubscriptionKind::AllWithVotes);
+        let all = self.all_count > 0 || self.retained.contains_key(&LogsSubscriptionKind::All);
+        let config = if all_with_votes {
             TransactionLogCollectorConfig {
                 filter: TransactionLogCollectorFilter::AllWithVotes,
                 mentioned_addresses,
             }
-        } else if self.all_count > 0 {
+        } else if all {
             TransactionLogCollectorConfig {
                 filter: TransactionLogCollectorFilter::All,
                 mentioned_addresses,
@@ -424,21 +664,28 @@ pub struct SubscriptionsTracker {
     gossip_watchers: HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
     // Slots, slots updates, roots, votes.
     node_progress_watchers: HashMap<SubscriptionParams, Arc<SubscriptionInfo>>,
+    // Transactions (processed and confirmed)
+    transaction_watchers: HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
 }
 
 impl SubscriptionsTracker {
-    pub fn new(bank_forks: Arc<RwLock<BankForks>>) -> Self {
+    /// `replay_buffer_slots` is the number of slots the replay buffer holds; logs keep being
+    /// collected for that many roots after their last subscription is dropped.
+    pub fn new(bank_forks: Arc<RwLock<BankForks>>, replay_buffer_slots: usize) -> Self {
         SubscriptionsTracker {
             logs_subscriptions_index: LogsSubscriptionsIndex {
                 all_count: 0,
                 all_with_votes_count: 0,
                 single_count: HashMap::new(),
+                retained: HashMap::new(),
+                retention_slots: replay_buffer_slots as Slot,
                 bank_forks,
             },
             by_signature: HashMap::new(),
             commitment_watchers: HashMap::new(),
             gossip_watchers: HashMap::new(),
             node_progress_watchers: HashMap::new(),
+            transaction_watchers: HashMap::new(),
         }
     }
 
@@ -477,6 +724,9 @@ impl SubscriptionsTracker {
             self.node_progress_watchers
                 .insert(info.params.clone(), Arc::clone(&info));
         }
+        if info.params.is_transaction_watcher() {
+            self.transaction_watchers.insert(id, Arc::clone(&info));
+        }
     }
 
     #[allow(clippy::collapsible_if)]
@@ -514,6 +764,15 @@ impl SubscriptionsTracker {
                 warn!("Subscriptions inconsistency (missing entry in node_progress_watchers)");
             }
         }
+        if params.is_transaction_watcher() {
+            if self.transaction_watchers.remove(&id).is_none() {
+                warn!("Subscriptions inconsistency (missing entry in transaction_watchers)");
+            }
+        }
+    }
+
+    pub fn set_root(&mut self, root: Slot) {
+        self.logs_subscriptions_index.set_root(root);
     }
 
     pub fn by_signature(
@@ -533,6 +792,10 @@ impl SubscriptionsTracker {
     pub fn node_progress_watchers(&self) -> &HashMap<SubscriptionParams, Arc<SubscriptionInfo>> {
         &self.node_progress_watchers
     }
+
+    pub fn transaction_watchers(&self) -> &HashMap<SubscriptionId, Arc<SubscriptionInfo>> {
+        &self.transaction_watchers
+    }
 }
 
 struct SubscriptionTokenInner {
@@ -559,6 +822,11 @@ impl Drop for SubscriptionTokenInner {
             // Check the strong refs count to ensure no other thread recreated this subscription (not token)
             // while we were acquiring the lock.
             DashEntry::Occupied(entry) if entry.get().0.strong_count() == 0 => {
+                if self.params.is_transaction_watcher() {
+                    self.control
+                        .num_transaction_subscriptions
+                        .fetch_sub(1, Ordering::Relaxed);
+                }
                 let _ = self
                     .control
                     .sender
@@ -613,6 +881,7 @@ mod tests {
 
             let control = SubscriptionControl::new(
                 PubSubConfig::default().max_active_subscriptions,
+                false,
                 sender,
                 broadcast_sender,
             );
@@ -704,7 +973,7 @@ mod tests {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
         let bank = Bank::new_for_tests(&genesis_config);
         let bank_forks = BankForks::new_rw_arc(bank);
-        let mut tracker = SubscriptionsTracker::new(bank_forks);
+        let mut tracker = SubscriptionsTracker::new(bank_forks, 0);
 
         tracker.subscribe(SubscriptionParams::Slot, 0.into(), || 0);
         let info = tracker
@@ -750,7 +1019,7 @@ mod tests {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
         let bank = Bank::new_for_tests(&genesis_config);
         let bank_forks = BankForks::new_rw_arc(bank);
-        let mut tracker = SubscriptionsTracker::new(bank_forks);
+        let mut tracker = SubscriptionsTracker::new(bank_forks, 0);
 
         tracker.subscribe(SubscriptionParams::Slot, 0.into(), || 0);
         assert_eq!(counts(&tracker), (0, 0, 0, 1));
@@ -789,4 +1058,139 @@ mod tests {
         tracker.unsubscribe(signature_params, 3.into());
         assert_eq!(counts(&tracker), (0, 0, 0, 0));
     }
+
+    #[test]
+    fn notify_subscribe_multi_account() {
+        let multi_account_params = || {
+            SubscriptionParams::MultiAccount(MultiAccountSubscriptionParams {
+                accounts: Arc::new(MultiAccountSet::new([Pubkey::default()])),
+                encoding: UiAccountEncoding::Base64,
+                data_slice: None,
+                commitment: CommitmentConfig::processed(),
+            })
+        };
+
+        // Multi-account subscriptions with the same accounts are not shared, since each of them
+        // can be changed independently
+        let control = ControlWrapper::new();
+        let params1 = multi_account_params();
+        let token1 = control.control.subscribe(params1.clone()).unwrap();
+        control.assert_subscribed(&params1, 0);
+        let params2 = multi_account_params();
+        let token2 = control.control.subscribe(params2.clone()).unwrap();
+        control.assert_subscribed(&params2, 1);
+
+        let SubscriptionParams::MultiAccount(params) = token1.params() else {
+            panic!("unexpected params");
+        };
+        control.control.notify_multi_accounts_added(params);
+        assert!(matches!(
+            control.receiver.recv().unwrap().entry,
+            NotificationEntry::MultiAccountsAdded(_)
+        ));
+        control.assert_silence();
+
+        drop(token1);
+        control.assert_unsubscribed(&params1, 0);
+        drop(token2);
+        control.assert_unsubscribed(&params2, 1);
+    }
+
+    #[test]
+    fn multi_account_set() {
+        let pubkey1 = solana_pubkey::new_rand();
+        let pubkey2 = solana_pubkey::new_rand();
+        let pubkey3 = solana_pubkey::new_rand();
+        let accounts = MultiAccountSet::new([pubkey1]);
+        assert_eq!(accounts.len(), 1);
+
+        // Adding an already tracked account is a no-op
+        assert_eq!(accounts.add([pubkey1, pubkey2], 2).unwrap(), 2);
+        assert!(matches!(
+            accounts.add([pubkey3], 2),
+            Err(Error::TooManyAccounts(2))
+        ));
+        assert!(!accounts.contains(&pubkey3));
+
+        assert_eq!(accounts.remove(&[pubkey1, pubkey3]), 1);
+        assert!(!accounts.contains(&pubkey1));
+        assert!(accounts.contains(&pubkey2));
+        assert_eq!(accounts.remove(&[pubkey2]), 0);
+        assert!(accounts.is_empty());
+    }
+
+    #[test]
+    fn transaction_subscription_filters() {
+        let mut keys = [
+            solana_pubkey::new_rand(),
+            solana_pubkey::new_rand(),
+            solana_pubkey::new_rand(),
+            solana_pubkey::new_rand(),
+        ];
+        keys.sort_unstable();
+        let [key1, key2, key3, key4] = keys;
+        let params = TransactionSubscriptionParams {
+            account_include: vec![key1, key2],
+            account_exclude: vec![key4],
+            account_required: vec![key3],
+            vote: false,
+            failed: true,
+            encoding: UiTransactionEncoding::Base64,
+            max_supported_transaction_version: None,
+            commitment: CommitmentConfig::processed(),
+        };
+        let matches = |is_vote, failed, keys: &[Pubkey]| {
+            params.matches(is_vote, failed, &AccountKeys::new(keys, None))
+        };
+
+        assert!(matches(false, false, &[key2, key3]));
+        assert!(matches(false, true, &[key1, key3]));
+        // Vote transactions are filtered out
+        assert!(!matches(true, false, &[key2, key3]));
+        // Neither included account is loaded
+        assert!(!matches(false, false, &[key3]));
+        // The required account is not loaded
+        assert!(!matches(false, false, &[key1, key2]));
+        // An excluded account is loaded
+        assert!(!matches(false, false, &[key1, key3, key4]));
+
+        let params = TransactionSubscriptionParams {
+            account_include: vec![],
+            account_exclude: vec![],
+            account_required: vec![],
+            vote: true,
+            failed: false,
+            ..params
+        };
+        assert!(params.matches(true, false, &AccountKeys::new(&[key1], None)));
+        assert!(!params.matches(false, true, &AccountKeys::new(&[key1], None)));
+    }
+
+    #[test]
+    fn notify_subscribe_transaction() {
+        let control = ControlWrapper::new();
+        let params = SubscriptionParams::Transaction(TransactionSubscriptionParams {
+            account_include: vec![],
+            account_exclude: vec![],
+            account_required: vec![],
+            vote: false,
+            failed: true,
+            encoding: UiTransactionEncoding::Base64,
+            max_supported_transaction_version: None,
+            commitment: CommitmentConfig::confirmed(),
+        });
+        assert!(!control.control.has_transaction_subscriptions());
+
+        let token1 = control.control.subscribe(params.clone()).unwrap();
+        control.assert_subscribed(&params, 0);
+        let token2 = control.control.subscribe(params.clone()).unwrap();
+        control.assert_silence();
+        assert!(control.control.has_transaction_subscriptions());
+
+        drop(token1);
+        assert!(control.control.has_transaction_subscriptions());
+        drop(token2);
+        control.assert_unsubscribed(&params, 0);
+        assert!(!control.control.has_transaction_subscriptions());
+    }
 }
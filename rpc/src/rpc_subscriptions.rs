@@ -6,49 +6,57 @@ use {
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         parsed_token_accounts::{get_parsed_token_account, get_parsed_token_accounts},
         rpc_pubsub_service::PubSubConfig,
+        rpc_replay_buffer::ReplayBuffer,
         rpc_subscription_tracker::{
             AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionInfo,
-            SubscriptionParams, SubscriptionsTracker,
+            LogsSubscriptionKind, LogsSubscriptionParams, MultiAccountSubscriptionParams,
+            ProgramSubscriptionParams, ReplayId, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionInfo, SubscriptionParams, SubscriptionsTracker,
         },
+        transaction_notifier_interface::TransactionNotifier,
     },
     crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
+    dashmap::DashMap,
     itertools::Either,
     rayon::prelude::*,
     serde::Serialize,
     solana_account::{AccountSharedData, ReadableAccount},
     solana_account_decoder::{
         encode_ui_account, parse_token::is_known_spl_token_id, UiAccount, UiAccountEncoding,
+        UiDataSliceConfig,
     },
     solana_clock::Slot,
+    solana_commitment_config::CommitmentConfig,
+    solana_hash::Hash,
     solana_ledger::{blockstore::Blockstore, get_tmp_ledger_path},
     solana_measure::measure::Measure,
     solana_pubkey::Pubkey,
     solana_rpc_client_api::response::{
         ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse, RpcBlockUpdate,
         RpcBlockUpdateError, RpcKeyedAccount, RpcLogsResponse, RpcResponseContext,
-        RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
+        RpcSignatureResult, RpcTransactionUpdate, RpcVote, SlotInfo, SlotUpdate,
     },
     solana_runtime::{
-        bank::{Bank, TransactionLogInfo},
+        bank::{Bank, TransactionLogCollector, TransactionLogInfo},
         bank_forks::BankForks,
         commitment::{BlockCommitmentCache, CommitmentSlots},
     },
     solana_signature::Signature,
     solana_time_utils::timestamp,
+    solana_transaction::versioned::VersionedTransaction,
     solana_transaction_status::{
-        BlockEncodingOptions, ConfirmedBlock, EncodeError, VersionedConfirmedBlock,
+        BlockEncodingOptions, ConfirmedBlock, EncodeError, TransactionStatusMeta,
+        VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
     },
     solana_vote::vote_transaction::VoteTransaction,
     std::{
         cell::RefCell,
-        collections::{HashMap, VecDeque},
+        collections::{HashMap, HashSet, VecDeque},
         io::Cursor,
         str,
         sync::{
             atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
-            Arc, Mutex, RwLock, Weak,
+            Arc, Mutex, OnceLock, RwLock, Weak,
         },
         thread::{Builder, JoinHandle},
         time::{Duration, Instant},
@@ -65,12 +73,19 @@ const RECEIVE_DELAY_MILLIS: u64 = 100;
 fn get_transaction_logs(
     bank: &Bank,
     params: &LogsSubscriptionParams,
+) -> Option<Vec<TransactionLogInfo>> {
+    filter_transaction_logs(&bank.transaction_log_collector.read().unwrap(), params)
+}
+
+fn filter_transaction_logs(
+    log_collector: &TransactionLogCollector,
+    params: &LogsSubscriptionParams,
 ) -> Option<Vec<TransactionLogInfo>> {
     let pubkey = match &params.kind {
         LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes => None,
         LogsSubscriptionKind::Single(pubkey) => Some(pubkey),
     };
-    let mut logs = bank.get_transaction_logs(pubkey);
+    let mut logs = log_collector.get_logs_for_address(pubkey);
     if matches!(params.kind, LogsSubscriptionKind::All) {
         // Filter out votes if the subscriber doesn't want them
         if let Some(logs) = &mut logs {
@@ -104,6 +119,28 @@ pub enum NotificationEntry {
     SignaturesReceived((Slot, Vec<Signature>)),
     Subscribed(SubscriptionParams, SubscriptionId),
     Unsubscribed(SubscriptionParams, SubscriptionId),
+    MultiAccountsAdded(MultiAccountSubscriptionParams),
+    Transaction(Box<TransactionUpdate>),
+    Replay(ReplayRequest),
+}
+
+/// A request to send the buffered notifications of a subscription to the connection that just
+/// subscribed with `fromSlot`.
+#[derive(Debug)]
+pub struct ReplayRequest {
+    pub params: SubscriptionParams,
+    pub id: SubscriptionId,
+    pub replay_id: ReplayId,
+    pub from_slot: Slot,
+}
+
+/// A transaction committed to a bank, as recorded by the transaction status service.
+pub struct TransactionUpdate {
+    pub slot: Slot,
+    pub index: usize,
+    pub signature: Signature,
+    pub is_vote: bool,
+    pub transaction: VersionedTransactionWithStatusMeta,
 }
 
 impl std::fmt::Debug for NotificationEntry {
@@ -128,6 +165,17 @@ impl std::fmt::Debug for NotificationEntry {
             NotificationEntry::Unsubscribed(params, id) => {
                 write!(f, "Unsubscribed({params:?}, {id:?})")
             }
+            NotificationEntry::MultiAccountsAdded(params) => {
+                write!(f, "MultiAccountsAdded({params:?})")
+            }
+            NotificationEntry::Transaction(update) => {
+                write!(
+                    f,
+                    "Transaction({{slot: {}, signature: {}}})",
+                    update.slot, update.signature
+                )
+            }
+            NotificationEntry::Replay(request) => write!(f, "Replay({request:?})"),
         }
     }
 }
@@ -180,6 +228,16 @@ pub struct RpcNotification {
     pub is_final: bool,
     pub json: Weak<String>,
     pub created_at: Instant,
+    /// Set on replayed notifications, which only go to the connection that asked for them
+    pub replay: Option<ReplayMarker>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMarker {
+    /// A buffered notification
+    Item(ReplayId),
+    /// Carries no notification; sent once every buffered notification has been sent
+    Done(ReplayId),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -290,16 +348,58 @@ impl RpcNotifier {
     fn notify<T>(&self, value: T, subscription: &SubscriptionInfo, is_final: bool)
     where
         T: serde::Serialize,
+    {
+        self.send(
+            value,
+            subscription.method(),
+            subscription.id(),
+            is_final,
+            None,
+        );
+    }
+
+    fn replay<T>(&self, value: T, request: &ReplayRequest, is_final: bool)
+    where
+        T: serde::Serialize,
+    {
+        self.send(
+            value,
+            request.params.method(),
+            request.id,
+            is_final,
+            Some(ReplayMarker::Item(request.replay_id)),
+        );
+    }
+
+    fn replay_done(&self, subscription_id: SubscriptionId, replay_id: ReplayId) {
+        let _ = self.sender.send(RpcNotification {
+            subscription_id,
+            is_final: false,
+            json: Weak::new(),
+            created_at: Instant::now(),
+            replay: Some(ReplayMarker::Done(replay_id)),
+        });
+    }
+
+    fn send<T>(
+        &self,
+        value: T,
+        method: &'static str,
+        subscription_id: SubscriptionId,
+        is_final: bool,
+        replay: Option<ReplayMarker>,
+    ) where
+        T: serde::Serialize,
     {
         let buf_arc = RPC_NOTIFIER_BUF.with(|buf| {
             let mut buf = buf.borrow_mut();
             buf.clear();
             let notification = Notification {
                 jsonrpc: Some(jsonrpc_core::Version::V2),
-                method: subscription.method(),
+                method,
                 params: NotificationParams {
                     result: value,
-                    subscription: subscription.id(),
+                    subscription: subscription_id,
                 },
             };
             serde_json::to_writer(Cursor::new(&mut *buf), &notification)
@@ -309,10 +409,11 @@ impl RpcNotifier {
         });
 
         let notification = RpcNotification {
-            subscription_id: subscription.id(),
+            subscription_id,
             json: Arc::downgrade(&buf_arc),
             is_final,
             created_at: Instant::now(),
+            replay,
         };
         // There is an unlikely case where this can fail: if the last subscription is closed
         // just as the notifier generates a notification for it.
@@ -370,11 +471,61 @@ fn filter_block_result_txs(
     }))
 }
 
+type EncodedAccountKey = (Slot, Pubkey, UiAccountEncoding, Option<UiDataSliceConfig>);
+
+/// Accounts loaded and encoded while notifying watchers of a single slot.
+///
+/// An account followed by many subscriptions, through `accountSubscribe` as well as
+/// `multiAccountSubscribe`, is loaded from the bank once and encoded once per encoding and data
+/// slice, rather than once per subscription.
+#[derive(Default)]
+struct AccountNotificationCache {
+    loaded: DashMap<(Slot, Pubkey), Option<(AccountSharedData, Slot)>>,
+    encoded: DashMap<EncodedAccountKey, UiAccount>,
+}
+
+impl AccountNotificationCache {
+    fn load(&self, bank: &Bank, pubkey: &Pubkey) -> Option<(AccountSharedData, Slot)> {
+        let key = (bank.slot(), *pubkey);
+        if let Some(result) = self.loaded.get(&key) {
+            return result.clone();
+        }
+        let result = bank.get_account_modified_slot(pubkey);
+        self.loaded.insert(key, result.clone());
+        result
+    }
+
+    /// Encodes `account`, which must be the state of `pubkey` as loaded from `bank`.
+    fn encode(
+        &self,
+        bank: &Bank,
+        pubkey: &Pubkey,
+        account: AccountSharedData,
+        encoding: UiAccountEncoding,
+        data_slice: Option<UiDataSliceConfig>,
+    ) -> UiAccount {
+        let key = (bank.slot(), *pubkey, encoding, data_slice);
+        if let Some(ui_account) = self.encoded.get(&key) {
+            return ui_account.clone();
+        }
+        let ui_account = if is_known_spl_token_id(account.owner())
+            && encoding == UiAccountEncoding::JsonParsed
+        {
+            get_parsed_token_account(bank, pubkey, account, None)
+        } else {
+            encode_ui_account(pubkey, &account, encoding, None, data_slice)
+        };
+        self.encoded.insert(key, ui_account.clone());
+        ui_account
+    }
+}
+
 fn filter_account_result(
     result: Option<(AccountSharedData, Slot)>,
     params: &AccountSubscriptionParams,
     last_notified_slot: Slot,
     bank: Arc<Bank>,
+    cache: &AccountNotificationCache,
 ) -> (Option<UiAccount>, Slot) {
     // If the account is not found, `last_modified_slot` will default to zero and
     // we will notify clients that the account no longer exists if we haven't already
@@ -383,17 +534,58 @@ fn filter_account_result(
     // If last_modified_slot < last_notified_slot this means that we last notified for a fork
     // and should notify that the account state has been reverted.
     let account = (last_modified_slot != last_notified_slot).then(|| {
-        if is_known_spl_token_id(account.owner())
-            && params.encoding == UiAccountEncoding::JsonParsed
-        {
-            get_parsed_token_account(&bank, &params.pubkey, account, None)
-        } else {
-            encode_ui_account(&params.pubkey, &account, params.encoding, None, None)
-        }
+        cache.encode(
+            &bank,
+            &params.pubkey,
+            account,
+            params.encoding,
+            params.data_slice,
+        )
     });
     (account, last_modified_slot)
 }
 
+/// Notifies `subscription` of every tracked account modified since it was last notified,
+/// returns the number of notifications sent.
+fn notify_multi_account(
+    params: &MultiAccountSubscriptionParams,
+    subscription: &SubscriptionInfo,
+    bank: &Bank,
+    cache: &AccountNotificationCache,
+    notifier: &RpcNotifier,
+) -> usize {
+    let mut num_notified = 0;
+    let mut accounts = params.accounts.write();
+    for (pubkey, last_notified_slot) in accounts.iter_mut() {
+        // As for single accounts, a missing account has a `last_modified_slot` of zero
+        let (account, last_modified_slot) = cache.load(bank, pubkey).unwrap_or_default();
+        match last_notified_slot {
+            // Newly added accounts are skipped until `initialize_multi_accounts` has recorded
+            // their state at the time they were added
+            None => {}
+            Some(last_notified_slot) if *last_notified_slot != last_modified_slot => {
+                let account =
+                    cache.encode(bank, pubkey, account, params.encoding, params.data_slice);
+                notifier.notify(
+                    RpcResponse::from(RpcNotificationResponse {
+                        context: RpcNotificationContext { slot: bank.slot() },
+                        value: RpcKeyedAccount {
+                            pubkey: pubkey.to_string(),
+                            account,
+                        },
+                    }),
+                    subscription,
+                    false,
+                );
+                *last_notified_slot = last_modified_slot;
+                num_notified += 1;
+            }
+            Some(_) => {}
+        }
+    }
+    num_notified
+}
+
 fn filter_signature_result(
     result: Option<transaction::Result<()>>,
     _params: &SignatureSubscriptionParams,
@@ -418,6 +610,7 @@ fn filter_program_results(
 ) -> (impl Iterator<Item = RpcKeyedAccount>, Slot) {
     let accounts_is_empty = accounts.is_empty();
     let encoding = params.encoding;
+    let data_slice = params.data_slice;
     let filters = params.filters.clone();
     let keyed_accounts = accounts.into_iter().filter(move |(_, account)| {
         filters
@@ -433,7 +626,7 @@ fn filter_program_results(
     } else {
         let accounts = keyed_accounts.map(move |(pubkey, account)| RpcKeyedAccount {
             pubkey: pubkey.to_string(),
-            account: encode_ui_account(&pubkey, &account, encoding, None, None),
+            account: encode_ui_account(&pubkey, &account, encoding, None, data_slice),
         });
         Either::Right(accounts)
     };
@@ -446,12 +639,15 @@ fn filter_logs_results(
     last_notified_slot: Slot,
     _bank: Arc<Bank>,
 ) -> (impl Iterator<Item = RpcLogsResponse>, Slot) {
-    let responses = logs.into_iter().flatten().map(|log| RpcLogsResponse {
+    (logs_responses(logs), last_notified_slot)
+}
+
+fn logs_responses(logs: Option<Vec<TransactionLogInfo>>) -> impl Iterator<Item = RpcLogsResponse> {
+    logs.into_iter().flatten().map(|log| RpcLogsResponse {
         signature: log.signature.to_string(),
         err: log.result.err().map(Into::into),
         logs: log.log_messages,
-    });
-    (responses, last_notified_slot)
+    })
 }
 
 fn initial_last_notified_slot(
@@ -462,24 +658,234 @@ fn initial_last_notified_slot(
 ) -> Option<Slot> {
     match params {
         SubscriptionParams::Account(params) => {
-            let slot = if params.commitment.is_finalized() {
-                block_commitment_cache
-                    .read()
-                    .unwrap()
-                    .highest_super_majority_root()
-            } else if params.commitment.is_confirmed() {
-                optimistically_confirmed_bank.read().unwrap().bank.slot()
-            } else {
-                block_commitment_cache.read().unwrap().slot()
-            };
-
-            let bank = bank_forks.read().unwrap().get(slot)?;
+            let bank = commitment_bank(
+                params.commitment,
+                bank_forks,
+                block_commitment_cache,
+                optimistically_confirmed_bank,
+            )?;
             Some(bank.get_account_modified_slot(&params.pubkey)?.1)
         }
         _ => None,
     }
 }
 
+fn commitment_bank(
+    commitment: CommitmentConfig,
+    bank_forks: &RwLock<BankForks>,
+    block_commitment_cache: &RwLock<BlockCommitmentCache>,
+    optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
+) -> Option<Arc<Bank>> {
+    let slot = if commitment.is_finalized() {
+        block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root()
+    } else if commitment.is_confirmed() {
+        optimistically_confirmed_bank.read().unwrap().bank.slot()
+    } else {
+        block_commitment_cache.read().unwrap().slot()
+    };
+    bank_forks.read().unwrap().get(slot)
+}
+
+/// Records the current modified slot of every account added to a multi-account subscription
+/// since the last call, so that only later changes are notified.
+fn initialize_multi_accounts(
+    params: &MultiAccountSubscriptionParams,
+    bank_forks: &RwLock<BankForks>,
+    block_commitment_cache: &RwLock<BlockCommitmentCache>,
+    optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
+) {
+    let bank = commitment_bank(
+        params.commitment,
+        bank_forks,
+        block_commitment_cache,
+        optimistically_confirmed_bank,
+    );
+    for (pubkey, last_notified_slot) in params.accounts.write().iter_mut() {
+        if last_notified_slot.is_none() {
+            let modified_slot = bank
+                .as_ref()
+                .and_then(|bank| bank.get_account_modified_slot(pubkey))
+                .map(|(_, slot)| slot);
+            *last_notified_slot = Some(modified_slot.unwrap_or(0));
+        }
+    }
+}
+
+/// Sends the notifications of `subscription` buffered since `request.from_slot` to the connection
+/// that requested them. Signature subscriptions are answered from the status cache of the
+/// commitment bank instead, since a signature is only notified once.
+fn replay_notifications(
+    request: &ReplayRequest,
+    replay_buffer: &ReplayBuffer,
+    bank_forks: &RwLock<BankForks>,
+    block_commitment_cache: &RwLock<BlockCommitmentCache>,
+    optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
+    notifier: &RpcNotifier,
+) {
+    let from_slot = request.from_slot;
+    match &request.params {
+        SubscriptionParams::Slot => {
+            for slot_info in replay_buffer.slots(from_slot) {
+                notifier.replay(slot_info, request, false);
+            }
+        }
+        SubscriptionParams::Root => {
+            for root in replay_buffer.roots(from_slot) {
+                notifier.replay(root, request, false);
+            }
+        }
+        SubscriptionParams::Logs(params) => {
+            for (slot, log_collector) in replay_buffer.logs(from_slot, params.commitment) {
+                let logs = filter_transaction_logs(&log_collector.read().unwrap(), params);
+                for response in logs_responses(logs) {
+                    notifier.replay(
+                        RpcResponse::from(RpcNotificationResponse {
+                            context: RpcNotificationContext { slot },
+                            value: response,
+                        }),
+                        request,
+                        false,
+                    );
+                }
+            }
+        }
+        SubscriptionParams::Signature(params) => {
+            let bank = commitment_bank(
+                params.commitment,
+                bank_forks,
+                block_commitment_cache,
+                optimistically_confirmed_bank,
+            );
+            let status = bank.as_ref().and_then(|bank| {
+                let (slot, result) = bank.get_signature_status_slot(&params.signature)?;
+                (slot >= from_slot).then_some((bank.slot(), result))
+            });
+            if let Some((slot, result)) = status {
+                notifier.replay(
+                    RpcResponse::from(RpcNotificationResponse {
+                        context: RpcNotificationContext { slot },
+                        value: RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+                            err: result.err().map(Into::into),
+                        }),
+                    }),
+                    request,
+                    true,
+                );
+            }
+        }
+        params => error!("replay is not supported for {params:?}"),
+    }
+}
+
+/// Delivers `update` to the transaction subscriptions of the given commitment whose filters it
+/// passes. The transaction is encoded once per encoding and supported transaction version.
+fn notify_transaction_watchers(
+    update: &TransactionUpdate,
+    subscriptions: &HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
+    is_confirmed: bool,
+    notifier: &RpcNotifier,
+) {
+    let account_keys = update.transaction.account_keys();
+    let failed = update.transaction.meta.status.is_err();
+    let mut encoded_transactions = HashMap::new();
+    for subscription in subscriptions.values() {
+        let SubscriptionParams::Transaction(params) = subscription.params() else {
+            error!("invalid params type in notify_transaction_watchers");
+            continue;
+        };
+        if params.commitment.is_confirmed() != is_confirmed
+            || !params.matches(update.is_vote, failed, &account_keys)
+        {
+            continue;
+        }
+        let encoded_transaction = encoded_transactions
+            .entry((params.encoding, params.max_supported_transaction_version))
+            .or_insert_with(|| {
+                update
+                    .transaction
+                    .clone()
+                    .encode(
+                        params.encoding,
+                        params.max_supported_transaction_version,
+                        false,
+                    )
+                    .ok()
+            });
+        // Transactions with a version the subscriber does not support are not delivered
+        let Some(transaction) = encoded_transaction else {
+            continue;
+        };
+        notifier.notify(
+            RpcResponse::from(RpcNotificationResponse {
+                context: RpcNotificationContext { slot: update.slot },
+                value: RpcTransactionUpdate {
+                    signature: update.signature.to_string(),
+                    index: update.index,
+                    is_vote: update.is_vote,
+                    transaction: transaction.clone(),
+                },
+            }),
+            subscription,
+            false,
+        );
+    }
+}
+
+/// Transactions held back from confirmed transaction subscriptions until their slot is
+/// optimistically confirmed, and the slots confirmed since the last root.
+#[derive(Default)]
+struct PendingTransactionUpdates {
+    by_slot: HashMap<Slot, Vec<TransactionUpdate>>,
+    confirmed_slots: HashSet<Slot>,
+    root: Slot,
+}
+
+impl PendingTransactionUpdates {
+    /// Queues `update` unless its slot is already confirmed, in which case it is returned to be
+    /// delivered right away.
+    fn push(
+        &mut self,
+        update: TransactionUpdate,
+        blockstore: &Blockstore,
+    ) -> Option<TransactionUpdate> {
+        let is_confirmed = if update.slot <= self.root {
+            // Only reachable if the transaction status service lags behind roots
+            blockstore.is_root(update.slot)
+        } else {
+            self.confirmed_slots.contains(&update.slot)
+        };
+        if is_confirmed {
+            return Some(update);
+        }
+        if update.slot > self.root {
+            self.by_slot.entry(update.slot).or_default().push(update);
+        }
+        None
+    }
+
+    /// Marks `slot` confirmed and returns the transactions that were waiting for it.
+    fn confirm(&mut self, slot: Slot) -> Vec<TransactionUpdate> {
+        if slot <= self.root {
+            return vec![];
+        }
+        self.confirmed_slots.insert(slot);
+        self.by_slot.remove(&slot).unwrap_or_default()
+    }
+
+    /// Marks `root` confirmed and drops the transactions of every older slot, which were either
+    /// delivered already or belong to abandoned forks.
+    fn set_root(&mut self, root: Slot) -> Vec<TransactionUpdate> {
+        let updates = self.confirm(root);
+        self.root = self.root.max(root);
+        self.by_slot.retain(|slot, _| *slot > root);
+        self.confirmed_slots.retain(|slot| *slot > root);
+        updates
+    }
+}
+
 #[derive(Default)]
 struct PubsubNotificationStats {
     since: Option<Instant>,
@@ -618,7 +1024,10 @@ impl RpcSubscriptions {
     ) -> Self {
         let (notification_sender, notification_receiver) = crossbeam_channel::unbounded();
 
-        let subscriptions = SubscriptionsTracker::new(bank_forks.clone());
+        let subscriptions =
+            SubscriptionsTracker::new(bank_forks.clone(), config.replay_buffer_slots);
+        let replay_buffer = ReplayBuffer::new(config.replay_buffer_slots);
+        let is_replay_enabled = replay_buffer.is_enabled();
 
         let (broadcast_sender, _) = broadcast::channel(config.queue_capacity_items);
 
@@ -651,6 +1060,7 @@ impl RpcSubscriptions {
                             notifier,
                             notification_receiver,
                             subscriptions,
+                            replay_buffer,
                             bank_forks,
                             block_commitment_cache,
                             optimistically_confirmed_bank,
@@ -662,6 +1072,7 @@ impl RpcSubscriptions {
 
         let control = SubscriptionControl::new(
             config.max_active_subscriptions,
+            is_replay_enabled,
             notification_sender.clone(),
             broadcast_sender,
         );
@@ -731,6 +1142,33 @@ impl RpcSubscriptions {
         self.enqueue_notification(NotificationEntry::Vote((vote_pubkey, vote, signature)));
     }
 
+    /// Notify transaction subscribers of a transaction committed to the bank at `slot`.
+    pub fn notify_transaction(
+        &self,
+        slot: Slot,
+        index: usize,
+        signature: &Signature,
+        is_vote: bool,
+        meta: &TransactionStatusMeta,
+        transaction: &VersionedTransaction,
+    ) {
+        if !self.control.has_transaction_subscriptions() {
+            return;
+        }
+        self.enqueue_notification(NotificationEntry::Transaction(Box::new(
+            TransactionUpdate {
+                slot,
+                index,
+                signature: *signature,
+                is_vote,
+                transaction: VersionedTransactionWithStatusMeta {
+                    transaction: transaction.clone(),
+                    meta: meta.clone(),
+                },
+            },
+        )));
+    }
+
     pub fn notify_roots(&self, mut rooted_slots: Vec<Slot>) {
         rooted_slots.sort_unstable();
         rooted_slots.into_iter().for_each(|root| {
@@ -761,11 +1199,13 @@ impl RpcSubscriptions {
         notifier: RpcNotifier,
         notification_receiver: Receiver<TimestampedNotificationEntry>,
         mut subscriptions: SubscriptionsTracker,
+        mut replay_buffer: ReplayBuffer,
         bank_forks: Arc<RwLock<BankForks>>,
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     ) {
         let mut stats = PubsubNotificationStats::default();
+        let mut pending_transactions = PendingTransactionUpdates::default();
 
         loop {
             if exit.load(Ordering::Relaxed) {
@@ -776,6 +1216,14 @@ impl RpcSubscriptions {
                     let TimestampedNotificationEntry { entry, queued_at } = notification_entry;
                     match entry {
                         NotificationEntry::Subscribed(params, id) => {
+                            if let SubscriptionParams::MultiAccount(params) = &params {
+                                initialize_multi_accounts(
+                                    params,
+                                    &bank_forks,
+                                    &block_commitment_cache,
+                                    &optimistically_confirmed_bank,
+                                );
+                            }
                             subscriptions.subscribe(params.clone(), id, || {
                                 initial_last_notified_slot(
                                     &params,
@@ -789,7 +1237,16 @@ impl RpcSubscriptions {
                         NotificationEntry::Unsubscribed(params, id) => {
                             subscriptions.unsubscribe(params, id);
                         }
+                        NotificationEntry::MultiAccountsAdded(params) => {
+                            initialize_multi_accounts(
+                                &params,
+                                &bank_forks,
+                                &block_commitment_cache,
+                                &optimistically_confirmed_bank,
+                            );
+                        }
                         NotificationEntry::Slot(slot_info) => {
+                            replay_buffer.push_slot(slot_info);
                             if let Some(sub) = subscriptions
                                 .node_progress_watchers()
                                 .get(&SubscriptionParams::Slot)
@@ -829,6 +1286,8 @@ impl RpcSubscriptions {
                             }
                         }
                         NotificationEntry::Root(root) => {
+                            replay_buffer.push_root(root);
+                            subscriptions.set_root(root);
                             if let Some(sub) = subscriptions
                                 .node_progress_watchers()
                                 .get(&SubscriptionParams::Root)
@@ -837,8 +1296,31 @@ impl RpcSubscriptions {
                                 inc_new_counter_info!("rpc-subscription-notify-root", 1);
                                 notifier.notify(root, sub, false);
                             }
+                            for update in pending_transactions.set_root(root) {
+                                notify_transaction_watchers(
+                                    &update,
+                                    subscriptions.transaction_watchers(),
+                                    true,
+                                    &notifier,
+                                );
+                            }
                         }
                         NotificationEntry::Bank(commitment_slots) => {
+                            if replay_buffer.is_enabled() {
+                                let r_bank_forks = bank_forks.read().unwrap();
+                                let banks = [
+                                    (commitment_slots.slot, CommitmentConfig::processed()),
+                                    (
+                                        commitment_slots.highest_super_majority_root,
+                                        CommitmentConfig::finalized(),
+                                    ),
+                                ];
+                                for (slot, commitment) in banks {
+                                    if let Some(bank) = r_bank_forks.get(slot) {
+                                        replay_buffer.push_logs(&bank, commitment);
+                                    }
+                                }
+                            }
                             const SOURCE: &str = "bank";
                             RpcSubscriptions::notify_watchers(
                                 max_complete_transaction_status_slot.clone(),
@@ -851,6 +1333,11 @@ impl RpcSubscriptions {
                             );
                         }
                         NotificationEntry::Gossip(slot) => {
+                            if replay_buffer.is_enabled() {
+                                if let Some(bank) = bank_forks.read().unwrap().get(slot) {
+                                    replay_buffer.push_logs(&bank, CommitmentConfig::confirmed());
+                                }
+                            }
                             let commitment_slots = CommitmentSlots {
                                 highest_confirmed_slot: slot,
                                 ..CommitmentSlots::default()
@@ -865,6 +1352,47 @@ impl RpcSubscriptions {
                                 &notifier,
                                 SOURCE,
                             );
+                            for update in pending_transactions.confirm(slot) {
+                                notify_transaction_watchers(
+                                    &update,
+                                    subscriptions.transaction_watchers(),
+                                    true,
+                                    &notifier,
+                                );
+                            }
+                        }
+                        NotificationEntry::Transaction(update) => {
+                            let watchers = subscriptions.transaction_watchers();
+                            notify_transaction_watchers(&update, watchers, false, &notifier);
+                            // Confirmed subscribers only get the transaction once its slot is
+                            // optimistically confirmed
+                            let has_confirmed_watchers = watchers.values().any(|subscription| {
+                                subscription
+                                    .commitment()
+                                    .is_some_and(|commitment| commitment.is_confirmed())
+                            });
+                            if has_confirmed_watchers {
+                                if let Some(update) =
+                                    pending_transactions.push(*update, &blockstore)
+                                {
+                                    notify_transaction_watchers(&update, watchers, true, &notifier);
+                                }
+                            }
+                        }
+                        NotificationEntry::Replay(request) => {
+                            // The subscription may already be gone from the tracker, e.g. a
+                            // signature subscription notified live just before this request,
+                            // so the notifications are built from the request alone
+                            inc_new_counter_info!("rpc-subscription-replay", 1);
+                            replay_notifications(
+                                &request,
+                                &replay_buffer,
+                                &bank_forks,
+                                &block_commitment_cache,
+                                &optimistically_confirmed_bank,
+                                &notifier,
+                            );
+                            notifier.replay_done(request.id, request.replay_id);
                         }
                         NotificationEntry::SignaturesReceived((slot, slot_signatures)) => {
                             for slot_signature in &slot_signatures {
@@ -921,9 +1449,14 @@ impl RpcSubscriptions {
     ) {
         let mut total_time = Measure::start("notify_watchers");
 
+        let account_cache = AccountNotificationCache::default();
+
         let num_accounts_found = AtomicUsize::new(0);
         let num_accounts_notified = AtomicUsize::new(0);
 
+        let num_multi_accounts_found = AtomicUsize::new(0);
+        let num_multi_accounts_notified = AtomicUsize::new(0);
+
         let num_blocks_found = AtomicUsize::new(0);
         let num_blocks_notified = AtomicUsize::new(0);
 
@@ -959,8 +1492,16 @@ impl RpcSubscriptions {
                             subscription,
                             bank_forks,
                             slot,
-                            |bank, params| bank.get_account_modified_slot(&params.pubkey),
-                            filter_account_result,
+                            |bank, params| account_cache.load(bank, &params.pubkey),
+                            |result, params, last_notified_slot, bank| {
+                                filter_account_result(
+                                    result,
+                                    params,
+                                    last_notified_slot,
+                                    bank,
+                                    &account_cache,
+                                )
+                            },
                             notifier,
                             false,
                         );
@@ -970,6 +1511,20 @@ impl RpcSubscriptions {
                         }
                     }
                 }
+                SubscriptionParams::MultiAccount(params) => {
+                    num_multi_accounts_found.fetch_add(1, Ordering::Relaxed);
+                    let bank = slot.and_then(|slot| bank_forks.read().unwrap().get(slot));
+                    if let Some(bank) = bank {
+                        let notified = notify_multi_account(
+                            params,
+                            subscription,
+                            &bank,
+                            &account_cache,
+                            notifier,
+                        );
+                        num_multi_accounts_notified.fetch_add(notified, Ordering::Relaxed);
+                    }
+                }
                 SubscriptionParams::Block(params) => {
                     num_blocks_found.fetch_add(1, Ordering::Relaxed);
                     if let Some(slot) = slot {
@@ -1123,17 +1678,20 @@ impl RpcSubscriptions {
         total_time.stop();
 
         let total_notified = num_accounts_notified.load(Ordering::Relaxed)
+            + num_multi_accounts_notified.load(Ordering::Relaxed)
             + num_logs_notified.load(Ordering::Relaxed)
             + num_programs_notified.load(Ordering::Relaxed)
             + num_signatures_notified.load(Ordering::Relaxed);
         let total_ms = total_time.as_ms();
         if total_notified > 0 || total_ms > 10 {
             debug!(
-                "notified({}): accounts: {} / {} logs: {} / {} programs: {} / {} signatures: {} / \
-                 {}",
+                "notified({}): accounts: {} / {} multi-accounts: {} / {} logs: {} / {} programs: \
+                 {} / {} signatures: {} / {}",
                 source,
                 num_accounts_found.load(Ordering::Relaxed),
                 num_accounts_notified.load(Ordering::Relaxed),
+                num_multi_accounts_found.load(Ordering::Relaxed),
+                num_multi_accounts_notified.load(Ordering::Relaxed),
                 num_logs_found.load(Ordering::Relaxed),
                 num_logs_notified.load(Ordering::Relaxed),
                 num_programs_found.load(Ordering::Relaxed),
@@ -1154,6 +1712,16 @@ impl RpcSubscriptions {
                     num_accounts_notified.load(Ordering::Relaxed),
                     i64
                 ),
+                (
+                    "num_multi_account_subscriptions",
+                    num_multi_accounts_found.load(Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "num_multi_account_pubkeys_notified",
+                    num_multi_accounts_notified.load(Ordering::Relaxed),
+                    i64
+                ),
                 (
                     "num_logs_subscriptions",
                     num_logs_found.load(Ordering::Relaxed),
@@ -1208,6 +1776,53 @@ impl RpcSubscriptions {
     }
 }
 
+/// Forwards the transactions recorded by the transaction status service to `transactionSubscribe`
+/// subscribers.
+///
+/// The transaction status service is started before `RpcSubscriptions`, so the notifier is
+/// created empty and attached to the subscriptions once they exist. Transactions committed before
+/// that are dropped.
+#[derive(Default)]
+pub struct RpcTransactionNotifier {
+    subscriptions: OnceLock<Weak<RpcSubscriptions>>,
+}
+
+impl RpcTransactionNotifier {
+    pub fn attach(&self, subscriptions: &Arc<RpcSubscriptions>) {
+        if self
+            .subscriptions
+            .set(Arc::downgrade(subscriptions))
+            .is_err()
+        {
+            warn!("RpcTransactionNotifier is already attached");
+        }
+    }
+}
+
+impl TransactionNotifier for RpcTransactionNotifier {
+    fn notify_transaction(
+        &self,
+        slot: Slot,
+        transaction_slot_index: usize,
+        signature: &Signature,
+        _message_hash: &Hash,
+        is_vote: bool,
+        transaction_status_meta: &TransactionStatusMeta,
+        transaction: &VersionedTransaction,
+    ) {
+        if let Some(subscriptions) = self.subscriptions.get().and_then(Weak::upgrade) {
+            subscriptions.notify_transaction(
+                slot,
+                transaction_slot_index,
+                signature,
+                is_vote,
+                transaction_status_meta,
+                transaction,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use {
@@ -1227,8 +1842,9 @@ pub(crate) mod tests {
         solana_message::Message,
         solana_rpc_client_api::config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcSlotSubscribeConfig,
+            RpcTransactionLogsConfig, RpcTransactionLogsFilter, RpcTransactionSubscribeConfig,
+            RpcTransactionSubscribeFilter,
         },
         solana_runtime::{
             commitment::BlockCommitment,
@@ -1418,6 +2034,236 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_check_account_and_multi_account_subscribe() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                1, 1,
+            ))),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+        let config = Some(RpcAccountInfoConfig {
+            commitment: Some(CommitmentConfig::processed()),
+            encoding: None,
+            data_slice: None,
+            min_context_slot: None,
+        });
+
+        let (account_rpc, mut account_receiver) =
+            rpc_pubsub_service::test_connection(&subscriptions);
+        account_rpc
+            .account_subscribe(alice.pubkey().to_string(), config.clone())
+            .unwrap();
+        let (multi_account_rpc, mut multi_account_receiver) =
+            rpc_pubsub_service::test_connection(&subscriptions);
+        multi_account_rpc
+            .multi_account_subscribe(
+                vec![alice.pubkey().to_string(), bob.pubkey().to_string()],
+                config,
+            )
+            .unwrap();
+        multi_account_rpc.block_until_processed(&subscriptions);
+
+        let tx = system_transaction::create_account(
+            &mint_keypair,
+            &alice,
+            blockhash,
+            1,
+            0,
+            &system_program::id(),
+        );
+        bank_forks
+            .read()
+            .unwrap()
+            .get(1)
+            .unwrap()
+            .process_transaction(&tx)
+            .unwrap();
+        subscriptions.notify_subscribers(CommitmentSlots {
+            slot: 1,
+            ..CommitmentSlots::default()
+        });
+
+        let expected = make_account_result(
+            true,
+            AccountResult {
+                lamports: 1,
+                subscription: 0,
+                space: 0,
+                data: "",
+            },
+        );
+        let response = account_receiver.recv();
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+
+        // Only the account that changed is notified, keyed by its pubkey
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "multiAccountNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 1 },
+                   "value": {
+                       "pubkey": alice.pubkey().to_string(),
+                       "account": expected["params"]["result"]["value"],
+                   },
+               },
+               "subscription": 1,
+           }
+        });
+        let response = multi_account_receiver.recv();
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_transaction_subscribe() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+        let subscribe = |filter, commitment| {
+            let (rpc, receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+            let config = RpcTransactionSubscribeConfig {
+                commitment: Some(commitment),
+                encoding: Some(UiTransactionEncoding::Base64),
+                max_supported_transaction_version: Some(0),
+            };
+            let id = rpc.transaction_subscribe(filter, Some(config)).unwrap();
+            (rpc, receiver, u64::from(id))
+        };
+        let alice_filter = RpcTransactionSubscribeFilter {
+            account_include: vec![alice.pubkey().to_string()],
+            ..RpcTransactionSubscribeFilter::default()
+        };
+        let (_processed_rpc, mut processed_receiver, processed_id) =
+            subscribe(alice_filter.clone(), CommitmentConfig::processed());
+        let (_confirmed_rpc, mut confirmed_receiver, confirmed_id) =
+            subscribe(alice_filter, CommitmentConfig::confirmed());
+        let (_excluded_rpc, mut excluded_receiver, _) = subscribe(
+            RpcTransactionSubscribeFilter {
+                account_exclude: vec![alice.pubkey().to_string()],
+                ..RpcTransactionSubscribeFilter::default()
+            },
+            CommitmentConfig::processed(),
+        );
+        let (_bob_rpc, mut bob_receiver, _) = subscribe(
+            RpcTransactionSubscribeFilter {
+                account_required: vec![alice.pubkey().to_string(), bob.pubkey().to_string()],
+                ..RpcTransactionSubscribeFilter::default()
+            },
+            CommitmentConfig::processed(),
+        );
+
+        let tx = VersionedTransaction::from(system_transaction::transfer(
+            &mint_keypair,
+            &alice.pubkey(),
+            1,
+            blockhash,
+        ));
+        let signature = tx.signatures[0];
+        subscriptions.notify_transaction(
+            1,
+            3,
+            &signature,
+            false,
+            &TransactionStatusMeta::default(),
+            &tx,
+        );
+
+        let expected = |subscription| {
+            json!({
+               "jsonrpc": "2.0",
+               "method": "transactionNotification",
+               "params": {
+                   "result": {
+                       "context": { "slot": 1 },
+                       "value": {
+                           "signature": signature.to_string(),
+                           "index": 3,
+                           "isVote": false,
+                       },
+                   },
+                   "subscription": subscription,
+               }
+            })
+        };
+        let check = |response: String, subscription| {
+            let mut response = serde_json::from_str::<serde_json::Value>(&response).unwrap();
+            let transaction = response["params"]["result"]["value"]
+                .as_object_mut()
+                .unwrap()
+                .remove("transaction")
+                .unwrap();
+            assert_eq!(transaction["version"], json!("legacy"));
+            assert_eq!(expected(subscription), response);
+        };
+        check(processed_receiver.recv(), processed_id);
+
+        // Confirmed subscribers wait for the slot to be optimistically confirmed
+        let timeout = Duration::from_millis(500);
+        assert!(confirmed_receiver.recv_timeout(timeout).is_err());
+        subscriptions.notify_gossip_subscribers(1);
+        check(confirmed_receiver.recv(), confirmed_id);
+
+        assert!(excluded_receiver.recv_timeout(timeout).is_err());
+        assert!(bob_receiver.recv_timeout(timeout).is_err());
+
+        // Transactions of an already confirmed slot are delivered right away
+        subscriptions.notify_transaction(
+            1,
+            3,
+            &signature,
+            false,
+            &TransactionStatusMeta::default(),
+            &tx,
+        );
+        check(processed_receiver.recv(), processed_id);
+        check(confirmed_receiver.recv(), confirmed_id);
+    }
+
     #[test]
     #[serial]
     fn test_check_confirmed_block_subscribe() {
@@ -2475,6 +3321,7 @@ pub(crate) mod tests {
                 Some(RpcSignatureSubscribeConfig {
                     commitment: Some(CommitmentConfig::processed()),
                     enable_received_notification: Some(false),
+                    from_slot: None,
                 }),
             )
             .unwrap();
@@ -2484,6 +3331,7 @@ pub(crate) mod tests {
                 Some(RpcSignatureSubscribeConfig {
                     commitment: Some(CommitmentConfig::finalized()),
                     enable_received_notification: Some(false),
+                    from_slot: None,
                 }),
             )
             .unwrap();
@@ -2493,6 +3341,7 @@ pub(crate) mod tests {
                 Some(RpcSignatureSubscribeConfig {
                     commitment: Some(CommitmentConfig::processed()),
                     enable_received_notification: Some(false),
+                    from_slot: None,
                 }),
             )
             .unwrap();
@@ -2502,6 +3351,7 @@ pub(crate) mod tests {
                 Some(RpcSignatureSubscribeConfig {
                     commitment: Some(CommitmentConfig::processed()),
                     enable_received_notification: Some(false),
+                    from_slot: None,
                 }),
             )
             .unwrap();
@@ -2513,6 +3363,7 @@ pub(crate) mod tests {
                 Some(RpcSignatureSubscribeConfig {
                     commitment: Some(CommitmentConfig::processed()),
                     enable_received_notification: Some(true),
+                    from_slot: None,
                 }),
             )
             .unwrap();
@@ -2633,7 +3484,7 @@ pub(crate) mod tests {
             optimistically_confirmed_bank,
         ));
         let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
-        let sub_id = rpc.slot_subscribe().unwrap();
+        let sub_id = rpc.slot_subscribe(None).unwrap();
 
         subscriptions
             .control
@@ -2678,7 +3529,7 @@ pub(crate) mod tests {
             optimistically_confirmed_bank,
         ));
         let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
-        let sub_id = rpc.root_subscribe().unwrap();
+        let sub_id = rpc.root_subscribe(None).unwrap();
 
         subscriptions
             .control
@@ -2703,6 +3554,113 @@ pub(crate) mod tests {
             .assert_unsubscribed(&SubscriptionParams::Root);
     }
 
+    #[test]
+    #[serial]
+    fn test_slot_subscribe_from_slot() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            bank_forks,
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            optimistically_confirmed_bank,
+        ));
+
+        // Notified before anyone subscribes, so only kept in the replay buffer
+        for slot in 1..=3 {
+            subscriptions.notify_slot(slot, slot - 1, 0);
+        }
+
+        let (live_rpc, mut live_receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let (resumed_rpc, mut resumed_receiver) =
+            rpc_pubsub_service::test_connection(&subscriptions);
+        live_rpc.slot_subscribe(None).unwrap();
+        resumed_rpc
+            .slot_subscribe(Some(RpcSlotSubscribeConfig { from_slot: Some(2) }))
+            .unwrap();
+
+        subscriptions.notify_slot(4, 3, 0);
+
+        let expected = |slot| {
+            let expected_res_str = serde_json::to_string(&SlotInfo {
+                parent: slot - 1,
+                slot,
+                root: 0,
+            })
+            .unwrap();
+            format!(
+                r#"{{"jsonrpc":"2.0","method":"slotNotification","params":{{"result":{expected_res_str},"subscription":0}}}}"#
+            )
+        };
+        for slot in 2..=4 {
+            assert_eq!(expected(slot), resumed_receiver.recv());
+        }
+        // The replay is only sent to the connection that asked for it
+        assert_eq!(expected(4), live_receiver.recv());
+        assert!(live_receiver
+            .recv_timeout(Duration::from_millis(300))
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_signature_subscribe_from_slot() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let tx = system_transaction::transfer(&mint_keypair, &Pubkey::new_unique(), 1, blockhash);
+        bank.process_transaction(&tx).unwrap();
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            bank_forks,
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            optimistically_confirmed_bank,
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id = rpc
+            .signature_subscribe(
+                tx.signatures[0].to_string(),
+                Some(RpcSignatureSubscribeConfig {
+                    commitment: Some(CommitmentConfig::processed()),
+                    from_slot: Some(0),
+                    ..RpcSignatureSubscribeConfig::default()
+                }),
+            )
+            .unwrap();
+
+        // The transaction was processed before subscribing, so the notification comes from the
+        // replay rather than from a new bank
+        let expected = json!({
+            "jsonrpc": "2.0",
+            "method": "signatureNotification",
+            "params": {
+                "result": {
+                    "context": { "slot": 0 },
+                    "value": { "err": null },
+                },
+                "subscription": u64::from(sub_id),
+            }
+        });
+        assert_eq!(serde_json::to_string(&expected).unwrap(), receiver.recv());
+    }
+
     #[test]
     #[serial]
     fn test_gossip_separate_account_notifications() {
@@ -2951,6 +3909,7 @@ pub(crate) mod tests {
 
         let sub_config = RpcTransactionLogsConfig {
             commitment: Some(CommitmentConfig::processed()),
+            from_slot: None,
         };
 
         let (rpc_all, mut receiver_all) = rpc_pubsub_service::test_connection(&subscriptions);
@@ -3048,7 +4007,7 @@ pub(crate) mod tests {
         assert_eq!(subscriptions.total(), 4);
 
         let (rpc5, _receiver5) = rpc_pubsub_service::test_connection(&subscriptions);
-        let sub_id5 = rpc5.slot_subscribe().unwrap();
+        let sub_id5 = rpc5.slot_subscribe(None).unwrap();
 
         assert_eq!(subscriptions.total(), 5);
 
@@ -3058,7 +4017,7 @@ pub(crate) mod tests {
         assert_eq!(subscriptions.total(), 6);
 
         let (rpc7, _receiver7) = rpc_pubsub_service::test_connection(&subscriptions);
-        let sub_id7 = rpc7.root_subscribe().unwrap();
+        let sub_id7 = rpc7.root_subscribe(None).unwrap();
 
         assert_eq!(subscriptions.total(), 7);
 
@@ -18,3 +18,31 @@ pub trait TransactionNotifier {
 }
 
 pub type TransactionNotifierArc = Arc<dyn TransactionNotifier + Sync + Send>;
+
+/// Forwards every transaction to each of the wrapped notifiers, in order.
+pub struct TransactionNotifiers(pub Vec<TransactionNotifierArc>);
+
+impl TransactionNotifier for TransactionNotifiers {
+    fn notify_transaction(
+        &self,
+        slot: Slot,
+        transaction_slot_index: usize,
+        signature: &Signature,
+        message_hash: &Hash,
+        is_vote: bool,
+        transaction_status_meta: &TransactionStatusMeta,
+        transaction: &VersionedTransaction,
+    ) {
+        for notifier in &self.0 {
+            notifier.notify_transaction(
+                slot,
+                transaction_slot_index,
+                signature,
+                message_hash,
+                is_vote,
+                transaction_status_meta,
+                transaction,
+            );
+        }
+    }
+}
@@ -106,7 +106,7 @@ use {
     solana_keypair::Keypair,
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{measure::Measure, measure_time, measure_us},
-    solana_message::{inner_instruction::InnerInstructions, AccountKeys, SanitizedMessage},
+    solana_message::{inner_instruction::InnerInstructions, SanitizedMessage},
     solana_native_token::LAMPORTS_PER_SOL,
     solana_packet::PACKET_DATA_SIZE,
     solana_precompile_error::PrecompileError,
@@ -135,7 +135,7 @@ use {
         transaction_commit_result::{CommittedTransaction, TransactionCommitResult},
         transaction_error_metrics::TransactionErrorMetrics,
         transaction_execution_result::{
-            TransactionExecutionDetails, TransactionLoadedAccountsStats,
+            InstructionTrace, TransactionExecutionDetails, TransactionLoadedAccountsStats,
         },
         transaction_processing_result::{
             ProcessedTransaction, TransactionProcessingResult,
@@ -303,11 +303,25 @@ pub struct LoadAndExecuteTransactionsOutput {
     pub balance_collector: Option<BalanceCollector>,
 }
 
+/// Options for [`Bank::simulate_transaction_with_config`] and
+/// [`Bank::simulate_transactions_with_config`]
+#[derive(Default)]
+pub struct TransactionSimulationConfig {
+    pub enable_cpi_recording: bool,
+    /// Record the compute units and account accesses of every instruction
+    pub enable_instruction_trace_recording: bool,
+    /// Accounts which replace the bank's during simulation
+    pub account_overrides: AccountOverrides,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct TransactionSimulationResult {
     pub result: Result<()>,
     pub logs: TransactionLogMessages,
     pub post_simulation_accounts: Vec<TransactionAccount>,
+    /// Accounts a failed transaction still changes, i.e. the fee payer charged with the fee and
+    /// the advanced nonce; empty for successful transactions
+    pub rollback_accounts: Vec<TransactionAccount>,
     pub units_consumed: u64,
     pub loaded_accounts_data_size: u32,
     pub return_data: Option<TransactionReturnData>,
@@ -317,6 +331,7 @@ pub struct TransactionSimulationResult {
     pub post_balances: Option<Vec<u64>>,
     pub pre_token_balances: Option<Vec<SvmTokenInfo>>,
     pub post_token_balances: Option<Vec<SvmTokenInfo>>,
+    pub instruction_trace: Option<Vec<InstructionTrace>>,
 }
 
 #[derive(Clone, Debug)]
@@ -2991,13 +3006,25 @@ impl Bank {
         &'a self,
         transaction: &'a Tx,
     ) -> TransactionBatch<'a, 'a, Tx> {
+        self.prepare_unlocked_batch(slice::from_ref(transaction))
+    }
+
+    /// Prepare a transaction batch from a list of transactions without locking accounts
+    ///
+    /// Since no locks are taken, the transactions may write to the same accounts.
+    pub fn prepare_unlocked_batch<'a, 'b, Tx: SVMMessage>(
+        &'a self,
+        transactions: &'b [Tx],
+    ) -> TransactionBatch<'a, 'b, Tx> {
         let tx_account_lock_limit = self.get_transaction_account_lock_limit();
-        let lock_result = validate_account_locks(transaction.account_keys(), tx_account_lock_limit);
-        let mut batch = TransactionBatch::new(
-            vec![lock_result],
-            self,
-            OwnedOrBorrowed::Borrowed(slice::from_ref(transaction)),
-        );
+        let lock_results = transactions
+            .iter()
+            .map(|transaction| {
+                validate_account_locks(transaction.account_keys(), tx_account_lock_limit)
+            })
+            .collect();
+        let mut batch =
+            TransactionBatch::new(lock_results, self, OwnedOrBorrowed::Borrowed(transactions));
         batch.set_needs_unlock(false);
         batch
     }
@@ -3028,14 +3055,82 @@ impl Bank {
         transaction: &impl TransactionWithMeta,
         enable_cpi_recording: bool,
     ) -> TransactionSimulationResult {
-        let account_keys = transaction.account_keys();
-        let number_of_accounts = account_keys.len();
-        let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
-        let batch = self.prepare_unlocked_batch_from_single_tx(transaction);
+        self.simulate_transactions_unchecked(slice::from_ref(transaction), enable_cpi_recording)
+            .pop()
+            .expect("one simulation result per transaction")
+    }
+
+    /// Run a transaction against a frozen bank without committing the results, with the
+    /// recording options and account overrides of `config`
+    pub fn simulate_transaction_with_config(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        config: TransactionSimulationConfig,
+    ) -> TransactionSimulationResult {
+        self.simulate_transactions_with_config(slice::from_ref(transaction), config)
+            .pop()
+            .expect("one simulation result per transaction")
+    }
+
+    /// Run an ordered list of transactions against a frozen bank without committing the results
+    ///
+    /// The transactions are executed sequentially as a single batch, so each transaction sees the
+    /// account changes made by the transactions before it.
+    pub fn simulate_transactions<Tx: TransactionWithMeta>(
+        &self,
+        transactions: &[Tx],
+        enable_cpi_recording: bool,
+    ) -> Vec<TransactionSimulationResult> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_transactions_unchecked(transactions, enable_cpi_recording)
+    }
+
+    /// Run an ordered list of transactions against a frozen bank without committing the results,
+    /// with the recording options and account overrides of `config`
+    pub fn simulate_transactions_with_config<Tx: TransactionWithMeta>(
+        &self,
+        transactions: &[Tx],
+        config: TransactionSimulationConfig,
+    ) -> Vec<TransactionSimulationResult> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_transactions_with_config_unchecked(transactions, config)
+    }
+
+    /// Run an ordered list of transactions against a bank without committing the results; does
+    /// not check if the bank is frozen, enabling use in single-Bank test frameworks
+    pub fn simulate_transactions_unchecked<Tx: TransactionWithMeta>(
+        &self,
+        transactions: &[Tx],
+        enable_cpi_recording: bool,
+    ) -> Vec<TransactionSimulationResult> {
+        self.simulate_transactions_with_config_unchecked(
+            transactions,
+            TransactionSimulationConfig {
+                enable_cpi_recording,
+                ..TransactionSimulationConfig::default()
+            },
+        )
+    }
+
+    fn simulate_transactions_with_config_unchecked<Tx: TransactionWithMeta>(
+        &self,
+        transactions: &[Tx],
+        config: TransactionSimulationConfig,
+    ) -> Vec<TransactionSimulationResult> {
+        let TransactionSimulationConfig {
+            enable_cpi_recording,
+            enable_instruction_trace_recording,
+            account_overrides,
+        } = config;
+        let account_overrides =
+            self.get_account_overrides_for_simulation(transactions, account_overrides);
+        let batch = self.prepare_unlocked_batch(transactions);
         let mut timings = ExecuteTimings::default();
 
         let LoadAndExecuteTransactionsOutput {
-            mut processing_results,
+            processing_results,
             balance_collector,
             ..
         } = self.load_and_execute_transactions(
@@ -3056,101 +3151,138 @@ impl Bank {
                     enable_log_recording: true,
                     enable_return_data_recording: true,
                     enable_transaction_balance_recording: true,
+                    enable_instruction_trace_recording,
                 },
             },
         );
 
-        debug!("simulate_transaction: {timings:?}");
-
-        let processing_result = processing_results
-            .pop()
-            .unwrap_or(Err(TransactionError::InvalidProgramForExecution));
-        let (
-            post_simulation_accounts,
-            result,
-            fee,
-            logs,
-            return_data,
-            inner_instructions,
-            units_consumed,
-            loaded_accounts_data_size,
-        ) = match processing_result {
-            Ok(processed_tx) => {
-                let executed_units = processed_tx.executed_units();
-                let loaded_accounts_data_size = processed_tx.loaded_accounts_data_size();
-
-                match processed_tx {
-                    ProcessedTransaction::Executed(executed_tx) => {
-                        let details = executed_tx.execution_details;
-                        let post_simulation_accounts = executed_tx
-                            .loaded_transaction
-                            .accounts
-                            .into_iter()
-                            .take(number_of_accounts)
-                            .collect::<Vec<_>>();
-                        (
-                            post_simulation_accounts,
-                            details.status,
-                            Some(executed_tx.loaded_transaction.fee_details.total_fee()),
-                            details.log_messages,
-                            details.return_data,
-                            details.inner_instructions,
-                            executed_units,
-                            loaded_accounts_data_size,
-                        )
-                    }
-                    ProcessedTransaction::FeesOnly(fees_only_tx) => (
-                        vec![],
-                        Err(fees_only_tx.load_error),
-                        Some(fees_only_tx.fee_details.total_fee()),
-                        None,
-                        None,
-                        None,
-                        executed_units,
-                        loaded_accounts_data_size,
-                    ),
-                }
-            }
-            Err(error) => (vec![], Err(error), None, None, None, None, 0, 0),
-        };
-        let logs = logs.unwrap_or_default();
+        debug!("simulate_transactions: {timings:?}");
 
         let (pre_balances, post_balances, pre_token_balances, post_token_balances) =
             match balance_collector {
                 Some(balance_collector) => {
-                    let (mut native_pre, mut native_post, mut token_pre, mut token_post) =
+                    let (native_pre, native_post, token_pre, token_post) =
                         balance_collector.into_vecs();
 
                     (
-                        native_pre.pop(),
-                        native_post.pop(),
-                        token_pre.pop(),
-                        token_post.pop(),
+                        native_pre.into_iter().map(Some).collect::<Vec<_>>(),
+                        native_post.into_iter().map(Some).collect::<Vec<_>>(),
+                        token_pre.into_iter().map(Some).collect::<Vec<_>>(),
+                        token_post.into_iter().map(Some).collect::<Vec<_>>(),
                     )
                 }
-                None => (None, None, None, None),
+                None => (vec![], vec![], vec![], vec![]),
             };
+        let mut pre_balances = pre_balances.into_iter();
+        let mut post_balances = post_balances.into_iter();
+        let mut pre_token_balances = pre_token_balances.into_iter();
+        let mut post_token_balances = post_token_balances.into_iter();
 
-        TransactionSimulationResult {
-            result,
-            logs,
-            post_simulation_accounts,
-            units_consumed,
-            loaded_accounts_data_size,
-            return_data,
-            inner_instructions,
-            fee,
-            pre_balances,
-            post_balances,
-            pre_token_balances,
-            post_token_balances,
-        }
+        transactions
+            .iter()
+            .zip(processing_results)
+            .map(|(transaction, processing_result)| {
+                let number_of_accounts = transaction.account_keys().len();
+                let (
+                    post_simulation_accounts,
+                    rollback_accounts,
+                    result,
+                    fee,
+                    logs,
+                    return_data,
+                    inner_instructions,
+                    units_consumed,
+                    loaded_accounts_data_size,
+                    instruction_trace,
+                ) = match processing_result {
+                    Ok(processed_tx) => {
+                        let executed_units = processed_tx.executed_units();
+                        let loaded_accounts_data_size = processed_tx.loaded_accounts_data_size();
+
+                        match processed_tx {
+                            ProcessedTransaction::Executed(executed_tx) => {
+                                let details = executed_tx.execution_details;
+                                let rollback_accounts = if details.status.is_err() {
+                                    executed_tx
+                                        .loaded_transaction
+                                        .rollback_accounts
+                                        .iter()
+                                        .cloned()
+                                        .collect()
+                                } else {
+                                    vec![]
+                                };
+                                let post_simulation_accounts = executed_tx
+                                    .loaded_transaction
+                                    .accounts
+                                    .into_iter()
+                                    .take(number_of_accounts)
+                                    .collect::<Vec<_>>();
+                                (
+                                    post_simulation_accounts,
+                                    rollback_accounts,
+                                    details.status,
+                                    Some(executed_tx.loaded_transaction.fee_details.total_fee()),
+                                    details.log_messages,
+                                    details.return_data,
+                                    details.inner_instructions,
+                                    executed_units,
+                                    loaded_accounts_data_size,
+                                    details.instruction_trace,
+                                )
+                            }
+                            ProcessedTransaction::FeesOnly(fees_only_tx) => (
+                                vec![],
+                                fees_only_tx.rollback_accounts.iter().cloned().collect(),
+                                Err(fees_only_tx.load_error),
+                                Some(fees_only_tx.fee_details.total_fee()),
+                                None,
+                                None,
+                                None,
+                                executed_units,
+                                loaded_accounts_data_size,
+                                None,
+                            ),
+                        }
+                    }
+                    Err(error) => (vec![], vec![], Err(error), None, None, None, 0, 0, None),
+                };
+                let logs = logs.unwrap_or_default();
+
+                TransactionSimulationResult {
+                    result,
+                    logs,
+                    post_simulation_accounts,
+                    rollback_accounts,
+                    units_consumed,
+                    loaded_accounts_data_size,
+                    return_data,
+                    inner_instructions,
+                    fee,
+                    pre_balances: pre_balances.next().flatten(),
+                    post_balances: post_balances.next().flatten(),
+                    pre_token_balances: pre_token_balances.next().flatten(),
+                    post_token_balances: post_token_balances.next().flatten(),
+                    instruction_trace,
+                }
+            })
+            .collect()
     }
 
-    fn get_account_overrides_for_simulation(&self, account_keys: &AccountKeys) -> AccountOverrides {
-        let mut account_overrides = AccountOverrides::default();
+    fn get_account_overrides_for_simulation(
+        &self,
+        transactions: &[impl SVMMessage],
+        mut account_overrides: AccountOverrides,
+    ) -> AccountOverrides {
         let slot_history_id = sysvar::slot_history::id();
-        if account_keys.iter().any(|pubkey| *pubkey == slot_history_id) {
+        if account_overrides.get(&slot_history_id).is_none()
+            && transactions.iter().any(|transaction| {
+                transaction
+                    .account_keys()
+                    .iter()
+                    .any(|pubkey| *pubkey == slot_history_id)
+            })
+        {
             let current_account = self.get_account_with_fixed_root(&slot_history_id);
             let slot_history = current_account
                 .as_ref()
@@ -3822,6 +3954,7 @@ impl Bank {
                 enable_log_recording: true,
                 enable_return_data_recording: true,
                 enable_transaction_balance_recording: false,
+                enable_instruction_trace_recording: false,
             },
             &mut ExecuteTimings::default(),
             Some(1000 * 1000),
@@ -4209,6 +4342,25 @@ impl Bank {
         )
     }
 
+    /// Returns up to `limit` of the program's accounts that pass `filter`, in pubkey order,
+    /// starting after `start_after` if any
+    pub fn get_filtered_program_accounts_in_order<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        program_id: &Pubkey,
+        filter: F,
+        start_after: Option<Pubkey>,
+        limit: usize,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        self.rc.accounts.load_by_program_with_filter_in_order(
+            &self.ancestors,
+            self.bank_id,
+            program_id,
+            filter,
+            start_after,
+            limit,
+        )
+    }
+
     pub fn get_filtered_indexed_accounts<F: Fn(&AccountSharedData) -> bool>(
         &self,
         index_key: &IndexKey,
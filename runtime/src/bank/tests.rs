@@ -99,7 +99,7 @@ use {
         account_loader::{FeesOnlyTransaction, LoadedTransaction},
         rollback_accounts::RollbackAccounts,
         transaction_commit_result::TransactionCommitResultExtensions,
-        transaction_execution_result::ExecutedTransaction,
+        transaction_execution_result::{ExecutedTransaction, InstructionTraceAccount},
     },
     solana_svm_timings::ExecuteTimings,
     solana_svm_transaction::svm_message::SVMMessage,
@@ -257,6 +257,7 @@ fn new_executed_processing_result(
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                instruction_trace: None,
             },
             programs_modified_by_tx: HashMap::new(),
         },
@@ -4776,6 +4777,7 @@ fn test_pre_post_transaction_balances() {
             enable_log_recording: false,
             enable_return_data_recording: false,
             enable_transaction_balance_recording: true,
+            enable_instruction_trace_recording: false,
         },
         &mut ExecuteTimings::default(),
         None,
@@ -8422,6 +8424,7 @@ fn test_tx_log_order(relax_intrabatch_account_locks: bool) {
                 enable_log_recording: true,
                 enable_return_data_recording: false,
                 enable_transaction_balance_recording: false,
+                enable_instruction_trace_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -8534,6 +8537,7 @@ fn test_tx_return_data() {
                     enable_log_recording: false,
                     enable_return_data_recording: true,
                     enable_transaction_balance_recording: false,
+                    enable_instruction_trace_recording: false,
                 },
                 &mut ExecuteTimings::default(),
                 None,
@@ -11577,13 +11581,19 @@ fn test_failed_simulation_load_error() {
     bank.freeze();
     let mint_balance = bank.get_account(&mint_keypair.pubkey()).unwrap().lamports();
     let sanitized = RuntimeTransaction::from_transaction_for_tests(transaction);
-    let simulation = bank.simulate_transaction(&sanitized, false);
+    let mut simulation = bank.simulate_transaction(&sanitized, false);
+    // the fee payer is still charged, even though the fee is zero here
+    let rollback_accounts = std::mem::take(&mut simulation.rollback_accounts);
+    assert_eq!(rollback_accounts.len(), 1);
+    assert_eq!(rollback_accounts[0].0, mint_keypair.pubkey());
+    assert_eq!(rollback_accounts[0].1.lamports(), mint_balance);
     assert_eq!(
         simulation,
         TransactionSimulationResult {
             result: Err(TransactionError::ProgramAccountNotFound),
             logs: vec![],
             post_simulation_accounts: vec![],
+            rollback_accounts: vec![],
             units_consumed: 0,
             loaded_accounts_data_size: 0,
             return_data: None,
@@ -11593,10 +11603,156 @@ fn test_failed_simulation_load_error() {
             post_balances: Some(vec![mint_balance, 0]),
             pre_token_balances: Some(vec![]),
             post_token_balances: Some(vec![]),
+            instruction_trace: None,
         }
     );
 }
 
+/// Test that simulating multiple transactions executes them in order, each one seeing the
+/// account changes of the ones before it, without committing anything to the bank
+#[test]
+fn test_simulate_transactions_sequential() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let blockhash = bank.last_blockhash();
+
+    // the second transfer can only succeed if the first one funded alice
+    let fund_alice = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &mint_keypair,
+        &alice.pubkey(),
+        LAMPORTS_PER_SOL / 2,
+        blockhash,
+    ));
+    let pay_bob = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &alice,
+        &bob,
+        LAMPORTS_PER_SOL / 4,
+        blockhash,
+    ));
+
+    bank.freeze();
+    let simulation = bank.simulate_transaction(&pay_bob, false);
+    assert_eq!(simulation.result, Err(TransactionError::AccountNotFound));
+
+    let simulations = bank.simulate_transactions(&[fund_alice, pay_bob], false);
+    assert_eq!(simulations.len(), 2);
+    assert_eq!(simulations[0].result, Ok(()));
+    assert_eq!(simulations[1].result, Ok(()));
+    let fee = simulations[1].fee.unwrap();
+    let pre_balances = simulations[1].pre_balances.as_ref().unwrap();
+    assert_eq!(pre_balances[..2], [LAMPORTS_PER_SOL / 2, 0]);
+    let post_balances = simulations[1].post_balances.as_ref().unwrap();
+    assert_eq!(
+        post_balances[..2],
+        [LAMPORTS_PER_SOL / 4 - fee, LAMPORTS_PER_SOL / 4]
+    );
+    let post_bob = simulations[1]
+        .post_simulation_accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == bob)
+        .map(|(_, account)| account.lamports());
+    assert_eq!(post_bob, Some(LAMPORTS_PER_SOL / 4));
+
+    // nothing was committed
+    assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+    assert_eq!(bank.get_balance(&bob), 0);
+}
+
+#[test]
+fn test_simulate_transaction_with_account_overrides() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let pay_bob = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &alice,
+        &bob,
+        LAMPORTS_PER_SOL / 4,
+        bank.last_blockhash(),
+    ));
+    bank.freeze();
+
+    let simulation = bank.simulate_transaction(&pay_bob, false);
+    assert_eq!(simulation.result, Err(TransactionError::AccountNotFound));
+
+    // alice only has funds in the overridden state
+    let mut account_overrides = AccountOverrides::default();
+    account_overrides.set_account(
+        &alice.pubkey(),
+        Some(AccountSharedData::new(
+            LAMPORTS_PER_SOL,
+            0,
+            &system_program::id(),
+        )),
+    );
+    let simulation = bank.simulate_transaction_with_config(
+        &pay_bob,
+        TransactionSimulationConfig {
+            account_overrides,
+            ..TransactionSimulationConfig::default()
+        },
+    );
+    assert_eq!(simulation.result, Ok(()));
+    let post_bob = simulation
+        .post_simulation_accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == bob)
+        .map(|(_, account)| account.lamports());
+    assert_eq!(post_bob, Some(LAMPORTS_PER_SOL / 4));
+
+    // nothing was committed
+    assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+    assert_eq!(bank.get_balance(&bob), 0);
+}
+
+#[test]
+fn test_simulate_transaction_with_instruction_trace() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let alice = Pubkey::new_unique();
+    let transaction = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &mint_keypair,
+        &alice,
+        LAMPORTS_PER_SOL / 2,
+        bank.last_blockhash(),
+    ));
+    bank.freeze();
+
+    let simulation = bank.simulate_transaction(&transaction, false);
+    assert_eq!(simulation.result, Ok(()));
+    assert_eq!(simulation.instruction_trace, None);
+
+    let simulation = bank.simulate_transaction_with_config(
+        &transaction,
+        TransactionSimulationConfig {
+            enable_instruction_trace_recording: true,
+            ..TransactionSimulationConfig::default()
+        },
+    );
+    assert_eq!(simulation.result, Ok(()));
+    let written_account = |index_in_transaction| InstructionTraceAccount {
+        index_in_transaction,
+        is_writable: true,
+        is_written: true,
+        pre_data_len: 0,
+        post_data_len: 0,
+    };
+    assert_eq!(
+        simulation.instruction_trace,
+        Some(vec![InstructionTrace {
+            stack_height: 1,
+            program_account_index: 2,
+            compute_units_consumed: simulation.units_consumed,
+            accounts: vec![written_account(0), written_account(1)],
+        }])
+    );
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_fee_details() {
     // TX  | PROCESSING RESULT           | COLLECT            | COLLECT
@@ -35,7 +35,7 @@ use {
     },
     log::*,
     solana_accounts_db::{
-        accounts_db::{AccountsDbConfig, AtomicAccountsFileId},
+        accounts_db::{AccountStorageEntry, AccountsDbConfig, AtomicAccountsFileId},
         accounts_update_notifier_interface::AccountsUpdateNotifier,
         utils::remove_dir_contents,
     },
@@ -717,10 +717,63 @@ fn bank_to_full_snapshot_archive_with(
     bank.force_flush_accounts_cache();
     bank.clean_accounts();
 
+    archive_full_snapshot_with_storages(
+        bank_snapshots_dir,
+        bank,
+        bank.get_snapshot_storages(None),
+        snapshot_version,
+        full_snapshot_archives_dir,
+        incremental_snapshot_archives_dir,
+        archive_format,
+        should_flush_and_hard_link_storages,
+    )
+}
+
+/// Convenience function to create a full snapshot archive out of a rooted Bank, archiving
+/// `snapshot_storages` in place of the bank's own storages
+///
+/// This is used to re-emit a snapshot after its storages have been rewritten, e.g. into a
+/// different accounts file format.  The caller must ensure `snapshot_storages` hold the same
+/// accounts as the bank's storages; this fn does not flush, clean, or verify anything.
+pub fn bank_to_full_snapshot_archive_with_storages(
+    bank_snapshots_dir: impl AsRef<Path>,
+    bank: &Bank,
+    snapshot_storages: Vec<Arc<AccountStorageEntry>>,
+    snapshot_version: Option<SnapshotVersion>,
+    full_snapshot_archives_dir: impl AsRef<Path>,
+    incremental_snapshot_archives_dir: impl AsRef<Path>,
+    archive_format: ArchiveFormat,
+) -> snapshot_utils::Result<FullSnapshotArchiveInfo> {
+    assert!(bank.is_complete());
+    let snapshot_version = snapshot_version.unwrap_or_default();
+    let temp_bank_snapshots_dir = tempfile::tempdir_in(bank_snapshots_dir)?;
+    archive_full_snapshot_with_storages(
+        &temp_bank_snapshots_dir,
+        bank,
+        snapshot_storages,
+        snapshot_version,
+        full_snapshot_archives_dir,
+        incremental_snapshot_archives_dir,
+        archive_format,
+        false, // the storages are not owned by accounts-db, so do not hard link them
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn archive_full_snapshot_with_storages(
+    bank_snapshots_dir: impl AsRef<Path>,
+    bank: &Bank,
+    snapshot_storages: Vec<Arc<AccountStorageEntry>>,
+    snapshot_version: SnapshotVersion,
+    full_snapshot_archives_dir: impl AsRef<Path>,
+    incremental_snapshot_archives_dir: impl AsRef<Path>,
+    archive_format: ArchiveFormat,
+    should_flush_and_hard_link_storages: bool,
+) -> snapshot_utils::Result<FullSnapshotArchiveInfo> {
     let snapshot_package = SnapshotPackage::new(
         SnapshotKind::FullSnapshot,
         bank,
-        bank.get_snapshot_storages(None),
+        snapshot_storages,
         bank.status_cache.read().unwrap().root_slot_deltas(),
     );
 
@@ -818,6 +871,8 @@ mod tests {
                 purge_bank_snapshots_older_than_slot, purge_incomplete_bank_snapshots,
                 purge_old_bank_snapshots, purge_old_bank_snapshots_at_startup,
                 snapshot_storage_rebuilder::get_slot_and_append_vec_id,
+                unpack_seekable_snapshot_archive_storages,
+                verify_seekable_snapshot_archive_storage, SeekableArchiveIndex, ZstdConfig,
                 SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME,
             },
             status_cache::Status,
@@ -825,7 +880,8 @@ mod tests {
         semver::Version,
         solana_accounts_db::{
             accounts_db::{MarkObsoleteAccounts, ACCOUNTS_DB_CONFIG_FOR_TESTING},
-            accounts_file::StorageAccess,
+            accounts_file::{AccountsFile, StorageAccess},
+            tiered_storage::{cold::COLD_FORMAT, hot::HOT_FORMAT, TieredStorageFormat},
         },
         solana_genesis_config::create_genesis_config,
         solana_keypair::Keypair,
@@ -930,6 +986,171 @@ mod tests {
         assert_eq!(original_bank, roundtrip_bank);
     }
 
+    /// Test roundtrip of bank to a seekable full snapshot, then back again, and reading single
+    /// storages out of the archive through its index
+    #[test]
+    fn test_roundtrip_bank_to_and_from_seekable_snapshot() {
+        let (genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+        let original_bank = Bank::new_for_tests(&genesis_config);
+        original_bank
+            .transfer(LAMPORTS_PER_SOL, &mint_keypair, &Pubkey::new_unique())
+            .unwrap();
+        original_bank.fill_bank_with_ticks_for_tests();
+
+        let (_tmp_dir, accounts_dir) = create_tmp_accounts_dir_for_tests();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_format = ArchiveFormat::TarZstdSeekable {
+            config: ZstdConfig::default(),
+        };
+
+        let snapshot_archive_info = bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &original_bank,
+            None,
+            snapshot_archives_dir.path(),
+            snapshot_archives_dir.path(),
+            snapshot_archive_format,
+        )
+        .unwrap();
+        assert_eq!(
+            snapshot_archive_info.archive_format(),
+            snapshot_archive_format
+        );
+
+        let roundtrip_bank = bank_from_snapshot_archives(
+            &[accounts_dir],
+            bank_snapshots_dir.path(),
+            &snapshot_archive_info,
+            None,
+            &genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            false,
+            false,
+            false,
+            ACCOUNTS_DB_CONFIG_FOR_TESTING,
+            None,
+            Arc::default(),
+        )
+        .unwrap();
+        assert_eq!(original_bank, roundtrip_bank);
+
+        let index = SeekableArchiveIndex::read_from(
+            &mut fs::File::open(snapshot_archive_info.path()).unwrap(),
+        )
+        .unwrap();
+        let storages = index
+            .storages()
+            .map(|(file_name, _)| file_name.to_string())
+            .collect::<Vec<_>>();
+        assert!(!storages.is_empty());
+        for storage in &storages {
+            let num_accounts =
+                verify_seekable_snapshot_archive_storage(snapshot_archive_info.path(), storage)
+                    .unwrap();
+            assert!(num_accounts > 0);
+        }
+        assert!(
+            verify_seekable_snapshot_archive_storage(snapshot_archive_info.path(), "0.12345")
+                .is_err()
+        );
+
+        // Only the selected storage is unpacked
+        let unpack_dir = tempfile::TempDir::new().unwrap();
+        let unpacked_storages = unpack_seekable_snapshot_archive_storages(
+            snapshot_archive_info.path(),
+            unpack_dir.path(),
+            &[unpack_dir.path().to_path_buf()],
+            |file_name| file_name == storages[0],
+        )
+        .unwrap();
+        assert_eq!(unpacked_storages.len(), 1);
+        assert!(unpacked_storages[&storages[0]].is_file());
+        assert_eq!(fs::read_dir(unpack_dir.path()).unwrap().count(), 1);
+    }
+
+    /// Test roundtrip of bank to a full snapshot holding its storages rewritten as tiered storage,
+    /// then back again
+    #[test_case(&HOT_FORMAT; "hot")]
+    #[test_case(&COLD_FORMAT; "cold")]
+    fn test_roundtrip_bank_to_and_from_tiered_storage_snapshot(format: &TieredStorageFormat) {
+        let (genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+        let original_bank = Bank::new_for_tests(&genesis_config);
+        original_bank
+            .transfer(LAMPORTS_PER_SOL, &mint_keypair, &Pubkey::new_unique())
+            .unwrap();
+        original_bank.fill_bank_with_ticks_for_tests();
+        original_bank.squash();
+        original_bank.force_flush_accounts_cache();
+
+        let tiered_storages_dir = tempfile::TempDir::new().unwrap();
+        let storages = original_bank.get_snapshot_storages(None);
+        let tiered_storages: Vec<_> = storages
+            .iter()
+            .map(|storage| {
+                let path = tiered_storages_dir
+                    .path()
+                    .join(AccountsFile::file_name(storage.slot(), storage.id()));
+                Arc::new(
+                    storage
+                        .rewrite_as_tiered_storage(path, format, None)
+                        .unwrap(),
+                )
+            })
+            .collect();
+        let accounts_lt_hash = original_bank
+            .rc
+            .accounts
+            .accounts_db
+            .calculate_accounts_lt_hash_from_storages(&tiered_storages, original_bank.slot());
+        assert_eq!(
+            SnapshotHash::new(accounts_lt_hash.0.checksum()),
+            original_bank.get_snapshot_hash(),
+        );
+
+        let (_tmp_dir, accounts_dir) = create_tmp_accounts_dir_for_tests();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_info = bank_to_full_snapshot_archive_with_storages(
+            &bank_snapshots_dir,
+            &original_bank,
+            tiered_storages,
+            None,
+            snapshot_archives_dir.path(),
+            snapshot_archives_dir.path(),
+            SnapshotConfig::default().archive_format,
+        )
+        .unwrap();
+
+        let roundtrip_bank = bank_from_snapshot_archives(
+            &[accounts_dir],
+            bank_snapshots_dir.path(),
+            &snapshot_archive_info,
+            None,
+            &genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            false,
+            false,
+            false,
+            ACCOUNTS_DB_CONFIG_FOR_TESTING,
+            None,
+            Arc::default(),
+        )
+        .unwrap();
+        assert_eq!(original_bank, roundtrip_bank);
+
+        // The storages are loaded as tiered storage, not as append vecs
+        let roundtrip_storages = roundtrip_bank.get_snapshot_storages(None);
+        assert_eq!(roundtrip_storages.len(), storages.len());
+        for storage in roundtrip_storages {
+            assert!(matches!(storage.accounts, AccountsFile::TieredStorage(_)));
+        }
+    }
+
     /// This tests handling of obsolete accounts during a full snapshot with obsolete accounts
     /// marked in the accounts database. This test injects them directly
     #[test]
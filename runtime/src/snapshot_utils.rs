@@ -54,9 +54,15 @@ use {
 };
 
 mod archive_format;
+mod seekable_archive;
 mod snapshot_interval;
 pub mod snapshot_storage_rebuilder;
-pub use {archive_format::*, snapshot_interval::SnapshotInterval};
+use seekable_archive::{SeekableArchiveReader, SeekableArchiveWriter};
+pub use {
+    archive_format::*,
+    seekable_archive::{SeekableArchiveEntry, SeekableArchiveIndex},
+    snapshot_interval::SnapshotInterval,
+};
 
 pub const SNAPSHOT_STATUS_CACHE_FILENAME: &str = "status_cache";
 pub const SNAPSHOT_VERSION_FILENAME: &str = "version";
@@ -85,9 +91,8 @@ pub const DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN: NonZeroUsize =
     NonZeroUsize::new(2).unwrap();
 pub const DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN: NonZeroUsize =
     NonZeroUsize::new(4).unwrap();
-pub const FULL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str =
-    r"^snapshot-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4)$";
-pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-snapshot-(?P<base>[[:digit:]]+)-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4)$";
+pub const FULL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^snapshot-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4|tar\.seekable\.zst)$";
+pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-snapshot-(?P<base>[[:digit:]]+)-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4|tar\.seekable\.zst)$";
 
 // Allows scheduling a large number of reads such that temporary disk access delays
 // shouldn't block decompression (unless read bandwidth is saturated).
@@ -376,6 +381,9 @@ pub enum SnapshotError {
 
     #[error("failed to rebuild snapshot storages: {0}")]
     RebuildStorages(String),
+
+    #[error("account storage '{0}' not found in snapshot archive '{1}'")]
+    MissingArchiveStorage(String, PathBuf),
 }
 
 #[derive(Error, Debug)]
@@ -1049,6 +1057,26 @@ fn serialize_snapshot(
     do_serialize_snapshot().map_err(|err| SnapshotError::AddBankSnapshot(err, slot))
 }
 
+/// The encoders a snapshot archive is written through
+///
+/// `end_entry()` is called after each entry has been appended to the tar
+/// archive, so that seekable archives can compress every entry separately.
+trait ArchiveEncoder: Write {
+    fn end_entry(&mut self, _path: &str) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ArchiveEncoder for zstd::stream::Encoder<'_, W> {}
+
+impl<W: Write> ArchiveEncoder for lz4::Encoder<W> {}
+
+impl<W: Write> ArchiveEncoder for SeekableArchiveWriter<W> {
+    fn end_entry(&mut self, path: &str) -> io::Result<()> {
+        SeekableArchiveWriter::end_entry(self, path)
+    }
+}
+
 /// Archives a snapshot into `archive_path`
 fn archive_snapshot(
     snapshot_kind: SnapshotKind,
@@ -1120,7 +1148,7 @@ fn archive_snapshot(
         let archive_file = fs::File::create(&staging_archive_path)
             .map_err(|err| E::CreateArchiveFile(err, staging_archive_path.clone()))?;
 
-        let do_archive_files = |encoder: &mut dyn Write| -> std::result::Result<(), E> {
+        let do_archive_files = |encoder: &mut dyn ArchiveEncoder| -> std::result::Result<(), E> {
             let mut archive = tar::Builder::new(encoder);
             // Disable sparse file handling.  This seems to be the root cause of an issue when
             // upgrading v2.0 to v2.1, and the tar crate from 0.4.41 to 0.4.42.
@@ -1136,24 +1164,34 @@ fn archive_snapshot(
             archive
                 .append_path_with_name(&staging_version_file, SNAPSHOT_VERSION_FILENAME)
                 .map_err(E::ArchiveVersionFile)?;
+            archive
+                .get_mut()
+                .end_entry(SNAPSHOT_VERSION_FILENAME)
+                .map_err(E::ArchiveVersionFile)?;
             archive
                 .append_dir_all(BANK_SNAPSHOTS_DIR, &staging_snapshots_dir)
                 .map_err(E::ArchiveSnapshotsDir)?;
+            archive
+                .get_mut()
+                .end_entry(BANK_SNAPSHOTS_DIR)
+                .map_err(E::ArchiveSnapshotsDir)?;
 
             let storages_orderer = AccountStoragesOrderer::with_small_to_large_ratio(
                 snapshot_storages,
                 INTERLEAVE_TAR_ENTRIES_SMALL_TO_LARGE_RATIO,
             );
             for storage in storages_orderer.iter() {
-                let path_in_archive = Path::new(ACCOUNTS_DIR)
-                    .join(AccountsFile::file_name(storage.slot(), storage.id()));
+                let path_in_archive = format!(
+                    "{ACCOUNTS_DIR}/{}",
+                    AccountsFile::file_name(storage.slot(), storage.id())
+                );
 
                 let reader =
                     AccountStorageReader::new(storage, Some(snapshot_slot)).map_err(|err| {
                         E::AccountStorageReaderError(err, storage.path().to_path_buf())
                     })?;
                 let mut header = tar::Header::new_gnu();
-                header.set_path(path_in_archive).map_err(|err| {
+                header.set_path(&path_in_archive).map_err(|err| {
                     E::ArchiveAccountStorageFile(err, storage.path().to_path_buf())
                 })?;
                 header.set_size(reader.len() as u64);
@@ -1161,6 +1199,12 @@ fn archive_snapshot(
                 archive.append(&header, reader).map_err(|err| {
                     E::ArchiveAccountStorageFile(err, storage.path().to_path_buf())
                 })?;
+                archive
+                    .get_mut()
+                    .end_entry(&path_in_archive)
+                    .map_err(|err| {
+                        E::ArchiveAccountStorageFile(err, storage.path().to_path_buf())
+                    })?;
             }
 
             archive.into_inner().map_err(E::FinishArchive)?;
@@ -1184,6 +1228,12 @@ fn archive_snapshot(
                 let (_output, result) = encoder.finish();
                 result.map_err(E::FinishEncoder)?;
             }
+            ArchiveFormat::TarZstdSeekable { config } => {
+                let mut encoder =
+                    SeekableArchiveWriter::new(archive_file, config.compression_level);
+                do_archive_files(&mut encoder)?;
+                encoder.finish().map_err(E::FinishEncoder)?;
+            }
         };
     }
 
@@ -1675,6 +1725,61 @@ fn streaming_unarchive_snapshot(
         .unwrap()
 }
 
+/// Unpacks the account storage files selected by `filter` out of a seekable
+/// snapshot archive, only decompressing the frames of the selected files
+///
+/// `filter` is called with the storage file names, ie `<slot>.<id>`.  Returns
+/// the unpacked files, keyed by file name.
+pub fn unpack_seekable_snapshot_archive_storages(
+    snapshot_archive_path: impl AsRef<Path>,
+    unpack_dir: impl AsRef<Path>,
+    account_paths: &[PathBuf],
+    mut filter: impl FnMut(&str) -> bool,
+) -> Result<hardened_unpack::UnpackedAppendVecMap> {
+    let mut archive_file = fs::File::open(&snapshot_archive_path)?;
+    let index = SeekableArchiveIndex::read_from(&mut archive_file)?;
+    let entries = index
+        .storages()
+        .filter(|(file_name, _)| filter(file_name))
+        .map(|(_, entry)| entry.clone())
+        .collect::<Vec<_>>();
+    let reader = SeekableArchiveReader::new(archive_file, entries);
+    Ok(hardened_unpack::unpack_snapshot(
+        Archive::new(reader),
+        MAX_SNAPSHOT_READER_BUF_SIZE as usize,
+        unpack_dir.as_ref(),
+        account_paths,
+    )?)
+}
+
+/// Verifies a single account storage file of a seekable snapshot archive,
+/// without unpacking the rest of the archive
+///
+/// The storage file's frame must pass its checksum and the file must hold
+/// valid accounts.  Returns the number of accounts in the file.
+pub fn verify_seekable_snapshot_archive_storage(
+    snapshot_archive_path: impl AsRef<Path>,
+    storage_file_name: &str,
+) -> Result<usize> {
+    let unpack_dir = tempfile::tempdir()?;
+    let unpacked_storages = unpack_seekable_snapshot_archive_storages(
+        &snapshot_archive_path,
+        unpack_dir.path(),
+        &[unpack_dir.path().to_path_buf()],
+        |file_name| file_name == storage_file_name,
+    )?;
+    let storage_path = unpacked_storages.get(storage_file_name).ok_or_else(|| {
+        SnapshotError::MissingArchiveStorage(
+            storage_file_name.to_string(),
+            snapshot_archive_path.as_ref().to_path_buf(),
+        )
+    })?;
+    let storage_len = fs::metadata(storage_path)?.len() as usize;
+    let (_storage, num_accounts) =
+        AccountsFile::new_from_file(storage_path, storage_len, StorageAccess::File)?;
+    Ok(num_accounts)
+}
+
 fn decompressed_tar_reader(
     archive_format: ArchiveFormat,
     archive_path: impl AsRef<Path>,
@@ -2739,6 +2844,20 @@ mod tests {
             .unwrap(),
             (45, SnapshotHash(Hash::default()), ArchiveFormat::TarLz4)
         );
+        assert_eq!(
+            parse_full_snapshot_archive_filename(&format!(
+                "snapshot-47-{}.tar.seekable.zst",
+                Hash::default()
+            ))
+            .unwrap(),
+            (
+                47,
+                SnapshotHash(Hash::default()),
+                ArchiveFormat::TarZstdSeekable {
+                    config: ZstdConfig::default(),
+                }
+            )
+        );
 
         assert!(parse_full_snapshot_archive_filename("invalid").is_err());
         assert!(
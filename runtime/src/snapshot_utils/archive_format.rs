@@ -5,17 +5,26 @@ use {
 
 // SUPPORTED_ARCHIVE_COMPRESSION lists the compression types that can be
 // specified on the command line.
-pub const SUPPORTED_ARCHIVE_COMPRESSION: &[&str] = &["zstd", "lz4"];
+pub const SUPPORTED_ARCHIVE_COMPRESSION: &[&str] = &["zstd", "lz4", "zstd-seekable"];
 pub const DEFAULT_ARCHIVE_COMPRESSION: &str = "zstd";
 
 pub const TAR_ZSTD_EXTENSION: &str = "tar.zst";
 pub const TAR_LZ4_EXTENSION: &str = "tar.lz4";
+pub const TAR_ZSTD_SEEKABLE_EXTENSION: &str = "tar.seekable.zst";
 
 /// The different archive formats used for snapshots
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Display)]
 pub enum ArchiveFormat {
-    TarZstd { config: ZstdConfig },
+    TarZstd {
+        config: ZstdConfig,
+    },
     TarLz4,
+    /// Like `TarZstd`, but every tar entry is compressed into its own zstd
+    /// frame and an index of the frames is appended, so that single entries
+    /// can be read without decompressing the whole archive
+    TarZstdSeekable {
+        config: ZstdConfig,
+    },
 }
 
 impl ArchiveFormat {
@@ -24,6 +33,7 @@ impl ArchiveFormat {
         match self {
             ArchiveFormat::TarZstd { .. } => TAR_ZSTD_EXTENSION,
             ArchiveFormat::TarLz4 => TAR_LZ4_EXTENSION,
+            ArchiveFormat::TarZstdSeekable { .. } => TAR_ZSTD_SEEKABLE_EXTENSION,
         }
     }
 
@@ -33,6 +43,9 @@ impl ArchiveFormat {
                 config: ZstdConfig::default(),
             }),
             "lz4" => Some(ArchiveFormat::TarLz4),
+            "zstd-seekable" => Some(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
             _ => None,
         }
     }
@@ -49,6 +62,9 @@ impl TryFrom<&str> for ArchiveFormat {
                 config: ZstdConfig::default(),
             }),
             TAR_LZ4_EXTENSION => Ok(ArchiveFormat::TarLz4),
+            TAR_ZSTD_SEEKABLE_EXTENSION => Ok(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
             _ => Err(ParseError::InvalidExtension(extension.to_string())),
         }
     }
@@ -70,7 +86,9 @@ pub enum ArchiveFormatDecompressor<R> {
 impl<R: std::io::BufRead> ArchiveFormatDecompressor<R> {
     pub fn new(format: ArchiveFormat, input: R) -> std::io::Result<Self> {
         Ok(match format {
-            ArchiveFormat::TarZstd { .. } => {
+            // The frames of a seekable archive, and the skippable frame holding
+            // its index, form a regular zstd stream
+            ArchiveFormat::TarZstd { .. } | ArchiveFormat::TarZstdSeekable { .. } => {
                 Self::Zstd(zstd::stream::read::Decoder::with_buffer(input)?)
             }
             ArchiveFormat::TarLz4 => {
@@ -126,6 +144,13 @@ mod tests {
             TAR_ZSTD_EXTENSION
         );
         assert_eq!(ArchiveFormat::TarLz4.extension(), TAR_LZ4_EXTENSION);
+        assert_eq!(
+            ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }
+            .extension(),
+            TAR_ZSTD_SEEKABLE_EXTENSION
+        );
     }
 
     #[test]
@@ -140,6 +165,12 @@ mod tests {
             ArchiveFormat::try_from(TAR_LZ4_EXTENSION),
             Ok(ArchiveFormat::TarLz4)
         );
+        assert_eq!(
+            ArchiveFormat::try_from(TAR_ZSTD_SEEKABLE_EXTENSION),
+            Ok(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            })
+        );
         assert_eq!(
             ArchiveFormat::try_from(INVALID_EXTENSION),
             Err(ParseError::InvalidExtension(INVALID_EXTENSION.to_string()))
@@ -158,6 +189,12 @@ mod tests {
             ArchiveFormat::from_str(TAR_LZ4_EXTENSION),
             Ok(ArchiveFormat::TarLz4)
         );
+        assert_eq!(
+            ArchiveFormat::from_str(TAR_ZSTD_SEEKABLE_EXTENSION),
+            Ok(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            })
+        );
         assert_eq!(
             ArchiveFormat::from_str(INVALID_EXTENSION),
             Err(ParseError::InvalidExtension(INVALID_EXTENSION.to_string()))
@@ -171,6 +208,9 @@ mod tests {
                 config: ZstdConfig::default(),
             }),
             Some(ArchiveFormat::TarLz4),
+            Some(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
         ];
 
         for (arg, expected) in zip(SUPPORTED_ARCHIVE_COMPRESSION.iter(), golden.into_iter()) {
@@ -0,0 +1,431 @@
+//! Snapshot archives made of independently compressed zstd frames
+//!
+//! Every entry of the tar archive is compressed into a zstd frame of its own,
+//! and a skippable frame holding an index of the entries is appended after the
+//! last one.  The index is located through a small footer at the very end of
+//! the file:
+//!
+//! ```text
+//! | frame | frame | ... | skippable frame header | index | index len | magic |
+//! ```
+//!
+//! Zstd decoders ignore skippable frames, so a seekable archive is still a
+//! valid zstd compressed tar archive and is unpacked like a `TarZstd` one.  The
+//! index additionally allows reading a single entry, e.g. one account storage
+//! file, by decompressing only its frame.
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::VecDeque,
+        io::{self, BufReader, Read, Seek, SeekFrom, Take, Write},
+    },
+};
+
+/// Magic number of the skippable frame holding the index
+///
+/// Zstd reserves the magic numbers 0x184D2A50 to 0x184D2A5F for skippable frames.
+const SKIPPABLE_FRAME_MAGIC: u32 = 0x184D2A5E;
+/// Magic number ending a seekable archive, "SSKI" in little endian
+const INDEX_FOOTER_MAGIC: u32 = 0x494B5353;
+/// Size of the footer: the index length and the footer magic
+const INDEX_FOOTER_SIZE: u64 = 8;
+/// Size of a skippable frame header: the magic and the frame size
+const SKIPPABLE_FRAME_HEADER_SIZE: u64 = 8;
+/// Upper bound on the serialized index, to not trust arbitrary lengths read
+/// from the archive
+const MAX_INDEX_SIZE: u32 = 256 * 1024 * 1024;
+
+/// An entry of a seekable archive, ie one zstd frame
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeekableArchiveEntry {
+    /// Path of the entry in the tar archive, e.g. `accounts/<slot>.<id>`
+    ///
+    /// The tar end-of-archive marker is indexed with an empty path.
+    pub path: String,
+    /// Offset of the frame in the archive file
+    pub offset: u64,
+    /// Length of the compressed frame
+    pub compressed_len: u64,
+    /// Length of the tar data the frame decompresses to
+    pub decompressed_len: u64,
+}
+
+/// The index of the frames of a seekable archive
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeekableArchiveIndex {
+    entries: Vec<SeekableArchiveEntry>,
+}
+
+impl SeekableArchiveIndex {
+    /// Reads the index from the end of a seekable archive
+    pub fn read_from<R: Read + Seek>(reader: &mut R) -> io::Result<Self> {
+        let archive_len = reader.seek(SeekFrom::End(0))?;
+        if archive_len < SKIPPABLE_FRAME_HEADER_SIZE + INDEX_FOOTER_SIZE {
+            return Err(invalid_data("archive is too short to hold an index"));
+        }
+
+        reader.seek(SeekFrom::Start(archive_len - INDEX_FOOTER_SIZE))?;
+        let index_len = read_u32(reader)?;
+        if read_u32(reader)? != INDEX_FOOTER_MAGIC {
+            return Err(invalid_data(
+                "archive is not seekable, index footer not found",
+            ));
+        }
+        if index_len > MAX_INDEX_SIZE
+            || u64::from(index_len) + SKIPPABLE_FRAME_HEADER_SIZE + INDEX_FOOTER_SIZE > archive_len
+        {
+            return Err(invalid_data(format!("invalid index length {index_len}")));
+        }
+
+        let frame_offset =
+            archive_len - INDEX_FOOTER_SIZE - u64::from(index_len) - SKIPPABLE_FRAME_HEADER_SIZE;
+        reader.seek(SeekFrom::Start(frame_offset))?;
+        let frame_magic = read_u32(reader)?;
+        let frame_size = read_u32(reader)?;
+        if frame_magic != SKIPPABLE_FRAME_MAGIC
+            || u64::from(frame_size) != u64::from(index_len) + INDEX_FOOTER_SIZE
+        {
+            return Err(invalid_data("invalid index frame header"));
+        }
+
+        let mut index = vec![0; index_len as usize];
+        reader.read_exact(&mut index)?;
+        let index: Self = bincode::deserialize(&index).map_err(invalid_data)?;
+        if index
+            .entries
+            .iter()
+            .any(|entry| entry.offset.saturating_add(entry.compressed_len) > frame_offset)
+        {
+            return Err(invalid_data("index entry out of the archive bounds"));
+        }
+        Ok(index)
+    }
+
+    /// All the entries, in archive order
+    pub fn entries(&self) -> &[SeekableArchiveEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry at `path` in the tar archive
+    pub fn get(&self, path: &str) -> Option<&SeekableArchiveEntry> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    /// Returns the account storage file entries, along with their file names
+    pub fn storages(&self) -> impl Iterator<Item = (&str, &SeekableArchiveEntry)> {
+        self.entries.iter().filter_map(|entry| {
+            entry
+                .path
+                .strip_prefix("accounts/")
+                .map(|file_name| (file_name, entry))
+        })
+    }
+}
+
+/// A zstd frame being written
+struct FrameEncoder<W: Write> {
+    encoder: zstd::stream::write::Encoder<'static, CountingWriter<W>>,
+    offset: u64,
+    decompressed_len: u64,
+}
+
+/// Writes a seekable archive
+///
+/// The tar data written between two calls to `end_entry()` is compressed into
+/// one frame, which is indexed under the path passed to `end_entry()`.
+pub struct SeekableArchiveWriter<W: Write> {
+    compression_level: i32,
+    // `None` while a frame is being written, as the frame's encoder then owns it
+    output: Option<CountingWriter<W>>,
+    frame: Option<FrameEncoder<W>>,
+    index: SeekableArchiveIndex,
+}
+
+impl<W: Write> SeekableArchiveWriter<W> {
+    pub fn new(output: W, compression_level: i32) -> Self {
+        Self {
+            compression_level,
+            output: Some(CountingWriter {
+                inner: output,
+                count: 0,
+            }),
+            frame: None,
+            index: SeekableArchiveIndex::default(),
+        }
+    }
+
+    /// Ends the frame holding the entry at `path`
+    ///
+    /// Does nothing if no data was written since the previous entry.
+    pub fn end_entry(&mut self, path: &str) -> io::Result<()> {
+        let Some(frame) = self.frame.take() else {
+            return Ok(());
+        };
+        let output = frame.encoder.finish()?;
+        self.index.entries.push(SeekableArchiveEntry {
+            path: path.to_string(),
+            offset: frame.offset,
+            compressed_len: output.count - frame.offset,
+            decompressed_len: frame.decompressed_len,
+        });
+        self.output = Some(output);
+        Ok(())
+    }
+
+    /// Ends the last frame and writes the index, returning the output
+    pub fn finish(mut self) -> io::Result<W> {
+        self.end_entry("")?;
+        let mut output = self.output()?;
+        let index = bincode::serialize(&self.index).map_err(io::Error::other)?;
+        let index_len = u32::try_from(index.len())
+            .ok()
+            .filter(|index_len| *index_len <= MAX_INDEX_SIZE)
+            .ok_or_else(|| io::Error::other(format!("index is too large: {}", index.len())))?;
+        output.write_all(&SKIPPABLE_FRAME_MAGIC.to_le_bytes())?;
+        output.write_all(&(index_len + INDEX_FOOTER_SIZE as u32).to_le_bytes())?;
+        output.write_all(&index)?;
+        output.write_all(&index_len.to_le_bytes())?;
+        output.write_all(&INDEX_FOOTER_MAGIC.to_le_bytes())?;
+        output.flush()?;
+        Ok(output.inner)
+    }
+
+    fn output(&mut self) -> io::Result<CountingWriter<W>> {
+        // The output is only lost if creating a frame's encoder failed
+        self.output
+            .take()
+            .ok_or_else(|| io::Error::other("seekable archive writer failed previously"))
+    }
+
+    fn frame(&mut self) -> io::Result<&mut FrameEncoder<W>> {
+        if self.frame.is_none() {
+            let output = self.output()?;
+            let offset = output.count;
+            let mut encoder = zstd::stream::write::Encoder::new(output, self.compression_level)?;
+            encoder.include_checksum(true)?;
+            self.frame = Some(FrameEncoder {
+                encoder,
+                offset,
+                decompressed_len: 0,
+            });
+        }
+        Ok(self.frame.as_mut().unwrap())
+    }
+}
+
+impl<W: Write> Write for SeekableArchiveWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let frame = self.frame()?;
+        let len = frame.encoder.write(buf)?;
+        frame.decompressed_len += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match (&mut self.frame, &mut self.output) {
+            (Some(frame), _) => frame.encoder.flush(),
+            (None, Some(output)) => output.flush(),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Reads the tar data of some entries of a seekable archive, decompressing
+/// their frames one after the other
+///
+/// Reading fails if a frame's checksum or decompressed length does not match.
+pub struct SeekableArchiveReader<R: Read + Seek> {
+    // `None` while a frame is being read, as the frame's decoder then owns it
+    reader: Option<R>,
+    entries: VecDeque<SeekableArchiveEntry>,
+    frame: Option<FrameDecoder<R>>,
+}
+
+/// A zstd frame being read
+struct FrameDecoder<R: Read> {
+    decoder: zstd::stream::read::Decoder<'static, BufReader<Take<R>>>,
+    entry: SeekableArchiveEntry,
+    decompressed_len: u64,
+}
+
+impl<R: Read + Seek> SeekableArchiveReader<R> {
+    pub fn new(reader: R, entries: impl IntoIterator<Item = SeekableArchiveEntry>) -> Self {
+        Self {
+            reader: Some(reader),
+            entries: entries.into_iter().collect(),
+            frame: None,
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for SeekableArchiveReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(frame) = &mut self.frame {
+                let len = frame.decoder.read(buf)?;
+                frame.decompressed_len += len as u64;
+                if len > 0 || buf.is_empty() {
+                    return Ok(len);
+                }
+                if frame.decompressed_len != frame.entry.decompressed_len {
+                    return Err(invalid_data(format!(
+                        "entry '{}' decompressed to {} bytes, expected {}",
+                        frame.entry.path, frame.decompressed_len, frame.entry.decompressed_len,
+                    )));
+                }
+                let frame = self.frame.take().unwrap();
+                self.reader = Some(frame.decoder.finish().into_inner().into_inner());
+            }
+
+            let Some(entry) = self.entries.pop_front() else {
+                return Ok(0);
+            };
+            let mut reader = self
+                .reader
+                .take()
+                .ok_or_else(|| io::Error::other("seekable archive reader failed previously"))?;
+            reader.seek(SeekFrom::Start(entry.offset))?;
+            let decoder = zstd::stream::read::Decoder::with_buffer(BufReader::new(
+                reader.take(entry.compressed_len),
+            ))?
+            .single_frame();
+            self.frame = Some(FrameDecoder {
+                decoder,
+                entry,
+                decompressed_len: 0,
+            });
+        }
+    }
+}
+
+/// Counts the bytes written, to know the offsets of the frames
+struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.count += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::Cursor};
+
+    fn write_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = SeekableArchiveWriter::new(Vec::new(), 1);
+        for (path, data) in entries {
+            writer.write_all(data).unwrap();
+            writer.end_entry(path).unwrap();
+        }
+        writer.write_all(&[0; 16]).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_seekable_archive() {
+        let entries: &[(&str, &[u8])] = &[
+            ("version", b"1.2.0"),
+            ("accounts/1.1", &[1; 1000]),
+            ("accounts/2.2", &[2; 3000]),
+        ];
+        let archive = write_archive(entries);
+
+        // The whole archive decompresses as a regular zstd stream
+        let mut expected = entries
+            .iter()
+            .flat_map(|(_, data)| data.iter().copied())
+            .collect::<Vec<_>>();
+        expected.extend([0; 16]);
+        assert_eq!(
+            zstd::stream::decode_all(archive.as_slice()).unwrap(),
+            expected
+        );
+
+        let mut reader = Cursor::new(archive);
+        let index = SeekableArchiveIndex::read_from(&mut reader).unwrap();
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|entry| (entry.path.as_str(), entry.decompressed_len))
+                .collect::<Vec<_>>(),
+            vec![
+                ("version", 5),
+                ("accounts/1.1", 1000),
+                ("accounts/2.2", 3000),
+                ("", 16)
+            ],
+        );
+        assert_eq!(
+            index
+                .storages()
+                .map(|(file_name, _)| file_name)
+                .collect::<Vec<_>>(),
+            vec!["1.1", "2.2"],
+        );
+
+        // Single entries are read by only decompressing their frames
+        let selected = [
+            index.get("accounts/2.2").unwrap().clone(),
+            index.get("version").unwrap().clone(),
+        ];
+        let mut data = Vec::new();
+        SeekableArchiveReader::new(&mut reader, selected)
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(data, [&[2; 3000][..], b"1.2.0"].concat());
+    }
+
+    #[test]
+    fn test_seekable_archive_corrupted() {
+        let archive = write_archive(&[("accounts/1.1", &[1; 1000])]);
+        let index = SeekableArchiveIndex::read_from(&mut Cursor::new(&archive)).unwrap();
+        let entry = index.get("accounts/1.1").unwrap().clone();
+
+        // A corrupted frame checksum is detected
+        let mut corrupted = archive.clone();
+        corrupted[entry.offset as usize + entry.compressed_len as usize - 1] ^= 0xff;
+        assert!(
+            SeekableArchiveReader::new(Cursor::new(corrupted), [entry.clone()])
+                .read_to_end(&mut Vec::new())
+                .is_err()
+        );
+
+        // A wrong decompressed length is detected
+        let wrong_len = SeekableArchiveEntry {
+            decompressed_len: 999,
+            ..entry
+        };
+        assert!(
+            SeekableArchiveReader::new(Cursor::new(&archive), [wrong_len])
+                .read_to_end(&mut Vec::new())
+                .is_err()
+        );
+
+        // Archives without an index, e.g. a regular zstd archive, are rejected
+        let not_seekable = zstd::stream::encode_all(&[1; 1000][..], 1).unwrap();
+        assert!(SeekableArchiveIndex::read_from(&mut Cursor::new(not_seekable)).is_err());
+        let mut truncated = archive;
+        truncated.pop();
+        assert!(SeekableArchiveIndex::read_from(&mut Cursor::new(truncated)).is_err());
+    }
+}
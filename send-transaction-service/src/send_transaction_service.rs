@@ -8,12 +8,13 @@ use {
     crossbeam_channel::{Receiver, RecvTimeoutError},
     itertools::Itertools,
     log::*,
+    solana_clock::Slot,
     solana_hash::Hash,
     solana_nonce_account as nonce_account,
     solana_pubkey::Pubkey,
     solana_runtime::{
         bank::Bank,
-        bank_forks::{BankForks, BankPair},
+        bank_forks::{BankForks, BankPair, SharableBanks},
     },
     solana_signature::Signature,
     std::{
@@ -60,6 +61,7 @@ pub const MAX_BATCH_SEND_RATE_MS: usize = 100_000;
 pub struct SendTransactionService {
     receive_txn_thread: JoinHandle<()>,
     retry_thread: JoinHandle<()>,
+    retry_status_reader: RetryStatusReader,
     exit: Arc<AtomicBool>,
 }
 
@@ -74,6 +76,10 @@ pub struct TransactionInfo {
     retries: usize,
     /// Last time the transaction was sent
     last_sent_time: Option<Instant>,
+    /// Working bank slot at the time the transaction was last sent
+    last_sent_slot: Option<Slot>,
+    /// TPU addresses the transaction was last sent to
+    last_sent_to: Vec<SocketAddr>,
 }
 
 impl TransactionInfo {
@@ -97,6 +103,8 @@ impl TransactionInfo {
             max_retries,
             retries: 0,
             last_sent_time,
+            last_sent_slot: None,
+            last_sent_to: Vec::new(),
         }
     }
 
@@ -111,6 +119,58 @@ impl TransactionInfo {
     }
 }
 
+/// Snapshot of a transaction's state in the retry pool
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionRetryStatus {
+    /// Number of times the transaction has been sent, including the first broadcast
+    pub send_attempts: usize,
+    /// Effective maximum number of retries, `None` if the transaction is retried until it expires
+    pub max_retries: Option<usize>,
+    /// Working bank slot at the time the transaction was last sent
+    pub last_sent_slot: Option<Slot>,
+    /// TPU addresses the transaction was last sent to
+    pub last_sent_to: Vec<SocketAddr>,
+    pub last_valid_block_height: u64,
+    /// The nonce account of a durable-nonce transaction
+    pub durable_nonce_account: Option<Pubkey>,
+}
+
+/// Read-only view of the transactions being retried by a [`SendTransactionService`]
+#[derive(Clone)]
+pub struct RetryStatusReader {
+    retry_transactions: Arc<Mutex<HashMap<Signature, TransactionInfo>>>,
+    default_max_retries: Option<usize>,
+    service_max_retries: usize,
+}
+
+impl RetryStatusReader {
+    /// Returns the retry status of `signature`, or `None` if the transaction is not in the
+    /// retry pool
+    pub fn get(&self, signature: &Signature) -> Option<TransactionRetryStatus> {
+        let retry_transactions = self.retry_transactions.lock().unwrap();
+        let transaction_info = retry_transactions.get(signature)?;
+        Some(TransactionRetryStatus {
+            send_attempts: transaction_info.retries
+                + usize::from(transaction_info.last_sent_time.is_some()),
+            max_retries: transaction_info
+                .get_max_retries(self.default_max_retries, self.service_max_retries),
+            last_sent_slot: transaction_info.last_sent_slot,
+            last_sent_to: transaction_info.last_sent_to.clone(),
+            last_valid_block_height: transaction_info.last_valid_block_height,
+            durable_nonce_account: transaction_info
+                .durable_nonce_info
+                .map(|(nonce_pubkey, _)| nonce_pubkey),
+        })
+    }
+
+    pub fn contains(&self, signature: &Signature) -> bool {
+        self.retry_transactions
+            .lock()
+            .unwrap()
+            .contains_key(signature)
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Eq)]
 struct ProcessTransactionsResult {
     rooted: u64,
@@ -167,8 +227,14 @@ impl SendTransactionService {
         let stats_report = Arc::new(SendTransactionServiceStatsReport::default());
 
         let retry_transactions = Arc::new(Mutex::new(HashMap::new()));
+        let retry_status_reader = RetryStatusReader {
+            retry_transactions: retry_transactions.clone(),
+            default_max_retries: config.default_max_retries,
+            service_max_retries: config.service_max_retries,
+        };
 
         let receive_txn_thread = Self::receive_txn_thread(
+            bank_forks.read().unwrap().sharable_banks(),
             receiver,
             client.clone(),
             retry_transactions.clone(),
@@ -188,12 +254,19 @@ impl SendTransactionService {
         Self {
             receive_txn_thread,
             retry_thread,
+            retry_status_reader,
             exit,
         }
     }
 
+    /// Returns a reader for the state of the transactions in the retry pool
+    pub fn retry_status_reader(&self) -> RetryStatusReader {
+        self.retry_status_reader.clone()
+    }
+
     /// Thread responsible for receiving transactions from RPC clients.
     fn receive_txn_thread<Client: TransactionClient + std::marker::Send + 'static>(
+        sharable_banks: SharableBanks,
         receiver: Receiver<TransactionInfo>,
         client: Client,
         retry_transactions: Arc<Mutex<HashMap<Signature, TransactionInfo>>>,
@@ -260,8 +333,9 @@ impl SendTransactionService {
                         .values()
                         .map(|transaction_info| transaction_info.wire_transaction.clone())
                         .collect::<Vec<Vec<u8>>>();
-                    client.send_transactions_in_batch(wire_transactions, stats);
+                    let last_sent_to = client.send_transactions_in_batch(wire_transactions, stats);
                     let last_sent_time = Instant::now();
+                    let last_sent_slot = sharable_banks.working().slot();
                     {
                         // take a lock of retry_transactions and move the batch to the retry set.
                         let mut retry_transactions = retry_transactions.lock().unwrap();
@@ -283,6 +357,8 @@ impl SendTransactionService {
                                     break;
                                 } else {
                                     transaction_info.last_sent_time = Some(last_sent_time);
+                                    transaction_info.last_sent_slot = Some(last_sent_slot);
+                                    transaction_info.last_sent_to = last_sent_to.clone();
                                     transactions_added_to_retry += 1;
                                     entry.or_insert(transaction_info);
                                 }
@@ -461,6 +537,7 @@ impl SendTransactionService {
 
                         batched_transactions.push(*signature);
                         transaction_info.last_sent_time = Some(now);
+                        transaction_info.last_sent_slot = Some(working_bank.slot());
 
                         let max_retries = transaction_info
                             .get_max_retries(default_max_retries, service_max_retries);
@@ -497,15 +574,20 @@ impl SendTransactionService {
 
         if !batched_transactions.is_empty() {
             // Processing the transactions in batch
-            let wire_transactions = batched_transactions
-                .iter()
-                .filter_map(|signature| transactions.get(signature))
-                .map(|transaction_info| transaction_info.wire_transaction.clone());
-
-            let iter = wire_transactions.chunks(batch_size);
+            let iter = batched_transactions.iter().chunks(batch_size);
             for chunk in &iter {
-                let chunk = chunk.collect();
-                client.send_transactions_in_batch(chunk, stats);
+                let chunk = chunk.collect::<Vec<_>>();
+                let wire_transactions = chunk
+                    .iter()
+                    .filter_map(|signature| transactions.get(*signature))
+                    .map(|transaction_info| transaction_info.wire_transaction.clone())
+                    .collect();
+                let last_sent_to = client.send_transactions_in_batch(wire_transactions, stats);
+                for signature in chunk {
+                    if let Some(transaction_info) = transactions.get_mut(signature) {
+                        transaction_info.last_sent_to = last_sent_to.clone();
+                    }
+                }
             }
         }
 
@@ -596,6 +678,8 @@ mod test {
             max_retries: None,
             retries: 0,
             last_sent_time: None,
+            last_sent_slot: None,
+            last_sent_to: Vec::new(),
         };
 
         let exit = Arc::new(AtomicBool::new(false));
@@ -1266,4 +1350,91 @@ mod test {
     async fn retry_durable_nonce_transactions_with_tpu_client_next() {
         retry_durable_nonce_transactions::<TpuClientNextClient>(Some(Handle::current()));
     }
+
+    fn retry_status_reader<C: ClientWithCreator>(maybe_runtime: Option<Handle>) {
+        let (genesis_config, _mint_keypair) = create_genesis_config(4);
+        let (root_bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        let working_bank = bank_forks
+            .write()
+            .unwrap()
+            .insert(Bank::new_from_parent(
+                root_bank.clone(),
+                &Pubkey::default(),
+                1,
+            ))
+            .clone_without_scheduler();
+
+        let config = Config {
+            default_max_retries: Some(5),
+            service_max_retries: 3,
+            ..Config::default()
+        };
+        let reader = RetryStatusReader {
+            retry_transactions: Arc::new(Mutex::new(HashMap::new())),
+            default_max_retries: config.default_max_retries,
+            service_max_retries: config.service_max_retries,
+        };
+        let signature = Signature::new_unique();
+        let nonce_address = Pubkey::new_unique();
+        assert!(!reader.contains(&signature));
+        assert_eq!(reader.get(&signature), None);
+
+        reader.retry_transactions.lock().unwrap().insert(
+            signature,
+            TransactionInfo::new(
+                Hash::new_unique(),
+                signature,
+                Hash::new_unique(),
+                vec![],
+                working_bank.block_height() + 10,
+                Some((nonce_address, Hash::new_unique())),
+                None,
+                None,
+            ),
+        );
+        assert!(reader.contains(&signature));
+        assert_eq!(
+            reader.get(&signature),
+            Some(TransactionRetryStatus {
+                send_attempts: 0,
+                max_retries: Some(3),
+                last_sent_slot: None,
+                last_sent_to: vec![],
+                last_valid_block_height: working_bank.block_height() + 10,
+                durable_nonce_account: Some(nonce_address),
+            })
+        );
+
+        // The first send is not counted as a retry but is reported as an attempt, along with
+        // the slot it was sent in and where it was sent to.
+        let tpu_address = "127.0.0.1:0".parse().unwrap();
+        let client = C::create_client(maybe_runtime, tpu_address, None, 1);
+        let stats = SendTransactionServiceStats::default();
+        SendTransactionService::process_transactions(
+            &working_bank,
+            &root_bank,
+            &mut reader.retry_transactions.lock().unwrap(),
+            &client,
+            &config,
+            &stats,
+        );
+        let status = reader.get(&signature).unwrap();
+        assert_eq!(status.send_attempts, 1);
+        assert_eq!(status.last_sent_slot, Some(working_bank.slot()));
+        assert!(status
+            .last_sent_to
+            .iter()
+            .all(|address| *address == tpu_address));
+        client.stop();
+    }
+
+    #[test]
+    fn retry_status_reader_with_connection_cache() {
+        retry_status_reader::<ConnectionCacheClient<NullTpuInfo>>(None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn retry_status_reader_with_tpu_client_next() {
+        retry_status_reader::<TpuClientNextClient>(Some(Handle::current()));
+    }
 }
@@ -9,7 +9,8 @@ use {
     solana_quic_definitions::NotifyKeyUpdate,
     solana_tpu_client_next::{
         connection_workers_scheduler::{
-            BindTarget, ConnectionWorkersSchedulerConfig, Fanout, StakeIdentity,
+            extract_send_leaders, BindTarget, ConnectionWorkersSchedulerConfig, Fanout,
+            StakeIdentity,
         },
         leader_updater::LeaderUpdater,
         transaction_batch::TransactionBatch,
@@ -39,11 +40,12 @@ pub trait TpuInfoWithSendStatic: TpuInfo + std::marker::Send + 'static {}
 impl<T> TpuInfoWithSendStatic for T where T: TpuInfo + std::marker::Send + 'static {}
 
 pub trait TransactionClient {
+    /// Sends `wire_transactions` and returns the TPU addresses they were sent to
     fn send_transactions_in_batch(
         &self,
         wire_transactions: Vec<Vec<u8>>,
         stats: &SendTransactionServiceStats,
-    );
+    ) -> Vec<SocketAddr>;
 
     #[cfg(any(test, feature = "dev-context-only-utils"))]
     fn protocol(&self) -> Protocol;
@@ -136,7 +138,7 @@ where
         &self,
         wire_transactions: Vec<Vec<u8>>,
         stats: &SendTransactionServiceStats,
-    ) {
+    ) -> Vec<SocketAddr> {
         // Processing the transactions in batch
         let mut addresses = self
             .tpu_peers
@@ -151,6 +153,7 @@ where
         for address in &addresses {
             self.send_transactions(address, wire_transactions.clone(), stats);
         }
+        addresses.into_iter().copied().collect()
     }
 
     #[cfg(any(test, feature = "dev-context-only-utils"))]
@@ -232,6 +235,8 @@ pub struct TpuClientNextClient {
     runtime_handle: Handle,
     sender: mpsc::Sender<TransactionBatch>,
     update_certificate_sender: watch::Sender<Option<StakeIdentity>>,
+    /// Leaders the scheduler sent its latest batch to
+    send_leaders: Arc<Mutex<Vec<SocketAddr>>>,
     #[cfg(any(test, feature = "dev-context-only-utils"))]
     cancel: CancellationToken,
 }
@@ -258,11 +263,14 @@ impl TpuClientNextClient {
         let (update_certificate_sender, update_certificate_receiver) = watch::channel(None);
 
         let leader_info_provider = CurrentLeaderInfo::new(leader_info);
+        let send_leaders = Arc::new(Mutex::new(Vec::new()));
         let leader_updater: SendTransactionServiceLeaderUpdater<T> =
             SendTransactionServiceLeaderUpdater {
                 leader_info_provider,
                 my_tpu_address,
                 tpu_peers,
+                send_fanout: leader_forward_count as usize,
+                send_leaders: send_leaders.clone(),
             };
         let config = Self::create_config(bind_socket, identity, leader_forward_count as usize);
 
@@ -283,6 +291,7 @@ impl TpuClientNextClient {
             runtime_handle,
             sender,
             update_certificate_sender,
+            send_leaders,
             #[cfg(any(test, feature = "dev-context-only-utils"))]
             cancel,
         }
@@ -329,7 +338,7 @@ impl TransactionClient for TpuClientNextClient {
         &self,
         wire_transactions: Vec<Vec<u8>>,
         stats: &SendTransactionServiceStats,
-    ) {
+    ) -> Vec<SocketAddr> {
         let mut measure = Measure::start("send-us");
         self.runtime_handle.spawn({
             let sender = self.sender.clone();
@@ -344,6 +353,9 @@ impl TransactionClient for TpuClientNextClient {
         measure.stop();
         stats.send_us.fetch_add(measure.as_us(), Ordering::Relaxed);
         stats.send_attempt_count.fetch_add(1, Ordering::Relaxed);
+        // The scheduler picks the leaders asynchronously, right before sending each batch. Until it
+        // gets to this one, the leaders of its previous batch are the best known.
+        self.send_leaders.lock().unwrap().clone()
     }
 
     #[cfg(any(test, feature = "dev-context-only-utils"))]
@@ -357,6 +369,10 @@ pub struct SendTransactionServiceLeaderUpdater<T: TpuInfoWithSendStatic> {
     leader_info_provider: CurrentLeaderInfo<T>,
     my_tpu_address: SocketAddr,
     tpu_peers: Option<Vec<SocketAddr>>,
+    /// Number of leaders the scheduler sends each batch to
+    send_fanout: usize,
+    /// Leaders the scheduler sent its latest batch to
+    send_leaders: Arc<Mutex<Vec<SocketAddr>>>,
 }
 
 #[async_trait]
@@ -375,6 +391,9 @@ where
             .unwrap_or_else(|| vec![&self.my_tpu_address]);
         let mut all_peers = self.tpu_peers.clone().unwrap_or_default();
         all_peers.extend(discovered_peers.into_iter().cloned());
+        // The scheduler asks for the next leaders before sending every batch and sends it to the
+        // first `send_fanout` of them
+        *self.send_leaders.lock().unwrap() = extract_send_leaders(&all_peers, self.send_fanout);
         all_peers
     }
     async fn stop(&mut self) {}
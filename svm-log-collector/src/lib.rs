@@ -3,11 +3,40 @@ use std::{cell::RefCell, rc::Rc};
 
 const LOG_MESSAGES_BYTES_LIMIT: usize = 10 * 1000;
 
+/// Compute units and account accesses of a single instruction, top level or CPI
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstructionTrace {
+    /// Invocation stack height, 1 for top level instructions
+    pub stack_height: usize,
+    /// Index of the program account in the transaction
+    pub program_account_index: u16,
+    /// Compute units consumed, including those of the instructions it invoked
+    pub compute_units_consumed: u64,
+    /// Accounts passed to the instruction, without duplicates
+    pub accounts: Vec<InstructionTraceAccount>,
+}
+
+/// Access of a single account by an instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InstructionTraceAccount {
+    /// Index of the account in the transaction
+    pub index_in_transaction: u16,
+    /// Whether the instruction was allowed to write to the account
+    pub is_writable: bool,
+    /// Whether the account changed while processing the instruction
+    pub is_written: bool,
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+}
+
 pub struct LogCollector {
     pub messages: Vec<String>,
     pub bytes_written: usize,
     pub bytes_limit: Option<usize>,
     pub limit_warning: bool,
+    /// Per instruction trace, in invocation order; only recorded when `Some`.
+    /// Unlike `messages` it is not subject to `bytes_limit`.
+    pub instruction_trace: Option<Vec<InstructionTrace>>,
 }
 
 impl Default for LogCollector {
@@ -17,6 +46,7 @@ impl Default for LogCollector {
             bytes_written: 0,
             bytes_limit: Some(LOG_MESSAGES_BYTES_LIMIT),
             limit_warning: false,
+            instruction_trace: None,
         }
     }
 }
@@ -55,6 +85,11 @@ impl LogCollector {
         }))
     }
 
+    /// Enables recording of the per instruction trace
+    pub fn enable_instruction_trace(&mut self) {
+        self.instruction_trace.get_or_insert_with(Vec::new);
+    }
+
     pub fn into_messages(self) -> Vec<String> {
         self.messages
     }
@@ -28,10 +28,7 @@ use {
     },
     solana_pubkey::Pubkey,
     solana_rent::Rent,
-    solana_sdk_ids::{
-        bpf_loader_upgradeable, native_loader,
-        sysvar::{self, slot_history},
-    },
+    solana_sdk_ids::{bpf_loader_upgradeable, native_loader, sysvar},
     solana_svm_callback::{AccountState, TransactionProcessingCallback},
     solana_svm_feature_set::SVMFeatureSet,
     solana_svm_transaction::svm_message::SVMMessage,
@@ -176,12 +173,14 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
     ) -> AccountLoader<'a, CB> {
         let mut loaded_accounts = AHashMap::with_capacity(capacity);
 
-        // SlotHistory may be overridden for simulation.
-        // No other uses of AccountOverrides are expected.
-        if let Some(slot_history) =
-            account_overrides.and_then(|overrides| overrides.get(&slot_history::id()))
-        {
-            loaded_accounts.insert(slot_history::id(), slot_history.clone());
+        // Overridden accounts, e.g. SlotHistory or caller-provided account
+        // states for simulation, take precedence over accounts-db.
+        if let Some(account_overrides) = account_overrides {
+            loaded_accounts.extend(
+                account_overrides
+                    .iter()
+                    .map(|(pubkey, account)| (*pubkey, account.clone())),
+            );
         }
 
         Self {
@@ -1332,6 +1331,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_overrides_take_precedence() {
+        solana_logger::setup();
+        let keypair = Keypair::new();
+        let overridden_key = Pubkey::new_unique();
+        let mut account_overrides = AccountOverrides::default();
+        let overridden_account = AccountSharedData::new(42, 3, &Pubkey::new_unique());
+        account_overrides.set_account(&overridden_key, Some(overridden_account.clone()));
+
+        let mut program_account = AccountSharedData::default();
+        program_account.set_lamports(1);
+        program_account.set_executable(true);
+        program_account.set_owner(native_loader::id());
+
+        let instructions = vec![CompiledInstruction::new(2, &(), vec![0])];
+        let tx = Transaction::new_with_compiled_instructions(
+            &[&keypair],
+            &[overridden_key],
+            Hash::default(),
+            vec![bpf_loader::id()],
+            instructions,
+        );
+
+        let loaded_accounts = load_accounts_no_store(
+            &[
+                (
+                    keypair.pubkey(),
+                    AccountSharedData::new(1_000_000, 0, &Pubkey::default()),
+                ),
+                (
+                    overridden_key,
+                    AccountSharedData::new(1, 0, &Pubkey::default()),
+                ),
+                (bpf_loader::id(), program_account),
+            ],
+            tx,
+            Some(&account_overrides),
+        );
+        match &loaded_accounts {
+            TransactionLoadResult::Loaded(loaded_transaction) => {
+                assert_eq!(loaded_transaction.accounts[1].0, overridden_key);
+                assert_eq!(loaded_transaction.accounts[1].1, overridden_account);
+            }
+            TransactionLoadResult::FeesOnly(fees_only_tx) => panic!("{}", fees_only_tx.load_error),
+            TransactionLoadResult::NotLoaded(e) => panic!("{e}"),
+        }
+    }
+
     #[test]
     fn test_accumulate_and_check_loaded_account_data_size() {
         let mut error_metrics = TransactionErrorMetrics::default();
@@ -13,7 +13,9 @@ pub struct AccountOverrides {
 
 impl AccountOverrides {
     /// Insert or remove an account with a given pubkey to/from the list of overrides.
-    fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
+    ///
+    /// Note: no checks are performed on the correctness of the contained data
+    pub fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
         match account {
             Some(account) => self.accounts.insert(*pubkey, account),
             None => self.accounts.remove(pubkey),
@@ -28,9 +30,14 @@ impl AccountOverrides {
     }
 
     /// Gets the account if it's found in the list of overrides
-    pub(crate) fn get(&self, pubkey: &Pubkey) -> Option<&AccountSharedData> {
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&AccountSharedData> {
         self.accounts.get(pubkey)
     }
+
+    /// Iterates over all overridden accounts
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Pubkey, &AccountSharedData)> {
+        self.accounts.iter()
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +69,24 @@ mod test {
 
         assert_eq!(accounts.get(&sysvar::slot_history::id()), Some(&data));
     }
+
+    #[test]
+    fn test_iter() {
+        let mut accounts = AccountOverrides::default();
+        assert_eq!(accounts.iter().count(), 0);
+
+        let key = Pubkey::new_unique();
+        let data = AccountSharedData::new(42, 0, &Pubkey::default());
+        accounts.set_account(&key, Some(data.clone()));
+        accounts.set_slot_history(Some(AccountSharedData::default()));
+
+        let mut overridden = accounts.iter().collect::<Vec<_>>();
+        overridden.sort_by_key(|(pubkey, _)| **pubkey);
+        let mut expected = vec![
+            (&key, &data),
+            (&sysvar::slot_history::id(), &AccountSharedData::default()),
+        ];
+        expected.sort_by_key(|(pubkey, _)| **pubkey);
+        assert_eq!(overridden, expected);
+    }
 }
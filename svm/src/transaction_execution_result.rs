@@ -1,3 +1,4 @@
+pub use solana_svm_log_collector::{InstructionTrace, InstructionTraceAccount};
 use {
     crate::account_loader::LoadedTransaction,
     solana_message::inner_instruction::InnerInstructionsList,
@@ -37,6 +38,9 @@ pub struct TransactionExecutionDetails {
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
+    /// Compute units and account accesses of each instruction, in invocation
+    /// order; only recorded if enabled in the `ExecutionRecordingConfig`
+    pub instruction_trace: Option<Vec<InstructionTrace>>,
 }
 
 impl TransactionExecutionDetails {
@@ -88,15 +88,20 @@ pub struct ExecutionRecordingConfig {
     pub enable_log_recording: bool,
     pub enable_return_data_recording: bool,
     pub enable_transaction_balance_recording: bool,
+    /// Record the compute units and account accesses of every instruction;
+    /// meant for simulation, as it copies the accounts written by each instruction
+    pub enable_instruction_trace_recording: bool,
 }
 
 impl ExecutionRecordingConfig {
+    /// Sets every setting except `enable_instruction_trace_recording`, which is always disabled
     pub fn new_single_setting(option: bool) -> Self {
         ExecutionRecordingConfig {
             enable_return_data_recording: option,
             enable_log_recording: option,
             enable_cpi_recording: option,
             enable_transaction_balance_recording: option,
+            enable_instruction_trace_recording: false,
         }
     }
 }
@@ -346,6 +351,12 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             account_keys_in_batch,
         );
 
+        // Create a batch-local sysvar cache if any cached sysvars are overridden,
+        // so that programs observe the same sysvar state via syscalls as via accounts.
+        let sysvar_cache_with_overrides = config.account_overrides.and_then(|account_overrides| {
+            Self::sysvar_cache_with_overrides(callbacks, account_overrides)
+        });
+
         // Create the transaction balance collector if recording is enabled.
         let mut balance_collector = config
             .recording_config
@@ -487,6 +498,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                         &mut program_cache_for_tx_batch,
                         environment,
                         config,
+                        sysvar_cache_with_overrides.as_ref(),
                     );
 
                     // Update loaded accounts cache with account states which might have changed.
@@ -827,6 +839,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         program_cache_for_tx_batch: &mut ProgramCacheForTxBatch,
         environment: &TransactionProcessingEnvironment,
         config: &TransactionProcessingConfig,
+        sysvar_cache_with_overrides: Option<&SysvarCache>,
     ) -> ExecutedTransaction {
         let transaction_accounts = std::mem::take(&mut loaded_transaction.accounts);
 
@@ -858,19 +871,27 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         let pre_account_state_info =
             TransactionAccountStateInfo::new(&transaction_context, tx, &environment.rent);
 
-        let log_collector = if config.recording_config.enable_log_recording {
-            match config.log_messages_bytes_limit {
-                None => Some(LogCollector::new_ref()),
-                Some(log_messages_bytes_limit) => Some(LogCollector::new_ref_with_limit(Some(
-                    log_messages_bytes_limit,
-                ))),
+        let recording_config = &config.recording_config;
+        let log_collector = if recording_config.enable_log_recording
+            || recording_config.enable_instruction_trace_recording
+        {
+            let log_collector = match config.log_messages_bytes_limit {
+                None => LogCollector::new_ref(),
+                Some(log_messages_bytes_limit) => {
+                    LogCollector::new_ref_with_limit(Some(log_messages_bytes_limit))
+                }
+            };
+            if recording_config.enable_instruction_trace_recording {
+                log_collector.borrow_mut().enable_instruction_trace();
             }
+            Some(log_collector)
         } else {
             None
         };
 
         let mut executed_units = 0u64;
-        let sysvar_cache = &self.sysvar_cache.read().unwrap();
+        let global_sysvar_cache = self.sysvar_cache.read().unwrap();
+        let sysvar_cache = sysvar_cache_with_overrides.unwrap_or(&global_sysvar_cache);
 
         let mut invoke_context = InvokeContext::new(
             &mut transaction_context,
@@ -928,12 +949,19 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 err
             });
 
-        let log_messages: Option<TransactionLogMessages> =
-            log_collector.and_then(|log_collector| {
-                Rc::try_unwrap(log_collector)
-                    .map(|log_collector| log_collector.into_inner().into_messages())
-                    .ok()
-            });
+        let (log_messages, instruction_trace) = log_collector
+            .and_then(|log_collector| Rc::try_unwrap(log_collector).ok())
+            .map(|log_collector| {
+                let LogCollector {
+                    messages,
+                    instruction_trace,
+                    ..
+                } = log_collector.into_inner();
+                let log_messages: Option<TransactionLogMessages> =
+                    recording_config.enable_log_recording.then_some(messages);
+                (log_messages, instruction_trace)
+            })
+            .unwrap_or_default();
 
         let inner_instructions = if config.recording_config.enable_cpi_recording {
             Some(Self::inner_instructions_list_from_instruction_trace(
@@ -979,6 +1007,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 return_data,
                 executed_units,
                 accounts_data_len_delta,
+                instruction_trace,
             },
             loaded_transaction,
             programs_modified_by_tx: program_cache_for_tx_batch.drain_modified_entries(),
@@ -1045,6 +1074,31 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         });
     }
 
+    /// Builds a sysvar cache from `account_overrides` and `callbacks`, if
+    /// any sysvar held by the cache is overridden.
+    fn sysvar_cache_with_overrides<CB: TransactionProcessingCallback>(
+        callbacks: &CB,
+        account_overrides: &AccountOverrides,
+    ) -> Option<SysvarCache> {
+        let mut sysvar_cache = SysvarCache::default();
+        let mut has_overrides = false;
+        sysvar_cache.fill_missing_entries(|pubkey, set_sysvar| {
+            if let Some(account) = account_overrides.get(pubkey) {
+                has_overrides = true;
+                set_sysvar(account.data());
+            }
+        });
+        if !has_overrides {
+            return None;
+        }
+        sysvar_cache.fill_missing_entries(|pubkey, set_sysvar| {
+            if let Some((account, _slot)) = callbacks.get_account_shared_data(pubkey) {
+                set_sysvar(account.data());
+            }
+        });
+        Some(sysvar_cache)
+    }
+
     pub fn reset_sysvar_cache(&self) {
         let mut sysvar_cache = self.sysvar_cache.write().unwrap();
         sysvar_cache.reset();
@@ -1350,6 +1404,7 @@ mod tests {
             &mut program_cache_for_tx_batch,
             &processing_environment,
             &processing_config,
+            None,
         );
         assert!(executed_tx.execution_details.log_messages.is_some());
 
@@ -1364,6 +1419,7 @@ mod tests {
             &mut program_cache_for_tx_batch,
             &processing_environment,
             &processing_config,
+            None,
         );
         assert!(executed_tx.execution_details.log_messages.is_some());
         assert!(executed_tx.execution_details.inner_instructions.is_none());
@@ -1381,6 +1437,7 @@ mod tests {
             &mut program_cache_for_tx_batch,
             &processing_environment,
             &processing_config,
+            None,
         );
 
         assert!(executed_tx.execution_details.log_messages.is_none());
@@ -1445,6 +1502,7 @@ mod tests {
             &mut program_cache_for_tx_batch,
             &TransactionProcessingEnvironment::default(),
             &processing_config,
+            None,
         );
 
         assert_eq!(error_metrics.instruction_error.0, 1);
@@ -1771,6 +1829,58 @@ mod tests {
         assert!(sysvar_cache.get_epoch_rewards().is_err());
     }
 
+    #[test]
+    fn test_sysvar_cache_with_overrides() {
+        let mock_bank = MockBankCallback::default();
+        let clock = Clock {
+            slot: 1,
+            unix_timestamp: 5,
+            ..Clock::default()
+        };
+        let rent = Rent::with_slots_per_epoch(2048);
+        {
+            let mut account_shared_data = mock_bank.account_shared_data.write().unwrap();
+            account_shared_data.insert(
+                sysvar::clock::id(),
+                create_account_shared_data_for_test(&clock),
+            );
+            account_shared_data.insert(
+                sysvar::rent::id(),
+                create_account_shared_data_for_test(&rent),
+            );
+        }
+
+        // overriding accounts which are not in the sysvar cache leaves it untouched
+        let mut account_overrides = AccountOverrides::default();
+        account_overrides.set_slot_history(Some(AccountSharedData::default()));
+        account_overrides.set_account(&Pubkey::new_unique(), Some(AccountSharedData::default()));
+        assert!(
+            TransactionBatchProcessor::<TestForkGraph>::sysvar_cache_with_overrides(
+                &mock_bank,
+                &account_overrides,
+            )
+            .is_none()
+        );
+
+        let overridden_clock = Clock {
+            slot: 100,
+            unix_timestamp: 500,
+            ..clock
+        };
+        account_overrides.set_account(
+            &sysvar::clock::id(),
+            Some(create_account_shared_data_for_test(&overridden_clock)),
+        );
+        let sysvar_cache = TransactionBatchProcessor::<TestForkGraph>::sysvar_cache_with_overrides(
+            &mock_bank,
+            &account_overrides,
+        )
+        .unwrap();
+        assert_eq!(sysvar_cache.get_clock().unwrap(), overridden_clock.into());
+        assert_eq!(sysvar_cache.get_rent().unwrap(), rent.into());
+        assert!(sysvar_cache.get_epoch_schedule().is_err());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn test_reset_and_fill_sysvar_cache() {
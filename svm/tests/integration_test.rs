@@ -150,6 +150,7 @@ impl SvmTestEnvironment<'_> {
                 enable_return_data_recording: true,
                 enable_cpi_recording: false,
                 enable_transaction_balance_recording: false,
+                enable_instruction_trace_recording: false,
             },
             ..Default::default()
         };
@@ -592,6 +592,23 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(false)
                 .help("Enable the unstable RPC PubSub `blockSubscribe` subscription"),
         )
+        .arg(
+            Arg::with_name("rpc_pubsub_enable_transaction_subscription")
+                .long("rpc-pubsub-enable-transaction-subscription")
+                .takes_value(false)
+                .help("Enable the unstable RPC PubSub `transactionSubscribe` subscription"),
+        )
+        .arg(
+            Arg::with_name("rpc_pubsub_replay_buffer_slots")
+                .long("rpc-pubsub-replay-buffer-slots")
+                .takes_value(true)
+                .value_name("SLOTS")
+                .validator(is_parsable::<usize>)
+                .help(
+                    "The number of recent slots whose RPC PubSub notifications are kept for \
+                     subscriptions resuming with `fromSlot` [default: 0 (disabled)]",
+                ),
+        )
         .arg(
             Arg::with_name("bpf_program")
                 .long("bpf-program")
@@ -5,7 +5,7 @@ use {
         commands::{FromClapArgMatches, Result},
     },
     clap::{values_t, App, Arg, ArgMatches},
-    solana_accounts_db::utils::create_and_canonicalize_directory,
+    solana_accounts_db::{accounts_index::MemcmpIndex, utils::create_and_canonicalize_directory},
     solana_clap_utils::{
         hidden_unless_forced,
         input_parsers::keypair_of,
@@ -24,7 +24,10 @@ use {
     solana_keypair::Keypair,
     solana_ledger::{blockstore_options::BlockstoreOptions, use_snapshot_archives_at_startup},
     solana_pubkey::Pubkey,
-    solana_rpc::{rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig},
+    solana_rpc::{
+        rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig,
+        rpc_rate_limiter::DEFAULT_RPC_RATE_LIMIT_CLIENT_IP_HEADER,
+    },
     solana_runtime::snapshot_utils::{SnapshotVersion, SUPPORTED_ARCHIVE_COMPRESSION},
     solana_send_transaction_service::send_transaction_service::{
         Config as SendTransactionServiceConfig, MAX_BATCH_SEND_RATE_MS, MAX_TRANSACTION_BATCH_SIZE,
@@ -32,7 +35,13 @@ use {
     solana_signer::Signer,
     solana_streamer::socket::SocketAddrSpace,
     solana_unified_scheduler_pool::DefaultSchedulerPool,
-    std::{collections::HashSet, net::SocketAddr, path::PathBuf, str::FromStr},
+    std::{
+        collections::HashSet,
+        net::{IpAddr, SocketAddr},
+        num::NonZeroU64,
+        path::PathBuf,
+        str::FromStr,
+    },
 };
 
 const EXCLUDE_KEY: &str = "account-index-exclude-key";
@@ -1194,6 +1203,79 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .default_value(&default_args.rpc_max_request_body_size)
             .help("The maximum request body size accepted by rpc service"),
     )
+    .arg(
+        Arg::with_name("rpc_rate_limit_credits_per_second")
+            .long("rpc-rate-limit-credits-per-second")
+            .value_name("CREDITS")
+            .takes_value(true)
+            .validator(is_parsable::<NonZeroU64>)
+            .help(
+                "Enable rate limiting of the RPC service, granting each client this many credits \
+                 per second. Every call is charged the cost of its method",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_burst_credits")
+            .long("rpc-rate-limit-burst-credits")
+            .value_name("CREDITS")
+            .takes_value(true)
+            .requires("rpc_rate_limit_credits_per_second")
+            .validator(is_parsable::<NonZeroU64>)
+            .help(
+                "The maximum number of credits a client can accumulate while idle [default: twice \
+                 --rpc-rate-limit-credits-per-second]",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_method_cost")
+            .long("rpc-rate-limit-method-cost")
+            .value_name("METHOD=CREDITS")
+            .takes_value(true)
+            .multiple(true)
+            .requires("rpc_rate_limit_credits_per_second")
+            .validator(|value| json_rpc_config::parse_rpc_method_limit(&value).map(|_| ()))
+            .help(
+                "The number of credits charged per call to an RPC method. Methods not specified \
+                 are charged one credit, except getBlock, getProgramAccounts and \
+                 getSignaturesForAddress which are charged more by default",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_method_quota")
+            .long("rpc-rate-limit-method-quota")
+            .value_name("METHOD=CALLS_PER_SECOND")
+            .takes_value(true)
+            .multiple(true)
+            .requires("rpc_rate_limit_credits_per_second")
+            .validator(|value| json_rpc_config::parse_rpc_method_limit(&value).map(|_| ()))
+            .help("The maximum number of calls per second to an RPC method across all clients"),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_client_ip_header")
+            .long("rpc-rate-limit-client-ip-header")
+            .value_name("HEADER")
+            .takes_value(true)
+            .default_value(DEFAULT_RPC_RATE_LIMIT_CLIENT_IP_HEADER)
+            .help(
+                "The HTTP header in which --rpc-rate-limit-trusted-proxy forwards the IP address \
+                 of the client",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_trusted_proxy")
+            .long("rpc-rate-limit-trusted-proxy")
+            .value_name("IP_ADDRESS")
+            .takes_value(true)
+            .multiple(true)
+            .requires("rpc_rate_limit_credits_per_second")
+            .validator(is_parsable::<IpAddr>)
+            .help(
+                "The address of a load balancer in front of the RPC service. Clients are rate \
+                 limited by the address they connect from, unless they connect through a trusted \
+                 proxy, in which case the address it forwards in \
+                 --rpc-rate-limit-client-ip-header is used",
+            ),
+    )
     .arg(
         Arg::with_name("geyser_plugin_config")
             .long("geyser-plugin-config")
@@ -1295,6 +1377,20 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .value_name("INDEX")
             .help("Enable an accounts index, indexed by the selected account field"),
     )
+    .arg(
+        Arg::with_name("account_index_memcmp")
+            .long("account-index-memcmp")
+            .takes_value(true)
+            .multiple(true)
+            .validator(|value| value.parse::<MemcmpIndex>().map(|_| ()))
+            .value_name("PROGRAM_ID:OFFSET:LENGTH")
+            .help(
+                "Enable an accounts index of the accounts owned by PROGRAM_ID, indexed by the \
+                 LENGTH bytes of account data at OFFSET. getProgramAccounts requests with a \
+                 memcmp filter on the same bytes are served from the index. LENGTH may be at most \
+                 32.",
+            ),
+    )
     .arg(
         Arg::with_name("account_index_exclude_key")
             .long(EXCLUDE_KEY)
@@ -1716,6 +1812,7 @@ mod tests {
                     notification_threads: None,
                     queue_capacity_items:
                         solana_rpc::rpc_pubsub_service::DEFAULT_QUEUE_CAPACITY_ITEMS,
+                    replay_buffer_slots: 0,
                     ..PubSubConfig::default_for_tests()
                 },
                 send_transaction_service_config: SendTransactionServiceConfig::default(),
@@ -2,7 +2,7 @@ use {
     crate::commands::{FromClapArgMatches, Result},
     clap::{values_t, ArgMatches},
     solana_accounts_db::accounts_index::{
-        AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
+        AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude, MemcmpIndex,
     },
     solana_pubkey::Pubkey,
     std::collections::HashSet,
@@ -19,6 +19,12 @@ impl FromClapArgMatches for AccountSecondaryIndexes {
                 "spl-token-owner" => AccountIndex::SplTokenOwner,
                 _ => unreachable!(),
             })
+            .chain(
+                values_t!(matches, "account_index_memcmp", MemcmpIndex)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(AccountIndex::Memcmp),
+            )
             .collect();
 
         let account_indexes_include_keys: HashSet<Pubkey> =
@@ -126,6 +132,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_account_index_memcmp() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let program_id = Pubkey::new_unique();
+        let expected_args = RunArgs {
+            json_rpc_config: JsonRpcConfig {
+                account_indexes: AccountSecondaryIndexes {
+                    keys: None,
+                    indexes: HashSet::from([
+                        AccountIndex::ProgramId,
+                        AccountIndex::Memcmp(MemcmpIndex::new(program_id, 8, 32).unwrap()),
+                        AccountIndex::Memcmp(MemcmpIndex::new(program_id, 40, 8).unwrap()),
+                    ]),
+                },
+                ..default_run_args.json_rpc_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec![
+                "--account-index",
+                "program-id",
+                "--account-index-memcmp",
+                &format!("{program_id}:8:32"),
+                "--account-index-memcmp",
+                &format!("{program_id}:40:8"),
+            ],
+            expected_args,
+        );
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_account_index_include_key() {
         // single key
@@ -1,10 +1,57 @@
 use {
-    crate::commands::{FromClapArgMatches, Result},
-    clap::{value_t, ArgMatches},
+    crate::commands::{Error, FromClapArgMatches, Result},
+    clap::{value_t, values_t, ArgMatches},
     solana_accounts_db::accounts_index::AccountSecondaryIndexes,
-    solana_rpc::rpc::{JsonRpcConfig, RpcBigtableConfig},
+    solana_rpc::{
+        rpc::{JsonRpcConfig, RpcBigtableConfig},
+        rpc_rate_limiter::RpcRateLimitConfig,
+    },
+    std::{collections::HashMap, net::IpAddr},
 };
 
+/// Parses a `METHOD=NUMBER` rate limit argument
+pub(crate) fn parse_rpc_method_limit(value: &str) -> std::result::Result<(String, u64), String> {
+    let (method, limit) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected METHOD=NUMBER, got '{value}'"))?;
+    let limit = limit
+        .parse::<u64>()
+        .ok()
+        .filter(|limit| *limit > 0)
+        .ok_or_else(|| format!("'{limit}' is not a positive number"))?;
+    Ok((method.to_string(), limit))
+}
+
+impl FromClapArgMatches for RpcRateLimitConfig {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        let parse_method_limits = |name: &str| {
+            matches
+                .values_of(name)
+                .into_iter()
+                .flatten()
+                .map(parse_rpc_method_limit)
+                .collect::<std::result::Result<HashMap<_, _>, _>>()
+                .map_err(|err| Error::Dynamic(Box::<dyn std::error::Error>::from(err)))
+        };
+
+        let credits_per_second = value_t!(matches, "rpc_rate_limit_credits_per_second", u64)?;
+        let mut method_costs = RpcRateLimitConfig::default().method_costs;
+        method_costs.extend(parse_method_limits("rpc_rate_limit_method_cost")?);
+        Ok(RpcRateLimitConfig {
+            credits_per_second,
+            burst_credits: value_t!(matches, "rpc_rate_limit_burst_credits", u64)
+                .unwrap_or(credits_per_second.saturating_mul(2)),
+            method_costs,
+            method_quotas: parse_method_limits("rpc_rate_limit_method_quota")?,
+            client_ip_header: value_t!(matches, "rpc_rate_limit_client_ip_header", String)?,
+            trusted_proxies: values_t!(matches, "rpc_rate_limit_trusted_proxy", IpAddr)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
 impl FromClapArgMatches for JsonRpcConfig {
     fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
         let rpc_bigtable_config = if matches.is_present("enable_rpc_bigtable_ledger_storage")
@@ -14,6 +61,11 @@ impl FromClapArgMatches for JsonRpcConfig {
         } else {
             None
         };
+        let rate_limit = if matches.is_present("rpc_rate_limit_credits_per_second") {
+            Some(RpcRateLimitConfig::from_clap_arg_match(matches)?)
+        } else {
+            None
+        };
 
         Ok(JsonRpcConfig {
             enable_rpc_transaction_history: matches.is_present("enable_rpc_transaction_history"),
@@ -40,6 +92,7 @@ impl FromClapArgMatches for JsonRpcConfig {
             full_api: matches.is_present("full_rpc_api"),
             rpc_scan_and_fix_roots: matches.is_present("rpc_scan_and_fix_roots"),
             max_request_body_size: Some(value_t!(matches, "rpc_max_request_body_size", usize)?),
+            rate_limit,
             disable_health_check: false,
         })
     }
@@ -57,6 +110,7 @@ mod tests {
         },
         solana_rpc::rpc_pubsub_service::PubSubConfig,
         std::{
+            collections::HashSet,
             net::{Ipv4Addr, SocketAddr},
             num::NonZeroUsize,
         },
@@ -319,4 +373,60 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn verify_args_struct_by_command_run_with_rpc_rate_limit() {
+        let default_run_args = RunArgs::default();
+        let mut method_costs = RpcRateLimitConfig::default().method_costs;
+        method_costs.insert("getBlock".to_string(), 50);
+        method_costs.insert("getSlot".to_string(), 2);
+        let expected_args = RunArgs {
+            json_rpc_config: JsonRpcConfig {
+                rate_limit: Some(RpcRateLimitConfig {
+                    credits_per_second: 10,
+                    burst_credits: 20,
+                    method_costs,
+                    method_quotas: HashMap::from([("getProgramAccounts".to_string(), 5)]),
+                    client_ip_header: "X-Real-IP".to_string(),
+                    trusted_proxies: HashSet::from([
+                        "10.0.0.1".parse().unwrap(),
+                        "::1".parse().unwrap(),
+                    ]),
+                }),
+                ..default_run_args.json_rpc_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec![
+                "--rpc-rate-limit-credits-per-second",
+                "10",
+                "--rpc-rate-limit-method-cost",
+                "getBlock=50",
+                "--rpc-rate-limit-method-cost",
+                "getSlot=2",
+                "--rpc-rate-limit-method-quota",
+                "getProgramAccounts=5",
+                "--rpc-rate-limit-client-ip-header",
+                "X-Real-IP",
+                "--rpc-rate-limit-trusted-proxy",
+                "10.0.0.1",
+                "--rpc-rate-limit-trusted-proxy",
+                "::1",
+            ],
+            expected_args,
+        );
+    }
+
+    #[test]
+    fn test_parse_rpc_method_limit() {
+        assert_eq!(
+            parse_rpc_method_limit("getBlock=10"),
+            Ok(("getBlock".to_string(), 10))
+        );
+        assert!(parse_rpc_method_limit("getBlock").is_err());
+        assert!(parse_rpc_method_limit("getBlock=0").is_err());
+        assert!(parse_rpc_method_limit("getBlock=ten").is_err());
+    }
 }
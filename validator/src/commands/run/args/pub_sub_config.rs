@@ -18,6 +18,9 @@ static DEFAULT_RPC_PUBSUB_QUEUE_CAPACITY_ITEMS: LazyLock<String> =
 static DEFAULT_RPC_PUBSUB_QUEUE_CAPACITY_BYTES: LazyLock<String> =
     LazyLock::new(|| PubSubConfig::default().queue_capacity_bytes.to_string());
 
+static DEFAULT_RPC_PUBSUB_REPLAY_BUFFER_SLOTS: LazyLock<String> =
+    LazyLock::new(|| PubSubConfig::default().replay_buffer_slots.to_string());
+
 const DEFAULT_RPC_PUBSUB_WORKER_THREADS: &str = "4";
 
 #[cfg_attr(test, qualifiers(pub(crate)))]
@@ -35,6 +38,11 @@ pub(crate) fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
             .long("rpc-pubsub-enable-vote-subscription")
             .takes_value(false)
             .help("Enable the unstable RPC PubSub `voteSubscribe` subscription"),
+        Arg::with_name("rpc_pubsub_enable_transaction_subscription")
+            .long("rpc-pubsub-enable-transaction-subscription")
+            .requires("full_rpc_api")
+            .takes_value(false)
+            .help("Enable the unstable RPC PubSub `transactionSubscribe` subscription"),
         Arg::with_name("rpc_pubsub_max_active_subscriptions")
             .long("rpc-pubsub-max-active-subscriptions")
             .takes_value(true)
@@ -45,6 +53,15 @@ pub(crate) fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
                 "The maximum number of active subscriptions that RPC PubSub will accept across \
                  all connections.",
             ),
+        Arg::with_name("rpc_pubsub_max_active_subscriptions_per_ip")
+            .long("rpc-pubsub-max-active-subscriptions-per-ip")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .validator(is_parsable::<usize>)
+            .help(
+                "The maximum number of active subscriptions that RPC PubSub will accept across \
+                 all connections from a single IP address [default: unlimited]",
+            ),
         Arg::with_name("rpc_pubsub_queue_capacity_items")
             .long("rpc-pubsub-queue-capacity-items")
             .takes_value(true)
@@ -65,6 +82,16 @@ pub(crate) fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
                 "The maximum total size of notifications that RPC PubSub will store across all \
                  connections.",
             ),
+        Arg::with_name("rpc_pubsub_replay_buffer_slots")
+            .long("rpc-pubsub-replay-buffer-slots")
+            .takes_value(true)
+            .value_name("SLOTS")
+            .validator(is_parsable::<usize>)
+            .default_value(&DEFAULT_RPC_PUBSUB_REPLAY_BUFFER_SLOTS)
+            .help(
+                "The number of recent slots whose notifications RPC PubSub keeps for \
+                 subscriptions resuming with `fromSlot`. 0 disables the replay buffer",
+            ),
         Arg::with_name("rpc_pubsub_worker_threads")
             .long("rpc-pubsub-worker-threads")
             .takes_value(true)
@@ -95,13 +122,22 @@ impl FromClapArgMatches for PubSubConfig {
         Ok(PubSubConfig {
             enable_block_subscription: matches.is_present("rpc_pubsub_enable_block_subscription"),
             enable_vote_subscription: matches.is_present("rpc_pubsub_enable_vote_subscription"),
+            enable_transaction_subscription: matches
+                .is_present("rpc_pubsub_enable_transaction_subscription"),
             max_active_subscriptions: value_t!(
                 matches,
                 "rpc_pubsub_max_active_subscriptions",
                 usize
             )?,
+            max_active_subscriptions_per_ip: value_t!(
+                matches,
+                "rpc_pubsub_max_active_subscriptions_per_ip",
+                usize
+            )
+            .ok(),
             queue_capacity_items: value_t!(matches, "rpc_pubsub_queue_capacity_items", usize)?,
             queue_capacity_bytes: value_t!(matches, "rpc_pubsub_queue_capacity_bytes", usize)?,
+            replay_buffer_slots: value_t!(matches, "rpc_pubsub_replay_buffer_slots", usize)?,
             worker_threads: value_t!(matches, "rpc_pubsub_worker_threads", usize)?,
             notification_threads: value_t!(matches, "rpc_pubsub_notification_threads", usize)
                 .ok()
@@ -161,6 +197,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_enable_transaction_subscription() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let expected_args = RunArgs {
+            json_rpc_config: JsonRpcConfig {
+                full_api: true,
+                ..default_run_args.json_rpc_config.clone()
+            },
+            pub_sub_config: PubSubConfig {
+                enable_transaction_subscription: true,
+                notification_threads: Some(
+                    NonZeroUsize::new(
+                        DEFAULT_RPC_PUBSUB_NUM_NOTIFICATION_THREADS
+                            .parse::<usize>()
+                            .unwrap(),
+                    )
+                    .unwrap(),
+                ),
+                ..default_run_args.pub_sub_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec![
+                "--full-rpc-api", // required by --rpc-pubsub-enable-transaction-subscription
+                "--rpc-pubsub-enable-transaction-subscription",
+            ],
+            expected_args,
+        );
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_max_active_subscriptions() {
         let default_run_args = crate::commands::run::args::RunArgs::default();
@@ -178,6 +246,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_max_active_subscriptions_per_ip() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let expected_args = RunArgs {
+            pub_sub_config: PubSubConfig {
+                max_active_subscriptions_per_ip: Some(100),
+                ..default_run_args.pub_sub_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec!["--rpc-pubsub-max-active-subscriptions-per-ip", "100"],
+            expected_args,
+        );
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_queue_capacity_items() {
         let default_run_args = crate::commands::run::args::RunArgs::default();
@@ -212,6 +297,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_replay_buffer_slots() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let expected_args = RunArgs {
+            pub_sub_config: PubSubConfig {
+                replay_buffer_slots: 150,
+                ..default_run_args.pub_sub_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec!["--rpc-pubsub-replay-buffer-slots", "150"],
+            expected_args,
+        );
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_worker_threads() {
         let default_run_args = crate::commands::run::args::RunArgs::default();
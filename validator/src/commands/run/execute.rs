@@ -1189,7 +1189,9 @@ fn new_snapshot_config(
         let archive_format_str = value_t_or_exit!(matches, "snapshot_archive_format", String);
         let mut archive_format = ArchiveFormat::from_cli_arg(&archive_format_str)
             .unwrap_or_else(|| panic!("Archive format not recognized: {archive_format_str}"));
-        if let ArchiveFormat::TarZstd { config } = &mut archive_format {
+        if let ArchiveFormat::TarZstd { config } | ArchiveFormat::TarZstdSeekable { config } =
+            &mut archive_format
+        {
             config.compression_level =
                 value_t_or_exit!(matches, "snapshot_zstd_compression_level", i32);
         }